@@ -0,0 +1,232 @@
+use std::{fmt::Display, iter};
+
+use chrono::NaiveDate;
+
+use crate::{
+    business::{business_days_between, Calendar},
+    holidays::HolidayCalendar,
+};
+
+/// The aging bucket an item falls into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AgingBucket {
+    /// The due date has not been reached yet
+    NotDue,
+    /// Aged between the two day counts, inclusive on both ends
+    Between(u32, u32),
+    /// Aged more than the number of days
+    Over(u32),
+}
+
+impl Display for AgingBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgingBucket::NotDue => f.write_str("not due"),
+            AgingBucket::Between(from, to) => write!(f, "{}-{}", from, to),
+            AgingBucket::Over(days) => write!(f, "{}+", days),
+        }
+    }
+}
+
+/// Configurable aging buckets for receivables
+///
+/// The age of an item is the number of days from its due date to the as of date, an item due on
+/// the as of date has an age of zero. Limits of `[30, 60, 90]` create the buckets 0-30, 31-60,
+/// 61-90 and 90+.
+///
+/// Ages are counted in calendar days unless a holiday calendar is supplied with
+/// [AgingBuckets::with_business_days].
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::bucket::{AgingBucket, AgingBuckets};
+///
+/// let buckets = AgingBuckets::standard();
+/// let as_of = NaiveDate::from_ymd_opt(2022, 3, 31).unwrap();
+///
+/// assert_eq!(
+///     buckets.bucket(as_of, NaiveDate::from_ymd_opt(2022, 3, 1).unwrap()),
+///     AgingBucket::Between(0, 30)
+/// );
+/// assert_eq!(
+///     buckets.bucket(as_of, NaiveDate::from_ymd_opt(2022, 2, 28).unwrap()),
+///     AgingBucket::Between(31, 60)
+/// );
+/// assert_eq!(
+///     buckets.bucket(as_of, NaiveDate::from_ymd_opt(2021, 12, 1).unwrap()),
+///     AgingBucket::Over(90)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgingBuckets<C = Calendar> {
+    limits: Vec<u32>,
+    business_days: Option<C>,
+}
+
+impl AgingBuckets {
+    /// Create aging buckets from the upper limit of each bucket
+    ///
+    /// # Panics
+    ///
+    /// When no limits are supplied
+    pub fn new(limits: impl IntoIterator<Item = u32>) -> Self {
+        let mut limits: Vec<u32> = limits.into_iter().collect();
+        limits.sort_unstable();
+        limits.dedup();
+        assert!(!limits.is_empty(), "aging buckets need at least one limit");
+
+        AgingBuckets {
+            limits,
+            business_days: None,
+        }
+    }
+
+    /// The usual 0-30, 31-60, 61-90 and 90+ buckets
+    pub fn standard() -> Self {
+        AgingBuckets::new([30, 60, 90])
+    }
+}
+
+impl<C: HolidayCalendar> AgingBuckets<C> {
+    /// Count the age of items in business days of the calendar rather than calendar days
+    ///
+    /// Any [HolidayCalendar] can be used, including a reference to one.
+    pub fn with_business_days<D: HolidayCalendar>(self, calendar: D) -> AgingBuckets<D> {
+        AgingBuckets {
+            limits: self.limits,
+            business_days: Some(calendar),
+        }
+    }
+
+    /// The age of an item in days, negative when it is not due yet
+    pub fn age(&self, as_of: NaiveDate, due: NaiveDate) -> i64 {
+        match &self.business_days {
+            Some(calendar) => business_days_between(due, as_of, calendar),
+            None => as_of.signed_duration_since(due).num_days(),
+        }
+    }
+
+    /// The bucket an item falls into
+    pub fn bucket(&self, as_of: NaiveDate, due: NaiveDate) -> AgingBucket {
+        let age = self.age(as_of, due);
+        if age < 0 {
+            return AgingBucket::NotDue;
+        }
+
+        self.ranges()
+            .find(|(_, to)| age <= i64::from(*to))
+            .map_or(self.over(), |(from, to)| AgingBucket::Between(from, to))
+    }
+
+    /// Every bucket in ascending order, excluding [AgingBucket::NotDue]
+    pub fn buckets(&self) -> Vec<AgingBucket> {
+        self.ranges()
+            .map(|(from, to)| AgingBucket::Between(from, to))
+            .chain(iter::once(self.over()))
+            .collect()
+    }
+
+    /// The days each bucket is between, a limit of [u32::MAX] can only be the last
+    fn ranges(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let limits = self.limits.iter().copied();
+        iter::once(0)
+            .chain(limits.clone().map_while(|limit| limit.checked_add(1)))
+            .zip(limits)
+    }
+
+    fn over(&self) -> AgingBucket {
+        AgingBucket::Over(*self.limits.last().unwrap())
+    }
+}
+
+impl Default for AgingBuckets {
+    fn default() -> Self {
+        AgingBuckets::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_bucket_edges() {
+        let buckets = AgingBuckets::standard();
+        let as_of = date(2022, 6, 30);
+        let days_ago = |days: i64| as_of - chrono::Duration::days(days);
+
+        assert_eq!(buckets.bucket(as_of, days_ago(-1)), AgingBucket::NotDue);
+        assert_eq!(
+            buckets.bucket(as_of, days_ago(0)),
+            AgingBucket::Between(0, 30)
+        );
+        assert_eq!(
+            buckets.bucket(as_of, days_ago(30)),
+            AgingBucket::Between(0, 30)
+        );
+        assert_eq!(
+            buckets.bucket(as_of, days_ago(31)),
+            AgingBucket::Between(31, 60)
+        );
+        assert_eq!(
+            buckets.bucket(as_of, days_ago(90)),
+            AgingBucket::Between(61, 90)
+        );
+        assert_eq!(buckets.bucket(as_of, days_ago(91)), AgingBucket::Over(90));
+    }
+
+    #[test]
+    fn test_business_day_aging() {
+        let buckets = AgingBuckets::new([5, 10]).with_business_days(Calendar::new());
+        // the 2022-01-03 to 2022-01-10 span has five business days
+        let due = date(2022, 1, 3);
+        let as_of = date(2022, 1, 10);
+
+        assert_eq!(buckets.age(as_of, due), 5);
+        assert_eq!(buckets.bucket(as_of, due), AgingBucket::Between(0, 5));
+        assert_eq!(
+            AgingBuckets::new([5, 10]).bucket(as_of, due),
+            AgingBucket::Between(6, 10)
+        );
+    }
+
+    #[test]
+    fn test_borrowed_calendar() {
+        let calendar = Calendar::new().with_holidays([date(2022, 1, 7)]);
+        let buckets = AgingBuckets::new([5, 10]).with_business_days(&calendar);
+
+        assert_eq!(buckets.age(date(2022, 1, 10), date(2022, 1, 3)), 4);
+    }
+
+    #[test]
+    fn test_largest_limit() {
+        let buckets = AgingBuckets::new([30, u32::MAX]);
+
+        assert_eq!(
+            buckets.buckets(),
+            vec![
+                AgingBucket::Between(0, 30),
+                AgingBucket::Between(31, u32::MAX),
+                AgingBucket::Over(u32::MAX)
+            ]
+        );
+        assert_eq!(
+            buckets.bucket(date(2022, 6, 30), date(2021, 6, 30)),
+            AgingBucket::Between(31, u32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_buckets_display() {
+        let labels: Vec<String> = AgingBuckets::standard()
+            .buckets()
+            .iter()
+            .map(|b| b.to_string())
+            .collect();
+
+        assert_eq!(labels, vec!["0-30", "31-60", "61-90", "90+"]);
+    }
+}
@@ -0,0 +1,6 @@
+//! Bucketing of dates for reporting
+pub mod aging;
+pub mod tenor;
+
+pub use aging::*;
+pub use tenor::*;
@@ -0,0 +1,242 @@
+use std::fmt::Display;
+
+use chrono::NaiveDate;
+
+use crate::{error::CalendsError, RelativeDuration};
+
+/// A length of time measured forwards from an as of date, such as 1M or 5Y
+///
+/// Tenor boundaries are calendar aware, a 1M tenor from 2022-01-31 ends at 2022-02-28.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tenor(RelativeDuration);
+
+impl Tenor {
+    pub fn new(duration: RelativeDuration) -> Self {
+        Tenor(duration)
+    }
+
+    /// A tenor of zero, the as of date itself
+    pub fn zero() -> Self {
+        Tenor(RelativeDuration::zero())
+    }
+
+    pub fn days(days: i32) -> Self {
+        Tenor(RelativeDuration::days(days))
+    }
+
+    pub fn weeks(weeks: i32) -> Self {
+        Tenor(RelativeDuration::weeks(weeks))
+    }
+
+    pub fn months(months: i32) -> Self {
+        Tenor(RelativeDuration::months(months))
+    }
+
+    /// A tenor of the number of years, counted as twelve months each
+    ///
+    /// # Panics
+    ///
+    /// When the number of months is beyond [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT), see
+    /// [try_years](Tenor::try_years) for a fallible alternative
+    pub fn years(years: i32) -> Self {
+        Tenor(RelativeDuration::years(years))
+    }
+
+    /// A tenor of the number of years, failing when the number of months is beyond
+    /// [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
+    pub fn try_years(years: i32) -> Result<Self, CalendsError> {
+        RelativeDuration::try_years(years).map(Tenor)
+    }
+
+    pub fn duration(&self) -> RelativeDuration {
+        self.0
+    }
+
+    /// The date this tenor ends on when measured from `as_of`
+    pub fn date_from(&self, as_of: NaiveDate) -> NaiveDate {
+        as_of + self.0
+    }
+}
+
+/// Formats the tenor the way it is usually quoted e.g. 1W, 3M or 10Y
+impl Display for Tenor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let months = self.0.num_months();
+        if self.0.is_zero() {
+            f.write_str("0D")
+        } else if months != 0
+            && months % 12 == 0
+            && self.0.num_weeks() == 0
+            && self.0.num_days() == 0
+        {
+            write!(f, "{}Y", months / 12)
+        } else {
+            f.write_str(self.0.iso8601().trim_start_matches('P'))
+        }
+    }
+}
+
+/// A bucket between two tenors
+///
+/// The bucket includes the date of its start tenor and excludes the date of its end tenor, the
+/// last bucket has no end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TenorBucket {
+    pub start: Tenor,
+    pub end: Option<Tenor>,
+}
+
+impl TenorBucket {
+    /// Whether the date falls within this bucket when measured from `as_of`
+    pub fn contains(&self, as_of: NaiveDate, date: NaiveDate) -> bool {
+        date >= self.start.date_from(as_of)
+            && self.end.is_none_or(|end| date < end.date_from(as_of))
+    }
+}
+
+impl Display for TenorBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.end {
+            Some(end) if self.start == Tenor::zero() => write!(f, "0-{}", end),
+            Some(end) => write!(f, "{}-{}", self.start, end),
+            None if self.start == Tenor::zero() => f.write_str("0+"),
+            None => write!(f, "{}+", self.start),
+        }
+    }
+}
+
+/// Assign dates to the buckets formed by a list of tenors
+///
+/// The tenors `[1M, 3M, 6M]` form the buckets 0-1M, 1M-3M, 3M-6M and 6M+. Every bucket is
+/// returned in ascending order even when it is empty so that reports have a stable shape, and the
+/// dates within each bucket are sorted. Dates before `as_of` have already matured and are
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::bucket::{bucket_by_tenor, Tenor};
+///
+/// let as_of = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+/// let buckets = bucket_by_tenor(
+///     as_of,
+///     [
+///         NaiveDate::from_ymd_opt(2022, 2, 27).unwrap(),
+///         NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(),
+///     ],
+///     &[Tenor::months(1), Tenor::months(3)],
+/// );
+///
+/// assert_eq!(buckets[0].0.to_string(), "0-1M");
+/// assert_eq!(buckets[0].1, vec![NaiveDate::from_ymd_opt(2022, 2, 27).unwrap()]);
+/// assert_eq!(buckets[1].0.to_string(), "1M-3M");
+/// assert_eq!(buckets[1].1, vec![NaiveDate::from_ymd_opt(2022, 2, 28).unwrap()]);
+/// assert_eq!(buckets[2].0.to_string(), "3M+");
+/// assert!(buckets[2].1.is_empty());
+/// ```
+pub fn bucket_by_tenor(
+    as_of: NaiveDate,
+    dates: impl IntoIterator<Item = NaiveDate>,
+    tenors: &[Tenor],
+) -> Vec<(TenorBucket, Vec<NaiveDate>)> {
+    let mut boundaries: Vec<Tenor> = tenors
+        .iter()
+        .copied()
+        .filter(|t| t.date_from(as_of) > as_of)
+        .collect();
+    boundaries.sort_by_key(|t| t.date_from(as_of));
+    boundaries.dedup_by_key(|t| t.date_from(as_of));
+
+    let mut buckets: Vec<(TenorBucket, Vec<NaiveDate>)> = Vec::with_capacity(boundaries.len() + 1);
+    let mut start = Tenor::zero();
+    for end in boundaries {
+        buckets.push((
+            TenorBucket {
+                start,
+                end: Some(end),
+            },
+            Vec::new(),
+        ));
+        start = end;
+    }
+    buckets.push((TenorBucket { start, end: None }, Vec::new()));
+
+    let mut dates: Vec<NaiveDate> = dates.into_iter().filter(|d| *d >= as_of).collect();
+    dates.sort();
+
+    let mut index = 0;
+    for date in dates {
+        while !buckets[index].0.contains(as_of, date) {
+            index += 1;
+        }
+        buckets[index].1.push(date);
+    }
+
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_tenor_display() {
+        assert_eq!(Tenor::months(1).to_string(), "1M");
+        assert_eq!(Tenor::months(18).to_string(), "18M");
+        assert_eq!(Tenor::years(5).to_string(), "5Y");
+        assert_eq!(Tenor::weeks(2).to_string(), "2W");
+        assert_eq!(Tenor::zero().to_string(), "0D");
+    }
+
+    #[test]
+    fn test_try_years() {
+        assert_eq!(Tenor::try_years(5), Ok(Tenor::years(5)));
+        assert!(Tenor::try_years(i32::MAX / 6).is_err());
+        assert!(Tenor::try_years(100_000).is_err());
+    }
+
+    #[test]
+    fn test_bucket_by_tenor_boundaries() {
+        let as_of = date(2022, 1, 15);
+        let dates = [
+            date(2022, 7, 15),
+            date(2022, 1, 14),
+            date(2022, 1, 15),
+            date(2022, 4, 14),
+            date(2022, 4, 15),
+        ];
+
+        let buckets = bucket_by_tenor(
+            as_of,
+            dates,
+            &[Tenor::months(6), Tenor::months(1), Tenor::months(3)],
+        );
+        let shape: Vec<(String, usize)> = buckets
+            .iter()
+            .map(|(bucket, dates)| (bucket.to_string(), dates.len()))
+            .collect();
+
+        assert_eq!(
+            shape,
+            vec![
+                ("0-1M".to_string(), 1),
+                ("1M-3M".to_string(), 1),
+                ("3M-6M".to_string(), 1),
+                ("6M+".to_string(), 1),
+            ]
+        );
+        assert_eq!(buckets[3].1, vec![date(2022, 7, 15)]);
+    }
+
+    #[test]
+    fn test_bucket_by_tenor_without_tenors() {
+        let as_of = date(2022, 1, 15);
+        let buckets = bucket_by_tenor(as_of, [as_of], &[]);
+
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].0.to_string(), "0+");
+        assert_eq!(buckets[0].1, vec![as_of]);
+    }
+}
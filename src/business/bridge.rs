@@ -0,0 +1,104 @@
+//! Bridge days between holidays and weekends
+//!
+//! [bridge_days] finds the single business days which are left between a holiday and another
+//! non-business day.
+use chrono::NaiveDate;
+
+use crate::holidays::HolidayCalendar;
+
+/// Business days within the year which are sandwiched between non-business days
+///
+/// These are the "puente" days, such as the Friday after a Thursday holiday, which many
+/// organisations give as an extra day off. A day only counts as a bridge when at least one of its
+/// neighbours is a holiday, a business day between two weekend days is not a bridge. A year outside
+/// the range of supported dates has no bridge days.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{bridge_days, Calendar};
+///
+/// // 2022-05-26 is a Thursday
+/// let calendar = Calendar::new().with_holidays([NaiveDate::from_ymd_opt(2022, 5, 26).unwrap()]);
+///
+/// assert_eq!(
+///     bridge_days(2022, &calendar),
+///     vec![NaiveDate::from_ymd_opt(2022, 5, 27).unwrap()]
+/// );
+/// ```
+pub fn bridge_days<C: HolidayCalendar + ?Sized>(year: i32, calendar: &C) -> Vec<NaiveDate> {
+    let (Some(start), Some(end)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Vec::new();
+    };
+
+    start
+        .iter_days()
+        .take_while(|d| *d <= end)
+        .filter(|d| is_bridge_day(*d, calendar))
+        .collect()
+}
+
+fn is_bridge_day<C: HolidayCalendar + ?Sized>(date: NaiveDate, calendar: &C) -> bool {
+    let (before, after) = match (date.pred_opt(), date.succ_opt()) {
+        (Some(before), Some(after)) => (before, after),
+        _ => return false,
+    };
+
+    calendar.is_business_day(date)
+        && !calendar.is_business_day(before)
+        && !calendar.is_business_day(after)
+        && (calendar.is_holiday(before) || calendar.is_holiday(after))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{business::Calendar, testing::date};
+
+    use super::*;
+
+    #[test]
+    fn test_bridge_before_holiday() {
+        // 2022-12-27 is a Tuesday so Monday the 26th is sandwiched
+        let calendar = Calendar::new().with_holidays([date(2022, 12, 27)]);
+        assert_eq!(bridge_days(2022, &calendar), vec![date(2022, 12, 26)]);
+    }
+
+    #[test]
+    fn test_no_bridge_across_two_days() {
+        // a Wednesday holiday leaves two working days either side
+        let calendar = Calendar::new().with_holidays([date(2022, 5, 25)]);
+        assert!(bridge_days(2022, &calendar).is_empty());
+    }
+
+    #[test]
+    fn test_any_holiday_calendar() {
+        let calendar = Calendar::new().with_holidays([date(2022, 5, 26)]);
+        let boxed: Box<dyn HolidayCalendar> = Box::new(calendar.clone());
+        assert_eq!(
+            bridge_days(2022, boxed.as_ref()),
+            bridge_days(2022, &calendar)
+        );
+    }
+
+    #[test]
+    fn test_years_out_of_range() {
+        assert!(bridge_days(i32::MAX, &Calendar::new()).is_empty());
+        assert!(bridge_days(i32::MIN, &Calendar::new()).is_empty());
+    }
+
+    #[test]
+    fn test_bridged_calendar() {
+        let holiday = date(2022, 5, 26);
+        let bridge = date(2022, 5, 27);
+        let calendar = Calendar::new()
+            .with_holidays([holiday])
+            .with_bridge_days(2022);
+
+        assert!(!calendar.is_business_day(bridge));
+        assert!(calendar.is_holiday(holiday));
+    }
+}
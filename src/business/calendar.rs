@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{trace, weekday::WeekdaySet};
+
+/// A business calendar made up of a weekend convention and a list of holidays
+///
+/// By default the weekend is Saturday and Sunday and there are no holidays.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::{NaiveDate, Weekday};
+/// # use calends::business::Calendar;
+///
+/// let calendar = Calendar::new()
+///     .with_weekend([Weekday::Fri, Weekday::Sat])
+///     .with_holidays([NaiveDate::from_ymd_opt(2022, 1, 3).unwrap()]);
+///
+/// assert!(!calendar.is_business_day(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()));
+/// assert!(calendar.is_business_day(NaiveDate::from_ymd_opt(2022, 1, 2).unwrap()));
+/// assert!(!calendar.is_business_day(NaiveDate::from_ymd_opt(2022, 1, 3).unwrap()));
+/// ```
+///
+/// A calendar cannot change once built, the `with_*` methods take it by value, so it is
+/// `Send + Sync` and can be shared behind an `Arc` without locking. Use a
+/// [CalendarRegistry](super::CalendarRegistry) for calendars which are replaced at runtime.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Calendar {
+    weekend: WeekdaySet,
+    holidays: BTreeSet<NaiveDate>,
+    /// Expected date of each tentative holiday with the number of days it may move either way
+    tentative: BTreeMap<NaiveDate, u32>,
+}
+
+impl Calendar {
+    /// Create a calendar with a Saturday and Sunday weekend and no holidays
+    pub fn new() -> Self {
+        Calendar {
+            weekend: WeekdaySet::WEEKEND,
+            holidays: BTreeSet::new(),
+            tentative: BTreeMap::new(),
+        }
+    }
+
+    /// Replace the days of the week which make up the weekend
+    ///
+    /// # Panics
+    ///
+    /// When every day of the week is part of the weekend, as there would be no business days
+    pub fn with_weekend(mut self, weekend: impl IntoIterator<Item = Weekday>) -> Self {
+        let weekend: WeekdaySet = weekend.into_iter().collect();
+        assert!(
+            weekend != WeekdaySet::ALL,
+            "a calendar needs at least one business day in the week"
+        );
+        self.weekend = weekend;
+        self
+    }
+
+    /// Add holidays to the calendar
+    pub fn with_holidays(mut self, holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(holidays);
+        self
+    }
+
+    /// Add a holiday whose date is not yet confirmed, such as one set by a moon sighting
+    ///
+    /// The holiday is treated as falling on its expected date, but it may move by up to `window`
+    /// days either way. See [Provisional](super::Provisional) for business day calculations which
+    /// flag results that depend on it.
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use calends::business::Calendar;
+    ///
+    /// let eid = NaiveDate::from_ymd_opt(2023, 4, 21).unwrap();
+    /// let calendar = Calendar::new().with_tentative_holiday(eid, 1);
+    ///
+    /// assert!(calendar.is_holiday(eid));
+    /// assert!(calendar.is_tentative(NaiveDate::from_ymd_opt(2023, 4, 20).unwrap()));
+    /// assert!(!calendar.is_tentative(NaiveDate::from_ymd_opt(2023, 4, 19).unwrap()));
+    /// ```
+    pub fn with_tentative_holiday(mut self, expected: NaiveDate, window: u32) -> Self {
+        self.holidays.insert(expected);
+        self.tentative.insert(expected, window);
+        self
+    }
+
+    /// Tentative holidays in ascending order of their expected date, with their window in days
+    pub fn tentative_holidays(&self) -> impl Iterator<Item = (NaiveDate, u32)> + '_ {
+        self.tentative.iter().map(|(date, window)| (*date, *window))
+    }
+
+    /// Whether the date is within the window of a tentative holiday, so whether it is a business
+    /// day may still change
+    pub fn is_tentative(&self, date: NaiveDate) -> bool {
+        self.tentative
+            .iter()
+            .any(|(expected, window)| (date - *expected).num_days().abs() <= i64::from(*window))
+    }
+
+    /// Derive a calendar which also treats the bridge days of the year as holidays
+    ///
+    /// See [bridge_days](super::bridge_days) for which days are bridge days.
+    pub fn with_bridge_days(&self, year: i32) -> Self {
+        let bridges = super::bridge_days(year, self);
+        self.clone().with_holidays(bridges)
+    }
+
+    /// Days of the week which are not worked
+    pub fn weekend(&self) -> WeekdaySet {
+        self.weekend
+    }
+
+    /// Holidays in ascending order
+    pub fn holidays(&self) -> impl Iterator<Item = &NaiveDate> {
+        self.holidays.iter()
+    }
+
+    /// Whether the date falls on the weekend
+    #[inline]
+    pub fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.weekend.contains(date.weekday())
+    }
+
+    /// Whether the date is one of the calendar's holidays
+    #[inline]
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date)
+    }
+
+    /// Whether the date is neither a weekend nor a holiday
+    #[inline]
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        let business_day = !self.is_weekend(date) && !self.is_holiday(date);
+        if !business_day {
+            trace::trace!(%date, holiday = self.is_holiday(date), "not a business day");
+        }
+        business_day
+    }
+}
+
+impl std::fmt::Debug for Calendar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Calendar");
+        debug
+            .field("weekend", &self.weekend)
+            .field("holidays", &self.holidays);
+        if !self.tentative.is_empty() {
+            debug.field("tentative", &self.tentative);
+        }
+        debug.finish()
+    }
+}
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Calendar::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_default_weekend() {
+        let calendar = Calendar::default();
+        // 2022-01-01 is a Saturday
+        assert!(calendar.is_weekend(date(2022, 1, 1)));
+        assert!(calendar.is_weekend(date(2022, 1, 2)));
+        assert!(calendar.is_business_day(date(2022, 1, 3)));
+    }
+
+    #[test]
+    fn test_holidays() {
+        let calendar = Calendar::new().with_holidays([date(2022, 12, 26), date(2022, 1, 3)]);
+
+        assert!(calendar.is_holiday(date(2022, 1, 3)));
+        assert!(!calendar.is_business_day(date(2022, 12, 26)));
+        assert_eq!(calendar.holidays().next(), Some(&date(2022, 1, 3)));
+    }
+
+    #[test]
+    fn test_debug_is_sorted() {
+        let calendar = Calendar::new()
+            .with_weekend([Weekday::Sun, Weekday::Fri])
+            .with_holidays([date(2022, 12, 26), date(2022, 1, 3)]);
+
+        assert_eq!(
+            format!("{:?}", calendar),
+            "Calendar { weekend: WeekdaySet(FR,SU), holidays: {2022-01-03, 2022-12-26} }"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_weekend_without_business_days() {
+        Calendar::new().with_weekend([
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]);
+    }
+}
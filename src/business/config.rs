@@ -0,0 +1,516 @@
+//! Calendars described by configuration
+//!
+//! With the `serde` feature a [CalendarConfig] can be deserialized from any serde format such as
+//! TOML or YAML, so holiday lists can be maintained as configuration rather than code. A calendar
+//! can inherit from a base calendar, adding its own holidays and optionally replacing the weekend.
+//! When it replaces the weekend the inherited holidays are observed against the new weekend.
+//!
+//! For example a TOML file with a table for each calendar, which deserializes into a
+//! `BTreeMap<String, CalendarConfig>` for [build_calendars]:
+//!
+//! ```toml
+//! [us]
+//! holidays = [
+//!     { name = "New Year's Day", month = 1, day = 1, observed = "nearest_weekday" },
+//!     { name = "Thanksgiving", month = 11, weekday = "4TH" },
+//! ]
+//!
+//! [us-nyse]
+//! inherits = "us"
+//! holidays = [{ name = "Good Friday", easter = -2 }]
+//! dates = ["2025-01-09"]
+//! ```
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+use chrono::{Datelike, Days, Duration, NaiveDate};
+
+use crate::{
+    error::CalendsError,
+    month::Month,
+    util::checked_easter_sunday,
+    weekday::{NthWeekday, WeekdaySet},
+};
+
+use super::Calendar;
+
+/// When a holiday falling on the weekend is observed
+///
+/// The weekend is the one configured for the calendar, so with a Friday and Saturday weekend a
+/// Friday holiday is observed on the Thursday before for [NearestWeekday](Observed::NearestWeekday)
+/// and on the Sunday after for [FollowingMonday](Observed::FollowingMonday).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Observed {
+    /// Only on the day itself
+    #[default]
+    Exact,
+    /// On the nearest day outside the weekend, the later one when two are as near
+    ///
+    /// With a Saturday and Sunday weekend, Saturday holidays are observed on the Friday before and
+    /// Sunday holidays on the Monday after.
+    NearestWeekday,
+    /// On the first day after the weekend, the following Monday for a Saturday and Sunday weekend
+    FollowingMonday,
+}
+
+impl Observed {
+    /// The date the holiday is observed on in a calendar with the weekend given
+    ///
+    /// # Panics
+    ///
+    /// When the observed date is outside the range of supported dates, see
+    /// [checked_apply](Observed::checked_apply) for a fallible alternative
+    pub fn apply(&self, date: NaiveDate, weekend: WeekdaySet) -> NaiveDate {
+        self.checked_apply(date, weekend).unwrap()
+    }
+
+    /// The date the holiday is observed on in a calendar with the weekend given, if it is within
+    /// the range of supported dates
+    ///
+    /// A weekend of every day of the week leaves nowhere to move the holiday to, so it stays put.
+    pub fn checked_apply(&self, date: NaiveDate, weekend: WeekdaySet) -> Option<NaiveDate> {
+        let outside = |date: &NaiveDate| !weekend.contains(date.weekday());
+        if outside(&date) || weekend == WeekdaySet::ALL {
+            return Some(date);
+        }
+
+        let after = |days| date.checked_add_days(Days::new(days));
+        let before = |days| date.checked_sub_days(Days::new(days));
+        match self {
+            Observed::Exact => Some(date),
+            Observed::NearestWeekday => (1..7).find_map(|days| {
+                after(days)
+                    .filter(outside)
+                    .or_else(|| before(days).filter(outside))
+            }),
+            Observed::FollowingMonday => (1..7).find_map(|days| after(days).filter(outside)),
+        }
+    }
+}
+
+/// A holiday which recurs every year
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged, deny_unknown_fields))]
+pub enum HolidayRule {
+    /// The same day of the same month, years without the day such as February 29th are skipped
+    Fixed {
+        name: String,
+        month: u32,
+        day: u32,
+        #[cfg_attr(feature = "serde", serde(default))]
+        observed: Observed,
+    },
+    /// An occurrence of a weekday within a month, e.g. `4TH` for the fourth Thursday
+    Weekday {
+        name: String,
+        month: u32,
+        weekday: NthWeekday,
+    },
+    /// A number of days from Easter Sunday, e.g. `-2` for Good Friday
+    Easter { name: String, easter: i32 },
+}
+
+impl HolidayRule {
+    pub fn name(&self) -> &str {
+        match self {
+            HolidayRule::Fixed { name, .. }
+            | HolidayRule::Weekday { name, .. }
+            | HolidayRule::Easter { name, .. } => name,
+        }
+    }
+
+    /// The date of the holiday in the year, observed dates are already applied for the weekend
+    pub fn date_in(&self, year: i32, weekend: WeekdaySet) -> Option<NaiveDate> {
+        match self {
+            HolidayRule::Fixed {
+                month,
+                day,
+                observed,
+                ..
+            } => NaiveDate::from_ymd_opt(year, *month, *day)
+                .and_then(|d| observed.checked_apply(d, weekend)),
+            HolidayRule::Weekday { month, weekday, .. } => {
+                Month::from_number(*month).and_then(|month| weekday.resolve(year, month))
+            }
+            HolidayRule::Easter { easter, .. } => {
+                checked_easter_sunday(year)?.checked_add_signed(Duration::days((*easter).into()))
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), CalendsError> {
+        let valid = match self {
+            // checked against a leap year so that February 29th is allowed
+            HolidayRule::Fixed { month, day, .. } => {
+                NaiveDate::from_ymd_opt(2000, *month, *day).is_some()
+            }
+            HolidayRule::Weekday { month, .. } => (1..=12).contains(month),
+            HolidayRule::Easter { easter, .. } => easter.abs() <= 366,
+        };
+
+        match valid {
+            true => Ok(()),
+            false => Err(CalendsError::InvalidHoliday(self.name().to_string())),
+        }
+    }
+}
+
+/// Configuration for a [Calendar]
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::config::CalendarConfig;
+/// # #[cfg(feature = "serde")] {
+///
+/// let config: CalendarConfig = serde_json::from_str(
+///     r#"{
+///         "weekend": "FR,SA",
+///         "holidays": [{ "name": "Labour Day", "month": 5, "day": 1 }],
+///         "dates": ["2022-04-18"]
+///     }"#,
+/// )?;
+///
+/// let calendar = config.build(2022..=2023)?;
+/// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2023, 5, 1).unwrap()));
+/// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2022, 4, 18).unwrap()));
+/// assert!(calendar.is_weekend(NaiveDate::from_ymd_opt(2022, 4, 22).unwrap()));
+/// # }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct CalendarConfig {
+    /// Name of the calendar this one extends
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub inherits: Option<String>,
+    /// Weekend of the calendar, when missing it is inherited or defaults to Saturday and Sunday
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub weekend: Option<WeekdaySet>,
+    /// Holidays which recur every year
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub holidays: Vec<HolidayRule>,
+    /// One off holidays
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dates: Vec<NaiveDate>,
+    /// Dates which are not holidays even though a rule or base calendar has them
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exclude: Vec<NaiveDate>,
+}
+
+impl CalendarConfig {
+    /// Build a calendar with the holidays of the years given
+    ///
+    /// Fails when the config inherits from another calendar, use [build_calendars] to resolve
+    /// inheritance.
+    pub fn build(&self, years: RangeInclusive<i32>) -> Result<Calendar, CalendsError> {
+        self.resolve(&BTreeMap::new(), &years, &mut Vec::new())
+            .map(|(weekend, holidays)| calendar(weekend, holidays))
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        configs: &'a BTreeMap<String, CalendarConfig>,
+        years: &RangeInclusive<i32>,
+        seen: &mut Vec<&'a str>,
+    ) -> Result<(WeekdaySet, BTreeSet<NaiveDate>), CalendsError> {
+        let chain = self.chain(configs, seen)?;
+
+        // holidays are observed against the weekend of this calendar, inherited ones included
+        let weekend = chain.iter().rev().find_map(|config| config.weekend);
+        let weekend = match weekend.unwrap_or(WeekdaySet::WEEKEND) {
+            WeekdaySet::ALL => return Err(CalendsError::NoBusinessDays),
+            weekend => weekend,
+        };
+
+        let mut holidays = BTreeSet::new();
+        for config in chain {
+            for rule in &config.holidays {
+                rule.validate()?;
+                holidays.extend(years.clone().filter_map(|year| rule.date_in(year, weekend)));
+            }
+            holidays.extend(config.dates.iter().copied());
+            for date in &config.exclude {
+                holidays.remove(date);
+            }
+        }
+
+        Ok((weekend, holidays))
+    }
+
+    /// This config and the ones it inherits from, the base calendar first
+    fn chain<'a>(
+        &'a self,
+        configs: &'a BTreeMap<String, CalendarConfig>,
+        seen: &mut Vec<&'a str>,
+    ) -> Result<Vec<&'a CalendarConfig>, CalendsError> {
+        let mut chain = match &self.inherits {
+            Some(base) if seen.contains(&base.as_str()) => {
+                return Err(CalendsError::CyclicCalendar(base.clone()))
+            }
+            Some(base) => {
+                let config = configs
+                    .get(base)
+                    .ok_or_else(|| CalendsError::UnknownCalendar(base.clone()))?;
+                seen.push(base);
+                config.chain(configs, seen)?
+            }
+            None => Vec::new(),
+        };
+        chain.push(self);
+
+        Ok(chain)
+    }
+}
+
+/// Build each of a set of named calendars, resolving inheritance between them
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use chrono::NaiveDate;
+/// # use calends::business::config::{build_calendars, CalendarConfig};
+/// # #[cfg(feature = "serde")] {
+///
+/// let configs: BTreeMap<String, CalendarConfig> = serde_json::from_str(
+///     r#"{
+///         "uk": { "holidays": [{ "name": "Christmas Day", "month": 12, "day": 25, "observed": "following_monday" }] },
+///         "uk-office": { "inherits": "uk", "dates": ["2022-12-23"] }
+///     }"#,
+/// )?;
+///
+/// let calendars = build_calendars(&configs, 2022..=2022)?;
+/// let office = &calendars["uk-office"];
+/// assert!(office.is_holiday(NaiveDate::from_ymd_opt(2022, 12, 23).unwrap()));
+/// assert!(office.is_holiday(NaiveDate::from_ymd_opt(2022, 12, 26).unwrap()));
+/// # }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn build_calendars(
+    configs: &BTreeMap<String, CalendarConfig>,
+    years: RangeInclusive<i32>,
+) -> Result<BTreeMap<String, Calendar>, CalendsError> {
+    configs
+        .iter()
+        .map(|(name, config)| {
+            let (weekend, holidays) = config.resolve(configs, &years, &mut vec![name.as_str()])?;
+            Ok((name.clone(), calendar(weekend, holidays)))
+        })
+        .collect()
+}
+
+fn calendar(weekend: WeekdaySet, holidays: BTreeSet<NaiveDate>) -> Calendar {
+    Calendar::new()
+        .with_weekend(weekend)
+        .with_holidays(holidays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+    use chrono::Weekday;
+
+    #[cfg(feature = "serde")]
+    fn configs(json: &str) -> BTreeMap<String, CalendarConfig> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_observed() {
+        // 2022-01-01 is a Saturday and 2022-12-25 a Sunday
+        let weekend = WeekdaySet::WEEKEND;
+        assert_eq!(
+            Observed::NearestWeekday.apply(date(2022, 1, 1), weekend),
+            date(2021, 12, 31)
+        );
+        assert_eq!(
+            Observed::NearestWeekday.apply(date(2022, 12, 25), weekend),
+            date(2022, 12, 26)
+        );
+        assert_eq!(
+            Observed::FollowingMonday.apply(date(2022, 1, 1), weekend),
+            date(2022, 1, 3)
+        );
+        assert_eq!(
+            Observed::Exact.apply(date(2022, 1, 1), weekend),
+            date(2022, 1, 1)
+        );
+        assert_eq!(
+            Observed::NearestWeekday.apply(date(2022, 1, 3), weekend),
+            date(2022, 1, 3)
+        );
+    }
+
+    #[test]
+    fn test_observed_with_other_weekends() {
+        // 2022-01-07 is a Friday
+        let weekend = WeekdaySet::single(Weekday::Fri).with(Weekday::Sat);
+        assert_eq!(
+            Observed::NearestWeekday.apply(date(2022, 1, 7), weekend),
+            date(2022, 1, 6)
+        );
+        assert_eq!(
+            Observed::NearestWeekday.apply(date(2022, 1, 8), weekend),
+            date(2022, 1, 9)
+        );
+        assert_eq!(
+            Observed::FollowingMonday.apply(date(2022, 1, 7), weekend),
+            date(2022, 1, 9)
+        );
+        // a Saturday holiday is left alone when only Sunday is the weekend
+        let weekend = WeekdaySet::single(Weekday::Sun);
+        assert_eq!(
+            Observed::NearestWeekday.apply(date(2022, 1, 1), weekend),
+            date(2022, 1, 1)
+        );
+        assert_eq!(
+            Observed::FollowingMonday.apply(date(2022, 1, 2), weekend),
+            date(2022, 1, 3)
+        );
+
+        let config = CalendarConfig {
+            weekend: Some(weekend),
+            holidays: vec![HolidayRule::Fixed {
+                name: "New Year's Eve".to_string(),
+                month: 12,
+                day: 31,
+                observed: Observed::NearestWeekday,
+            }],
+            ..Default::default()
+        };
+        // 2023-12-31 is a Sunday
+        let calendar = config.build(2023..=2023).unwrap();
+        assert!(calendar.is_holiday(date(2024, 1, 1)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_rules() {
+        let config: CalendarConfig = serde_json::from_str(
+            r#"{ "holidays": [
+                { "name": "Thanksgiving", "month": 11, "weekday": "4TH" },
+                { "name": "Easter Monday", "easter": 1 },
+                { "name": "Leap Day", "month": 2, "day": 29 }
+            ] }"#,
+        )
+        .unwrap();
+        let calendar = config.build(2023..=2024).unwrap();
+
+        assert_eq!(
+            calendar.holidays().copied().collect::<Vec<_>>(),
+            vec![
+                date(2023, 4, 10),
+                date(2023, 11, 23),
+                date(2024, 2, 29),
+                date(2024, 4, 1),
+                date(2024, 11, 28)
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_inheritance() {
+        let configs = configs(
+            r#"{
+                "base": { "weekend": "SU", "holidays": [{ "name": "May Day", "month": 5, "day": 1 }] },
+                "child": { "inherits": "base", "exclude": ["2022-05-01"], "dates": ["2022-05-02"] },
+                "grandchild": { "inherits": "child", "weekend": "SA,SU" }
+            }"#,
+        );
+        let calendars = build_calendars(&configs, 2022..=2022).unwrap();
+
+        assert!(calendars["base"].is_holiday(date(2022, 5, 1)));
+        assert!(!calendars["child"].is_holiday(date(2022, 5, 1)));
+        assert_eq!(
+            calendars["child"].weekend(),
+            WeekdaySet::single(Weekday::Sun)
+        );
+        assert!(calendars["grandchild"].is_holiday(date(2022, 5, 2)));
+        assert_eq!(calendars["grandchild"].weekend(), WeekdaySet::WEEKEND);
+    }
+
+    #[test]
+    fn test_inherited_holidays_use_the_weekend_of_the_calendar() {
+        // 2022-01-01 is a Saturday, observed on the Friday before with a Saturday and Sunday
+        // weekend but on the Sunday after with a Friday and Saturday one
+        let base = CalendarConfig {
+            holidays: vec![HolidayRule::Fixed {
+                name: "New Year's Day".to_string(),
+                month: 1,
+                day: 1,
+                observed: Observed::NearestWeekday,
+            }],
+            ..Default::default()
+        };
+        let child = CalendarConfig {
+            inherits: Some("base".to_string()),
+            weekend: Some(WeekdaySet::single(Weekday::Fri).with(Weekday::Sat)),
+            ..Default::default()
+        };
+        let configs = BTreeMap::from([("base".to_string(), base), ("child".to_string(), child)]);
+        let calendars = build_calendars(&configs, 2022..=2022).unwrap();
+
+        assert!(calendars["base"].is_holiday(date(2021, 12, 31)));
+        assert!(calendars["child"].is_holiday(date(2022, 1, 2)));
+        assert!(!calendars["child"].is_holiday(date(2021, 12, 31)));
+    }
+
+    #[test]
+    fn test_outside_the_supported_dates() {
+        let rule = HolidayRule::Easter {
+            name: "Easter Monday".to_string(),
+            easter: 1,
+        };
+        let max = NaiveDate::MAX.year();
+        assert_eq!(rule.date_in(max + 1, WeekdaySet::WEEKEND), None);
+        assert!(rule.date_in(max, WeekdaySet::WEEKEND).is_some());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_errors() {
+        let cyclic = configs(r#"{ "a": { "inherits": "b" }, "b": { "inherits": "a" } }"#);
+        assert!(matches!(
+            build_calendars(&cyclic, 2022..=2022),
+            Err(CalendsError::CyclicCalendar(_))
+        ));
+
+        let unknown = configs(r#"{ "a": { "inherits": "missing" } }"#);
+        assert_eq!(
+            build_calendars(&unknown, 2022..=2022),
+            Err(CalendsError::UnknownCalendar("missing".to_string()))
+        );
+
+        let invalid =
+            configs(r#"{ "a": { "holidays": [{ "name": "Nope", "month": 2, "day": 30 }] } }"#);
+        assert_eq!(
+            build_calendars(&invalid, 2022..=2022),
+            Err(CalendsError::InvalidHoliday("Nope".to_string()))
+        );
+
+        let every_day: CalendarConfig =
+            serde_json::from_str(r#"{ "weekend": "MO,TU,WE,TH,FR,SA,SU" }"#).unwrap();
+        assert_eq!(
+            every_day.build(2022..=2022),
+            Err(CalendsError::NoBusinessDays)
+        );
+
+        assert!(serde_json::from_str::<CalendarConfig>(r#"{ "weekends": "SU" }"#).is_err());
+    }
+}
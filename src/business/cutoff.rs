@@ -0,0 +1,100 @@
+//! Business dates of timestamps
+//!
+//! [business_date] assigns a timestamp to the business day it is processed on, given the time of
+//! day after which work rolls to the next business day.
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone};
+
+use crate::{holidays::HolidayCalendar, trace};
+
+use super::next_business_day;
+
+/// The business date a timestamp is processed on
+///
+/// Back offices typically stop accepting work for the day at a cutoff time. A timestamp before
+/// the cutoff on a business day belongs to that day, anything at or after the cutoff rolls to the
+/// next business day. Timestamps on non-business days are always processed on the next business
+/// day.
+///
+/// The cutoff is compared against the local time of `now` in its own timezone.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+/// # use calends::business::{business_date, Calendar};
+///
+/// let cutoff = NaiveTime::from_hms_opt(17, 0, 0).unwrap();
+/// let calendar = Calendar::new();
+///
+/// // 2022-01-07 is a Friday
+/// let before = Utc.with_ymd_and_hms(2022, 1, 7, 16, 59, 59).unwrap();
+/// let after = Utc.with_ymd_and_hms(2022, 1, 7, 17, 0, 0).unwrap();
+///
+/// assert_eq!(
+///     business_date(before, cutoff, &calendar),
+///     NaiveDate::from_ymd_opt(2022, 1, 7).unwrap()
+/// );
+/// assert_eq!(
+///     business_date(after, cutoff, &calendar),
+///     NaiveDate::from_ymd_opt(2022, 1, 10).unwrap()
+/// );
+/// ```
+pub fn business_date<Tz: TimeZone, C: HolidayCalendar + ?Sized>(
+    now: DateTime<Tz>,
+    cutoff: NaiveTime,
+    calendar: &C,
+) -> NaiveDate {
+    let local = now.naive_local();
+    let date = local.date();
+
+    if local.time() < cutoff && calendar.is_business_day(date) {
+        trace::debug!(%local, %cutoff, "before cutoff on a business day");
+        date
+    } else {
+        trace::debug!(%local, %cutoff, "rolled to the next business day");
+        next_business_day(date, calendar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, Utc};
+
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    fn cutoff() -> NaiveTime {
+        NaiveTime::from_hms_opt(17, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_business_date_on_weekend() {
+        // 2022-01-08 is a Saturday
+        let now = Utc.with_ymd_and_hms(2022, 1, 8, 9, 0, 0).unwrap();
+        assert_eq!(
+            business_date(now, cutoff(), &Calendar::new()),
+            date(2022, 1, 10)
+        );
+    }
+
+    #[test]
+    fn test_business_date_rolls_past_holiday() {
+        let calendar = Calendar::new().with_holidays([date(2022, 1, 10)]);
+        let now = Utc.with_ymd_and_hms(2022, 1, 7, 18, 0, 0).unwrap();
+        assert_eq!(business_date(now, cutoff(), &calendar), date(2022, 1, 11));
+    }
+
+    #[test]
+    fn test_business_date_uses_local_time() {
+        // 2022-01-06 22:30 UTC is already 2022-01-07 07:30 in UTC+9
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        let now = Utc
+            .with_ymd_and_hms(2022, 1, 6, 22, 30, 0)
+            .unwrap()
+            .with_timezone(&tokyo);
+        assert_eq!(
+            business_date(now, cutoff(), &Calendar::new()),
+            date(2022, 1, 7)
+        );
+    }
+}
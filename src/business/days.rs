@@ -0,0 +1,212 @@
+//! Stepping and counting business days
+//!
+//! Each function takes any [HolidayCalendar], so a [Calendar](super::Calendar), a built in
+//! country calendar or a `Box<dyn HolidayCalendar>` can be used alike.
+use chrono::NaiveDate;
+
+use crate::{holidays::HolidayCalendar, trace};
+
+/// The first business day after the date supplied
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{next_business_day, Calendar};
+///
+/// // 2022-01-07 is a Friday
+/// assert_eq!(
+///     next_business_day(NaiveDate::from_ymd_opt(2022, 1, 7).unwrap(), &Calendar::new()),
+///     NaiveDate::from_ymd_opt(2022, 1, 10).unwrap()
+/// );
+/// ```
+pub fn next_business_day<C: HolidayCalendar + ?Sized>(date: NaiveDate, calendar: &C) -> NaiveDate {
+    let mut next = date.succ_opt().unwrap();
+    while !calendar.is_business_day(next) {
+        next = next.succ_opt().unwrap();
+    }
+    trace::debug!(%date, %next, "next business day");
+    next
+}
+
+/// The last business day before the date supplied
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{prev_business_day, Calendar};
+///
+/// // 2022-01-10 is a Monday
+/// assert_eq!(
+///     prev_business_day(NaiveDate::from_ymd_opt(2022, 1, 10).unwrap(), &Calendar::new()),
+///     NaiveDate::from_ymd_opt(2022, 1, 7).unwrap()
+/// );
+/// ```
+pub fn prev_business_day<C: HolidayCalendar + ?Sized>(date: NaiveDate, calendar: &C) -> NaiveDate {
+    let mut prev = date.pred_opt().unwrap();
+    while !calendar.is_business_day(prev) {
+        prev = prev.pred_opt().unwrap();
+    }
+    trace::debug!(%date, %prev, "previous business day");
+    prev
+}
+
+/// Whether the date is a business day in the calendar
+///
+/// The same as [HolidayCalendar::is_business_day].
+pub fn is_business_day<C: HolidayCalendar + ?Sized>(date: NaiveDate, calendar: &C) -> bool {
+    calendar.is_business_day(date)
+}
+
+/// Move the given number of business days forwards, or backwards when negative
+///
+/// Adding zero days gives the date itself even when it is not a business day. Starting from a
+/// business day, [business_days_between] the date and the result gives back `n`.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{add_business_days, Calendar};
+///
+/// // 2022-01-07 is a Friday and 2022-01-10 a holiday
+/// let calendar = Calendar::new().with_holidays([NaiveDate::from_ymd_opt(2022, 1, 10).unwrap()]);
+/// let friday = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap();
+///
+/// assert_eq!(add_business_days(friday, 2, &calendar), NaiveDate::from_ymd_opt(2022, 1, 12).unwrap());
+/// assert_eq!(add_business_days(friday, -5, &calendar), NaiveDate::from_ymd_opt(2021, 12, 31).unwrap());
+/// ```
+pub fn add_business_days<C: HolidayCalendar + ?Sized>(
+    date: NaiveDate,
+    n: i64,
+    calendar: &C,
+) -> NaiveDate {
+    let step = if n < 0 {
+        prev_business_day
+    } else {
+        next_business_day
+    };
+    (0..n.unsigned_abs()).fold(date, |date, _| step(date, calendar))
+}
+
+/// Move the given number of business days backwards, or forwards when negative
+///
+/// See [add_business_days].
+pub fn sub_business_days<C: HolidayCalendar + ?Sized>(
+    date: NaiveDate,
+    n: i64,
+    calendar: &C,
+) -> NaiveDate {
+    add_business_days(date, -n, calendar)
+}
+
+/// Count the business days after `start` up to and including `end`
+///
+/// When `end` is before `start` the count is negative, so that swapping the arguments negates the
+/// result.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{business_days_between, Calendar};
+///
+/// // Friday to the following Monday is a single business day
+/// let friday = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap();
+/// let monday = NaiveDate::from_ymd_opt(2022, 1, 10).unwrap();
+///
+/// assert_eq!(business_days_between(friday, monday, &Calendar::new()), 1);
+/// assert_eq!(business_days_between(monday, friday, &Calendar::new()), -1);
+/// ```
+pub fn business_days_between<C: HolidayCalendar + ?Sized>(
+    start: NaiveDate,
+    end: NaiveDate,
+    calendar: &C,
+) -> i64 {
+    if end < start {
+        return -business_days_between(end, start, calendar);
+    }
+
+    start
+        .iter_days()
+        .skip(1)
+        .take_while(|d| *d <= end)
+        .filter(|d| calendar.is_business_day(*d))
+        .count() as i64
+}
+
+/// Number of business days from `as_of` until the target, negative once the target has passed
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{business_days_until, Calendar};
+///
+/// // 2022-01-07 is a Friday
+/// let target = NaiveDate::from_ymd_opt(2022, 1, 11).unwrap();
+/// let as_of = NaiveDate::from_ymd_opt(2022, 1, 7).unwrap();
+///
+/// assert_eq!(business_days_until(target, as_of, &Calendar::new()), 2);
+/// assert_eq!(business_days_until(as_of, target, &Calendar::new()), -2);
+/// ```
+pub fn business_days_until<C: HolidayCalendar + ?Sized>(
+    target: NaiveDate,
+    as_of: NaiveDate,
+    calendar: &C,
+) -> i64 {
+    business_days_between(as_of, target, calendar)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    #[quickcheck]
+    fn adding_business_days_is_counted_back(offset: u16, n: i16) -> bool {
+        let calendar = Calendar::new().with_holidays([date(2022, 1, 10), date(2022, 12, 26)]);
+        let start = next_business_day(
+            date(2021, 12, 31) + chrono::Duration::days(offset.into()),
+            &calendar,
+        );
+        let n = i64::from(n % 500);
+
+        let end = add_business_days(start, n, &calendar);
+        business_days_between(start, end, &calendar) == n
+            && sub_business_days(end, n, &calendar) == start
+    }
+
+    #[test]
+    fn test_next_business_day_skips_holidays() {
+        let calendar = Calendar::new().with_holidays([date(2022, 1, 10)]);
+
+        assert_eq!(
+            next_business_day(date(2022, 1, 7), &calendar),
+            date(2022, 1, 11)
+        );
+        assert_eq!(
+            next_business_day(date(2022, 1, 11), &calendar),
+            date(2022, 1, 12)
+        );
+    }
+
+    #[test]
+    fn test_business_days_between() {
+        let calendar = Calendar::new().with_holidays([date(2022, 1, 10)]);
+        let start = date(2022, 1, 3);
+
+        assert_eq!(business_days_between(start, start, &calendar), 0);
+        assert_eq!(
+            business_days_between(start, date(2022, 1, 14), &calendar),
+            8
+        );
+        assert_eq!(
+            business_days_between(date(2022, 1, 14), start, &calendar),
+            -8
+        );
+    }
+}
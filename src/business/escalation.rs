@@ -0,0 +1,138 @@
+//! Escalating steps such as dunning notices
+//!
+//! [escalation_series] places each step a duration on from the one before and rolls every step
+//! onto a business day.
+use chrono::NaiveDate;
+
+use crate::{holidays::HolidayCalendar, RelativeDuration};
+
+use super::RollPolicy;
+
+/// A step of an [EscalationSeries]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EscalationStep {
+    /// The date before business day adjustment
+    pub scheduled: NaiveDate,
+    /// The date the step takes place
+    pub date: NaiveDate,
+}
+
+/// Dates of a series of escalating steps, such as the reminders and notices of a dunning process
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EscalationSeries {
+    start: NaiveDate,
+    steps: Vec<EscalationStep>,
+}
+
+impl EscalationSeries {
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    pub fn steps(&self) -> &[EscalationStep] {
+        &self.steps
+    }
+
+    /// Number of steps in the series
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Dates on which the steps take place
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.steps.iter().map(|step| step.date)
+    }
+
+    /// The first step taking place after the date along with its position in the series
+    pub fn next_after(&self, date: NaiveDate) -> Option<(usize, &EscalationStep)> {
+        self.steps
+            .iter()
+            .enumerate()
+            .find(|(_, step)| step.date > date)
+    }
+}
+
+/// Dates of escalating steps, each step's duration counted on from the previous step
+///
+/// Durations are added to the scheduled dates before adjustment, so a step rolled over a weekend
+/// does not push back the steps after it.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::RelativeDuration;
+/// # use calends::business::{escalation_series, Calendar, RollPolicy};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let steps = [
+///     RelativeDuration::days(3),
+///     RelativeDuration::days(7),
+///     RelativeDuration::days(14),
+///     RelativeDuration::months(1),
+/// ];
+///
+/// let series = escalation_series(date(1, 3), &steps, RollPolicy::Following, &Calendar::new());
+/// assert_eq!(
+///     series.dates().collect::<Vec<_>>(),
+///     vec![date(1, 6), date(1, 13), date(1, 27), date(2, 28)]
+/// );
+/// ```
+pub fn escalation_series<C: HolidayCalendar + ?Sized>(
+    start: NaiveDate,
+    steps: &[RelativeDuration],
+    adjust: RollPolicy,
+    calendar: &C,
+) -> EscalationSeries {
+    let mut scheduled = start;
+    let steps = steps
+        .iter()
+        .map(|step| {
+            scheduled = scheduled + *step;
+            EscalationStep {
+                scheduled,
+                date: adjust.apply(scheduled, calendar),
+            }
+        })
+        .collect();
+
+    EscalationSeries { start, steps }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    #[test]
+    fn test_adjustment_does_not_accumulate() {
+        // 2022-01-08 is a Saturday
+        let steps = [RelativeDuration::days(5), RelativeDuration::days(2)];
+        let series = escalation_series(
+            date(2022, 1, 3),
+            &steps,
+            RollPolicy::Following,
+            &Calendar::new(),
+        );
+
+        assert_eq!(
+            series.steps(),
+            &[
+                EscalationStep {
+                    scheduled: date(2022, 1, 8),
+                    date: date(2022, 1, 10)
+                },
+                EscalationStep {
+                    scheduled: date(2022, 1, 10),
+                    date: date(2022, 1, 10)
+                },
+            ]
+        );
+        assert_eq!(series.next_after(date(2022, 1, 3)).map(|(i, _)| i), Some(0));
+        assert!(series.next_after(date(2022, 1, 10)).is_none());
+    }
+}
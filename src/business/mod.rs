@@ -0,0 +1,33 @@
+//! Business day calculations
+//!
+//! A [Calendar] describes which days are working days, combining a weekend convention with a
+//! set of holidays. The functions within this module use it to move between business days, and
+//! also accept any other [HolidayCalendar](crate::holidays::HolidayCalendar) such as the built-in
+//! [UsFederal](crate::holidays::UsFederal).
+pub mod bridge;
+pub mod calendar;
+pub mod config;
+pub mod cutoff;
+pub mod days;
+pub mod escalation;
+pub mod provisional;
+pub mod registry;
+pub mod reminders;
+pub mod roll;
+#[cfg(feature = "async")]
+pub mod source;
+
+pub use bridge::*;
+pub use calendar::Calendar;
+pub use config::{build_calendars, CalendarConfig};
+pub use cutoff::*;
+pub use days::*;
+pub use escalation::{escalation_series, EscalationSeries, EscalationStep};
+pub use provisional::{
+    business_days_between_provisional, next_business_day_provisional, Provisional,
+};
+pub use registry::{CalendarHandle, CalendarRegistry};
+pub use reminders::{reminders_before, ReminderOffset};
+pub use roll::{roll_date, RollPolicy};
+#[cfg(feature = "async")]
+pub use source::{AsyncCalendarSource, SnapshotCalendar};
@@ -0,0 +1,143 @@
+//! Business day results which depend on tentative holidays
+//!
+//! Some holidays are only confirmed shortly before they happen, such as those set by sighting the
+//! moon. [Provisional] flags results which would change if such a holiday moves.
+use chrono::NaiveDate;
+
+use crate::holidays::HolidayCalendar;
+
+use super::next_business_day;
+
+/// A result which may change once the calendar's tentative holidays are confirmed
+///
+/// See [Calendar::with_tentative_holiday](super::Calendar::with_tentative_holiday) and
+/// [HolidayCalendar::is_tentative].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Provisional<T> {
+    /// Depends only on confirmed holidays
+    Confirmed(T),
+    /// Depends on a day within the window of a tentative holiday
+    Tentative(T),
+}
+
+impl<T> Provisional<T> {
+    fn new(value: T, tentative: bool) -> Self {
+        match tentative {
+            true => Provisional::Tentative(value),
+            false => Provisional::Confirmed(value),
+        }
+    }
+
+    /// The result as best known now
+    pub fn value(self) -> T {
+        match self {
+            Provisional::Confirmed(value) | Provisional::Tentative(value) => value,
+        }
+    }
+
+    pub fn is_tentative(&self) -> bool {
+        matches!(self, Provisional::Tentative(_))
+    }
+}
+
+/// The first business day after the date, flagged when any day passed over or landed on may
+/// still change
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{next_business_day_provisional, Calendar, Provisional};
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2023, 4, d).unwrap();
+/// let calendar = Calendar::new().with_tentative_holiday(date(21), 1);
+///
+/// assert_eq!(next_business_day_provisional(date(19), &calendar), Provisional::Tentative(date(20)));
+/// assert_eq!(next_business_day_provisional(date(17), &calendar), Provisional::Confirmed(date(18)));
+/// ```
+pub fn next_business_day_provisional<C: HolidayCalendar + ?Sized>(
+    date: NaiveDate,
+    calendar: &C,
+) -> Provisional<NaiveDate> {
+    let next = next_business_day(date, calendar);
+    let tentative = date
+        .iter_days()
+        .skip(1)
+        .take_while(|d| *d <= next)
+        .any(|d| calendar.is_tentative(d));
+
+    Provisional::new(next, tentative)
+}
+
+/// Count the business days after `start` up to and including `end`, flagged when any of the days
+/// counted may still change
+///
+/// See [business_days_between](super::business_days_between).
+pub fn business_days_between_provisional<C: HolidayCalendar + ?Sized>(
+    start: NaiveDate,
+    end: NaiveDate,
+    calendar: &C,
+) -> Provisional<i64> {
+    let (first, last) = (start.min(end), start.max(end));
+    let tentative = first
+        .iter_days()
+        .skip(1)
+        .take_while(|d| *d <= last)
+        .any(|d| calendar.is_tentative(d));
+
+    Provisional::new(
+        super::business_days_between(start, end, calendar),
+        tentative,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    #[test]
+    fn test_next_business_day_over_tentative_holiday() {
+        // 2023-04-21 is a Friday so the next business day is either Friday or Monday
+        let calendar = Calendar::new().with_tentative_holiday(date(2023, 4, 21), 1);
+
+        assert_eq!(
+            next_business_day_provisional(date(2023, 4, 20), &calendar),
+            Provisional::Tentative(date(2023, 4, 24))
+        );
+        assert_eq!(
+            next_business_day_provisional(date(2023, 4, 24), &calendar),
+            Provisional::Confirmed(date(2023, 4, 25))
+        );
+
+        let boxed: Box<dyn HolidayCalendar> = Box::new(calendar);
+        assert_eq!(
+            next_business_day_provisional(date(2023, 4, 20), &boxed),
+            Provisional::Tentative(date(2023, 4, 24))
+        );
+        assert!(!next_business_day_provisional(date(2023, 4, 20), &Calendar::new()).is_tentative());
+    }
+
+    #[test]
+    fn test_business_days_between() {
+        let calendar = Calendar::new().with_tentative_holiday(date(2023, 4, 21), 0);
+
+        assert_eq!(
+            business_days_between_provisional(date(2023, 4, 17), date(2023, 4, 28), &calendar),
+            Provisional::Tentative(8)
+        );
+        assert_eq!(
+            business_days_between_provisional(date(2023, 4, 28), date(2023, 4, 17), &calendar),
+            Provisional::Tentative(-8)
+        );
+        assert!(
+            !business_days_between_provisional(date(2023, 4, 3), date(2023, 4, 14), &calendar)
+                .is_tentative()
+        );
+        assert_eq!(
+            business_days_between_provisional(date(2023, 4, 3), date(2023, 4, 14), &calendar)
+                .value(),
+            9
+        );
+    }
+}
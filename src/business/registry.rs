@@ -0,0 +1,243 @@
+use std::{
+    collections::BTreeMap,
+    ops::RangeInclusive,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use chrono::NaiveDate;
+
+use crate::{error::CalendsError, holidays::HolidayCalendar};
+
+use super::{build_calendars, Calendar, CalendarConfig};
+
+/// Named calendars which can be replaced while they are in use
+///
+/// The registry is cheap to clone and clones share the same calendars, so it can be handed to
+/// each part of a long running service. Lookups return a snapshot of the calendar, replacing a
+/// calendar only affects later lookups so a calculation never sees a mix of old and new holidays.
+///
+/// The registry and its handles are `Send + Sync` and lookups never block for longer than it
+/// takes to clone an `Arc`, so they can be used directly from async handlers.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{Calendar, CalendarRegistry};
+///
+/// let registry = CalendarRegistry::new();
+/// registry.insert("uk", Calendar::new());
+///
+/// let uk = registry.handle("uk");
+/// let christmas = NaiveDate::from_ymd_opt(2023, 12, 25).unwrap();
+/// assert!(!uk.current().unwrap().is_holiday(christmas));
+///
+/// registry.insert("uk", Calendar::new().with_holidays([christmas]));
+/// assert!(uk.current().unwrap().is_holiday(christmas));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CalendarRegistry {
+    calendars: Arc<RwLock<BTreeMap<String, Arc<Calendar>>>>,
+}
+
+impl CalendarRegistry {
+    pub fn new() -> Self {
+        CalendarRegistry::default()
+    }
+
+    /// Add or replace a calendar, returning the calendar it replaced
+    pub fn insert(&self, name: impl Into<String>, calendar: Calendar) -> Option<Arc<Calendar>> {
+        self.write().insert(name.into(), Arc::new(calendar))
+    }
+
+    /// Remove a calendar, returning it if it was registered
+    pub fn remove(&self, name: &str) -> Option<Arc<Calendar>> {
+        self.write().remove(name)
+    }
+
+    /// The current definition of the calendar
+    pub fn get(&self, name: &str) -> Option<Arc<Calendar>> {
+        self.read().get(name).cloned()
+    }
+
+    /// Names of the registered calendars in ascending order
+    pub fn names(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    /// A handle which always looks up the current definition of the named calendar
+    pub fn handle(&self, name: impl Into<String>) -> CalendarHandle {
+        CalendarHandle {
+            registry: self.clone(),
+            name: name.into(),
+        }
+    }
+
+    /// Replace every calendar at once
+    pub fn replace_all(&self, calendars: impl IntoIterator<Item = (String, Calendar)>) {
+        let calendars = calendars
+            .into_iter()
+            .map(|(name, calendar)| (name, Arc::new(calendar)))
+            .collect();
+        *self.write() = calendars;
+    }
+
+    /// Build calendars from configuration and replace every calendar with them
+    ///
+    /// The registry is left unchanged when any of the calendars fail to build.
+    pub fn reload(
+        &self,
+        configs: &BTreeMap<String, CalendarConfig>,
+        years: RangeInclusive<i32>,
+    ) -> Result<(), CalendsError> {
+        self.replace_all(build_calendars(configs, years)?);
+        Ok(())
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<String, Arc<Calendar>>> {
+        // calendars are only ever swapped whole so a panicking writer cannot leave them half updated
+        self.calendars
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, BTreeMap<String, Arc<Calendar>>> {
+        self.calendars
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A calendar in a [CalendarRegistry] looked up by name each time it is used
+///
+/// The handle is itself a [HolidayCalendar] so it can be passed to the business day functions.
+/// Each call looks the calendar up again, take a [current](CalendarHandle::current) snapshot when a
+/// calculation has to see the same holidays throughout. A calendar which is not registered has no
+/// holidays and the usual Saturday and Sunday weekend.
+#[derive(Debug, Clone)]
+pub struct CalendarHandle {
+    registry: CalendarRegistry,
+    name: String,
+}
+
+impl CalendarHandle {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The current definition of the calendar, [None] when it is not registered
+    pub fn current(&self) -> Option<Arc<Calendar>> {
+        self.registry.get(&self.name)
+    }
+
+    fn snapshot(&self) -> Arc<Calendar> {
+        self.current().unwrap_or_else(|| Arc::new(Calendar::new()))
+    }
+}
+
+impl HolidayCalendar for CalendarHandle {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.snapshot().is_holiday(date)
+    }
+
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.snapshot().is_weekend(date)
+    }
+
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        self.snapshot().is_business_day(date)
+    }
+
+    fn is_tentative(&self, date: NaiveDate) -> bool {
+        self.snapshot().is_tentative(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use crate::{business::next_business_day, testing::date};
+
+    use super::*;
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Calendar>();
+        assert_send_sync::<CalendarRegistry>();
+        assert_send_sync::<CalendarHandle>();
+        #[cfg(feature = "country-calendars")]
+        assert_send_sync::<crate::holidays::UsFederal>();
+        assert_send_sync::<crate::versioned::Versioned<Calendar>>();
+    }
+
+    #[test]
+    fn test_handle_as_holiday_calendar() {
+        let registry = CalendarRegistry::new();
+        let handle = registry.handle("x");
+        // 2022-01-07 is a Friday
+        let friday = date(2022, 1, 7);
+        let monday = date(2022, 1, 10);
+
+        assert!(!handle.is_holiday(friday));
+        assert_eq!(next_business_day(friday, &handle), monday);
+
+        registry.insert(
+            "x",
+            Calendar::new()
+                .with_weekend([Weekday::Sun])
+                .with_holidays([friday]),
+        );
+        assert!(handle.is_holiday(friday));
+        assert_eq!(next_business_day(friday, &handle), date(2022, 1, 8));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_reload_is_atomic() {
+        let registry = CalendarRegistry::new();
+        registry.insert("old", Calendar::new());
+
+        let configs: BTreeMap<String, CalendarConfig> =
+            serde_json::from_str(r#"{ "a": {}, "b": { "inherits": "missing" } }"#).unwrap();
+        assert!(registry.reload(&configs, 2022..=2022).is_err());
+        assert_eq!(registry.names(), vec!["old".to_string()]);
+
+        let configs: BTreeMap<String, CalendarConfig> =
+            serde_json::from_str(r#"{ "a": {}, "b": { "inherits": "a" } }"#).unwrap();
+        registry.reload(&configs, 2022..=2022).unwrap();
+        assert_eq!(registry.names(), vec!["a".to_string(), "b".to_string()]);
+        assert!(registry.handle("old").current().is_none());
+    }
+
+    #[test]
+    fn test_replace_while_reading() {
+        let registry = CalendarRegistry::new();
+        registry.insert("x", Calendar::new());
+        let date = date(2022, 1, 3);
+
+        std::thread::scope(|scope| {
+            let handle = registry.handle("x");
+            let reader = scope.spawn(move || {
+                (0..1000)
+                    .filter(|_| handle.current().unwrap().is_holiday(date))
+                    .count()
+            });
+
+            for i in 0..1000 {
+                match i % 2 {
+                    0 => registry.insert("x", Calendar::new().with_holidays([date])),
+                    _ => registry.insert("x", Calendar::new()),
+                };
+            }
+            assert!(reader.join().unwrap() <= 1000);
+        });
+
+        // a snapshot is unaffected by later replacements
+        let snapshot = registry.get("x").unwrap();
+        registry.insert("x", Calendar::new().with_holidays([date]));
+        assert!(!snapshot.is_holiday(date));
+        assert!(registry.get("x").unwrap().is_holiday(date));
+    }
+}
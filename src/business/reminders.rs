@@ -0,0 +1,142 @@
+//! Reminders ahead of an event
+//!
+//! [reminders_before] counts each [ReminderOffset] back from an event, in calendar or business
+//! days, merging offsets which land on the same date.
+use chrono::NaiveDate;
+
+use crate::{holidays::HolidayCalendar, RelativeDuration};
+
+use super::{prev_business_day, roll::RollPolicy};
+
+/// How far before an event a reminder is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReminderOffset {
+    /// A calendar duration such as two weeks or a month
+    Calendar(RelativeDuration),
+    /// A number of business days
+    BusinessDays(u32),
+}
+
+/// Dates to send reminders of an event, each with the offsets which produced it
+///
+/// Every offset is counted back from the event and the result adjusted with the roll policy. When
+/// several offsets land on the same date they share a single reminder, listing the positions of
+/// the offsets in the slice. Reminders are in ascending date order and any rolled past the event
+/// are dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{reminders_before, Calendar, ReminderOffset, RollPolicy};
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 3, d).unwrap();
+///
+/// // the event is on Monday, one and two days before are both rolled back to Friday
+/// let offsets = [
+///     ReminderOffset::BusinessDays(5),
+///     ReminderOffset::Calendar(calends::RelativeDuration::days(2)),
+///     ReminderOffset::Calendar(calends::RelativeDuration::days(1)),
+/// ];
+///
+/// assert_eq!(
+///     reminders_before(date(21), &offsets, RollPolicy::Preceding, &Calendar::new()),
+///     vec![(date(14), vec![0]), (date(18), vec![1, 2])]
+/// );
+/// ```
+pub fn reminders_before<C: HolidayCalendar + ?Sized>(
+    event: NaiveDate,
+    offsets: &[ReminderOffset],
+    adjust: RollPolicy,
+    calendar: &C,
+) -> Vec<(NaiveDate, Vec<usize>)> {
+    let mut dates: Vec<(NaiveDate, usize)> = offsets
+        .iter()
+        .enumerate()
+        .map(|(i, offset)| {
+            let date = match offset {
+                ReminderOffset::Calendar(duration) => event + -*duration,
+                ReminderOffset::BusinessDays(days) => {
+                    (0..*days).fold(event, |date, _| prev_business_day(date, calendar))
+                }
+            };
+            (adjust.apply(date, calendar), i)
+        })
+        .filter(|(date, _)| *date <= event)
+        .collect();
+    dates.sort();
+
+    let mut reminders: Vec<(NaiveDate, Vec<usize>)> = Vec::new();
+    for (date, i) in dates {
+        match reminders.last_mut() {
+            Some((last, offsets)) if *last == date => offsets.push(i),
+            _ => reminders.push((date, vec![i])),
+        }
+    }
+    reminders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    #[test]
+    fn test_business_day_offsets_skip_holidays() {
+        // 2022-03-18 is a Friday
+        let calendar = Calendar::new().with_holidays([date(2022, 3, 16)]);
+        let offsets = [
+            ReminderOffset::BusinessDays(1),
+            ReminderOffset::BusinessDays(3),
+            ReminderOffset::BusinessDays(0),
+        ];
+
+        assert_eq!(
+            reminders_before(
+                date(2022, 3, 18),
+                &offsets,
+                RollPolicy::Unadjusted,
+                &calendar
+            ),
+            vec![
+                (date(2022, 3, 14), vec![1]),
+                (date(2022, 3, 17), vec![0]),
+                (date(2022, 3, 18), vec![2])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_roll_policies() {
+        // 2022-03-19 and 2022-03-20 are a weekend
+        let calendar = Calendar::new();
+        let offsets = [ReminderOffset::Calendar(RelativeDuration::days(1))];
+
+        assert_eq!(
+            reminders_before(
+                date(2022, 3, 21),
+                &offsets,
+                RollPolicy::Unadjusted,
+                &calendar
+            ),
+            vec![(date(2022, 3, 20), vec![0])]
+        );
+        assert_eq!(
+            reminders_before(
+                date(2022, 3, 21),
+                &offsets,
+                RollPolicy::Following,
+                &calendar
+            ),
+            vec![(date(2022, 3, 21), vec![0])]
+        );
+        // rolling a reminder for a Sunday event forward would send it after the event
+        assert!(reminders_before(
+            date(2022, 3, 20),
+            &offsets,
+            RollPolicy::Following,
+            &calendar
+        )
+        .is_empty());
+    }
+}
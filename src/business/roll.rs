@@ -0,0 +1,128 @@
+//! Moving dates which are not business days
+//!
+//! [RollPolicy] covers the business day conventions used for financial schedules. Generated
+//! dates can be rolled one at a time with [roll_date] or as a stream with
+//! [DateStream::roll](crate::period::DateStream::roll).
+use chrono::{Datelike, NaiveDate};
+
+use crate::holidays::HolidayCalendar;
+
+use super::{next_business_day, prev_business_day};
+
+/// How a date falling on a non-business day is moved
+///
+/// The default is [RollPolicy::Preceding], so a reminder or deadline is never late.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RollPolicy {
+    /// Keep the date even when it is not a business day
+    Unadjusted,
+    /// The last business day before the date
+    #[default]
+    Preceding,
+    /// The next business day after the date
+    Following,
+    /// The next business day after the date unless that is in the following month, in which case
+    /// the last business day before the date
+    ModifiedFollowing,
+    /// The last business day before the date unless that is in the previous month, in which case
+    /// the next business day after the date
+    ModifiedPreceding,
+}
+
+impl RollPolicy {
+    /// The date itself when it is a business day, otherwise the day the policy moves it to
+    pub fn apply<C: HolidayCalendar + ?Sized>(&self, date: NaiveDate, calendar: &C) -> NaiveDate {
+        if calendar.is_business_day(date) {
+            return date;
+        }
+        let same_month = |rolled: NaiveDate| rolled.month() == date.month();
+        match self {
+            RollPolicy::Unadjusted => date,
+            RollPolicy::Preceding => prev_business_day(date, calendar),
+            RollPolicy::Following => next_business_day(date, calendar),
+            RollPolicy::ModifiedFollowing => Some(next_business_day(date, calendar))
+                .filter(|rolled| same_month(*rolled))
+                .unwrap_or_else(|| prev_business_day(date, calendar)),
+            RollPolicy::ModifiedPreceding => Some(prev_business_day(date, calendar))
+                .filter(|rolled| same_month(*rolled))
+                .unwrap_or_else(|| next_business_day(date, calendar)),
+        }
+    }
+}
+
+/// The date itself when it is a business day of the calendar, otherwise the day the policy moves
+/// it to
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::{roll_date, Calendar, RollPolicy};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let calendar = Calendar::new();
+///
+/// // the 30th of April 2022 is a Saturday
+/// assert_eq!(roll_date(date(4, 30), RollPolicy::Following, &calendar), date(5, 2));
+/// assert_eq!(roll_date(date(4, 30), RollPolicy::ModifiedFollowing, &calendar), date(4, 29));
+/// assert_eq!(roll_date(date(4, 29), RollPolicy::ModifiedFollowing, &calendar), date(4, 29));
+/// ```
+pub fn roll_date<C: HolidayCalendar + ?Sized>(
+    date: NaiveDate,
+    policy: RollPolicy,
+    calendar: &C,
+) -> NaiveDate {
+    policy.apply(date, calendar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    #[test]
+    fn test_modified_conventions_stay_in_the_month() {
+        // the 1st of January 2022 is a Saturday and the 3rd is a holiday
+        let calendar = Calendar::new().with_holidays([date(2022, 1, 3)]);
+        let roll = |date, policy| roll_date(date, policy, &calendar);
+
+        assert_eq!(
+            roll(date(2022, 1, 1), RollPolicy::Preceding),
+            date(2021, 12, 31)
+        );
+        assert_eq!(
+            roll(date(2022, 1, 1), RollPolicy::ModifiedPreceding),
+            date(2022, 1, 4)
+        );
+        assert_eq!(
+            roll(date(2022, 1, 3), RollPolicy::ModifiedPreceding),
+            date(2022, 1, 4)
+        );
+        assert_eq!(
+            roll(date(2022, 1, 1), RollPolicy::Following),
+            date(2022, 1, 4)
+        );
+        assert_eq!(
+            roll(date(2022, 1, 1), RollPolicy::Unadjusted),
+            date(2022, 1, 1)
+        );
+
+        // the 31st of July 2022 is a Sunday
+        assert_eq!(
+            roll(date(2022, 7, 31), RollPolicy::Following),
+            date(2022, 8, 1)
+        );
+        assert_eq!(
+            roll(date(2022, 7, 31), RollPolicy::ModifiedFollowing),
+            date(2022, 7, 29)
+        );
+        assert_eq!(
+            roll(date(2022, 7, 30), RollPolicy::ModifiedFollowing),
+            date(2022, 7, 29)
+        );
+        assert_eq!(
+            roll(date(2022, 7, 16), RollPolicy::ModifiedFollowing),
+            date(2022, 7, 18)
+        );
+    }
+}
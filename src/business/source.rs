@@ -0,0 +1,162 @@
+//! Calendars backed by asynchronous sources
+//!
+//! Holiday data kept in a remote service or database is fetched with an [AsyncCalendarSource].
+//! A [SnapshotCalendar] compiles the fetched years into a [Calendar] and swaps it in whole, so
+//! business day calculations only ever read an in-memory snapshot and never wait on the source.
+//!
+//! No runtime is assumed, the futures can be driven by any executor.
+use std::{
+    future::Future,
+    ops::RangeInclusive,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+use chrono::NaiveDate;
+
+use crate::trace;
+
+use super::Calendar;
+
+/// Somewhere holidays are fetched from a year at a time
+pub trait AsyncCalendarSource {
+    type Error;
+
+    /// The holidays within the year
+    fn fetch_year(
+        &self,
+        year: i32,
+    ) -> impl Future<Output = Result<Vec<NaiveDate>, Self::Error>> + Send;
+}
+
+/// The latest calendar compiled from a source
+///
+/// The weekend and any holidays known up front come from the base calendar, the fetched holidays
+/// are added to it. Until the first refresh the snapshot is the base calendar.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+/// # use chrono::NaiveDate;
+/// # use calends::business::{AsyncCalendarSource, Calendar, SnapshotCalendar};
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut future = pin!(future);
+/// #     let mut context = Context::from_waker(Waker::noop());
+/// #     loop {
+/// #         if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+/// #             return output;
+/// #         }
+/// #     }
+/// # }
+/// struct NewYears;
+///
+/// impl AsyncCalendarSource for NewYears {
+///     type Error = std::convert::Infallible;
+///
+///     async fn fetch_year(&self, year: i32) -> Result<Vec<NaiveDate>, Self::Error> {
+///         Ok(vec![NaiveDate::from_ymd_opt(year, 1, 1).unwrap()])
+///     }
+/// }
+///
+/// let calendar = SnapshotCalendar::new(NewYears, Calendar::new());
+/// let new_year = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+/// assert!(!calendar.current().is_holiday(new_year));
+///
+/// block_on(calendar.refresh(2022..=2024)).unwrap();
+/// assert!(calendar.current().is_holiday(new_year));
+/// ```
+#[derive(Debug)]
+pub struct SnapshotCalendar<S> {
+    source: S,
+    base: Calendar,
+    current: RwLock<Arc<Calendar>>,
+}
+
+impl<S: AsyncCalendarSource> SnapshotCalendar<S> {
+    pub fn new(source: S, base: Calendar) -> Self {
+        SnapshotCalendar {
+            source,
+            current: RwLock::new(Arc::new(base.clone())),
+            base,
+        }
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// The calendar compiled by the last successful refresh
+    pub fn current(&self) -> Arc<Calendar> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Fetch the years and replace the snapshot with a calendar compiled from them
+    ///
+    /// Years not in the range are dropped from the snapshot. The snapshot is left unchanged when
+    /// any year fails to fetch, and no lock is held while fetching.
+    pub async fn refresh(&self, years: RangeInclusive<i32>) -> Result<(), S::Error> {
+        let mut holidays = Vec::new();
+        for year in years {
+            let fetched = self.source.fetch_year(year).await?;
+            trace::debug!(year, holidays = fetched.len(), "fetched holidays");
+            holidays.extend(fetched);
+        }
+
+        let calendar = Arc::new(self.base.clone().with_holidays(holidays));
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = calendar;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use super::*;
+    use crate::testing::date;
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    /// Christmas Day, failing for years after the last one available
+    struct Christmas {
+        last: i32,
+    }
+
+    impl AsyncCalendarSource for Christmas {
+        type Error = i32;
+
+        async fn fetch_year(&self, year: i32) -> Result<Vec<NaiveDate>, i32> {
+            match year <= self.last {
+                true => Ok(vec![date(year, 12, 25)]),
+                false => Err(year),
+            }
+        }
+    }
+
+    #[test]
+    fn test_failed_refresh_keeps_snapshot() {
+        let base = Calendar::new().with_holidays([date(2022, 1, 3)]);
+        let calendar = SnapshotCalendar::new(Christmas { last: 2023 }, base);
+
+        block_on(calendar.refresh(2022..=2023)).unwrap();
+        let snapshot = calendar.current();
+        assert_eq!(snapshot.holidays().count(), 3);
+
+        assert_eq!(block_on(calendar.refresh(2023..=2025)), Err(2024));
+        assert!(Arc::ptr_eq(&snapshot, &calendar.current()));
+    }
+}
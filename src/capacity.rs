@@ -0,0 +1,239 @@
+//! Capacity planning for shared resources
+//!
+//! A [CapacityPlan] books units of named resources over intervals of days and reports how much of
+//! each resource is used on every day, along with the days on which the bookings exceed the
+//! resource's capacity.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chrono::NaiveDate;
+//! # use calends::{capacity::CapacityPlan, interval::ClosedInterval, IntervalLike};
+//!
+//! let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+//!
+//! let mut plan = CapacityPlan::new().with_resource("projector", 1);
+//! plan.book("projector", &ClosedInterval::with_dates(date(3), date(7)), 1)?;
+//! plan.book("projector", &ClosedInterval::with_dates(date(6), date(10)), 1)?;
+//!
+//! let over = plan.over_allocated("projector")?;
+//! assert_eq!(over.iter().map(|i| i.iso8601()).collect::<Vec<_>>(), vec!["2022-01-06/2022-01-07"]);
+//! # Ok::<(), calends::CalendsError>(())
+//! ```
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::{
+    error::CalendsError,
+    interval::{
+        marker::{End, Start},
+        ClosedInterval, IntervalSet,
+    },
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Resource {
+    capacity: u32,
+    /// First day, last day and units of each booking
+    bookings: Vec<(NaiveDate, NaiveDate, u32)>,
+}
+
+/// Bookings of resources which each have a fixed capacity per day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapacityPlan {
+    resources: BTreeMap<String, Resource>,
+}
+
+impl CapacityPlan {
+    pub fn new() -> Self {
+        CapacityPlan::default()
+    }
+
+    /// Add a resource or change its capacity, keeping any bookings
+    pub fn with_resource(mut self, name: impl Into<String>, capacity: u32) -> Self {
+        self.resources.entry(name.into()).or_default().capacity = capacity;
+        self
+    }
+
+    /// Capacity of the resource, [None] when it is unknown
+    pub fn capacity(&self, resource: &str) -> Option<u32> {
+        self.resources.get(resource).map(|r| r.capacity)
+    }
+
+    /// Book units of the resource on every day of the interval
+    ///
+    /// Bookings of a known resource are accepted even beyond its capacity, see
+    /// [CapacityPlan::over_allocated] for the days on which they exceed it. An interval ending
+    /// before it starts is rejected.
+    pub fn book<I: Start + End>(
+        &mut self,
+        resource: &str,
+        interval: &I,
+        units: u32,
+    ) -> Result<(), CalendsError> {
+        let (start, end) = (interval.start(), interval.end());
+        if end < start {
+            return Err(CalendsError::ReversedInterval { start, end });
+        }
+        self.resource_mut(resource)?
+            .bookings
+            .push((start, end, units));
+        Ok(())
+    }
+
+    /// Units of the resource booked on each day with any bookings
+    ///
+    /// Days booked beyond [u32::MAX] units report [u32::MAX].
+    pub fn utilization(&self, resource: &str) -> Result<BTreeMap<NaiveDate, u32>, CalendsError> {
+        Ok(self
+            .booked(resource)?
+            .into_iter()
+            .map(|(day, booked)| (day, u32::try_from(booked).unwrap_or(u32::MAX)))
+            .collect())
+    }
+
+    /// Days on which the bookings of the resource exceed its capacity
+    pub fn over_allocated(&self, resource: &str) -> Result<IntervalSet, CalendsError> {
+        let capacity = self.resource(resource)?.capacity;
+
+        let mut over = IntervalSet::new();
+        for (date, booked) in self.booked(resource)? {
+            if booked > i64::from(capacity) {
+                over.insert(&ClosedInterval::with_dates_unchecked(date, date));
+            }
+        }
+        Ok(over)
+    }
+
+    /// Units of the resource booked on each day with any bookings, wide enough not to overflow
+    fn booked(&self, resource: &str) -> Result<BTreeMap<NaiveDate, i64>, CalendsError> {
+        let resource = self.resource(resource)?;
+
+        // the change in units booked at the start of each day
+        let mut deltas: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+        for (start, end, units) in &resource.bookings {
+            *deltas.entry(*start).or_default() += i64::from(*units);
+            if let Some(after) = end.succ_opt() {
+                *deltas.entry(after).or_default() -= i64::from(*units);
+            }
+        }
+
+        let mut usage = BTreeMap::new();
+        let mut booked = 0;
+        let mut changes = deltas.into_iter().peekable();
+        while let Some((date, delta)) = changes.next() {
+            booked += delta;
+            if booked == 0 {
+                continue;
+            }
+            let until = changes.peek().map(|(next, _)| *next);
+            for day in date
+                .iter_days()
+                .take_while(|d| until.is_none_or(|u| *d < u))
+            {
+                usage.insert(day, booked);
+            }
+        }
+        Ok(usage)
+    }
+
+    fn resource(&self, name: &str) -> Result<&Resource, CalendsError> {
+        self.resources
+            .get(name)
+            .ok_or_else(|| CalendsError::UnknownResource(name.to_string()))
+    }
+
+    fn resource_mut(&mut self, name: &str) -> Result<&mut Resource, CalendsError> {
+        self.resources
+            .get_mut(name)
+            .ok_or_else(|| CalendsError::UnknownResource(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_utilization() {
+        let mut plan = CapacityPlan::new().with_resource("room", 10);
+        plan.book(
+            "room",
+            &ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 3)),
+            4,
+        )
+        .unwrap();
+        plan.book(
+            "room",
+            &ClosedInterval::with_dates(date(2022, 1, 3), date(2022, 1, 4)),
+            7,
+        )
+        .unwrap();
+        plan.book(
+            "room",
+            &ClosedInterval::with_dates(date(2022, 1, 8), date(2022, 1, 8)),
+            11,
+        )
+        .unwrap();
+
+        let usage = plan.utilization("room").unwrap();
+        assert_eq!(
+            usage.into_iter().collect::<Vec<_>>(),
+            vec![
+                (date(2022, 1, 1), 4),
+                (date(2022, 1, 2), 4),
+                (date(2022, 1, 3), 11),
+                (date(2022, 1, 4), 7),
+                (date(2022, 1, 8), 11)
+            ]
+        );
+
+        let over = plan.over_allocated("room").unwrap();
+        assert_eq!(over.len(), 2);
+        assert!(over.contains(date(2022, 1, 3)) && over.contains(date(2022, 1, 8)));
+        assert!(!over.contains(date(2022, 1, 4)));
+    }
+
+    #[test]
+    fn test_unknown_resource() {
+        let mut plan = CapacityPlan::new();
+        let interval = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 1));
+
+        assert!(matches!(
+            plan.book("room", &interval, 1),
+            Err(CalendsError::UnknownResource(name)) if name == "room"
+        ));
+        assert!(plan.over_allocated("room").is_err());
+    }
+
+    #[test]
+    fn test_reversed_booking() {
+        let mut plan = CapacityPlan::new().with_resource("room", 1);
+        let (start, end) = (date(2022, 1, 10), date(2022, 1, 5));
+
+        assert_eq!(
+            plan.book("room", &ClosedInterval::with_dates_unchecked(start, end), 1),
+            Err(CalendsError::ReversedInterval { start, end })
+        );
+        assert!(plan.utilization("room").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_utilization_saturates() {
+        let mut plan = CapacityPlan::new().with_resource("room", u32::MAX);
+        let interval = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 1));
+        plan.book("room", &interval, u32::MAX).unwrap();
+        plan.book("room", &interval, 2).unwrap();
+
+        assert_eq!(
+            plan.utilization("room").unwrap()[&date(2022, 1, 1)],
+            u32::MAX
+        );
+        assert!(plan
+            .over_allocated("room")
+            .unwrap()
+            .contains(date(2022, 1, 1)));
+    }
+}
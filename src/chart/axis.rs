@@ -0,0 +1,167 @@
+//! Ticks for a time axis
+//!
+//! [axis_labels] picks the finest of days, weeks, months, quarters, years or longer spans which
+//! keeps the number of ticks within a target, and places each tick on the first day of a calendar
+//! period so the ticks line up with the periods the data was aggregated into.
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    grain::Grain,
+    interval::marker::{End, Start},
+    period::{FiscalYear, Period},
+};
+
+/// Grains tried from the finest, with the average number of days in each
+const GRAINS: [(Grain, f64); 8] = [
+    (Grain::Day, 1.0),
+    (Grain::Week, 7.0),
+    (Grain::Month, 30.44),
+    (Grain::Quarter, 91.31),
+    (Grain::Year, 365.25),
+    (Grain::Lustrum, 1826.25),
+    (Grain::Decade, 3652.5),
+    (Grain::Century, 36525.0),
+];
+
+/// Ticks for the interval with their labels, no more than `target_count` of them
+///
+/// Ticks are the first day of each period within the interval, weeks start on Monday. Labels
+/// include the year on the first tick and wherever the year changes, e.g. `Dec 30, 2024`, `Jan 6,
+/// 2025` and `Jan 13` for weeks, `Nov 2024`, `Dec` and `Jan 2025` for months, `Q1 2022` for
+/// quarters and `2022` for years and longer. A target of zero gives no ticks.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{chart::axis_labels, interval::ClosedInterval};
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// let interval = ClosedInterval::with_dates(date(2021, 11, 15), date(2022, 3, 31));
+///
+/// let labels: Vec<String> = axis_labels(&interval, 6).into_iter().map(|(_, l)| l).collect();
+/// assert_eq!(labels, vec!["Dec 2021", "Jan 2022", "Feb", "Mar"]);
+///
+/// let labels: Vec<String> = axis_labels(&interval, 2).into_iter().map(|(_, l)| l).collect();
+/// assert_eq!(labels, vec!["Q1 2022"]);
+/// ```
+pub fn axis_labels<I: Start + End>(interval: &I, target_count: usize) -> Vec<(NaiveDate, String)> {
+    let (start, end) = (interval.start(), interval.end());
+    if target_count == 0 {
+        return Vec::new();
+    }
+    let days = ((end - start).num_days() + 1) as f64;
+
+    for (grain, average_days) in GRAINS {
+        // estimate first so that long intervals are not walked a day at a time
+        if days / average_days > target_count as f64 + 1.0 {
+            continue;
+        }
+        let ticks = ticks(start, end, grain);
+        if ticks.len() <= target_count {
+            return labelled(ticks, grain);
+        }
+    }
+    Vec::new()
+}
+
+/// First day of each period of the grain starting within the dates
+fn ticks(start: NaiveDate, end: NaiveDate, grain: Grain) -> Vec<NaiveDate> {
+    let period = Period::containing(start, grain, &FiscalYear::calendar());
+    let first = match period.start() < start {
+        true => period.checked_succ(),
+        false => Some(period),
+    };
+    std::iter::successors(first, Period::checked_succ)
+        .map(|period| period.start())
+        .take_while(|tick| *tick <= end)
+        .collect()
+}
+
+fn labelled(ticks: Vec<NaiveDate>, grain: Grain) -> Vec<(NaiveDate, String)> {
+    let mut year = None;
+    ticks
+        .into_iter()
+        .map(|tick| {
+            let new_year = year.replace(tick.year()) != Some(tick.year());
+            let label = match grain {
+                Grain::Day | Grain::Week if new_year => tick.format("%b %-d, %Y").to_string(),
+                Grain::Day | Grain::Week => tick.format("%b %-d").to_string(),
+                Grain::Month if new_year => tick.format("%b %Y").to_string(),
+                Grain::Month => tick.format("%b").to_string(),
+                Grain::Quarter | Grain::Half => {
+                    let months = grain.months().unwrap() as u32;
+                    let prefix = if grain == Grain::Quarter { "Q" } else { "H" };
+                    format!(
+                        "{}{} {}",
+                        prefix,
+                        (tick.month() - 1) / months + 1,
+                        tick.year()
+                    )
+                }
+                _ => tick.year().to_string(),
+            };
+            (tick, label)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interval::ClosedInterval, testing::date};
+
+    fn labels(start: NaiveDate, end: NaiveDate, target: usize) -> Vec<(NaiveDate, String)> {
+        axis_labels(&ClosedInterval::with_dates(start, end), target)
+    }
+
+    #[test]
+    fn test_days_and_weeks() {
+        let days = labels(date(2022, 12, 30), date(2023, 1, 2), 4);
+        assert_eq!(
+            days.iter().map(|(_, l)| l.as_str()).collect::<Vec<_>>(),
+            vec!["Dec 30, 2022", "Dec 31", "Jan 1, 2023", "Jan 2"]
+        );
+
+        let weeks = labels(date(2022, 12, 30), date(2023, 1, 31), 10);
+        assert_eq!(weeks.len(), 5);
+        assert_eq!(weeks[0], (date(2023, 1, 2), "Jan 2, 2023".to_string()));
+        assert!(weeks
+            .iter()
+            .all(|(tick, _)| tick.weekday() == chrono::Weekday::Mon));
+    }
+
+    #[test]
+    fn test_longer_grains() {
+        let years = labels(date(2001, 6, 1), date(2010, 1, 1), 10);
+        assert_eq!(years.len(), 9);
+        assert_eq!(years[0], (date(2002, 1, 1), "2002".to_string()));
+
+        let decades = labels(date(1950, 1, 1), date(2022, 1, 1), 10);
+        assert_eq!(
+            decades.iter().map(|(_, l)| l.as_str()).collect::<Vec<_>>(),
+            vec!["1950", "1960", "1970", "1980", "1990", "2000", "2010", "2020"]
+        );
+    }
+
+    #[test]
+    fn test_within_target() {
+        for target in 0..40 {
+            assert!(labels(date(2022, 1, 1), date(2022, 12, 31), target).len() <= target);
+        }
+        assert!(labels(date(2022, 1, 2), date(2022, 1, 2), 1).len() == 1);
+        // no period starts within a few days in the middle of a century
+        assert!(labels(date(2022, 1, 2), date(2022, 1, 5), 0).is_empty());
+    }
+
+    #[test]
+    fn test_ending_at_the_last_date() {
+        let start = NaiveDate::MAX - chrono::Days::new(3);
+        let days = labels(start, NaiveDate::MAX, 4);
+        assert_eq!(days.len(), 4);
+        assert_eq!(days.last().map(|(tick, _)| *tick), Some(NaiveDate::MAX));
+
+        let years = labels(date(262000, 1, 1), NaiveDate::MAX, 200);
+        assert_eq!(years.first().map(|(_, l)| l.as_str()), Some("262000"));
+    }
+}
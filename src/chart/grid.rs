@@ -0,0 +1,150 @@
+//! Month views for date pickers and calendar pages
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::{holidays::HolidayCalendar, month::Month, util::checked_beginning_of_week_starting};
+
+/// A day shown in a [month_grid]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GridDay {
+    pub date: NaiveDate,
+    /// Whether the day is in the month shown, rather than filling out its first or last week
+    pub in_month: bool,
+    pub is_weekend: bool,
+    pub is_holiday: bool,
+}
+
+/// The weeks of a month, filled out with days from the months either side
+///
+/// Every row is a week of seven days beginning on `weekstart`, and there are as many rows as it
+/// takes to cover the month: four for a February beginning on the first day of the week and up to
+/// six otherwise. Weekends and holidays are those of the calendar. The grid is empty when the
+/// month or its first or last week is outside the range of dates chrono supports.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::{NaiveDate, Weekday};
+/// # use calends::{business::Calendar, chart::month_grid, month::Month};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let calendar = Calendar::new().with_holidays([date(12, 26)]);
+///
+/// let grid = month_grid(2022, Month::December, Weekday::Mon, &calendar);
+/// assert_eq!(grid.len(), 5);
+/// assert_eq!(grid[0][0].date, date(11, 28));
+/// assert!(!grid[0][0].in_month);
+/// assert!(grid[4][0].is_holiday);
+/// assert!(grid[4][6].is_weekend);
+/// assert_eq!(grid[4][6].date, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+/// ```
+pub fn month_grid<C: HolidayCalendar>(
+    year: i32,
+    month: Month,
+    weekstart: Weekday,
+    calendar: &C,
+) -> Vec<Vec<GridDay>> {
+    let first = match month.first_day(year) {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+    let last = first + Duration::days(i64::from(month.days_in(year)) - 1);
+    let start = match checked_beginning_of_week_starting(&first, weekstart) {
+        Some(start) => start,
+        None => return Vec::new(),
+    };
+    let weeks = ((last - start).num_days() / 7 + 1) as i32;
+
+    let days: Option<Vec<NaiveDate>> = (0..7 * weeks)
+        .map(|day| start.checked_add_signed(Duration::days(day.into())))
+        .collect();
+    days.unwrap_or_default()
+        .chunks(7)
+        .map(|week| {
+            week.iter()
+                .map(|&date| GridDay {
+                    date,
+                    in_month: date.year() == year && date.month() == month.number(),
+                    is_weekend: calendar.is_weekend(date),
+                    is_holiday: calendar.is_holiday(date),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::business::Calendar;
+
+    #[test]
+    fn test_month_grid_rows() {
+        let calendar = Calendar::new();
+
+        // February 2021 starts on a Monday and has exactly four weeks
+        assert_eq!(
+            month_grid(2021, Month::February, Weekday::Mon, &calendar).len(),
+            4
+        );
+        assert_eq!(
+            month_grid(2021, Month::February, Weekday::Sun, &calendar).len(),
+            5
+        );
+        // August 2021 starts on a Sunday and ends on a Tuesday
+        assert_eq!(
+            month_grid(2021, Month::August, Weekday::Mon, &calendar).len(),
+            6
+        );
+        assert_eq!(
+            month_grid(2021, Month::August, Weekday::Sun, &calendar).len(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_month_grid_days() {
+        let calendar = Calendar::new().with_weekend([Weekday::Fri, Weekday::Sat]);
+
+        for month in (1..=12).filter_map(Month::from_number) {
+            let grid = month_grid(2024, month, Weekday::Sun, &calendar);
+            let days: Vec<&GridDay> = grid.iter().flatten().collect();
+
+            assert!(grid.iter().all(|week| week.len() == 7));
+            assert!(grid
+                .iter()
+                .all(|week| week[0].date.weekday() == Weekday::Sun));
+            assert!(days
+                .windows(2)
+                .all(|w| w[1].date - w[0].date == Duration::days(1)));
+            assert_eq!(
+                days.iter().filter(|day| day.in_month).count() as u32,
+                month.days_in(2024)
+            );
+            // neither the first nor the last week is only filler
+            assert!(grid[0].iter().any(|day| day.in_month));
+            assert!(grid[grid.len() - 1].iter().any(|day| day.in_month));
+            assert!(days
+                .iter()
+                .all(|day| day.is_weekend
+                    == matches!(day.date.weekday(), Weekday::Fri | Weekday::Sat)));
+        }
+
+        assert!(month_grid(i32::MAX, Month::January, Weekday::Mon, &calendar).is_empty());
+    }
+
+    #[test]
+    fn test_month_grid_at_the_limits() {
+        let calendar = Calendar::new();
+        let (min, max) = (NaiveDate::MIN, NaiveDate::MAX);
+
+        // weeks starting on the first supported date fit, any other start would precede it
+        let grid = month_grid(min.year(), Month::January, min.weekday(), &calendar);
+        assert_eq!(grid[0][0].date, min);
+        assert!(month_grid(min.year(), Month::January, min.weekday().succ(), &calendar).is_empty());
+
+        // likewise weeks must end on the last supported date
+        let grid = month_grid(max.year(), Month::December, max.weekday().succ(), &calendar);
+        assert_eq!(grid.last().unwrap()[6].date, max);
+        assert!(month_grid(max.year(), Month::December, max.weekday(), &calendar).is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+//! Grids of dates for contribution style heatmaps
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::util::checked_beginning_of_week_starting;
+
+/// The days of the year as rows of weeks, each with a column for every day of the week
+///
+/// Weeks begin on `weekstart` and the first and last weeks are padded with [None] for the days
+/// which fall in the neighbouring years, so every row has seven entries and a column is always
+/// the same day of the week. The grid is empty for years chrono cannot represent, and for a year
+/// whose first week begins before the earliest supported date.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::{NaiveDate, Weekday};
+/// # use calends::chart::week_grid;
+///
+/// // 2022 starts on a Saturday and ends on a Saturday
+/// let grid = week_grid(2022, Weekday::Mon);
+/// assert_eq!(grid.len(), 53);
+/// assert_eq!(grid[0][4], None);
+/// assert_eq!(grid[0][5], NaiveDate::from_ymd_opt(2022, 1, 1));
+/// assert_eq!(grid[52][5], NaiveDate::from_ymd_opt(2022, 12, 31));
+/// assert_eq!(grid[52][6], None);
+/// ```
+pub fn week_grid(year: i32, weekstart: Weekday) -> Vec<Vec<Option<NaiveDate>>> {
+    let (first, last) = match (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Vec::new(),
+    };
+
+    let mut grid = Vec::new();
+    let mut week = checked_beginning_of_week_starting(&first, weekstart);
+    while let Some(start) = week.filter(|start| *start <= last) {
+        grid.push(
+            (0..7)
+                .map(|day| start.checked_add_signed(Duration::days(day)))
+                .map(|date| date.filter(|date| date.year() == year))
+                .collect(),
+        );
+        week = start.checked_add_signed(Duration::weeks(1));
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_week_grid_covers_the_year() {
+        for year in [2020, 2021, 2022, 2023] {
+            for weekstart in [Weekday::Mon, Weekday::Sun, Weekday::Sat] {
+                let grid = week_grid(year, weekstart);
+                let dates: Vec<NaiveDate> = grid.iter().flatten().flatten().copied().collect();
+
+                assert!(grid.iter().all(|week| week.len() == 7));
+                assert_eq!(dates.len() as i64, if year == 2020 { 366 } else { 365 });
+                assert!(dates.windows(2).all(|w| w[1] - w[0] == Duration::days(1)));
+                assert!(grid.iter().all(|week| week
+                    .iter()
+                    .enumerate()
+                    .all(|(i, date)| date.is_none_or(|date| {
+                        date.weekday() == (0..i).fold(weekstart, |day, _| day.succ())
+                    }))));
+            }
+        }
+    }
+
+    #[test]
+    fn test_week_grid_at_the_limits() {
+        let grid = week_grid(NaiveDate::MAX.year(), Weekday::Mon);
+        let dates: Vec<NaiveDate> = grid.iter().flatten().flatten().copied().collect();
+        assert_eq!(dates.last(), Some(&NaiveDate::MAX));
+
+        // a first week starting on any other day would begin before the earliest date
+        let weekstart = NaiveDate::MIN.weekday();
+        assert_eq!(
+            week_grid(NaiveDate::MIN.year(), weekstart)[0][0],
+            Some(NaiveDate::MIN)
+        );
+        assert!(week_grid(NaiveDate::MIN.year(), weekstart.succ()).is_empty());
+    }
+
+    #[test]
+    fn test_week_grid_padding() {
+        let grid = week_grid(2022, Weekday::Sun);
+        assert_eq!(grid.len(), 53);
+        assert_eq!(grid[0].iter().filter(|date| date.is_none()).count(), 6);
+        assert!(grid[52].iter().all(|date| date.is_some()));
+
+        // 2018 starts on a Monday
+        assert_eq!(week_grid(2018, Weekday::Mon)[0][0], Some(date(2018, 1, 1)));
+        assert!(week_grid(i32::MAX, Weekday::Mon).is_empty());
+    }
+}
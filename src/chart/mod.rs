@@ -0,0 +1,8 @@
+//! Calendar aware helpers for charts and calendar views
+pub mod axis;
+pub mod grid;
+pub mod heatmap;
+
+pub use axis::axis_labels;
+pub use grid::{month_grid, GridDay};
+pub use heatmap::week_grid;
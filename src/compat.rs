@@ -0,0 +1,173 @@
+//! Conversions between calends and chrono's own calendar types
+//!
+//! chrono has grown [Months], [Days] and [NaiveWeek] which overlap with [RelativeDuration],
+//! [Period] and [ClosedInterval]. Converting between them lets code which already uses chrono's
+//! API adopt calends a piece at a time.
+//!
+//! ```
+//! # use chrono::{Months, NaiveDate, Weekday};
+//! # use calends::{interval::ClosedInterval, period::Period, IntervalLike, RelativeDuration};
+//!
+//! let duration = RelativeDuration::try_from(Months::new(3))?;
+//! assert_eq!(Months::try_from(duration), Ok(Months::new(3)));
+//!
+//! let week = NaiveDate::from_ymd_opt(2022, 1, 5).unwrap().week(Weekday::Sun);
+//! let interval = ClosedInterval::from(week);
+//! assert_eq!(interval.start_opt(), NaiveDate::from_ymd_opt(2022, 1, 2));
+//! assert_eq!(interval.end_opt(), NaiveDate::from_ymd_opt(2022, 1, 8));
+//!
+//! // only weeks starting on a Monday are periods
+//! assert!(Period::try_from(week).is_err());
+//! # Ok::<(), calends::CalendsError>(())
+//! ```
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveWeek, Weekday};
+
+use crate::{
+    error::CalendsError,
+    grain::Grain,
+    interval::ClosedInterval,
+    period::{FiscalYear, Period},
+    Interval, RelativeDuration,
+};
+
+impl TryFrom<Months> for RelativeDuration {
+    type Error = CalendsError;
+
+    fn try_from(months: Months) -> Result<Self, Self::Error> {
+        match months.as_u32() {
+            m if m <= RelativeDuration::MAX_COMPONENT => Ok(RelativeDuration::months(m as i32)),
+            m => Err(CalendsError::IncompatibleConversion(format!(
+                "{} months",
+                m
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Days> for RelativeDuration {
+    type Error = CalendsError;
+
+    fn try_from(days: Days) -> Result<Self, Self::Error> {
+        // chrono does not expose the number of days so measure it from a fixed date
+        let epoch = NaiveDate::default();
+        match epoch.checked_add_days(days) {
+            Some(date) if (date - epoch).num_days() <= RelativeDuration::MAX_COMPONENT.into() => {
+                Ok(RelativeDuration::days((date - epoch).num_days() as i32))
+            }
+            _ => Err(CalendsError::IncompatibleConversion(format!("{:?}", days))),
+        }
+    }
+}
+
+/// A duration of whole months and nothing else
+impl TryFrom<RelativeDuration> for Months {
+    type Error = CalendsError;
+
+    fn try_from(duration: RelativeDuration) -> Result<Self, Self::Error> {
+        if duration.num_months() < 0 || duration.num_weeks() != 0 || duration.num_days() != 0 {
+            return Err(CalendsError::IncompatibleConversion(duration.iso8601()));
+        }
+        Ok(Months::new(duration.num_months() as u32))
+    }
+}
+
+/// A duration of weeks and days without months, weeks are counted as seven days
+impl TryFrom<RelativeDuration> for Days {
+    type Error = CalendsError;
+
+    fn try_from(duration: RelativeDuration) -> Result<Self, Self::Error> {
+        let days = 7 * i64::from(duration.num_weeks()) + i64::from(duration.num_days());
+        if duration.num_months() != 0 || days < 0 {
+            return Err(CalendsError::IncompatibleConversion(duration.iso8601()));
+        }
+        Ok(Days::new(days as u64))
+    }
+}
+
+/// The seven days of the week, whichever day it starts on
+impl From<NaiveWeek> for ClosedInterval {
+    fn from(week: NaiveWeek) -> Self {
+        ClosedInterval::with_dates(week.first_day(), week.last_day())
+    }
+}
+
+impl From<NaiveWeek> for Interval {
+    fn from(week: NaiveWeek) -> Self {
+        Interval::Closed(week.into())
+    }
+}
+
+/// A week period, which must start on a Monday
+impl TryFrom<NaiveWeek> for Period {
+    type Error = CalendsError;
+
+    fn try_from(week: NaiveWeek) -> Result<Self, Self::Error> {
+        match week.first_day().weekday() {
+            Weekday::Mon => Ok(Period::containing(
+                week.first_day(),
+                Grain::Week,
+                &FiscalYear::calendar(),
+            )),
+            _ => Err(CalendsError::IncompatibleConversion(format!(
+                "week starting on {}",
+                week.first_day()
+            ))),
+        }
+    }
+}
+
+/// The week of a week period
+impl TryFrom<Period> for NaiveWeek {
+    type Error = CalendsError;
+
+    fn try_from(period: Period) -> Result<Self, Self::Error> {
+        match period.grain() {
+            Grain::Week => Ok(period.start().week(Weekday::Mon)),
+            grain => Err(CalendsError::IncompatibleConversion(format!(
+                "{:?} period starting on {}",
+                grain,
+                period.start()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_durations() {
+        assert_eq!(
+            RelativeDuration::try_from(Days::new(10)),
+            Ok(RelativeDuration::days(10))
+        );
+        assert!(RelativeDuration::try_from(Days::new(1 << 20)).is_err());
+        assert!(RelativeDuration::try_from(Months::new(1 << 20)).is_err());
+
+        assert_eq!(
+            Days::try_from(RelativeDuration::weeks(1).with_days(2)),
+            Ok(Days::new(9))
+        );
+        assert!(Days::try_from(RelativeDuration::months(1)).is_err());
+        assert!(Days::try_from(RelativeDuration::days(-1)).is_err());
+        assert!(Months::try_from(RelativeDuration::months(1).with_days(1)).is_err());
+        assert!(Months::try_from(RelativeDuration::months(-1)).is_err());
+    }
+
+    #[test]
+    fn test_weeks() {
+        let wednesday = date(2022, 1, 5);
+        let period = Period::try_from(wednesday.week(Weekday::Mon)).unwrap();
+
+        assert_eq!(period.start(), date(2022, 1, 3));
+        assert_eq!(
+            NaiveWeek::try_from(period),
+            Ok(wednesday.week(Weekday::Mon))
+        );
+
+        let month = Period::containing(wednesday, Grain::Month, &FiscalYear::calendar());
+        assert!(NaiveWeek::try_from(month).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+//! Conformance tests against checked in golden files
+//!
+//! The expected outputs in `testdata/` were produced with python-dateutil, the month arithmetic
+//! from `relativedelta` and the recurrence examples of RFC 5545 from `rrule`, so code ported from
+//! Python can be checked for equivalence. The same examples are also expanded from their RRULEs
+//! with `rrulestr`. Lines starting with `#` are comments.
+use chrono::NaiveDate;
+
+#[cfg(feature = "rrule")]
+use crate::recurrence::RRule;
+use crate::{
+    duration::parse::parse_relative_duration, util::EomPolicy, Recurrence, RelativeDuration, Rule,
+};
+
+const RELATIVEDELTA: &str = include_str!("../testdata/relativedelta.txt");
+const RFC5545: &str = include_str!("../testdata/rfc5545.txt");
+#[cfg(feature = "rrule")]
+const RRULE: &str = include_str!("../testdata/rrule.txt");
+
+fn cases(corpus: &str) -> impl Iterator<Item = &str> {
+    corpus
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+}
+
+fn date(s: &str) -> NaiveDate {
+    s.parse()
+        .unwrap_or_else(|e| panic!("{} is not a date: {}", s, e))
+}
+
+fn duration(s: &str) -> RelativeDuration {
+    match parse_relative_duration(s.as_bytes()) {
+        Ok((b"", duration)) => duration,
+        _ => panic!("{} is not a duration", s),
+    }
+}
+
+/// Parse a rule from the form given by [Rule::canonical]
+fn rule(s: &str) -> Rule {
+    let parts: Vec<&str> = s.split('/').collect();
+    let rule = match parts.as_slice() {
+        ["offset", d, offset] => Rule::Offset(duration(d), offset.parse().unwrap()),
+        ["weekdays", set] => Rule::Weekdays(set.parse().unwrap()),
+        ["monthday", day] => Rule::MonthDay(day.parse().unwrap()),
+        ["nthweekday", nth] => Rule::NthWeekday(nth.parse().unwrap()),
+        _ => panic!("unsupported rule {}", s),
+    };
+    assert_eq!(rule.canonical(), s);
+    rule
+}
+
+#[test]
+fn test_relativedelta() {
+    let mut count = 0;
+    for case in cases(RELATIVEDELTA) {
+        let fields: Vec<&str> = case.split_whitespace().collect();
+        let [start, rd, expected] = fields.as_slice() else {
+            panic!("malformed case {}", case);
+        };
+
+        assert_eq!(
+            duration(rd).add_with_policy(date(start), EomPolicy::ClampOnly),
+            date(expected),
+            "{}",
+            case
+        );
+        count += 1;
+    }
+    assert!(count > 0);
+}
+
+#[test]
+fn test_rfc5545() {
+    let mut count = 0;
+    for case in cases(RFC5545) {
+        let (spec, expected) = case.split_once(" = ").expect("missing occurrences");
+        let fields: Vec<&str> = spec.split_whitespace().collect();
+        let [r, start, limit] = fields.as_slice() else {
+            panic!("malformed case {}", case);
+        };
+        let expected: Vec<NaiveDate> = expected.split_whitespace().map(date).collect();
+
+        let recurrence = Recurrence::with_start(rule(r), date(start));
+        let occurrences: Vec<NaiveDate> = match limit.split_once('=') {
+            Some(("count", n)) => recurrence.take(n.parse().unwrap()).collect(),
+            Some(("until", until)) => recurrence.until_and_including(date(until)).collect(),
+            _ => panic!("unsupported limit {}", limit),
+        };
+
+        assert_eq!(occurrences, expected, "{}", case);
+        count += 1;
+    }
+    assert!(count > 0);
+}
+
+#[cfg(feature = "rrule")]
+#[test]
+fn test_rrule() {
+    let mut count = 0;
+    for case in cases(RRULE) {
+        let (spec, expected) = case.split_once(" = ").expect("missing occurrences");
+        let fields: Vec<&str> = spec.split_whitespace().collect();
+        let [r, start, limit] = fields.as_slice() else {
+            panic!("malformed case {}", case);
+        };
+        let expected: Vec<NaiveDate> = expected.split_whitespace().map(date).collect();
+
+        let rule: RRule = r.parse().unwrap_or_else(|e| panic!("{}: {}", case, e));
+        let occurrences: Vec<NaiveDate> = match limit.split_once('=') {
+            Some(("take", n)) => rule.iter(date(start)).take(n.parse().unwrap()).collect(),
+            None if *limit == "all" => rule.iter(date(start)).collect(),
+            _ => panic!("unsupported limit {}", limit),
+        };
+
+        assert_eq!(occurrences, expected, "{}", case);
+        count += 1;
+    }
+    assert!(count > 0);
+}
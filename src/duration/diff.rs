@@ -0,0 +1,62 @@
+//! Differences between dates limited to the smaller units
+use chrono::NaiveDate;
+
+use crate::{grain::Grain, RelativeDuration};
+
+/// The duration from `a` to `b` using units no larger than `max_unit`
+///
+/// Grains of a month or longer give the same months and days as
+/// [RelativeDuration::from_duration_between], a week gives weeks and days, and a day gives only
+/// days. Whatever the unit, adding the duration to `a` gives `b`.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{duration::diff_in, grain::Grain, RelativeDuration};
+///
+/// let a = NaiveDate::from_ymd_opt(2022, 1, 15).unwrap();
+/// let b = NaiveDate::from_ymd_opt(2023, 3, 1).unwrap();
+///
+/// assert_eq!(diff_in(a, b, Grain::Year).iso8601(), "P13M14D");
+/// assert_eq!(diff_in(a, b, Grain::Week).iso8601(), "P58W4D");
+/// assert_eq!(diff_in(a, b, Grain::Day), RelativeDuration::days(410));
+/// ```
+pub fn diff_in(a: NaiveDate, b: NaiveDate, max_unit: Grain) -> RelativeDuration {
+    let days = b.signed_duration_since(a).num_days() as i32;
+
+    match max_unit {
+        Grain::Day => RelativeDuration::days(days),
+        Grain::Week => RelativeDuration::from_mwd(0, days / 7, days % 7),
+        _ => RelativeDuration::from_duration_between(a, b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::testing::date;
+
+    #[quickcheck]
+    fn adding_the_difference_gives_the_end(a: u16, b: u16, unit: u8) -> bool {
+        let epoch = date(2000, 1, 1);
+        let (a, b) = (
+            epoch + chrono::Duration::days(a.into()),
+            epoch + chrono::Duration::days(b.into()),
+        );
+        let unit = [Grain::Day, Grain::Week, Grain::Month, Grain::Year][unit as usize % 4];
+
+        a + diff_in(a, b, unit) == b
+    }
+
+    #[test]
+    fn test_negative_differences() {
+        let a = date(2022, 3, 1);
+        let b = date(2022, 2, 20);
+
+        assert_eq!(diff_in(a, b, Grain::Week).iso8601(), "P-1W-2D");
+        assert_eq!(diff_in(a, b, Grain::Day), RelativeDuration::days(-9));
+    }
+}
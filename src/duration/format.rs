@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::fmt::{Debug, Display};
 
 use crate::RelativeDuration;
 
@@ -16,7 +16,7 @@ pub fn pluralize(unit: &str, num: i32) -> Option<String> {
 
 impl Display for RelativeDuration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let build = vec![
+        let build = [
             pluralize("month", self.num_months()),
             pluralize("week", self.num_weeks()),
             pluralize("day", self.num_days()),
@@ -37,3 +37,23 @@ impl Display for RelativeDuration {
         f.write_str(&result)
     }
 }
+
+/// Debug output uses the ISO8601 form rather than the bitpacked fields, e.g. `RelativeDuration(P1M2D)`
+impl Debug for RelativeDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RelativeDuration({})", self.iso8601())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(
+            format!("{:?}", RelativeDuration::months(1).with_days(-2)),
+            "RelativeDuration(P1M-2D)"
+        );
+    }
+}
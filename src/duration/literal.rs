@@ -0,0 +1,182 @@
+//! Duration literals checked at compile time
+//!
+//! The [caldur](crate::caldur) macro turns a literal such as `caldur!("1y 2m 10d")` or
+//! `caldur!(3 months)` into a [RelativeDuration]. The literal is parsed in
+//! a constant, so a malformed literal or one out of range fails to compile rather than panicking at
+//! runtime.
+//!
+//! A literal is a sequence of signed whole numbers each followed by a unit: `y`, `year` or `years`,
+//! `m`, `month` or `months`, `w`, `week` or `weeks`, and `d`, `day` or `days`. Each unit may only
+//! be given once and years are counted as twelve months.
+use crate::RelativeDuration;
+
+/// Parse a duration literal into its months, weeks and days
+///
+/// Used by [caldur](crate::caldur), panics on an invalid literal so that evaluating it in a
+/// constant fails to compile.
+#[doc(hidden)]
+pub const fn parse_literal(literal: &str) -> (i32, i32, i32) {
+    let bytes = literal.as_bytes();
+    // months from years, months, weeks and days
+    let mut parts: [Option<i64>; 4] = [None; 4];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b' ' {
+            i += 1;
+            continue;
+        }
+
+        let negative = bytes[i] == b'-';
+        if negative || bytes[i] == b'+' {
+            i += 1;
+        }
+        let digits = i;
+        let mut n: i64 = 0;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            n = n * 10 + (bytes[i] - b'0') as i64;
+            if n > RelativeDuration::MAX_COMPONENT as i64 {
+                panic!("duration literal is out of range");
+            }
+            i += 1;
+        }
+        if i == digits {
+            panic!("expected a number in the duration literal");
+        }
+        while i < bytes.len() && bytes[i] == b' ' {
+            i += 1;
+        }
+
+        let word = i;
+        while i < bytes.len() && bytes[i].is_ascii_alphabetic() {
+            i += 1;
+        }
+        let unit = if is_unit(bytes, word, i, b"y", b"year") {
+            0
+        } else if is_unit(bytes, word, i, b"m", b"month") {
+            1
+        } else if is_unit(bytes, word, i, b"w", b"week") {
+            2
+        } else if is_unit(bytes, word, i, b"d", b"day") {
+            3
+        } else {
+            panic!("expected a unit of y, m, w or d in the duration literal");
+        };
+
+        if parts[unit].is_some() {
+            panic!("a unit is given more than once in the duration literal");
+        }
+        parts[unit] = Some(if negative { -n } else { n });
+    }
+
+    let months = unwrap_or_zero(parts[0]) * 12 + unwrap_or_zero(parts[1]);
+    if parts[0].is_none() && parts[1].is_none() && parts[2].is_none() && parts[3].is_none() {
+        panic!("duration literal is empty");
+    }
+    if months.abs() > RelativeDuration::MAX_COMPONENT as i64 {
+        panic!("duration literal is out of range");
+    }
+    (
+        months as i32,
+        unwrap_or_zero(parts[2]) as i32,
+        unwrap_or_zero(parts[3]) as i32,
+    )
+}
+
+const fn unwrap_or_zero(part: Option<i64>) -> i64 {
+    match part {
+        Some(n) => n,
+        None => 0,
+    }
+}
+
+/// Whether the bytes from start to end are the short unit or the long unit, singular or plural
+const fn is_unit(bytes: &[u8], start: usize, end: usize, short: &[u8], long: &[u8]) -> bool {
+    let len = end - start;
+    if len == short.len() && matches_at(bytes, start, short) {
+        return true;
+    }
+    matches_at(bytes, start, long)
+        && (len == long.len() || (len == long.len() + 1 && bytes[end - 1] == b's'))
+}
+
+const fn matches_at(bytes: &[u8], start: usize, word: &[u8]) -> bool {
+    if start + word.len() > bytes.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < word.len() {
+        if bytes[start + i].to_ascii_lowercase() != word[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// A [RelativeDuration](crate::RelativeDuration) from a literal checked at compile time
+///
+/// Takes either a string such as `"1y 2m 10d"` or bare numbers and units such as `3 months`, see
+/// the [literal](crate::duration::literal) module for the units accepted.
+///
+/// # Examples
+///
+/// ```
+/// # use calends::{caldur, RelativeDuration};
+///
+/// assert_eq!(caldur!("1y 2m 10d"), RelativeDuration::from_mwd(14, 0, 10));
+/// assert_eq!(caldur!(3 months), RelativeDuration::months(3));
+/// assert_eq!(caldur!(2 weeks -1 day), RelativeDuration::from_mwd(0, 2, -1));
+/// ```
+///
+/// Invalid literals do not compile:
+///
+/// ```compile_fail
+/// # use calends::caldur;
+/// let duration = caldur!("1y 2q");
+/// ```
+///
+/// ```compile_fail
+/// # use calends::caldur;
+/// let duration = caldur!(3 fortnights);
+/// ```
+#[macro_export]
+macro_rules! caldur {
+    ($literal:literal) => {{
+        const MWD: (i32, i32, i32) = $crate::duration::literal::parse_literal($literal);
+        $crate::RelativeDuration::from_mwd(MWD.0, MWD.1, MWD.2)
+    }};
+    ($($n:literal $unit:ident)+) => {
+        $crate::caldur!(@literal concat!($(stringify!($n), stringify!($unit), " "),+))
+    };
+    (@literal $literal:expr) => {{
+        const MWD: (i32, i32, i32) = $crate::duration::literal::parse_literal($literal);
+        $crate::RelativeDuration::from_mwd(MWD.0, MWD.1, MWD.2)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_literal() {
+        assert_eq!(parse_literal("1y 2m 10d"), (14, 0, 10));
+        assert_eq!(parse_literal("2 Weeks -1day"), (0, 2, -1));
+        assert_eq!(parse_literal("-1y+13months"), (1, 0, 0));
+        assert_eq!(parse_literal("1048575d"), (0, 0, 1048575));
+    }
+
+    #[test]
+    fn test_invalid_literals_panic() {
+        for literal in [
+            "", "1", "d", "1q", "1y 1y", "1dayss", "1048576d", "87382y", "1 - 2d",
+        ] {
+            assert!(
+                std::panic::catch_unwind(|| parse_literal(literal)).is_err(),
+                "{}",
+                literal
+            );
+        }
+    }
+}
@@ -1,7 +1,14 @@
+pub mod diff;
 pub mod format;
+pub mod literal;
 pub mod parse;
 pub mod relative;
+pub mod round;
+#[cfg(feature = "serde")]
 pub mod serde;
 
+#[cfg(feature = "serde")]
 pub use self::serde::rd_iso8601;
+pub use diff::diff_in;
 pub use relative::*;
+pub use round::Midpoint;
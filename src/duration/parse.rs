@@ -1,14 +1,16 @@
+use std::str::FromStr;
+
 use nom::{
     bytes::complete::tag,
     character::complete::one_of,
-    combinator::opt,
-    error::Error,
+    combinator::{all_consuming, opt},
+    error::{Error, ErrorKind},
     multi::count,
     sequence::{preceded, tuple},
     Err, IResult,
 };
 
-use crate::{parser::take_signed_digits, RelativeDuration};
+use crate::{error::CalendsError, parser::take_signed_digits, RelativeDuration};
 
 #[derive(Debug, PartialEq)]
 pub enum Unit {
@@ -35,17 +37,55 @@ fn parse_duration_chunk(input: &[u8]) -> IResult<&[u8], Unit> {
 pub fn parse_relative_duration(input: &[u8]) -> IResult<&[u8], RelativeDuration> {
     let (leftover, units) = preceded(tag("P"), count(opt(parse_duration_chunk), 4))(input)?;
 
-    let rd = units
-        .iter()
-        .flatten()
-        .fold(RelativeDuration::default(), |start, unit| match unit {
-            Unit::Years(y) => start.with_months(y * 12),
-            Unit::Months(m) => start.with_months(*m),
-            Unit::Weeks(w) => start.with_weeks(*w),
-            Unit::Days(d) => start.with_days(*d),
-        });
-
-    Ok((leftover, rd))
+    if units.iter().all(Option::is_none) {
+        return Err(Err::Error(Error::new(leftover, ErrorKind::Many1)));
+    }
+
+    // years and months are both kept as months, so `P1Y6M` is eighteen months
+    let (mut months, mut weeks, mut days) = (0i32, 0i32, 0i32);
+    for unit in units.iter().flatten() {
+        let added = match unit {
+            Unit::Years(y) => y.checked_mul(12).and_then(|y| months.checked_add(y)),
+            Unit::Months(m) => months.checked_add(*m),
+            Unit::Weeks(w) => weeks.checked_add(*w),
+            Unit::Days(d) => days.checked_add(*d),
+        };
+        let field = match unit {
+            Unit::Years(_) | Unit::Months(_) => &mut months,
+            Unit::Weeks(_) => &mut weeks,
+            Unit::Days(_) => &mut days,
+        };
+        *field = added
+            .filter(|n| n.unsigned_abs() <= RelativeDuration::MAX_COMPONENT)
+            .ok_or(Err::Error(Error::new(leftover, ErrorKind::TooLarge)))?;
+    }
+
+    Ok((leftover, RelativeDuration::from_mwd(months, weeks, days)))
+}
+
+/// Parse an ISO8601-2:2019 duration such as `P3Y6M4D` or `P1M-1D`
+///
+/// Years are converted to months and hours or smaller units are not supported.
+///
+/// # Examples
+///
+/// ```
+/// # use calends::RelativeDuration;
+///
+/// let duration: RelativeDuration = "P3Y6M4D".parse()?;
+/// assert_eq!(duration, RelativeDuration::from_mwd(42, 0, 4));
+///
+/// assert!("P3H".parse::<RelativeDuration>().is_err());
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+impl FromStr for RelativeDuration {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all_consuming(parse_relative_duration)(s.as_bytes())
+            .map(|(_, duration)| duration)
+            .map_err(|_| CalendsError::InvalidDuration(s.to_string()))
+    }
 }
 
 #[cfg(test)]
@@ -76,6 +116,40 @@ mod tests {
         assert_eq!(duration, Unit::Days(180))
     }
 
+    #[test]
+    fn test_parse_years_and_months() {
+        let (_input, duration) = parse_relative_duration("P1Y6M".as_bytes()).unwrap();
+        assert_eq!(duration, RelativeDuration::months(18));
+    }
+
+    #[test]
+    fn test_from_str_rejects_invalid_durations() {
+        for s in [
+            "",
+            "P",
+            "1M",
+            "P1M ",
+            "PT1H",
+            "P99999999999D",
+            "P1048576M",
+            "P87382Y",
+        ] {
+            assert_eq!(
+                s.parse::<RelativeDuration>(),
+                Err(CalendsError::InvalidDuration(s.to_string())),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            "P1048575M"
+                .parse::<RelativeDuration>()
+                .unwrap()
+                .num_months(),
+            1048575
+        );
+    }
+
     #[test]
     fn test_parse_duration() {
         let (_input, duration) = parse_relative_duration("P3W2D".as_bytes()).unwrap();
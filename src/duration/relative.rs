@@ -1,11 +1,13 @@
 //! Implement a Duration that extends chrono and adds Quarter and Month
+// modular_bitfield expands the field types of `RelativeImpl` inside parentheses
+#![allow(unused_parens)]
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
 use chrono::{Datelike, NaiveDate};
 use modular_bitfield::bitfield;
 use modular_bitfield::prelude::B20;
 
-use crate::shift;
+use crate::{error::CalendsError, shift, util::EomPolicy};
 
 #[bitfield]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -44,14 +46,20 @@ pub struct RelativeImpl {
 ///       ◀ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─ ─
 ///
 /// ```
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct RelativeDuration(RelativeImpl);
 
 impl RelativeDuration {
+    /// The largest number of months, weeks or days a duration can hold, either way
+    pub const MAX_COMPONENT: u32 = (1 << 20) - 1;
+
     /// Returns a RelativeDuration for a given set of dates
     ///
     /// Calculate the difference between two sets of dates and return back a duration
     ///
+    /// The duration is made up of the most whole months which do not pass the end date followed
+    /// by the remaining days, so that adding the duration to `start` always gives `end`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -59,28 +67,74 @@ impl RelativeDuration {
     /// # use chrono::NaiveDate;
     ///
     /// let duration = RelativeDuration::from_duration_between(
-    ///     NaiveDate::from_ymd(2022, 1, 1),
-    ///     NaiveDate::from_ymd(2023, 1, 1),
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
     ///  );
     ///
     /// assert_eq!(
-    ///     NaiveDate::from_ymd(2022, 1, 1) + duration,
-    ///     NaiveDate::from_ymd(2023, 1, 1)
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap() + duration,
+    ///     NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()
     /// );
     /// ```
     pub fn from_duration_between(start: NaiveDate, end: NaiveDate) -> RelativeDuration {
         let mut months = (end.year() - start.year()) * 12;
         months += end.month() as i32 - start.month() as i32;
-        let days = end.day() as i32 - start.day() as i32;
+
+        // shifting by months clamps the day of the month, step back a month when that overshoots
+        let mut shifted = shift::shift_months(start, months);
+        if end >= start && shifted > end {
+            months -= 1;
+            shifted = shift::shift_months(start, months);
+        } else if end < start && shifted < end {
+            months += 1;
+            shifted = shift::shift_months(start, months);
+        }
+
+        let days = end.signed_duration_since(shifted).num_days() as i32;
         RelativeDuration::from_raw(months, 0, days).unwrap()
     }
 
+    /// Create a RelativeDuration from months, weeks and days
+    ///
+    /// # Panics
+    ///
+    /// When any of the components is beyond [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT), see
+    /// [try_from_mwd](RelativeDuration::try_from_mwd) for a fallible alternative
     pub fn from_mwd(months: i32, weeks: i32, days: i32) -> RelativeDuration {
         RelativeDuration::from_raw(months, weeks, days)
             .expect("relative duration is invalid and exceeds bounds")
     }
 
+    /// Create a RelativeDuration from months, weeks and days, failing when any of them is beyond
+    /// [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
+    ///
+    /// ```
+    /// # use calends::RelativeDuration;
+    ///
+    /// assert_eq!(
+    ///     RelativeDuration::try_from_mwd(1, 2, 3),
+    ///     Ok(RelativeDuration::from_mwd(1, 2, 3))
+    /// );
+    /// assert!(RelativeDuration::try_from_mwd(0, 0, 1 << 20).is_err());
+    /// ```
+    pub fn try_from_mwd(
+        months: i32,
+        weeks: i32,
+        days: i32,
+    ) -> Result<RelativeDuration, CalendsError> {
+        RelativeDuration::from_raw(months, weeks, days).ok_or_else(|| {
+            CalendsError::DurationOutOfRange(format!(
+                "{} months {} weeks {} days",
+                months, weeks, days
+            ))
+        })
+    }
+
     fn from_raw(months: i32, weeks: i32, days: i32) -> Option<RelativeDuration> {
+        let fits = |n: i32| n.unsigned_abs() <= RelativeDuration::MAX_COMPONENT;
+        if !(fits(months) && fits(weeks) && fits(days)) {
+            return None;
+        }
         Some(
             RelativeDuration(RelativeImpl::default())
                 .with_months(months)
@@ -89,20 +143,68 @@ impl RelativeDuration {
         )
     }
 
+    /// Create a RelativeDuration with the number of years, which are stored as twelve months each
+    ///
+    /// # Panics
+    ///
+    /// When the number of months is beyond [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
+    pub fn years(years: i32) -> RelativeDuration {
+        RelativeDuration::try_years(years).expect("relative duration is invalid and exceeds bounds")
+    }
+
+    /// Create a RelativeDuration with the number of years, failing when the number of months is
+    /// beyond [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
+    pub fn try_years(years: i32) -> Result<RelativeDuration, CalendsError> {
+        match years.checked_mul(12) {
+            Some(months) => RelativeDuration::try_months(months),
+            None => Err(CalendsError::DurationOutOfRange(format!("{} years", years))),
+        }
+    }
+
     /// Create a RelativeDuration with the number of months
+    ///
+    /// # Panics
+    ///
+    /// When the number is beyond [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
     pub fn months(months: i32) -> RelativeDuration {
-        RelativeDuration::default().with_months(months)
+        RelativeDuration::from_mwd(months, 0, 0)
+    }
+
+    /// Create a RelativeDuration with the number of months, failing when the number is beyond
+    /// [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
+    pub fn try_months(months: i32) -> Result<RelativeDuration, CalendsError> {
+        RelativeDuration::try_from_mwd(months, 0, 0)
     }
 
     /// Create a RelativeDuration with the numer of weeks
+    ///
+    /// # Panics
+    ///
+    /// When the number is beyond [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
     pub fn weeks(weeks: i32) -> RelativeDuration {
-        RelativeDuration::default().with_weeks(weeks)
+        RelativeDuration::from_mwd(0, weeks, 0)
+    }
+
+    /// Create a RelativeDuration with the number of weeks, failing when the number is beyond
+    /// [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
+    pub fn try_weeks(weeks: i32) -> Result<RelativeDuration, CalendsError> {
+        RelativeDuration::try_from_mwd(0, weeks, 0)
     }
 
     /// Create a RelativeDuration with the number of days
+    ///
+    /// # Panics
+    ///
+    /// When the number is beyond [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
     #[inline]
     pub fn days(days: i32) -> RelativeDuration {
-        RelativeDuration::zero().with_days(days)
+        RelativeDuration::from_mwd(0, 0, days)
+    }
+
+    /// Create a RelativeDuration with the number of days, failing when the number is beyond
+    /// [MAX_COMPONENT](RelativeDuration::MAX_COMPONENT)
+    pub fn try_days(days: i32) -> Result<RelativeDuration, CalendsError> {
+        RelativeDuration::try_from_mwd(0, 0, days)
     }
 
     /// Set the number of months in the duration
@@ -201,10 +303,10 @@ impl RelativeDuration {
     /// - 'P120M400D' is a duration of 120 months and 400 days
     /// - 'P4W3D' is a duration of 4 weeks and 3 days
     /// - 'P-4M3W' is a duration of negative 4 months and positive 3 weeks, the minus sign can be
-    /// applied to each of the components within the serialization format
+    ///   applied to each of the components within the serialization format
     ///
     pub fn iso8601(&self) -> String {
-        let build = vec![
+        let build = [
             (self.num_months(), "M"),
             (self.num_weeks(), "W"),
             (self.num_days(), "D"),
@@ -221,15 +323,74 @@ impl RelativeDuration {
 
         result
     }
+
+    /// Add the duration to a date with the given end of month policy
+    ///
+    /// Adding with [EomPolicy::Preserve] is the same as `date + duration`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use calends::{util::EomPolicy, RelativeDuration};
+    /// # use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 2, 28).unwrap();
+    /// let duration = RelativeDuration::months(1);
+    ///
+    /// assert_eq!(
+    ///     duration.add_with_policy(date, EomPolicy::ClampOnly),
+    ///     NaiveDate::from_ymd_opt(2022, 3, 28).unwrap()
+    /// );
+    /// ```
+    pub fn add_with_policy(&self, date: NaiveDate, policy: EomPolicy) -> NaiveDate {
+        self.try_add_with_policy(date, policy).unwrap()
+    }
+
+    /// Add the duration to a date, as `date + duration`, failing instead of panicking when the
+    /// result is outside the range of supported dates
+    ///
+    /// ```
+    /// # use calends::RelativeDuration;
+    /// # use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+    ///
+    /// assert_eq!(
+    ///     RelativeDuration::months(1).try_add_to(date),
+    ///     Ok(NaiveDate::from_ymd_opt(2022, 2, 28).unwrap())
+    /// );
+    /// assert!(RelativeDuration::days(1).try_add_to(NaiveDate::MAX).is_err());
+    /// ```
+    pub fn try_add_to(&self, date: NaiveDate) -> Result<NaiveDate, CalendsError> {
+        self.try_add_with_policy(date, EomPolicy::Preserve)
+    }
+
+    /// Subtract the duration from a date, as `date - duration`, failing instead of panicking when
+    /// the result is outside the range of supported dates
+    pub fn try_sub_from(&self, date: NaiveDate) -> Result<NaiveDate, CalendsError> {
+        (-*self).try_add_to(date)
+    }
+
+    /// Add the duration to a date with the given end of month policy, failing instead of panicking
+    /// when the result is outside the range of supported dates
+    pub fn try_add_with_policy(
+        &self,
+        date: NaiveDate,
+        policy: EomPolicy,
+    ) -> Result<NaiveDate, CalendsError> {
+        shift::try_shift_months_with_policy(date, self.num_months(), policy)
+            .and_then(|date| shift::try_shift_weeks(date, self.num_weeks()))
+            .and_then(|date| shift::try_shift_days(date, self.num_days()))
+            .map_err(|_| CalendsError::DateOutOfRange {
+                date,
+                shift: self.to_string(),
+            })
+    }
 }
 
 impl PartialOrd for RelativeDuration {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        (self.num_months(), self.num_weeks(), self.num_days()).partial_cmp(&(
-            other.num_months(),
-            other.num_weeks(),
-            other.num_days(),
-        ))
+        Some(self.cmp(other))
     }
 }
 
@@ -306,67 +467,130 @@ impl Div<i32> for RelativeDuration {
 
 /// Add a duration to a [NaiveDate]
 ///
-/// Precendence for adding is from largest unit to smallest unit
+/// Precendence for adding is from largest unit to smallest unit: months (including years) are
+/// shifted first, then weeks and then days. Each component may be negative.
+///
+/// ```
+/// # use calends::RelativeDuration;
+/// # use chrono::NaiveDate;
+///
+/// // one year and two months, then back three days
+/// let duration = (RelativeDuration::years(1) + RelativeDuration::months(2)).with_days(-3);
+///
+/// assert_eq!(
+///     NaiveDate::from_ymd_opt(2022, 1, 31).unwrap() + duration,
+///     NaiveDate::from_ymd_opt(2023, 3, 28).unwrap()
+/// );
+/// ```
 impl Add<RelativeDuration> for NaiveDate {
     type Output = NaiveDate;
 
     #[inline]
     fn add(self, rhs: RelativeDuration) -> NaiveDate {
-        let date = shift::shift_months(self, rhs.num_months());
-        let date = shift::shift_weeks(date, rhs.num_weeks());
-        shift::shift_days(date, rhs.num_days())
+        rhs.try_add_to(self).unwrap()
+    }
+}
+
+/// Subtract a duration from a [NaiveDate]
+///
+/// The same as adding the negated duration, so months are shifted back first and the end of
+/// month is kept in the same way as when adding.
+///
+/// ```
+/// # use calends::RelativeDuration;
+/// # use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2022, 3, 31).unwrap();
+///
+/// assert_eq!(date - RelativeDuration::months(1), NaiveDate::from_ymd_opt(2022, 2, 28).unwrap());
+/// assert_eq!(date - RelativeDuration::months(1) + RelativeDuration::months(1), date);
+/// ```
+impl Sub<RelativeDuration> for NaiveDate {
+    type Output = NaiveDate;
+
+    #[inline]
+    fn sub(self, rhs: RelativeDuration) -> NaiveDate {
+        self + -rhs
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::date;
 
     #[test]
-    fn test_from_duration_transits_year() {
-        let duration = RelativeDuration::from_duration_between(
-            NaiveDate::from_ymd(2022, 1, 1),
-            NaiveDate::from_ymd(2023, 1, 1),
-        );
+    fn test_try_constructors() {
+        let max = RelativeDuration::MAX_COMPONENT as i32;
 
         assert_eq!(
-            NaiveDate::from_ymd(2022, 1, 1) + duration,
-            NaiveDate::from_ymd(2023, 1, 1)
+            RelativeDuration::try_days(-max).map(|d| d.num_days()),
+            Ok(-max)
         );
+        assert_eq!(
+            RelativeDuration::try_weeks(max + 1),
+            Err(CalendsError::DurationOutOfRange(format!(
+                "0 months {} weeks 0 days",
+                max + 1
+            )))
+        );
+        assert!(RelativeDuration::try_months(i32::MIN).is_err());
+        assert!(RelativeDuration::try_from_mwd(0, 0, max + 1).is_err());
+        assert_eq!(
+            RelativeDuration::try_years(87_381),
+            Ok(RelativeDuration::months(1_048_572))
+        );
+        assert!(RelativeDuration::try_years(87_382).is_err());
+        assert_eq!(
+            RelativeDuration::try_years(i32::MAX),
+            Err(CalendsError::DurationOutOfRange(format!(
+                "{} years",
+                i32::MAX
+            )))
+        );
+    }
+
+    #[test]
+    fn test_from_duration_transits_year() {
+        let duration = RelativeDuration::from_duration_between(date(2022, 1, 1), date(2023, 1, 1));
+
+        assert_eq!(date(2022, 1, 1) + duration, date(2023, 1, 1));
     }
 
     #[test]
     fn test_from_duration_transits_month() {
-        let duration = RelativeDuration::from_duration_between(
-            NaiveDate::from_ymd(2023, 3, 1),
-            NaiveDate::from_ymd(2023, 3, 31),
-        );
+        let duration = RelativeDuration::from_duration_between(date(2023, 3, 1), date(2023, 3, 31));
 
-        assert_eq!(
-            NaiveDate::from_ymd(2023, 3, 1) + duration,
-            NaiveDate::from_ymd(2023, 3, 31)
-        );
+        assert_eq!(date(2023, 3, 1) + duration, date(2023, 3, 31));
     }
 
     #[test]
     fn test_from_duration_transits_months_and_days() {
-        let duration = RelativeDuration::from_duration_between(
-            NaiveDate::from_ymd(2023, 3, 1),
-            NaiveDate::from_ymd(2023, 4, 20),
-        );
+        let duration = RelativeDuration::from_duration_between(date(2023, 3, 1), date(2023, 4, 20));
 
-        assert_eq!(
-            NaiveDate::from_ymd(2023, 3, 1) + duration,
-            NaiveDate::from_ymd(2023, 4, 20)
-        );
+        assert_eq!(date(2023, 3, 1) + duration, date(2023, 4, 20));
+    }
+
+    #[test]
+    fn test_from_duration_between_end_of_month() {
+        let start = date(2022, 1, 31);
+        let end = date(2022, 2, 6);
+        let duration = RelativeDuration::from_duration_between(start, end);
+
+        assert_eq!(duration, RelativeDuration::days(6));
+        assert_eq!(start + duration, end);
+
+        let start = date(2022, 3, 31);
+        let end = date(2022, 2, 27);
+        let duration = RelativeDuration::from_duration_between(start, end);
+
+        assert_eq!(duration, RelativeDuration::months(-1).with_days(-1));
+        assert_eq!(start + duration, end);
     }
 
     #[test]
     fn test_from_duration_between_year() {
-        let duration = RelativeDuration::from_duration_between(
-            NaiveDate::from_ymd(2022, 1, 1),
-            NaiveDate::from_ymd(2023, 1, 1),
-        );
+        let duration = RelativeDuration::from_duration_between(date(2022, 1, 1), date(2023, 1, 1));
 
         assert_eq!(duration.num_months(), 12);
         assert_eq!(duration.num_weeks(), 0);
@@ -375,10 +599,7 @@ mod tests {
 
     #[test]
     fn test_from_duration_between_month() {
-        let duration = RelativeDuration::from_duration_between(
-            NaiveDate::from_ymd(2022, 1, 1),
-            NaiveDate::from_ymd(2022, 2, 1),
-        );
+        let duration = RelativeDuration::from_duration_between(date(2022, 1, 1), date(2022, 2, 1));
 
         assert_eq!(duration.num_months(), 1);
         assert_eq!(duration.num_days(), 0);
@@ -484,10 +705,27 @@ mod tests {
         assert_eq!(RelativeDuration::days(-1).num_days(), -1)
     }
 
+    #[test]
+    fn test_try_add_out_of_range() {
+        let duration = RelativeDuration::months(1).with_days(1);
+        let date = date(2022, 1, 31);
+
+        assert_eq!(duration.try_add_to(date), Ok(date + duration));
+        assert_eq!(duration.try_sub_from(date), Ok(date - duration));
+        assert_eq!(
+            duration.try_add_to(NaiveDate::MAX),
+            Err(CalendsError::DateOutOfRange {
+                date: NaiveDate::MAX,
+                shift: duration.to_string()
+            })
+        );
+        assert!(duration.try_sub_from(NaiveDate::MIN).is_err());
+    }
+
     #[test]
     fn test_add_year() {
         let rd = RelativeDuration::months(12);
-        let next = NaiveDate::from_ymd(2022, 1, 1) + rd;
-        assert_eq!(next, NaiveDate::from_ymd(2023, 1, 1));
+        let next = date(2022, 1, 1) + rd;
+        assert_eq!(next, date(2023, 1, 1));
     }
 }
@@ -0,0 +1,143 @@
+//! Rounding durations to calendar units
+use crate::{grain::Grain, RelativeDuration};
+
+/// Average length of a month in the Gregorian calendar, in days
+///
+/// Used to weigh weeks and days against months since a duration is not anchored to a date.
+const DAYS_PER_MONTH: f64 = 146_097.0 / 4_800.0;
+
+/// Which way a duration exactly half way between two units is rounded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Midpoint {
+    /// e.g. six months rounds to one year and minus six months to minus one year
+    #[default]
+    AwayFromZero,
+    /// e.g. six months rounds to zero years
+    TowardZero,
+    /// Round to the even number of units, e.g. six months rounds to zero years and eighteen
+    /// months to two years
+    ToEven,
+}
+
+impl Midpoint {
+    fn round(&self, units: f64) -> i32 {
+        let rounded = match self {
+            Midpoint::AwayFromZero => units.round(),
+            Midpoint::TowardZero if units.fract().abs() == 0.5 => units.trunc(),
+            Midpoint::TowardZero => units.round(),
+            Midpoint::ToEven => units.round_ties_even(),
+        };
+        rounded as i32
+    }
+}
+
+impl RelativeDuration {
+    /// Round the duration to a whole number of the grain, halves are rounded away from zero
+    ///
+    /// See [RelativeDuration::round_with] for how the units of the duration are weighed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use calends::{grain::Grain, RelativeDuration};
+    /// # use chrono::NaiveDate;
+    ///
+    /// // one year and seven months
+    /// assert_eq!(RelativeDuration::months(19).round_to(Grain::Year), RelativeDuration::months(24));
+    ///
+    /// let tenure = RelativeDuration::from_duration_between(
+    ///     NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2022, 2, 20).unwrap(),
+    /// );
+    /// assert_eq!(tenure.round_to(Grain::Month), RelativeDuration::months(12));
+    /// ```
+    pub fn round_to(&self, grain: Grain) -> RelativeDuration {
+        self.round_with(grain, Midpoint::default())
+    }
+
+    /// Round the duration to a whole number of the grain with the given midpoint behavior
+    ///
+    /// - Rounding to days leaves the duration unchanged
+    /// - Rounding to weeks keeps the months and rounds the weeks and days to whole weeks
+    /// - Rounding to a month or longer grain gives a duration of only months, with weeks and days
+    ///   weighed as a fraction of an average Gregorian month of 30.436875 days
+    ///
+    /// ```
+    /// # use calends::{duration::Midpoint, grain::Grain, RelativeDuration};
+    ///
+    /// let half = RelativeDuration::months(6);
+    /// assert_eq!(half.round_with(Grain::Year, Midpoint::AwayFromZero), RelativeDuration::months(12));
+    /// assert_eq!(half.round_with(Grain::Year, Midpoint::TowardZero), RelativeDuration::zero());
+    /// ```
+    pub fn round_with(&self, grain: Grain, midpoint: Midpoint) -> RelativeDuration {
+        let days = 7 * self.num_weeks() + self.num_days();
+
+        match grain.months() {
+            None if grain == Grain::Week => RelativeDuration::months(self.num_months())
+                .with_weeks(midpoint.round(days as f64 / 7.0)),
+            None => *self,
+            Some(months) => {
+                let total = self.num_months() as f64 + days as f64 / DAYS_PER_MONTH;
+                RelativeDuration::months(months * midpoint.round(total / months as f64))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_to_weeks() {
+        let duration = RelativeDuration::months(1).with_days(10);
+        assert_eq!(
+            duration.round_to(Grain::Week),
+            RelativeDuration::months(1).with_weeks(1)
+        );
+        assert_eq!(
+            RelativeDuration::days(-4).round_to(Grain::Week),
+            RelativeDuration::weeks(-1)
+        );
+        assert_eq!(duration.round_to(Grain::Day), duration);
+    }
+
+    #[test]
+    fn test_round_days_into_months() {
+        // 45 days is just under one and a half months
+        assert_eq!(
+            RelativeDuration::days(45).round_to(Grain::Month),
+            RelativeDuration::months(1)
+        );
+        assert_eq!(
+            RelativeDuration::months(2)
+                .with_days(-20)
+                .round_to(Grain::Month),
+            RelativeDuration::months(1)
+        );
+        assert_eq!(
+            RelativeDuration::months(4)
+                .with_weeks(3)
+                .round_to(Grain::Quarter),
+            RelativeDuration::months(6)
+        );
+    }
+
+    #[test]
+    fn test_midpoints() {
+        let years = |months, midpoint| {
+            RelativeDuration::months(months)
+                .round_with(Grain::Year, midpoint)
+                .num_months()
+                / 12
+        };
+
+        assert_eq!(years(18, Midpoint::AwayFromZero), 2);
+        assert_eq!(years(-18, Midpoint::AwayFromZero), -2);
+        assert_eq!(years(18, Midpoint::TowardZero), 1);
+        assert_eq!(years(-18, Midpoint::TowardZero), -1);
+        assert_eq!(years(18, Midpoint::ToEven), 2);
+        assert_eq!(years(30, Midpoint::ToEven), 2);
+        assert_eq!(years(19, Midpoint::TowardZero), 2);
+    }
+}
@@ -1,4 +1,4 @@
-use serde::{ser::SerializeStruct, Serialize, Serializer};
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::RelativeDuration;
 
@@ -16,6 +16,40 @@ impl Serialize for RelativeDuration {
     }
 }
 
+/// Deserialize a `RelativeDuration` from the struct it serializes as or from an ISO8601-2 duration
+///
+/// Missing fields of the struct are zero, so `{"months":3}` and `"P3M"` are the same duration.
+impl<'de> Deserialize<'de> for RelativeDuration {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            #[serde(deserialize_with = "rd_iso8601::deserialize")]
+            Iso8601(RelativeDuration),
+            Fields(Fields),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Fields {
+            #[serde(default)]
+            months: i32,
+            #[serde(default)]
+            weeks: i32,
+            #[serde(default)]
+            days: i32,
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Iso8601(rd) => Ok(rd),
+            Repr::Fields(f) => Ok(RelativeDuration::from_mwd(f.months, f.weeks, f.days)),
+        }
+    }
+}
+
 /// Used to serialize/deserialize from ISO8601-2:2019 Durations
 ///
 /// # Example:
@@ -133,4 +167,23 @@ mod tests {
         let parsed: S = serde_json::from_str(&serde_json::to_string(&s).unwrap()).unwrap();
         assert_eq!(rd, parsed.rd)
     }
+
+    #[test]
+    fn test_deserialize_either_form() {
+        let rd = RelativeDuration::from_mwd(3, 0, -3);
+
+        assert_eq!(
+            serde_json::from_str::<RelativeDuration>(&serde_json::to_string(&rd).unwrap()).unwrap(),
+            rd
+        );
+        assert_eq!(
+            serde_json::from_str::<RelativeDuration>(r#"{"months":3,"days":-3}"#).unwrap(),
+            rd
+        );
+        assert_eq!(
+            serde_json::from_str::<RelativeDuration>(r#""P3M-3D""#).unwrap(),
+            rd
+        );
+        assert!(serde_json::from_str::<RelativeDuration>(r#"{"years":1}"#).is_err());
+    }
 }
@@ -0,0 +1,373 @@
+//! Compact binary encoding of intervals, sets of dates and interval sets
+//!
+//! Dates are written as the number of days since 1970-01-01 using LEB128 style variable length
+//! integers, with signed values zigzag encoded so dates close to the epoch stay small. Sets of dates
+//! are written as the first date followed by the gap to each following date, so a year of daily
+//! availability takes a little over a byte per date. Interval sets are written the same way, with
+//! the length of each interval and the gap to the next one, so their size depends on the number of
+//! intervals rather than the number of dates.
+//!
+//! # Examples
+//!
+//! ```
+//! # use std::collections::BTreeSet;
+//! # use chrono::NaiveDate;
+//! # use calends::interval::{ClosedInterval, IntervalSet};
+//! # use calends::{encoding, Interval, RelativeDuration};
+//!
+//! let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+//! let interval = Interval::closed_from_start(start, RelativeDuration::months(1));
+//!
+//! let bytes = encoding::encode_interval(&interval);
+//! assert_eq!(bytes.len(), 7);
+//! assert_eq!(encoding::decode_interval(&bytes)?, interval);
+//!
+//! let dates: BTreeSet<NaiveDate> = start.iter_days().take(365).collect();
+//! let bytes = encoding::encode_dates(&dates);
+//! assert_eq!(bytes.len(), 367);
+//! assert_eq!(encoding::decode_dates(&bytes)?, dates);
+//!
+//! let mut set = IntervalSet::new();
+//! set.insert(&ClosedInterval::with_dates(start, start + RelativeDuration::months(1)));
+//! let bytes = encoding::encode_interval_set(&set);
+//! assert_eq!(bytes.len(), 4);
+//! assert_eq!(encoding::decode_interval_set(&bytes)?, set);
+//! # Ok::<(), calends::CalendsError>(())
+//! ```
+use std::collections::BTreeSet;
+
+use chrono::{Duration, NaiveDate};
+
+use crate::{
+    error::CalendsError,
+    interval::{
+        marker::{End, Start},
+        ClosedInterval, IntervalSet, OpenEndInterval, OpenStartInterval,
+    },
+    Interval, IntervalLike, RelativeDuration,
+};
+
+const CLOSED: u8 = 0;
+const OPEN_START: u8 = 1;
+const OPEN_END: u8 = 2;
+
+/// Encode an interval as its kind followed by its dates
+///
+/// Closed intervals keep their start and duration so they decode to an equal interval.
+pub fn encode_interval(interval: &Interval) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    match interval {
+        Interval::Closed(closed) => {
+            bytes.push(CLOSED);
+            put_date(&mut bytes, closed.start_opt().unwrap());
+            put_signed(&mut bytes, closed.duration.num_months().into());
+            put_signed(&mut bytes, closed.duration.num_weeks().into());
+            put_signed(&mut bytes, closed.duration.num_days().into());
+        }
+        Interval::OpenStart(open) => {
+            bytes.push(OPEN_START);
+            put_date(&mut bytes, open.end_opt().unwrap());
+        }
+        Interval::OpenEnd(open) => {
+            bytes.push(OPEN_END);
+            put_date(&mut bytes, open.start_opt().unwrap());
+        }
+    }
+    bytes
+}
+
+/// Decode an interval written by [encode_interval]
+pub fn decode_interval(bytes: &[u8]) -> Result<Interval, CalendsError> {
+    let mut reader = Reader { bytes };
+
+    let interval = match reader.byte()? {
+        CLOSED => {
+            let start = reader.date()?;
+            let duration = RelativeDuration::from_mwd(
+                reader.component()?,
+                reader.component()?,
+                reader.component()?,
+            );
+            let closed = ClosedInterval::try_from_start(start, duration)
+                .map_err(|e| invalid(e.to_string()))?;
+            Interval::Closed(closed)
+        }
+        OPEN_START => Interval::OpenStart(OpenStartInterval::new(reader.date()?)),
+        OPEN_END => Interval::OpenEnd(OpenEndInterval::new(reader.date()?)),
+        kind => return Err(invalid(format!("unknown interval kind {}", kind))),
+    };
+
+    reader.finish()?;
+    Ok(interval)
+}
+
+/// Encode a set of dates as the first date followed by the gaps between dates
+pub fn encode_dates(dates: &BTreeSet<NaiveDate>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut previous: Option<NaiveDate> = None;
+
+    for date in dates {
+        match previous {
+            None => put_date(&mut bytes, *date),
+            Some(previous) => put_unsigned(
+                &mut bytes,
+                date.signed_duration_since(previous).num_days() as u64,
+            ),
+        }
+        previous = Some(*date);
+    }
+    bytes
+}
+
+/// Decode a set of dates written by [encode_dates]
+pub fn decode_dates(bytes: &[u8]) -> Result<BTreeSet<NaiveDate>, CalendsError> {
+    let mut reader = Reader { bytes };
+    let mut dates = BTreeSet::new();
+
+    if reader.bytes.is_empty() {
+        return Ok(dates);
+    }
+
+    let mut date = reader.date()?;
+    dates.insert(date);
+    while !reader.bytes.is_empty() {
+        let gap = match reader.unsigned()? {
+            0 => return Err(invalid("repeated date")),
+            gap => i64::try_from(gap).map_err(|_| invalid("gap is out of range"))?,
+        };
+        date = Duration::try_days(gap)
+            .and_then(|gap| date.checked_add_signed(gap))
+            .ok_or_else(|| invalid("date is out of range"))?;
+        dates.insert(date);
+    }
+
+    Ok(dates)
+}
+
+/// Encode an interval set as the first date followed by the length of each interval and the gap
+/// to the next
+pub fn encode_interval_set(set: &IntervalSet) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut previous: Option<NaiveDate> = None;
+
+    for interval in set {
+        let (start, end) = (interval.start(), interval.end());
+        match previous {
+            None => put_date(&mut bytes, start),
+            Some(previous) => put_unsigned(&mut bytes, days_between(previous, start)),
+        }
+        put_unsigned(&mut bytes, days_between(start, end));
+        previous = Some(end);
+    }
+    bytes
+}
+
+/// Decode an interval set written by [encode_interval_set]
+///
+/// Intervals which overlap or touch are rejected, as an encoded set never has them.
+pub fn decode_interval_set(bytes: &[u8]) -> Result<IntervalSet, CalendsError> {
+    let mut reader = Reader { bytes };
+    let mut set = IntervalSet::new();
+
+    if reader.bytes.is_empty() {
+        return Ok(set);
+    }
+
+    let mut start = reader.date()?;
+    loop {
+        let end = after(start, reader.unsigned()?)?;
+        set.insert(&ClosedInterval::with_dates_unchecked(start, end));
+        if reader.bytes.is_empty() {
+            return Ok(set);
+        }
+        start = match reader.unsigned()? {
+            0 | 1 => return Err(invalid("intervals overlap or touch")),
+            gap => after(end, gap)?,
+        };
+    }
+}
+
+/// The date a number of days after another
+fn after(date: NaiveDate, days: u64) -> Result<NaiveDate, CalendsError> {
+    i64::try_from(days)
+        .ok()
+        .and_then(Duration::try_days)
+        .and_then(|days| date.checked_add_signed(days))
+        .ok_or_else(|| invalid("date is out of range"))
+}
+
+fn days_between(from: NaiveDate, to: NaiveDate) -> u64 {
+    to.signed_duration_since(from).num_days() as u64
+}
+
+fn invalid(reason: impl Into<String>) -> CalendsError {
+    CalendsError::InvalidEncoding(reason.into())
+}
+
+fn put_unsigned(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn put_signed(bytes: &mut Vec<u8>, value: i64) {
+    put_unsigned(bytes, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+fn put_date(bytes: &mut Vec<u8>, date: NaiveDate) {
+    put_signed(
+        bytes,
+        date.signed_duration_since(NaiveDate::default()).num_days(),
+    );
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, CalendsError> {
+        let (first, rest) = self
+            .bytes
+            .split_first()
+            .ok_or_else(|| invalid("unexpected end of input"))?;
+        self.bytes = rest;
+        Ok(*first)
+    }
+
+    fn unsigned(&mut self) -> Result<u64, CalendsError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("integer is too long"))
+    }
+
+    fn signed(&mut self) -> Result<i64, CalendsError> {
+        let value = self.unsigned()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn component(&mut self) -> Result<i32, CalendsError> {
+        let value = self.signed()?;
+        match i32::try_from(value) {
+            Ok(value) if value.unsigned_abs() <= RelativeDuration::MAX_COMPONENT => Ok(value),
+            _ => Err(invalid("duration is out of range")),
+        }
+    }
+
+    fn date(&mut self) -> Result<NaiveDate, CalendsError> {
+        let days = self.signed()?;
+        Duration::try_days(days)
+            .and_then(|days| NaiveDate::default().checked_add_signed(days))
+            .ok_or_else(|| invalid("date is out of range"))
+    }
+
+    fn finish(&self) -> Result<(), CalendsError> {
+        match self.bytes.len() {
+            0 => Ok(()),
+            n => Err(invalid(format!("{} trailing bytes", n))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_interval_round_trip() {
+        for interval in [
+            Interval::closed_from_start(
+                date(1969, 12, 31),
+                RelativeDuration::months(1).with_days(-1),
+            ),
+            Interval::closed_with_dates(date(2022, 1, 1), date(2022, 1, 1)),
+            Interval::open_start(date(2022, 6, 30)),
+            Interval::open_end(date(1066, 10, 14)),
+        ] {
+            assert_eq!(decode_interval(&encode_interval(&interval)), Ok(interval));
+        }
+    }
+
+    #[test]
+    fn test_dates_round_trip() {
+        let dates: BTreeSet<NaiveDate> = [date(1900, 3, 1), date(1970, 1, 1), date(2400, 2, 29)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(decode_dates(&encode_dates(&dates)), Ok(dates));
+        assert_eq!(decode_dates(&[]), Ok(BTreeSet::new()));
+    }
+
+    #[test]
+    fn test_interval_set_round_trip() {
+        let mut set = IntervalSet::new();
+        for (start, end) in [
+            (date(1969, 12, 1), date(1970, 1, 2)),
+            (date(2022, 1, 1), date(2022, 1, 1)),
+            (date(2022, 1, 3), date(2022, 3, 31)),
+        ] {
+            set.insert(&ClosedInterval::with_dates_unchecked(start, end));
+        }
+
+        let bytes = encode_interval_set(&set);
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(decode_interval_set(&bytes), Ok(set));
+        assert_eq!(decode_interval_set(&[]), Ok(IntervalSet::new()));
+    }
+
+    #[test]
+    fn test_rejects_malformed_interval_sets() {
+        // touching and overlapping intervals
+        assert!(decode_interval_set(&[0, 0, 1, 0]).is_err());
+        assert!(decode_interval_set(&[0, 3, 0, 0]).is_err());
+        // a gap without the interval after it
+        assert!(decode_interval_set(&[0, 0, 2]).is_err());
+        assert!(
+            decode_interval_set(&[0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        let interval = encode_interval(&Interval::open_end(date(2022, 1, 1)));
+
+        assert!(decode_interval(&interval[..interval.len() - 1]).is_err());
+        assert!(decode_interval(&[interval.as_slice(), &[0]].concat()).is_err());
+        assert!(decode_interval(&[7, 0]).is_err());
+        // a closed interval with a duration of minus one day
+        assert!(decode_interval(&[CLOSED, 0, 0, 0, 1]).is_err());
+        assert!(decode_dates(&[0, 0]).is_err());
+        assert!(decode_dates(&[0xff; 11]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_durations_out_of_range() {
+        let closed = |start, months, days| {
+            let mut bytes = vec![CLOSED];
+            put_date(&mut bytes, start);
+            put_signed(&mut bytes, months);
+            put_signed(&mut bytes, 0);
+            put_signed(&mut bytes, days);
+            bytes
+        };
+
+        assert!(matches!(
+            decode_interval(&closed(NaiveDate::MAX, 1, 0)),
+            Err(CalendsError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            decode_interval(&closed(NaiveDate::MIN, 0, -1)),
+            Err(CalendsError::InvalidEncoding(_))
+        ));
+    }
+}
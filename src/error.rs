@@ -0,0 +1,103 @@
+//! Errors returned by calends
+use chrono::NaiveDate;
+
+use crate::grain::Grain;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CalendsError {
+    #[error("interval ends on {end} which is before its start on {start}")]
+    ReversedInterval { start: NaiveDate, end: NaiveDate },
+
+    #[error("interval is unbounded on both sides")]
+    UnboundedInterval,
+
+    #[error("{date} shifted by {shift} is outside the range of supported dates")]
+    DateOutOfRange { date: NaiveDate, shift: String },
+
+    #[error("{0} is too long for a relative duration")]
+    DurationOutOfRange(String),
+
+    #[error("{0} is not an ISO 8601 duration")]
+    InvalidDuration(String),
+
+    #[error("{0} is not an ISO 8601 interval")]
+    InvalidInterval(String),
+
+    #[error("{0} is not a valid cursor")]
+    InvalidCursor(String),
+
+    #[error("cursor {0} belongs to a different recurrence")]
+    StaleCursor(String),
+
+    #[error("{0} is not a valid fingerprint")]
+    InvalidFingerprint(String),
+
+    #[error("{0} is not a valid occurrence id")]
+    InvalidOccurrenceId(String),
+
+    #[error("rule {0} can never produce a series of dates")]
+    UnsatisfiableRule(String),
+
+    #[error("expansion produced more than {0} occurrences")]
+    TooManyOccurrences(usize),
+
+    #[error("expansion reached past its horizon of {0}")]
+    HorizonExceeded(NaiveDate),
+
+    #[error("{0} is not a weekday, expected one of MO, TU, WE, TH, FR, SA or SU")]
+    InvalidWeekday(String),
+
+    #[error("{0} is not a day of the month, expected 1 to 31 or -1 to -31")]
+    InvalidDayOfMonth(String),
+
+    #[error("{0} is not a month, expected 1 to 12 or a month name")]
+    InvalidMonth(String),
+
+    #[error("{0} is not a valid period")]
+    InvalidPeriod(String),
+
+    #[error("no period is labeled {0}")]
+    UnknownPeriod(String),
+
+    #[error("{0} is not an nth weekday, expected an occurrence and weekday such as 2TU or -1FR")]
+    InvalidNthWeekday(String),
+
+    #[error("invalid RRULE: {0}")]
+    InvalidRRule(String),
+
+    #[error("invalid schedule: {0}")]
+    InvalidSchedule(String),
+
+    #[error("{0} is not an RFC 3339 or RFC 9557 timestamp")]
+    InvalidTimestamp(String),
+
+    #[error("cannot resample from {from:?} to {to:?} with this method")]
+    InvalidResample { from: Grain, to: Grain },
+
+    #[error("{0} has no equivalent in the target type")]
+    IncompatibleConversion(String),
+
+    #[error("invalid encoding: {0}")]
+    InvalidEncoding(String),
+
+    #[error("holiday {0} can never occur")]
+    InvalidHoliday(String),
+
+    #[error("calendar {0} is not defined")]
+    UnknownCalendar(String),
+
+    #[error("calendar {0} inherits from itself")]
+    CyclicCalendar(String),
+
+    #[error("a calendar needs at least one business day in the week")]
+    NoBusinessDays,
+
+    #[error("{0} is not a valid sub-year grouping")]
+    InvalidSubYear(String),
+
+    #[error("resource {0} is not defined")]
+    UnknownResource(String),
+}
+
+/// The error returned by calends, an alias of [CalendsError]
+pub type Error = CalendsError;
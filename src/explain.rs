@@ -0,0 +1,347 @@
+//! Explanations of derived dates
+//!
+//! Billing and settlement dates are usually derived from a base date by shifting it and rolling
+//! it onto a business day. An [Explanation] records each step taken as structured data so that
+//! auditing and support tooling can show how a date was reached.
+use std::fmt::Display;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{holidays::HolidayCalendar, shift, util::end_of_month, RelativeDuration};
+
+/// How a month shift treated the day of the month
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MonthEnd {
+    /// The day of the month was kept
+    Unchanged,
+    /// The date was the last day of its month so the result is the last day of the target month
+    Preserved,
+    /// The target month is too short so the day was clamped to its last day
+    Clamped,
+}
+
+/// Why a date was skipped when rolling onto a business day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum NonBusinessDay {
+    Weekend,
+    Holiday,
+}
+
+/// A single step in deriving a date
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "step", rename_all = "snake_case"))]
+pub enum Step {
+    /// The date the derivation started from
+    Base {
+        date: NaiveDate,
+    },
+    ShiftMonths {
+        months: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+        month_end: MonthEnd,
+    },
+    ShiftWeeks {
+        weeks: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    ShiftDays {
+        days: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    /// Rolled forward to the next business day, listing the days skipped over
+    RollForward {
+        from: NaiveDate,
+        to: NaiveDate,
+        skipped: Vec<(NaiveDate, NonBusinessDay)>,
+    },
+    StartOfMonth {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    EndOfMonth {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    /// Moved back to the Monday of the week
+    StartOfWeek {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    /// Moved to the first day on the weekday after the date
+    NextWeekday {
+        weekday: Weekday,
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    /// Moved to the last day on the weekday before the date
+    PrevWeekday {
+        weekday: Weekday,
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+}
+
+impl Step {
+    /// The date produced by the step
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            Step::Base { date } => *date,
+            Step::ShiftMonths { to, .. }
+            | Step::ShiftWeeks { to, .. }
+            | Step::ShiftDays { to, .. }
+            | Step::RollForward { to, .. }
+            | Step::StartOfMonth { to, .. }
+            | Step::EndOfMonth { to, .. }
+            | Step::StartOfWeek { to, .. }
+            | Step::NextWeekday { to, .. }
+            | Step::PrevWeekday { to, .. } => *to,
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Base { date } => write!(f, "{}", date),
+            Step::ShiftMonths {
+                months,
+                to,
+                month_end,
+                ..
+            } => match month_end {
+                MonthEnd::Unchanged => write!(f, "{:+}M to {}", months, to),
+                MonthEnd::Preserved => {
+                    write!(f, "{:+}M with end of month preserved to {}", months, to)
+                }
+                MonthEnd::Clamped => write!(f, "{:+}M clamped to end of month {}", months, to),
+            },
+            Step::ShiftWeeks { weeks, to, .. } => write!(f, "{:+}W to {}", weeks, to),
+            Step::ShiftDays { days, to, .. } => write!(f, "{:+}D to {}", days, to),
+            Step::RollForward { to, skipped, .. } => {
+                write!(f, "rolled forward past")?;
+                for (i, (date, reason)) in skipped.iter().enumerate() {
+                    let reason = match reason {
+                        NonBusinessDay::Weekend => "weekend",
+                        NonBusinessDay::Holiday => "holiday",
+                    };
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{} {}", separator, reason, date)?;
+                }
+                write!(f, " to {}", to)
+            }
+            Step::StartOfMonth { to, .. } => write!(f, "start of month {}", to),
+            Step::EndOfMonth { to, .. } => write!(f, "end of month {}", to),
+            Step::StartOfWeek { to, .. } => write!(f, "start of week {}", to),
+            Step::NextWeekday { weekday, to, .. } => write!(f, "next {} {}", weekday, to),
+            Step::PrevWeekday { weekday, to, .. } => write!(f, "previous {} {}", weekday, to),
+        }
+    }
+}
+
+/// The chain of steps used to derive a date
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{business::Calendar, explain::Explanation, RelativeDuration};
+///
+/// let calendar = Calendar::new().with_holidays([NaiveDate::from_ymd_opt(2022, 2, 28).unwrap()]);
+///
+/// let explanation = Explanation::new(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap())
+///     .shift(RelativeDuration::months(1))
+///     .roll_forward(&calendar);
+///
+/// assert_eq!(explanation.date(), NaiveDate::from_ymd_opt(2022, 3, 1).unwrap());
+/// assert_eq!(
+///     explanation.to_string(),
+///     "2022-01-31 → +1M with end of month preserved to 2022-02-28 → rolled forward past holiday 2022-02-28 to 2022-03-01"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Explanation {
+    steps: Vec<Step>,
+}
+
+impl Explanation {
+    pub fn new(base: NaiveDate) -> Self {
+        Explanation {
+            steps: vec![Step::Base { date: base }],
+        }
+    }
+
+    /// The derived date
+    pub fn date(&self) -> NaiveDate {
+        self.steps.last().map(Step::date).unwrap()
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Record a step which was taken elsewhere
+    pub(crate) fn push(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Add a duration, recording the months, weeks and days separately in the order they apply
+    pub fn shift(mut self, duration: RelativeDuration) -> Self {
+        let from = self.date();
+
+        if duration.num_months() != 0 {
+            let months = duration.num_months();
+            let to = shift::shift_months(from, months);
+            let month_end = if from == end_of_month(&from) {
+                MonthEnd::Preserved
+            } else if to.day() < from.day() {
+                MonthEnd::Clamped
+            } else {
+                MonthEnd::Unchanged
+            };
+            self.steps.push(Step::ShiftMonths {
+                months,
+                from,
+                to,
+                month_end,
+            });
+        }
+
+        if duration.num_weeks() != 0 {
+            let (from, weeks) = (self.date(), duration.num_weeks());
+            let to = shift::shift_weeks(from, weeks);
+            self.steps.push(Step::ShiftWeeks { weeks, from, to });
+        }
+
+        if duration.num_days() != 0 {
+            let (from, days) = (self.date(), duration.num_days());
+            let to = shift::shift_days(from, days);
+            self.steps.push(Step::ShiftDays { days, from, to });
+        }
+
+        self
+    }
+
+    /// Roll forward onto a business day, nothing is recorded when the date already is one
+    pub fn roll_forward<C: HolidayCalendar + ?Sized>(mut self, calendar: &C) -> Self {
+        let from = self.date();
+        let mut to = from;
+        let mut skipped = Vec::new();
+
+        while !calendar.is_business_day(to) {
+            let reason = if calendar.is_holiday(to) {
+                NonBusinessDay::Holiday
+            } else {
+                NonBusinessDay::Weekend
+            };
+            skipped.push((to, reason));
+            to = to.succ_opt().unwrap();
+        }
+
+        if !skipped.is_empty() {
+            self.steps.push(Step::RollForward { from, to, skipped });
+        }
+        self
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, " → ")?;
+            }
+            write!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+impl RelativeDuration {
+    /// Explain how adding the duration to the date produces its result
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use calends::RelativeDuration;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 30).unwrap();
+    /// let explanation = RelativeDuration::months(1).explain(date);
+    ///
+    /// assert_eq!(explanation.date(), date + RelativeDuration::months(1));
+    /// assert_eq!(explanation.to_string(), "2022-01-30 → +1M clamped to end of month 2022-02-28");
+    /// ```
+    pub fn explain(&self, date: NaiveDate) -> Explanation {
+        Explanation::new(date).shift(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    #[test]
+    fn test_explain_matches_addition() {
+        let duration = RelativeDuration::months(-1).with_weeks(1).with_days(-2);
+        let start = date(2022, 3, 15);
+        let explanation = duration.explain(start);
+
+        assert_eq!(explanation.date(), start + duration);
+        assert_eq!(
+            explanation.to_string(),
+            "2022-03-15 → -1M to 2022-02-15 → +1W to 2022-02-22 → -2D to 2022-02-20"
+        );
+    }
+
+    #[test]
+    fn test_roll_forward_lists_skipped_days() {
+        let calendar = Calendar::new().with_holidays([date(2022, 12, 26), date(2022, 12, 27)]);
+        let explanation = Explanation::new(date(2022, 12, 24)).roll_forward(&calendar);
+
+        assert_eq!(
+            explanation.steps()[1],
+            Step::RollForward {
+                from: date(2022, 12, 24),
+                to: date(2022, 12, 28),
+                skipped: vec![
+                    (date(2022, 12, 24), NonBusinessDay::Weekend),
+                    (date(2022, 12, 25), NonBusinessDay::Weekend),
+                    (date(2022, 12, 26), NonBusinessDay::Holiday),
+                    (date(2022, 12, 27), NonBusinessDay::Holiday),
+                ],
+            }
+        );
+
+        // already a business day
+        let calendar = Calendar::new().with_weekend([Weekday::Fri]);
+        assert_eq!(
+            Explanation::new(date(2022, 12, 24))
+                .roll_forward(&calendar)
+                .steps()
+                .len(),
+            1
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize() {
+        let explanation = RelativeDuration::months(1).explain(date(2022, 1, 31));
+
+        assert_eq!(
+            serde_json::to_string(&explanation).unwrap(),
+            r#"{"steps":[{"step":"base","date":"2022-01-31"},{"step":"shift_months","months":1,"from":"2022-01-31","to":"2022-02-28","month_end":"preserved"}]}"#
+        );
+    }
+}
@@ -1,5 +1,11 @@
 use crate::RelativeDuration;
 
+/// The granularity of a period of time
+///
+/// Grains (de)serialize as their lowercase names, e.g. `"quarter"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Grain {
     Day,
     Week,
@@ -21,9 +27,32 @@ impl Grain {
             Grain::Quarter => RelativeDuration::months(3),
             Grain::Half => RelativeDuration::months(6),
             Grain::Year => RelativeDuration::months(12),
-            Grain::Lustrum => todo!(),
-            Grain::Decade => todo!(),
-            Grain::Century => todo!(),
+            Grain::Lustrum => RelativeDuration::months(12 * 5),
+            Grain::Decade => RelativeDuration::months(12 * 10),
+            Grain::Century => RelativeDuration::months(12 * 100),
+        }
+    }
+
+    /// The most days a period of the grain can have
+    pub fn max_days(&self) -> u32 {
+        match self {
+            Grain::Day => 1,
+            Grain::Week => 7,
+            Grain::Month => 31,
+            Grain::Quarter => 92,
+            Grain::Half => 184,
+            Grain::Year => 366,
+            Grain::Lustrum => 366 * 2 + 365 * 3,
+            Grain::Decade => 366 * 3 + 365 * 7,
+            Grain::Century => 366 * 25 + 365 * 75,
+        }
+    }
+
+    /// Number of months in the grain, [None] for grains shorter than a month
+    pub fn months(&self) -> Option<i32> {
+        match self {
+            Grain::Day | Grain::Week => None,
+            _ => Some(self.into_duration().num_months()),
         }
     }
 }
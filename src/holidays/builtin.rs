@@ -0,0 +1,77 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDate;
+
+use crate::error::CalendsError;
+
+use super::{HolidayCalendar, Target2, UkBank, UsFederal};
+
+/// One of the built-in holiday calendars, chosen by name
+///
+/// The names are `us`, `uk` and `target2`, matched without regard to case, so a calendar can be
+/// picked from configuration or a command line flag.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::holidays::{Builtin, HolidayCalendar};
+///
+/// let calendar: Builtin = "US".parse()?;
+/// assert_eq!(calendar, Builtin::UsFederal);
+/// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2022, 7, 4).unwrap()));
+/// assert!("mars".parse::<Builtin>().is_err());
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Builtin {
+    UsFederal,
+    UkBank,
+    Target2,
+}
+
+impl Builtin {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Builtin::UsFederal => "us",
+            Builtin::UkBank => "uk",
+            Builtin::Target2 => "target2",
+        }
+    }
+
+    /// The holidays of the calendar within the year in ascending order
+    pub fn holidays_in(&self, year: i32) -> Vec<NaiveDate> {
+        match self {
+            Builtin::UsFederal => UsFederal.holidays_in(year),
+            Builtin::UkBank => UkBank.holidays_in(year),
+            Builtin::Target2 => Target2.holidays_in(year),
+        }
+    }
+}
+
+impl HolidayCalendar for Builtin {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        match self {
+            Builtin::UsFederal => UsFederal.is_holiday(date),
+            Builtin::UkBank => UkBank.is_holiday(date),
+            Builtin::Target2 => Target2.is_holiday(date),
+        }
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Builtin {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Builtin::UsFederal, Builtin::UkBank, Builtin::Target2]
+            .into_iter()
+            .find(|builtin| s.trim().eq_ignore_ascii_case(builtin.name()))
+            .ok_or_else(|| CalendsError::UnknownCalendar(s.to_string()))
+    }
+}
@@ -0,0 +1,133 @@
+use std::{rc::Rc, sync::Arc};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::business::Calendar;
+
+/// Which days are holidays and which are the weekend
+///
+/// Only [is_holiday](HolidayCalendar::is_holiday) has to be implemented, the weekend defaults to
+/// Saturday and Sunday. The trait is object safe and implemented for references and smart
+/// pointers, so calendars of different types can be kept together as `Box<dyn HolidayCalendar>`
+/// and passed straight to the business day functions.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::{Datelike, NaiveDate};
+/// # use calends::business::next_business_day;
+/// # use calends::holidays::HolidayCalendar;
+///
+/// /// Every first of the month is a holiday
+/// struct Firsts;
+///
+/// impl HolidayCalendar for Firsts {
+///     fn is_holiday(&self, date: NaiveDate) -> bool {
+///         date.day() == 1
+///     }
+/// }
+///
+/// assert_eq!(
+///     next_business_day(NaiveDate::from_ymd_opt(2022, 5, 31).unwrap(), &Firsts),
+///     NaiveDate::from_ymd_opt(2022, 6, 2).unwrap()
+/// );
+/// ```
+pub trait HolidayCalendar {
+    /// Whether the date is a holiday
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+
+    /// Whether the date falls on the weekend
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+    }
+
+    /// Whether the date is neither a weekend nor a holiday
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Whether the date is near a holiday which may still move, so whether it is a business day
+    /// may still change, never for calendars whose holidays are all confirmed
+    fn is_tentative(&self, _date: NaiveDate) -> bool {
+        false
+    }
+}
+
+macro_rules! forward {
+    ($($ptr:ty),*) => {$(
+        impl<C: HolidayCalendar + ?Sized> HolidayCalendar for $ptr {
+            fn is_holiday(&self, date: NaiveDate) -> bool {
+                (**self).is_holiday(date)
+            }
+
+            fn is_weekend(&self, date: NaiveDate) -> bool {
+                (**self).is_weekend(date)
+            }
+
+            fn is_business_day(&self, date: NaiveDate) -> bool {
+                (**self).is_business_day(date)
+            }
+
+            fn is_tentative(&self, date: NaiveDate) -> bool {
+                (**self).is_tentative(date)
+            }
+        }
+    )*};
+}
+
+forward!(&C, Box<C>, Rc<C>, Arc<C>);
+
+impl HolidayCalendar for Calendar {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        Calendar::is_holiday(self, date)
+    }
+
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        Calendar::is_weekend(self, date)
+    }
+
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        Calendar::is_business_day(self, date)
+    }
+
+    fn is_tentative(&self, date: NaiveDate) -> bool {
+        Calendar::is_tentative(self, date)
+    }
+}
+
+#[cfg(all(test, feature = "country-calendars"))]
+mod tests {
+    use super::*;
+    use crate::{
+        business::next_business_day,
+        holidays::{Target2, UkBank, UsFederal},
+        testing::date,
+    };
+
+    #[test]
+    fn test_boxed_calendars() {
+        let day = |m, d| date(2022, m, d);
+        let calendars: Vec<(&str, Box<dyn HolidayCalendar>)> = vec![
+            ("us", Box::new(UsFederal)),
+            ("uk", Box::new(UkBank)),
+            ("target2", Box::new(Target2)),
+            ("none", Box::new(Calendar::new())),
+        ];
+
+        // Friday before the Easter weekend in 2022
+        let next: Vec<_> = calendars
+            .iter()
+            .map(|(name, calendar)| (*name, next_business_day(day(4, 14), calendar)))
+            .collect();
+        assert_eq!(
+            next,
+            vec![
+                ("us", day(4, 15)),
+                ("uk", day(4, 19)),
+                ("target2", day(4, 19)),
+                ("none", day(4, 15)),
+            ]
+        );
+        assert!(Arc::new(UkBank).is_holiday(day(6, 3)));
+    }
+}
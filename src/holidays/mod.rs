@@ -0,0 +1,24 @@
+//! Holiday calendars
+//!
+//! [HolidayCalendar] is what the business day functions need to know about a calendar, so they
+//! work with a configured [Calendar](crate::business::Calendar) or one of the built-in calendars
+//! of public holidays.
+#[cfg(feature = "country-calendars")]
+pub mod builtin;
+pub mod calendar;
+#[cfg(feature = "country-calendars")]
+pub mod target2;
+#[cfg(feature = "country-calendars")]
+pub mod uk;
+#[cfg(feature = "country-calendars")]
+pub mod us;
+
+#[cfg(feature = "country-calendars")]
+pub use builtin::Builtin;
+pub use calendar::HolidayCalendar;
+#[cfg(feature = "country-calendars")]
+pub use target2::Target2;
+#[cfg(feature = "country-calendars")]
+pub use uk::UkBank;
+#[cfg(feature = "country-calendars")]
+pub use us::UsFederal;
@@ -0,0 +1,105 @@
+use std::ops::RangeInclusive;
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::{business::Calendar, util::checked_easter_sunday};
+
+use super::HolidayCalendar;
+
+/// Closing days of the TARGET2 payment system
+///
+/// The same six days have been closing days since 2002. Holidays falling on a weekend are not
+/// moved.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::business::next_business_day;
+/// # use calends::holidays::Target2;
+///
+/// // Easter Monday 2022 was the 18th of April
+/// assert_eq!(
+///     next_business_day(NaiveDate::from_ymd_opt(2022, 4, 14).unwrap(), &Target2),
+///     NaiveDate::from_ymd_opt(2022, 4, 19).unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Target2;
+
+impl Target2 {
+    /// The closing days of the year in ascending order, none outside the range of supported dates
+    pub fn holidays_in(&self, year: i32) -> Vec<NaiveDate> {
+        let day = |month, day| NaiveDate::from_ymd_opt(year, month, day);
+        let days = [
+            day(1, 1),
+            easter(year, -2),
+            easter(year, 1),
+            day(5, 1),
+            day(12, 25),
+            day(12, 26),
+        ];
+
+        days.into_iter().collect::<Option<_>>().unwrap_or_default()
+    }
+
+    /// A calendar with the closing days of the years
+    pub fn calendar(&self, years: RangeInclusive<i32>) -> Calendar {
+        Calendar::new().with_holidays(years.flat_map(|year| self.holidays_in(year)))
+    }
+}
+
+impl HolidayCalendar for Target2 {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        let year = date.year();
+        match (date.month(), date.day()) {
+            (1, 1) | (5, 1) | (12, 25) | (12, 26) => true,
+            (3 | 4, _) => [easter(year, -2), easter(year, 1)].contains(&Some(date)),
+            _ => false,
+        }
+    }
+}
+
+/// The day the given number of days from Easter Sunday
+fn easter(year: i32, days: i64) -> Option<NaiveDate> {
+    checked_easter_sunday(year)?.checked_add_signed(Duration::days(days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_weekend_holidays_are_not_moved() {
+        let day = |m, d| date(2021, m, d);
+
+        // the first of May and Christmas Day 2021 were Saturdays
+        assert!(Target2.is_holiday(day(5, 1)));
+        assert!(Target2.is_business_day(day(5, 3)));
+        assert!(Target2.is_business_day(day(12, 27)));
+        assert_eq!(Target2.calendar(2021..=2022).holidays().count(), 12);
+    }
+
+    #[test]
+    fn test_is_holiday_matches_holidays_in() {
+        for year in 2019..=2024 {
+            let holidays = Target2.holidays_in(year);
+            let days = date(year, 1, 1)
+                .iter_days()
+                .take_while(|d| d.year() == year);
+            for day in days {
+                assert_eq!(Target2.is_holiday(day), holidays.contains(&day), "{}", day);
+            }
+        }
+    }
+
+    #[test]
+    fn test_outside_the_supported_dates() {
+        assert!(Target2.holidays_in(NaiveDate::MAX.year() + 1).is_empty());
+        assert!(Target2.holidays_in(i32::MIN).is_empty());
+        assert_eq!(Target2.holidays_in(NaiveDate::MIN.year()).len(), 6);
+        assert!(Target2.is_holiday(NaiveDate::MIN));
+        assert!(!Target2.is_holiday(NaiveDate::MAX));
+    }
+}
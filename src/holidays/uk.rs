@@ -0,0 +1,204 @@
+use std::ops::RangeInclusive;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::{
+    business::config::Observed,
+    business::Calendar,
+    month::Month,
+    util::checked_easter_sunday,
+    weekday::{NthWeekday, WeekdaySet},
+};
+
+use super::HolidayCalendar;
+
+/// Bank holidays in England and Wales
+///
+/// Holidays falling on a weekend are replaced by a substitute day on the following Monday, or the
+/// Tuesday when Christmas Day and Boxing Day both need one. Days moved or added by proclamation,
+/// such as for jubilees, are included from 1995.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::holidays::{HolidayCalendar, UkBank};
+///
+/// // Christmas Day 2021 was a Saturday
+/// assert!(UkBank.is_holiday(NaiveDate::from_ymd_opt(2021, 12, 27).unwrap()));
+/// assert!(UkBank.is_holiday(NaiveDate::from_ymd_opt(2021, 12, 28).unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UkBank;
+
+impl UkBank {
+    /// The bank holidays of the year in ascending order, none outside the range of supported
+    /// dates
+    pub fn holidays_in(&self, year: i32) -> Vec<NaiveDate> {
+        holidays_in(year).unwrap_or_default()
+    }
+
+    /// A calendar with the bank holidays of the years
+    pub fn calendar(&self, years: RangeInclusive<i32>) -> Calendar {
+        Calendar::new().with_holidays(years.flat_map(|year| self.holidays_in(year)))
+    }
+}
+
+impl HolidayCalendar for UkBank {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        let year = date.year();
+        let is = |holiday: Option<NaiveDate>| holiday == Some(date);
+        let special = SPECIAL.contains(&(year, date.month(), date.day()));
+
+        special
+            || match date.month() {
+                1 => is(new_year(year)),
+                3 | 4 => easter(year).is_some_and(|days| days.contains(&date)),
+                5 | 6 => is(early_may(year)) || is(spring(year)),
+                8 => is(summer(year)),
+                12 => christmas(year).is_some_and(|days| days.contains(&date)),
+                _ => false,
+            }
+    }
+}
+
+/// One-off bank holidays
+const SPECIAL: [(i32, u32, u32); 7] = [
+    (1999, 12, 31),
+    (2002, 6, 3),
+    (2011, 4, 29),
+    (2012, 6, 5),
+    (2022, 6, 3),
+    (2022, 9, 19),
+    (2023, 5, 8),
+];
+
+fn holidays_in(year: i32) -> Option<Vec<NaiveDate>> {
+    let mut holidays = vec![new_year(year)?];
+    holidays.extend(easter(year)?);
+    holidays.extend([early_may(year)?, spring(year)?, summer(year)?]);
+    holidays.extend(christmas(year)?);
+    holidays.extend(
+        SPECIAL
+            .iter()
+            .filter(|(y, ..)| *y == year)
+            .filter_map(|(y, m, d)| NaiveDate::from_ymd_opt(*y, *m, *d)),
+    );
+    holidays.sort();
+    Some(holidays)
+}
+
+fn new_year(year: i32) -> Option<NaiveDate> {
+    Observed::FollowingMonday.checked_apply(ymd(year, 1, 1)?, WeekdaySet::WEEKEND)
+}
+
+/// Good Friday and Easter Monday
+fn easter(year: i32) -> Option<[NaiveDate; 2]> {
+    let easter = checked_easter_sunday(year)?;
+    Some([
+        easter.checked_sub_signed(Duration::days(2))?,
+        easter.checked_add_signed(Duration::days(1))?,
+    ])
+}
+
+fn early_may(year: i32) -> Option<NaiveDate> {
+    match year {
+        1995 | 2020 => ymd(year, 5, 8),
+        _ => NthWeekday::first(Weekday::Mon).resolve(year, Month::May),
+    }
+}
+
+fn spring(year: i32) -> Option<NaiveDate> {
+    match year {
+        2002 | 2012 => ymd(year, 6, 4),
+        2022 => ymd(year, 6, 2),
+        _ => NthWeekday::last(Weekday::Mon).resolve(year, Month::May),
+    }
+}
+
+fn summer(year: i32) -> Option<NaiveDate> {
+    NthWeekday::last(Weekday::Mon).resolve(year, Month::August)
+}
+
+/// Christmas Day and Boxing Day, or their substitute days
+fn christmas(year: i32) -> Option<[NaiveDate; 2]> {
+    let day = |day| ymd(year, 12, day);
+    Some(match day(25)?.weekday() {
+        Weekday::Fri => [day(25)?, day(28)?],
+        Weekday::Sat => [day(27)?, day(28)?],
+        Weekday::Sun => [day(26)?, day(27)?],
+        _ => [day(25)?, day(26)?],
+    })
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_holidays_in_2022() {
+        let day = |m, d| date(2022, m, d);
+
+        assert_eq!(
+            UkBank.holidays_in(2022),
+            vec![
+                day(1, 3),
+                day(4, 15),
+                day(4, 18),
+                day(5, 2),
+                day(6, 2),
+                day(6, 3),
+                day(8, 29),
+                day(9, 19),
+                day(12, 26),
+                day(12, 27),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_substitute_days() {
+        // Christmas Day 2020 was a Friday and 2016 a Sunday
+        assert_eq!(
+            christmas(2020),
+            Some([date(2020, 12, 25), date(2020, 12, 28)])
+        );
+        assert_eq!(
+            christmas(2016),
+            Some([date(2016, 12, 26), date(2016, 12, 27)])
+        );
+        assert_eq!(
+            christmas(2019),
+            Some([date(2019, 12, 25), date(2019, 12, 26)])
+        );
+        assert_eq!(UkBank.holidays_in(2017)[0], date(2017, 1, 2));
+    }
+
+    #[test]
+    fn test_is_holiday_matches_holidays_in() {
+        for year in [1995, 1999, 2002, 2012, 2020, 2021, 2022, 2023] {
+            let holidays = UkBank.holidays_in(year);
+            let days = date(year, 1, 1)
+                .iter_days()
+                .take_while(|d| d.year() == year);
+            for day in days {
+                assert_eq!(UkBank.is_holiday(day), holidays.contains(&day), "{}", day);
+            }
+        }
+    }
+
+    #[test]
+    fn test_outside_the_supported_dates() {
+        assert!(UkBank.holidays_in(NaiveDate::MAX.year() + 1).is_empty());
+        assert!(UkBank.holidays_in(i32::MIN).is_empty());
+        assert!(!UkBank.holidays_in(NaiveDate::MAX.year()).is_empty());
+        assert!(!UkBank.is_holiday(NaiveDate::MAX));
+        let calendar = UkBank.calendar(NaiveDate::MAX.year()..=NaiveDate::MAX.year() + 1);
+        assert_eq!(calendar.holidays().count(), 8);
+    }
+}
@@ -0,0 +1,201 @@
+use std::ops::RangeInclusive;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{
+    business::config::Observed,
+    business::Calendar,
+    month::Month,
+    weekday::{NthWeekday, WeekdaySet},
+};
+
+use super::HolidayCalendar;
+
+/// United States federal holidays
+///
+/// Holidays falling on a Saturday are observed on the Friday before and those falling on a Sunday
+/// on the Monday after, so New Year's Day can be observed on the 31st of December of the year
+/// before. Juneteenth is included from 2021.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::holidays::{HolidayCalendar, UsFederal};
+///
+/// // Independence Day 2021 was a Sunday
+/// assert!(UsFederal.is_holiday(NaiveDate::from_ymd_opt(2021, 7, 5).unwrap()));
+/// assert!(!UsFederal.is_holiday(NaiveDate::from_ymd_opt(2021, 7, 4).unwrap()));
+///
+/// // New Year's Day 2022 was a Saturday
+/// assert!(UsFederal.is_holiday(NaiveDate::from_ymd_opt(2021, 12, 31).unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UsFederal;
+
+impl UsFederal {
+    /// The holidays observed within the year in ascending order
+    pub fn holidays_in(&self, year: i32) -> Vec<NaiveDate> {
+        // New Year's Day of the following year may be observed on the 31st of December
+        let mut holidays: Vec<NaiveDate> = [Some(year), year.checked_add(1)]
+            .into_iter()
+            .flatten()
+            .flat_map(|year| {
+                HOLIDAYS
+                    .iter()
+                    .filter_map(move |holiday| holiday.observed(year))
+            })
+            .filter(|date| date.year() == year)
+            .collect();
+        holidays.sort();
+        holidays
+    }
+
+    /// A calendar with the holidays observed within the years
+    pub fn calendar(&self, years: RangeInclusive<i32>) -> Calendar {
+        Calendar::new().with_holidays(years.flat_map(|year| self.holidays_in(year)))
+    }
+}
+
+impl HolidayCalendar for UsFederal {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        // a holiday is observed at most a day away, so only holidays of the month and the months
+        // either side can fall on the date
+        (-1..=1).any(|months| {
+            let (month, years) = Month::of(&date) + months;
+            date.year().checked_add(years).is_some_and(|year| {
+                HOLIDAYS
+                    .iter()
+                    .filter(|holiday| holiday.month == month)
+                    .any(|holiday| holiday.observed(year) == Some(date))
+            })
+        })
+    }
+}
+
+/// A federal holiday and the first year it was observed
+struct Holiday {
+    month: Month,
+    day: Day,
+    since: i32,
+}
+
+/// When the holiday falls within its month
+enum Day {
+    /// The same day every year, moved to the nearest weekday when it falls on the weekend
+    Fixed(u32),
+    /// An occurrence of a weekday, negative when counting from the end of the month
+    Nth(i32, Weekday),
+}
+
+const HOLIDAYS: [Holiday; 11] = [
+    Holiday::fixed(Month::January, 1),
+    Holiday::nth(Month::January, 3, Weekday::Mon),
+    Holiday::nth(Month::February, 3, Weekday::Mon),
+    Holiday::nth(Month::May, -1, Weekday::Mon),
+    Holiday {
+        since: 2021,
+        ..Holiday::fixed(Month::June, 19)
+    },
+    Holiday::fixed(Month::July, 4),
+    Holiday::nth(Month::September, 1, Weekday::Mon),
+    Holiday::nth(Month::October, 2, Weekday::Mon),
+    Holiday::fixed(Month::November, 11),
+    Holiday::nth(Month::November, 4, Weekday::Thu),
+    Holiday::fixed(Month::December, 25),
+];
+
+impl Holiday {
+    const fn fixed(month: Month, day: u32) -> Self {
+        Holiday {
+            month,
+            day: Day::Fixed(day),
+            since: i32::MIN,
+        }
+    }
+
+    const fn nth(month: Month, n: i32, weekday: Weekday) -> Self {
+        Holiday {
+            month,
+            day: Day::Nth(n, weekday),
+            since: i32::MIN,
+        }
+    }
+
+    /// The date the holiday is observed on in the year, if it was a holiday then and the date is
+    /// within the range of supported dates
+    fn observed(&self, year: i32) -> Option<NaiveDate> {
+        let first = self.month.first_day(year).filter(|_| year >= self.since)?;
+        match self.day {
+            Day::Fixed(day) => {
+                Observed::NearestWeekday.checked_apply(first.with_day(day)?, WeekdaySet::WEEKEND)
+            }
+            Day::Nth(n, weekday) => NthWeekday::new(n, weekday).ok()?.resolve(year, self.month),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_holidays_in_2022() {
+        assert_eq!(
+            UsFederal.holidays_in(2022),
+            vec![
+                date(2022, 1, 17),
+                date(2022, 2, 21),
+                date(2022, 5, 30),
+                date(2022, 6, 20),
+                date(2022, 7, 4),
+                date(2022, 9, 5),
+                date(2022, 10, 10),
+                date(2022, 11, 11),
+                date(2022, 11, 24),
+                date(2022, 12, 26),
+            ]
+        );
+        // New Year's Day 2023 was a Sunday and is observed in 2023
+        assert_eq!(UsFederal.holidays_in(2023)[0], date(2023, 1, 2));
+    }
+
+    #[test]
+    fn test_new_years_day_observed_the_year_before() {
+        let holidays = UsFederal.holidays_in(2021);
+
+        // both New Year's Days, with Christmas observed on Friday the 24th
+        assert_eq!(holidays.len(), 12);
+        assert_eq!(holidays[0], date(2021, 1, 1));
+        assert_eq!(holidays[10..], [date(2021, 12, 24), date(2021, 12, 31)]);
+    }
+
+    #[test]
+    fn test_is_holiday_matches_holidays_in() {
+        let holidays = UsFederal.calendar(2019..=2023);
+        for date in date(2020, 1, 1).iter_days().take_while(|d| d.year() < 2023) {
+            assert_eq!(
+                UsFederal.is_holiday(date),
+                holidays.is_holiday(date),
+                "{}",
+                date
+            );
+        }
+    }
+
+    #[test]
+    fn test_years_at_the_limits() {
+        let last = NaiveDate::MAX.year();
+        assert!(!UsFederal.holidays_in(last).is_empty());
+        assert!(!UsFederal.holidays_in(NaiveDate::MIN.year()).is_empty());
+        assert!(UsFederal.holidays_in(last + 1).is_empty());
+        assert!(UsFederal.holidays_in(i32::MAX).is_empty());
+        for date in [NaiveDate::MIN, NaiveDate::MAX] {
+            assert_eq!(
+                UsFederal.is_holiday(date),
+                UsFederal.holidays_in(date.year()).contains(&date)
+            );
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! The range of dates calculations are carried out over
+//!
+//! Open-ended contracts and unbounded intervals have no natural end, so expanding them needs a
+//! limit. A [Horizon] is the earliest and latest date an application supports: unbounded intervals
+//! resolve to it and expansions configured with it stop at its edges instead of failing.
+use chrono::NaiveDate;
+
+use crate::{
+    error::CalendsError,
+    interval::{bound::Bound, ClosedInterval},
+    IntervalLike,
+};
+
+/// The earliest and latest supported dates, both inclusive
+///
+/// The default horizon runs from 1900-01-01 to 2199-12-31.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{horizon::Horizon, Interval, IntervalLike};
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// let horizon = Horizon::new(date(2020, 1, 1), date(2030, 12, 31))?;
+///
+/// let contract = Interval::open_end(date(2022, 4, 1));
+/// assert_eq!(horizon.resolve(&contract).unwrap().iso8601(), "2022-04-01/2030-12-31");
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Horizon {
+    min: NaiveDate,
+    max: NaiveDate,
+}
+
+impl Horizon {
+    /// A horizon from `min` to `max`, failing when `max` is before `min`
+    pub fn new(min: NaiveDate, max: NaiveDate) -> Result<Self, CalendsError> {
+        if max < min {
+            return Err(CalendsError::ReversedInterval {
+                start: min,
+                end: max,
+            });
+        }
+        Ok(Horizon { min, max })
+    }
+
+    pub fn min(&self) -> NaiveDate {
+        self.min
+    }
+
+    pub fn max(&self) -> NaiveDate {
+        self.max
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.min <= date && date <= self.max
+    }
+
+    /// The nearest date within the horizon
+    pub fn clamp(&self, date: NaiveDate) -> NaiveDate {
+        date.clamp(self.min, self.max)
+    }
+
+    /// The part of the interval within the horizon, unbounded sides extending to its edges
+    ///
+    /// [None] when the interval lies entirely outside the horizon.
+    pub fn resolve<I: IntervalLike>(&self, interval: &I) -> Option<ClosedInterval> {
+        let start = match interval.bound_start() {
+            Bound::Included(start) => start.max(self.min),
+            Bound::Unbounded => self.min,
+        };
+        let end = match interval.bound_end() {
+            Bound::Included(end) => end.min(self.max),
+            Bound::Unbounded => self.max,
+        };
+        (start <= end).then(|| ClosedInterval::with_dates_unchecked(start, end))
+    }
+}
+
+impl Default for Horizon {
+    fn default() -> Self {
+        Horizon {
+            min: NaiveDate::from_ymd_opt(1900, 1, 1).unwrap(),
+            max: NaiveDate::from_ymd_opt(2199, 12, 31).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::date, Interval};
+
+    #[test]
+    fn test_resolve() {
+        let horizon = Horizon::new(date(2020, 1, 1), date(2030, 12, 31)).unwrap();
+        let resolve = |interval: Interval| horizon.resolve(&interval).map(|i| i.iso8601());
+
+        assert_eq!(
+            resolve(Interval::open_start(date(2022, 1, 1))),
+            Some("2020-01-01/2022-01-01".to_string())
+        );
+        assert_eq!(
+            resolve(Interval::closed_with_dates(
+                date(2010, 1, 1),
+                date(2040, 1, 1)
+            )),
+            Some("2020-01-01/2030-12-31".to_string())
+        );
+        assert_eq!(resolve(Interval::open_end(date(2031, 1, 1))), None);
+        assert_eq!(horizon.clamp(date(2019, 6, 1)), date(2020, 1, 1));
+    }
+
+    #[test]
+    fn test_reversed_horizon() {
+        assert!(Horizon::new(date(2030, 1, 1), date(2020, 1, 1)).is_err());
+        assert!(Horizon::new(date(2030, 1, 1), date(2030, 1, 1)).is_ok());
+    }
+}
@@ -1,12 +1,13 @@
 use chrono::NaiveDate;
 
-use crate::{IntervalLike, RelativeDuration};
+use crate::{error::CalendsError, grain::Grain, IntervalLike, RelativeDuration};
 
 use super::bound::Bound;
 use super::closed::ClosedInterval;
-use super::iter::UntilAfter;
+use super::iter::{Occurrences, UntilAfter};
 use super::marker;
 use super::open::{OpenEndInterval, OpenStartInterval};
+use super::split::{Split, SplitPolicy};
 
 #[derive(Debug, thiserror::Error)]
 pub enum IntervalError {
@@ -30,14 +31,14 @@ pub enum IntervalError {
 /// These rules have been adapted from ISO 8601-2:2019 7.14 Time Intervals.
 ///
 /// - **Start and end:** A given start and end, we will calculate a duration based on the difference of
-/// these two time sets and assign that as the duration. This may not produce the correct results
-/// in the case of months.
+///   these two time sets and assign that as the duration. This may not produce the correct results
+///   in the case of months.
 ///
 /// - **Start and duration:** The start time plus the duration creates the end of the interval.
-/// Intervals are then iterated on with the given duration. e.g. if the duration is 1 month, then
-/// the next call to the iterator would give you a month in the future.
+///   Intervals are then iterated on with the given duration. e.g. if the duration is 1 month, then
+///   the next call to the iterator would give you a month in the future.
 /// - **End and duration:** The end time minus the duration creates the beginning of the interval.
-/// Intervals are then iterated on with the given duration.
+///   Intervals are then iterated on with the given duration.
 ///
 /// ## Other notes
 ///
@@ -48,8 +49,9 @@ pub enum IntervalError {
 /// We use this over [std::ops::Bound] because bound supports exclusive boundaries and we have made the
 /// decision that it adds too much cognitive load / API cruft so we do not include it.
 ///
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(untagged)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum Interval {
     /// A closed interval that will always have a start and end
     Closed(ClosedInterval),
@@ -58,8 +60,67 @@ pub enum Interval {
 }
 
 impl Interval {
+    /// Create an interval from its bounds
+    ///
+    /// Fails when the end is before the start or when both bounds are unbounded, so an interval
+    /// created this way always covers at least one date.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use calends::{Interval, IntervalLike};
+    /// use calends::interval::bound::Bound;
+    ///
+    /// let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+    ///
+    /// let interval = Interval::new(Bound::Included(start), Bound::Included(end)).unwrap();
+    /// assert_eq!(interval.end_opt(), Some(end));
+    ///
+    /// assert!(Interval::new(Bound::Included(end), Bound::Included(start)).is_err());
+    /// assert!(Interval::new(Bound::Unbounded, Bound::Unbounded).is_err());
+    /// ```
+    pub fn new(start: Bound<NaiveDate>, end: Bound<NaiveDate>) -> Result<Self, CalendsError> {
+        match (start, end) {
+            (Bound::Included(start), Bound::Included(end)) if end < start => {
+                Err(CalendsError::ReversedInterval { start, end })
+            }
+            (Bound::Unbounded, Bound::Unbounded) => Err(CalendsError::UnboundedInterval),
+            (start, end) => Ok(Interval::new_unchecked(start, end)),
+        }
+    }
+
+    /// Create an interval from its bounds without checking that the end is on or after the start
+    ///
+    /// Only for callers which already know the order of the bounds; the rest of the crate assumes
+    /// intervals never end before they start.
+    ///
+    /// # Panics
+    ///
+    /// When both bounds are unbounded as there is no interval to represent it
+    pub fn new_unchecked(start: Bound<NaiveDate>, end: Bound<NaiveDate>) -> Self {
+        match (start, end) {
+            (Bound::Included(start), Bound::Included(end)) => {
+                Interval::Closed(ClosedInterval::with_dates_unchecked(start, end))
+            }
+            (Bound::Included(start), Bound::Unbounded) => {
+                Interval::OpenEnd(OpenEndInterval::new(start))
+            }
+            (Bound::Unbounded, Bound::Included(end)) => {
+                Interval::OpenStart(OpenStartInterval::new(end))
+            }
+            (Bound::Unbounded, Bound::Unbounded) => panic!("{}", CalendsError::UnboundedInterval),
+        }
+    }
+
     /// Create an interval from a start and a duration
     ///
+    /// # Panics
+    ///
+    /// When the duration would end the interval before it starts or outside the range of
+    /// supported dates, see [ClosedInterval::try_from_start] for a fallible alternative
+    ///
     /// # Example
     ///
     /// ```
@@ -67,13 +128,13 @@ impl Interval {
     /// use calends::{Interval, IntervalLike, RelativeDuration};
     /// use calends::interval::marker::{End, Start};
     ///
-    /// let start = NaiveDate::from_ymd(2022, 1, 1);
+    /// let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
     /// let duration = RelativeDuration::months(1).with_days(-1);
     ///
     /// let mut interval = Interval::closed_from_start(start, duration);
     ///
     /// assert_eq!(interval.start_opt().unwrap(), start);
-    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd(2022, 1, 31));
+    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd_opt(2022, 1, 31).unwrap());
     /// ```
     pub fn closed_from_start(date: NaiveDate, duration: RelativeDuration) -> Self {
         Interval::Closed(ClosedInterval::from_start(date, duration))
@@ -81,6 +142,11 @@ impl Interval {
 
     /// Create an interval from an end and a duration
     ///
+    /// # Panics
+    ///
+    /// When the duration would start the interval after it ends or outside the range of supported
+    /// dates, see [ClosedInterval::try_from_end] for a fallible alternative
+    ///
     /// # Example
     ///
     /// ```
@@ -89,18 +155,22 @@ impl Interval {
     /// use calends::interval::marker::{End, Start};
     ///
     /// let interval = Interval::closed_from_end(
-    ///     NaiveDate::from_ymd(2022, 1, 1),
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
     ///     RelativeDuration::months(1).with_weeks(-2).with_days(2),
     /// );
     ///
-    /// assert_eq!(interval.start_opt().unwrap(), NaiveDate::from_ymd(2021, 12, 13));
-    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd(2022, 1, 1));
+    /// assert_eq!(interval.start_opt().unwrap(), NaiveDate::from_ymd_opt(2021, 12, 13).unwrap());
+    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
     /// ```
     pub fn closed_from_end(end: NaiveDate, duration: RelativeDuration) -> Self {
         Interval::Closed(ClosedInterval::from_end(end, duration))
     }
 
-    /// Create an interval from an end and a duration
+    /// Create an interval with a specified set of dates
+    ///
+    /// # Panics
+    ///
+    /// When the end is before the start, see [Interval::new] for a fallible alternative
     ///
     /// # Example
     ///
@@ -110,12 +180,12 @@ impl Interval {
     /// use calends::interval::marker::{End, Start};
     ///
     /// let interval = Interval::closed_with_dates(
-    ///     NaiveDate::from_ymd(2022, 1, 1),
-    ///     NaiveDate::from_ymd(2023, 1, 1),
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
     /// );
     ///
-    /// assert_eq!(interval.start_opt().unwrap(), NaiveDate::from_ymd(2022, 1, 1));
-    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd(2023, 1, 1));
+    /// assert_eq!(interval.start_opt().unwrap(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
     /// ```
     pub fn closed_with_dates(start: NaiveDate, end: NaiveDate) -> Self {
         Interval::Closed(ClosedInterval::with_dates(start, end))
@@ -139,6 +209,24 @@ impl Interval {
             Interval::OpenEnd(_) => Err(IntervalError::NotIterable),
         }
     }
+
+    /// Pieces of the interval in each period of the grain, see [Split]
+    ///
+    /// An interval without a start cannot be split.
+    pub fn split_by(&self, grain: Grain, policy: SplitPolicy) -> Result<Split, IntervalError> {
+        match self.start_opt() {
+            Some(start) => Ok(Split::new(start, self.end_opt(), grain, policy)),
+            None => Err(IntervalError::NotIterable),
+        }
+    }
+
+    /// Occurrences of a closed interval, see [ClosedInterval::iter]
+    pub fn iter(&self) -> Result<Occurrences, IntervalError> {
+        match self {
+            Interval::Closed(closed) => Ok(closed.iter()),
+            Interval::OpenStart(_) | Interval::OpenEnd(_) => Err(IntervalError::NotIterable),
+        }
+    }
 }
 
 impl IntervalLike for Interval {
@@ -186,8 +274,9 @@ impl From<IntervalWithEnd> for Interval {
 }
 
 /// An interval that has a guaranteed start but deos not guarantee and end
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(untagged)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum IntervalWithStart {
     Closed(ClosedInterval),
     OpenEnd(OpenEndInterval),
@@ -204,12 +293,12 @@ impl IntervalWithStart {
     /// use calends::interval::marker::{End, Start};
     ///
     /// let interval = IntervalWithStart::closed_with_dates(
-    ///     NaiveDate::from_ymd(2022, 1, 1),
-    ///     NaiveDate::from_ymd(2023, 1, 1),
+    ///     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+    ///     NaiveDate::from_ymd_opt(2023, 1, 1).unwrap(),
     /// );
     ///
-    /// assert_eq!(interval.start_opt().unwrap(), NaiveDate::from_ymd(2022, 1, 1));
-    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd(2023, 1, 1));
+    /// assert_eq!(interval.start_opt().unwrap(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    /// assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
     /// ```
     pub fn closed_with_dates(start: NaiveDate, end: NaiveDate) -> Self {
         IntervalWithStart::Closed(ClosedInterval::with_dates(start, end))
@@ -263,8 +352,9 @@ impl TryFrom<Interval> for IntervalWithStart {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
-#[serde(untagged)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
 pub enum IntervalWithEnd {
     Closed(ClosedInterval),
     OpenStart(OpenStartInterval),
@@ -310,21 +400,126 @@ impl TryFrom<Interval> for IntervalWithEnd {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::date;
 
     #[test]
-    fn test_reciprocity() {
-        let start = NaiveDate::from_ymd(2022, 1, 1);
-        let interval = Interval::closed_with_dates(start, NaiveDate::from_ymd(2022, 12, 31));
+    fn test_new_validates_bounds() {
+        let start = date(2022, 1, 31);
+        let end = date(2022, 3, 1);
+
+        let interval = Interval::new(Bound::Included(start), Bound::Included(end)).unwrap();
+        assert_eq!(interval.start_opt(), Some(start));
+        assert_eq!(interval.end_opt(), Some(end));
+
+        assert_eq!(
+            Interval::new(Bound::Included(start), Bound::Included(start))
+                .unwrap()
+                .end_opt(),
+            Some(start)
+        );
+        assert_eq!(
+            Interval::new(Bound::Included(end), Bound::Included(start)),
+            Err(CalendsError::ReversedInterval {
+                start: end,
+                end: start
+            })
+        );
+        assert_eq!(
+            Interval::new(Bound::Unbounded, Bound::Unbounded),
+            Err(CalendsError::UnboundedInterval)
+        );
+        assert!(matches!(
+            Interval::new(Bound::Unbounded, Bound::Included(end)),
+            Ok(Interval::OpenStart(_))
+        ));
+    }
 
+    #[test]
+    fn test_reversed_closed_constructors() {
+        let start = date(2022, 2, 1);
+        let end = date(2022, 1, 1);
+        let reversed = CalendsError::ReversedInterval { start, end };
+
+        // only the unchecked constructors keep reversed intervals as they are
+        let back = RelativeDuration::months(-1);
         assert_eq!(
-            interval.start_opt().unwrap(),
-            NaiveDate::from_ymd(2022, 1, 1)
+            Interval::new_unchecked(Bound::Included(start), Bound::Included(end)).end_opt(),
+            Some(end)
         );
         assert_eq!(
-            interval.end_opt().unwrap(),
-            NaiveDate::from_ymd(2022, 12, 31)
+            ClosedInterval::with_dates_unchecked(start, end).end_opt(),
+            Some(end)
+        );
+        assert_eq!(
+            ClosedInterval::from_start_unchecked(start, back).end_opt(),
+            Some(end)
+        );
+        assert_eq!(
+            ClosedInterval::from_end_unchecked(end, back).start_opt(),
+            Some(start)
         );
 
+        assert_eq!(
+            ClosedInterval::try_with_dates(start, end),
+            Err(reversed.clone())
+        );
+        assert_eq!(
+            ClosedInterval::try_from_start(start, back),
+            Err(reversed.clone())
+        );
+        assert_eq!(ClosedInterval::try_from_end(end, back), Err(reversed));
+        assert_eq!(
+            ClosedInterval::try_from_start(end, RelativeDuration::months(1))
+                .map(|interval| interval.end_opt()),
+            Ok(Some(start))
+        );
+        assert_eq!(
+            ClosedInterval::try_from_end(start, RelativeDuration::months(1))
+                .map(|interval| interval.start_opt()),
+            Ok(Some(end))
+        );
+        assert!(matches!(
+            ClosedInterval::try_from_start(NaiveDate::MAX, RelativeDuration::days(1)),
+            Err(CalendsError::DateOutOfRange { .. })
+        ));
+        assert!(matches!(
+            ClosedInterval::try_from_end(NaiveDate::MIN, RelativeDuration::days(1)),
+            Err(CalendsError::DateOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_closed_with_reversed_dates_panics() {
+        Interval::closed_with_dates(date(2022, 2, 1), date(2022, 1, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_closed_from_start_with_negative_duration_panics() {
+        Interval::closed_from_start(date(2022, 2, 1), RelativeDuration::days(-1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_closed_from_end_with_negative_duration_panics() {
+        Interval::closed_from_end(date(2022, 2, 1), RelativeDuration::days(-1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_new_unchecked_without_bounds_panics() {
+        Interval::new_unchecked(Bound::Unbounded, Bound::Unbounded);
+    }
+
+    #[test]
+    fn test_reciprocity() {
+        let start = date(2022, 1, 1);
+        let interval = Interval::closed_with_dates(start, date(2022, 12, 31));
+
+        assert_eq!(interval.start_opt().unwrap(), date(2022, 1, 1));
+        assert_eq!(interval.end_opt().unwrap(), date(2022, 12, 31));
+
         let duration = interval.duration().unwrap();
 
         let interval_duration = Interval::closed_from_start(start, duration);
@@ -334,55 +529,40 @@ mod tests {
 
     #[test]
     fn test_interval_closed_from_start() {
-        let mut iter = Interval::closed_from_start(
-            NaiveDate::from_ymd(2022, 1, 1),
-            RelativeDuration::months(1),
-        )
-        .until_after(NaiveDate::from_ymd(2023, 1, 1))
-        .unwrap();
+        let mut iter = Interval::closed_from_start(date(2022, 1, 1), RelativeDuration::months(1))
+            .until_after(date(2023, 1, 1))
+            .unwrap();
 
         let next = iter.next().unwrap();
-        assert_eq!(next.start_opt().unwrap(), NaiveDate::from_ymd(2022, 1, 1));
-        assert_eq!(next.end_opt().unwrap(), NaiveDate::from_ymd(2022, 2, 1));
+        assert_eq!(next.start_opt().unwrap(), date(2022, 1, 1));
+        assert_eq!(next.end_opt().unwrap(), date(2022, 2, 1));
 
         let next = iter.next().unwrap();
-        assert_eq!(next.start_opt().unwrap(), NaiveDate::from_ymd(2022, 2, 1));
-        assert_eq!(next.end_opt().unwrap(), NaiveDate::from_ymd(2022, 3, 1));
+        assert_eq!(next.start_opt().unwrap(), date(2022, 2, 1));
+        assert_eq!(next.end_opt().unwrap(), date(2022, 3, 1));
 
         let next = iter.next().unwrap();
-        assert_eq!(next.start_opt().unwrap(), NaiveDate::from_ymd(2022, 3, 1));
+        assert_eq!(next.start_opt().unwrap(), date(2022, 3, 1));
     }
 
     #[test]
     fn test_interval_closed_from_end() {
         let interval = Interval::closed_from_end(
-            NaiveDate::from_ymd(2022, 1, 1),
+            date(2022, 1, 1),
             RelativeDuration::months(1).with_weeks(-2).with_days(2),
         );
 
-        assert_eq!(
-            interval.start_opt().unwrap(),
-            NaiveDate::from_ymd(2021, 12, 13)
-        );
-        assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd(2022, 1, 1));
+        assert_eq!(interval.start_opt().unwrap(), date(2021, 12, 13));
+        assert_eq!(interval.end_opt().unwrap(), date(2022, 1, 1));
     }
 
     #[test]
     fn test_interval_closed_with_dates() {
-        let mut iter = Interval::closed_with_dates(
-            NaiveDate::from_ymd(2022, 1, 1),
-            NaiveDate::from_ymd(2023, 1, 1),
-        )
-        .until_after(NaiveDate::from_ymd(2025, 1, 1))
-        .unwrap();
+        let mut iter = Interval::closed_with_dates(date(2022, 1, 1), date(2023, 1, 1))
+            .until_after(date(2025, 1, 1))
+            .unwrap();
 
-        assert_eq!(
-            iter.next().unwrap().start_opt(),
-            Some(NaiveDate::from_ymd(2022, 1, 1))
-        );
-        assert_eq!(
-            iter.next().unwrap().start_opt(),
-            Some(NaiveDate::from_ymd(2023, 1, 1))
-        );
+        assert_eq!(iter.next().unwrap().start_opt(), Some(date(2022, 1, 1)));
+        assert_eq!(iter.next().unwrap().start_opt(), Some(date(2023, 1, 1)));
     }
 }
@@ -23,7 +23,20 @@ where
         (None, None) => Ordering::Equal,
         (None, Some(_)) => Ordering::Greater,
         (Some(_), None) => Ordering::Less,
-        (Some(r1), Some(ref r2)) => r1.cmp(r2),
+        (Some(r1), Some(r2)) => r1.cmp(r2),
+    }
+}
+
+/// Compare two start bounds, where unbounded is before every value rather than after
+pub fn cmp_start_bound<Q>(e1: &Bound<Q>, e2: &Bound<Q>) -> Ordering
+where
+    Q: Ord,
+{
+    match (e1, e2) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, Bound::Included(_)) => Ordering::Less,
+        (Bound::Included(_), Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(r1), Bound::Included(r2)) => r1.cmp(r2),
     }
 }
 
@@ -43,10 +56,9 @@ where
     Q: Ord,
 {
     let item_bound = Bound::Included(item);
-    match cmp_bound(&item_bound, start) {
+    match cmp_start_bound(&item_bound, start) {
         Ordering::Less => false,
-        Ordering::Equal => true,
-        Ordering::Greater => match cmp_bound(&item_bound, end) {
+        Ordering::Equal | Ordering::Greater => match cmp_bound(&item_bound, end) {
             Ordering::Less => true,
             Ordering::Equal => true,
             Ordering::Greater => false,
@@ -67,7 +79,9 @@ mod tests {
 
     #[test]
     fn test_within() {
-        assert!(within(3, &Bound::Included(1), &Bound::Unbounded))
+        assert!(within(3, &Bound::Included(1), &Bound::Unbounded));
+        assert!(within(3, &Bound::Unbounded, &Bound::Included(3)));
+        assert!(!within(4, &Bound::Unbounded, &Bound::Included(3)));
     }
 
     #[test]
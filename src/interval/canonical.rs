@@ -0,0 +1,194 @@
+//! Closed intervals in a normal form which can be used as map keys
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{duration::RelativeDuration, error::CalendsError};
+
+use super::{bound::Bound, marker, ClosedInterval, Interval, IntervalLike};
+
+/// A non-empty closed interval held as its first and last dates
+///
+/// Intervals covering the same dates are equal however they were written, so
+/// `2022-01-01/P1M-1D` and `2022-01-01/2022-01-31` are the same key. Intervals are ordered by their
+/// start and then their end, which makes them suitable keys for effective-dated configuration in
+/// a [BTreeMap](std::collections::BTreeMap). They (de)serialize as strings, so they can also be the
+/// keys of a JSON object.
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use chrono::NaiveDate;
+/// # use calends::interval::CanonicalInterval;
+///
+/// let rates: BTreeMap<CanonicalInterval, f64> = [
+///     ("2022-01-01/2022-06-30".parse()?, 1.5),
+///     ("2022-07-01/P6M-1D".parse()?, 1.75),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let date = NaiveDate::from_ymd_opt(2022, 8, 1).unwrap();
+/// let rate = rates
+///     .range(..=CanonicalInterval::new(date, NaiveDate::MAX)?)
+///     .rev()
+///     .find(|(interval, _)| interval.contains(date))
+///     .map(|(_, rate)| *rate);
+/// assert_eq!(rate, Some(1.75));
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CanonicalInterval {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+impl CanonicalInterval {
+    /// The interval from `start` to `end` inclusive, failing when the end is before the start
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Result<Self, CalendsError> {
+        if end < start {
+            return Err(CalendsError::ReversedInterval { start, end });
+        }
+        Ok(CanonicalInterval { start, end })
+    }
+
+    /// Whether the date is within the interval
+    #[inline]
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+impl IntervalLike for CanonicalInterval {
+    fn bound_start(&self) -> Bound<NaiveDate> {
+        Bound::Included(self.start)
+    }
+
+    fn bound_end(&self) -> Bound<NaiveDate> {
+        Bound::Included(self.end)
+    }
+
+    fn duration(&self) -> Option<RelativeDuration> {
+        Some(RelativeDuration::from_duration_between(
+            self.start, self.end,
+        ))
+    }
+}
+
+impl marker::Start for CanonicalInterval {}
+impl marker::End for CanonicalInterval {}
+
+impl From<ClosedInterval> for CanonicalInterval {
+    fn from(interval: ClosedInterval) -> Self {
+        use marker::{End, Start};
+
+        CanonicalInterval {
+            start: interval.start(),
+            end: interval.end(),
+        }
+    }
+}
+
+impl From<CanonicalInterval> for ClosedInterval {
+    fn from(interval: CanonicalInterval) -> Self {
+        ClosedInterval::with_dates(interval.start, interval.end)
+    }
+}
+
+/// Only closed intervals have a normal form, intervals unbounded on either side fail
+impl TryFrom<Interval> for CanonicalInterval {
+    type Error = CalendsError;
+
+    fn try_from(interval: Interval) -> Result<Self, Self::Error> {
+        match interval {
+            Interval::Closed(interval) => Ok(interval.into()),
+            _ => Err(CalendsError::IncompatibleConversion(interval.to_string())),
+        }
+    }
+}
+
+impl FromStr for CanonicalInterval {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<ClosedInterval>().map(CanonicalInterval::from)
+    }
+}
+
+/// Serialize as the first and last dates, e.g. `2022-01-01/2022-03-31`
+#[cfg(feature = "serde")]
+impl Serialize for CanonicalInterval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for CanonicalInterval {
+    fn deserialize<D>(deserializer: D) -> Result<CanonicalInterval, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serde")]
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_equal_however_written() {
+        let by_dates: CanonicalInterval = "2022-01-01/2022-01-31".parse().unwrap();
+        let by_duration: CanonicalInterval = "2022-01-01/P1M-1D".parse().unwrap();
+        let by_end: CanonicalInterval = "P30D/2022-01-31".parse::<ClosedInterval>().unwrap().into();
+
+        assert_eq!(by_dates, by_duration);
+        assert_eq!(by_dates, by_end);
+        assert_eq!(by_dates.to_string(), "2022-01-01/2022-01-31");
+        assert!(by_dates.contains(date(2022, 1, 31)) && !by_dates.contains(date(2022, 2, 1)));
+    }
+
+    #[test]
+    fn test_ordering_and_errors() {
+        let a = CanonicalInterval::new(date(2022, 1, 1), date(2022, 1, 31)).unwrap();
+        let b = CanonicalInterval::new(date(2022, 1, 1), date(2022, 2, 28)).unwrap();
+        let c = CanonicalInterval::new(date(2022, 1, 2), date(2022, 1, 2)).unwrap();
+        assert!(a < b && b < c);
+
+        assert_eq!(
+            CanonicalInterval::new(date(2022, 1, 2), date(2022, 1, 1)),
+            Err(CalendsError::ReversedInterval {
+                start: date(2022, 1, 2),
+                end: date(2022, 1, 1)
+            })
+        );
+        assert!(CanonicalInterval::try_from(Interval::open_end(date(2022, 1, 1))).is_err());
+        assert!("2022-01-01/..".parse::<CanonicalInterval>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_map_keys() {
+        let config: BTreeMap<CanonicalInterval, u32> =
+            serde_json::from_str(r#"{"2022-07-01/P6M-1D": 2, "2022-01-01/2022-06-30": 1}"#)
+                .unwrap();
+
+        assert_eq!(config.values().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"2022-01-01/2022-06-30":1,"2022-07-01/2022-12-31":2}"#
+        );
+    }
+}
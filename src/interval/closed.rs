@@ -1,9 +1,18 @@
-use crate::{duration::RelativeDuration, IntervalLike};
+use crate::{duration::RelativeDuration, error::CalendsError, grain::Grain, IntervalLike};
 
-use super::{bound::Bound, iter::UntilAfter, marker, parse::parse_interval};
+use super::{
+    bound::Bound,
+    iter::{Occurrences, UntilAfter},
+    marker,
+    split::{Split, SplitPolicy},
+};
 use chrono::NaiveDate;
+#[cfg(feature = "serde")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "serde")]
+use super::parse::parse_interval;
+
 /// An interval that is constructed off of the idea of the standard calendar (Gregorian Proleptic
 /// calendar).
 ///
@@ -19,12 +28,68 @@ pub struct ClosedInterval {
 
 impl ClosedInterval {
     /// Create an interval from a start and a duration
+    ///
+    /// # Panics
+    ///
+    /// When the duration would end the interval before it starts or outside the range of
+    /// supported dates, see [try_from_start](ClosedInterval::try_from_start) for a fallible
+    /// alternative
     pub fn from_start(date: NaiveDate, duration: RelativeDuration) -> Self {
+        ClosedInterval::try_from_start(date, duration).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create an interval from a start and a duration, failing when the duration would end the
+    /// interval before it starts or outside the range of supported dates
+    pub fn try_from_start(
+        date: NaiveDate,
+        duration: RelativeDuration,
+    ) -> Result<Self, CalendsError> {
+        duration.try_add_to(date)?;
+        let interval = ClosedInterval::from_start_unchecked(date, duration);
+        interval.validate()?;
+        Ok(interval)
+    }
+
+    /// Create an interval from a start and a duration without checking that it ends on or after
+    /// the start
+    ///
+    /// Only for callers which already know the duration is not negative; the rest of the crate
+    /// assumes intervals never end before they start.
+    pub fn from_start_unchecked(date: NaiveDate, duration: RelativeDuration) -> Self {
         ClosedInterval { date, duration }
     }
 
     /// Create an interval from an end and a duration
+    ///
+    /// # Panics
+    ///
+    /// When the duration would start the interval after it ends or outside the range of supported
+    /// dates, see [try_from_end](ClosedInterval::try_from_end) for a fallible alternative
     pub fn from_end(end: NaiveDate, duration: RelativeDuration) -> Self {
+        ClosedInterval::try_from_end(end, duration).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create an interval from an end and a duration, failing when the duration would start the
+    /// interval after it ends or outside the range of supported dates
+    pub fn try_from_end(end: NaiveDate, duration: RelativeDuration) -> Result<Self, CalendsError> {
+        let interval = ClosedInterval {
+            date: duration.try_sub_from(end)?,
+            duration,
+        };
+        interval.validate()?;
+        Ok(interval)
+    }
+
+    /// Create an interval from an end and a duration without checking that it starts on or
+    /// before the end
+    ///
+    /// Only for callers which already know the duration is not negative; the rest of the crate
+    /// assumes intervals never end before they start.
+    ///
+    /// # Panics
+    ///
+    /// When the start is outside the range of supported dates
+    pub fn from_end_unchecked(end: NaiveDate, duration: RelativeDuration) -> Self {
         ClosedInterval {
             date: end + -duration,
             duration,
@@ -32,13 +97,45 @@ impl ClosedInterval {
     }
 
     /// Create an interval with a specified set of dates
+    ///
+    /// # Panics
+    ///
+    /// When the end is before the start, see [try_with_dates](ClosedInterval::try_with_dates) or
+    /// [Interval::new](super::Interval::new) for a fallible alternative
     pub fn with_dates(start: NaiveDate, end: NaiveDate) -> Self {
+        ClosedInterval::try_with_dates(start, end).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create an interval with a specified set of dates, failing when the end is before the start
+    pub fn try_with_dates(start: NaiveDate, end: NaiveDate) -> Result<Self, CalendsError> {
+        if end < start {
+            return Err(CalendsError::ReversedInterval { start, end });
+        }
+        Ok(ClosedInterval::with_dates_unchecked(start, end))
+    }
+
+    /// Create an interval with a specified set of dates without checking that the end is on or
+    /// after the start
+    ///
+    /// Only for callers which already know the order of the dates; the rest of the crate assumes
+    /// intervals never end before they start.
+    pub fn with_dates_unchecked(start: NaiveDate, end: NaiveDate) -> Self {
         ClosedInterval {
             date: start,
             duration: RelativeDuration::from_duration_between(start, end),
         }
     }
 
+    /// Ensure the interval does not end before it starts
+    pub(crate) fn validate(&self) -> Result<(), CalendsError> {
+        let (start, end) = (self.computed_start_date(), self.computed_end_date());
+        if end < start {
+            Err(CalendsError::ReversedInterval { start, end })
+        } else {
+            Ok(())
+        }
+    }
+
     #[allow(dead_code)]
     fn adjust_duration(duration: RelativeDuration) -> RelativeDuration {
         match duration.cmp(&RelativeDuration::zero()) {
@@ -61,6 +158,51 @@ impl ClosedInterval {
     pub fn until_after(self, until: NaiveDate) -> UntilAfter<ClosedInterval> {
         UntilAfter::new(self, until)
     }
+
+    /// Occurrences of the interval repeating every duration, starting with the interval itself
+    ///
+    /// Unlike iterating the interval directly this leaves it unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use calends::{interval::ClosedInterval, IntervalLike, RelativeDuration};
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    /// let billing = ClosedInterval::from_start(date(1, 30), RelativeDuration::months(1));
+    ///
+    /// let starts: Vec<_> = billing
+    ///     .iter()
+    ///     .take_until(date(4, 15))
+    ///     .map(|period| period.start_opt().unwrap())
+    ///     .collect();
+    /// assert_eq!(starts, vec![date(1, 30), date(2, 28), date(3, 30)]);
+    ///
+    /// assert_eq!(billing.iter().nth_occurrence(10).start_opt(), Some(date(11, 30)));
+    /// ```
+    pub fn iter(&self) -> Occurrences {
+        Occurrences::new(self.computed_start_date(), self.duration)
+    }
+
+    /// Pieces of the interval in each period of the grain, see [Split]
+    pub fn split_by(&self, grain: Grain, policy: SplitPolicy) -> Split {
+        Split::new(
+            self.computed_start_date(),
+            Some(self.computed_end_date()),
+            grain,
+            policy,
+        )
+    }
+}
+
+impl IntoIterator for &ClosedInterval {
+    type Item = ClosedInterval;
+    type IntoIter = Occurrences;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl IntervalLike for ClosedInterval {
@@ -81,6 +223,7 @@ impl marker::Start for ClosedInterval {}
 impl marker::End for ClosedInterval {}
 
 /// Serialize a `Interval` as a ISO8601-2:2019 compatible format
+#[cfg(feature = "serde")]
 impl Serialize for ClosedInterval {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -90,8 +233,10 @@ impl Serialize for ClosedInterval {
     }
 }
 
+#[cfg(feature = "serde")]
 pub struct IntervalVisitor;
 
+#[cfg(feature = "serde")]
 impl<'de> de::Visitor<'de> for IntervalVisitor {
     type Value = ClosedInterval;
 
@@ -109,6 +254,7 @@ impl<'de> de::Visitor<'de> for IntervalVisitor {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for ClosedInterval {
     fn deserialize<D>(deserializer: D) -> Result<ClosedInterval, D::Error>
     where
@@ -122,9 +268,66 @@ impl Iterator for ClosedInterval {
     type Item = ClosedInterval;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let interval = ClosedInterval::from_start(self.date, self.duration);
+        let interval = ClosedInterval {
+            date: self.date,
+            duration: self.duration,
+        };
         // to prevent overlapping dates we add one day
         self.date = self.date + self.duration;
         Some(interval)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interval::marker::{End, Start},
+        testing::date,
+    };
+
+    #[test]
+    fn test_try_with_dates() {
+        assert_eq!(
+            ClosedInterval::try_with_dates(date(2022, 1, 1), date(2022, 1, 1)).map(|i| i.end()),
+            Ok(date(2022, 1, 1))
+        );
+        assert_eq!(
+            ClosedInterval::try_with_dates(date(2022, 1, 2), date(2022, 1, 1)),
+            Err(CalendsError::ReversedInterval {
+                start: date(2022, 1, 2),
+                end: date(2022, 1, 1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_occurrences_are_anchored_to_the_first() {
+        let interval = ClosedInterval::from_start(date(2022, 1, 31), RelativeDuration::months(1));
+        let occurrences: Vec<ClosedInterval> = (&interval).into_iter().take(3).collect();
+
+        assert_eq!(
+            occurrences
+                .iter()
+                .map(|o| (o.start(), o.end()))
+                .collect::<Vec<_>>(),
+            vec![
+                (date(2022, 1, 31), date(2022, 2, 28)),
+                (date(2022, 2, 28), date(2022, 3, 31)),
+                (date(2022, 3, 31), date(2022, 4, 30)),
+            ]
+        );
+        let mut iter = interval.iter();
+        iter.next();
+        assert_eq!(iter.nth_occurrence(2), occurrences[2]);
+        assert_eq!(iter.next().as_ref(), occurrences.get(1));
+    }
+
+    #[test]
+    fn test_take_until_includes_the_date() {
+        let weeks = ClosedInterval::from_start(date(2022, 1, 3), RelativeDuration::days(6));
+
+        assert_eq!(weeks.iter().take_until(date(2022, 1, 9)).count(), 2);
+        assert_eq!(weeks.iter().take_until(date(2022, 1, 2)).count(), 0);
+    }
+}
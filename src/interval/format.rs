@@ -0,0 +1,211 @@
+//! Formatting intervals for people and for machines
+//!
+//! Every interval displays in the ISO8601-2:2019 form. [IntervalLike::format] also writes the
+//! interval in the styles of [IntervalFormat], such as `Jan 1 – Mar 31, 2022` for a report.
+use std::fmt::Display;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::util::{beginning_of_month, end_of_month};
+
+use super::{
+    bound::Bound, CanonicalInterval, ClosedInterval, Interval, IntervalLike, IntervalWithEnd,
+    IntervalWithStart, OpenEndInterval, OpenStartInterval,
+};
+
+/// A style of writing intervals
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{interval::IntervalFormat, Interval, IntervalLike};
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// let q1 = Interval::closed_with_dates(date(2022, 1, 1), date(2022, 3, 31));
+///
+/// assert_eq!(q1.format(&IntervalFormat::Iso8601), "2022-01-01/2022-03-31");
+/// assert_eq!(q1.format(&IntervalFormat::Human), "Jan 1 – Mar 31, 2022");
+/// assert_eq!(q1.format(&IntervalFormat::Compact), "2022-Q1");
+///
+/// let winter = Interval::closed_with_dates(date(2021, 12, 20), date(2022, 1, 5));
+/// assert_eq!(winter.format(&IntervalFormat::Human), "Dec 20, 2021 – Jan 5, 2022");
+/// assert_eq!(winter.format(&IntervalFormat::Compact), "2021-12-20/2022-01-05");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IntervalFormat {
+    /// The ISO8601-2:2019 form with both dates in full, e.g. `2022-01-01/2022-03-31`
+    #[default]
+    Iso8601,
+    /// Abbreviated month names without repeating the year or month both dates share, e.g.
+    /// `Jan 1 – Mar 31, 2022` or `Jan 1 – 15, 2022`
+    ///
+    /// Open intervals are written as `from Jan 1, 2022` and `until Mar 31, 2022`.
+    Human,
+    /// The shortest ISO8601 form
+    ///
+    /// Whole calendar years, quarters and months are written as `2022`, `2022-Q1` and `2022-01`,
+    /// and a single day as its date. Other intervals leave out the parts of the end date which are
+    /// the same as the start, as ISO8601 allows, e.g. `2022-01-10/02-20`.
+    Compact,
+}
+
+/// Write the bounds of an interval in the style
+pub(crate) fn format_bounds(
+    start: Bound<NaiveDate>,
+    end: Bound<NaiveDate>,
+    format: &IntervalFormat,
+) -> String {
+    let (start, end) = match (format, start, end) {
+        (IntervalFormat::Iso8601, start, end) => return iso8601(start, end),
+        (_, Bound::Included(start), Bound::Included(end)) => (start, end),
+        (IntervalFormat::Human, Bound::Included(start), Bound::Unbounded) => {
+            return format!("from {}", human(start, true))
+        }
+        (IntervalFormat::Human, Bound::Unbounded, Bound::Included(end)) => {
+            return format!("until {}", human(end, true))
+        }
+        (IntervalFormat::Human, Bound::Unbounded, Bound::Unbounded) => return "all dates".into(),
+        (IntervalFormat::Compact, start, end) => return iso8601(start, end),
+    };
+
+    match format {
+        IntervalFormat::Human => match (start.year() == end.year(), start.month() == end.month()) {
+            _ if start == end => human(start, true),
+            (true, true) => format!("{} – {}, {}", human(start, false), end.day(), end.year()),
+            (true, false) => format!("{} – {}", human(start, false), human(end, true)),
+            (false, _) => format!("{} – {}", human(start, true), human(end, true)),
+        },
+        IntervalFormat::Compact => compact(start, end),
+        IntervalFormat::Iso8601 => unreachable!(),
+    }
+}
+
+fn iso8601(start: Bound<NaiveDate>, end: Bound<NaiveDate>) -> String {
+    let side = |bound: Bound<NaiveDate>| match bound {
+        Bound::Included(date) => date.to_string(),
+        Bound::Unbounded => "..".to_string(),
+    };
+    format!("{}/{}", side(start), side(end))
+}
+
+fn human(date: NaiveDate, with_year: bool) -> String {
+    match with_year {
+        true => format!("{}, {}", date.format("%b %-d"), date.year()),
+        false => date.format("%b %-d").to_string(),
+    }
+}
+
+fn compact(start: NaiveDate, end: NaiveDate) -> String {
+    let whole_months = start == beginning_of_month(&start) && end == end_of_month(&end);
+    let months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32 + 1;
+
+    match months {
+        _ if start == end => start.to_string(),
+        12 if whole_months && start.month() == 1 => start.year().to_string(),
+        3 if whole_months && start.month() % 3 == 1 => {
+            format!("{}-Q{}", start.year(), start.month() / 3 + 1)
+        }
+        1 if whole_months => start.format("%Y-%m").to_string(),
+        _ if start.year() != end.year() => format!("{}/{}", start, end),
+        _ if start.month() != end.month() => format!("{}/{}", start, end.format("%m-%d")),
+        _ => format!("{}/{}", start, end.format("%d")),
+    }
+}
+
+macro_rules! display_iso8601 {
+    ($($ty:ty),*) => {
+        $(
+            /// Display in the ISO8601-2:2019 form, e.g. `2022-01-01/2022-03-31`
+            impl Display for $ty {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(&self.iso8601())
+                }
+            }
+        )*
+    };
+}
+
+display_iso8601!(
+    CanonicalInterval,
+    ClosedInterval,
+    OpenStartInterval,
+    OpenEndInterval,
+    Interval,
+    IntervalWithStart,
+    IntervalWithEnd
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    fn format(start: NaiveDate, end: NaiveDate, format: IntervalFormat) -> String {
+        ClosedInterval::with_dates(start, end).format(&format)
+    }
+
+    #[test]
+    fn test_human() {
+        let human = |start, end| format(start, end, IntervalFormat::Human);
+
+        assert_eq!(human(date(2022, 1, 1), date(2022, 1, 1)), "Jan 1, 2022");
+        assert_eq!(
+            human(date(2022, 1, 1), date(2022, 1, 15)),
+            "Jan 1 – 15, 2022"
+        );
+        assert_eq!(
+            human(date(2022, 1, 1), date(2023, 1, 1)),
+            "Jan 1, 2022 – Jan 1, 2023"
+        );
+        assert_eq!(
+            OpenEndInterval::new(date(2022, 1, 1)).format(&IntervalFormat::Human),
+            "from Jan 1, 2022"
+        );
+        assert_eq!(
+            OpenStartInterval::new(date(2022, 3, 31)).format(&IntervalFormat::Human),
+            "until Mar 31, 2022"
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        let compact = |start, end| format(start, end, IntervalFormat::Compact);
+
+        assert_eq!(compact(date(2022, 1, 1), date(2022, 12, 31)), "2022");
+        assert_eq!(compact(date(2022, 10, 1), date(2022, 12, 31)), "2022-Q4");
+        assert_eq!(
+            compact(date(2022, 2, 1), date(2022, 4, 30)),
+            "2022-02-01/04-30"
+        );
+        assert_eq!(compact(date(2024, 2, 1), date(2024, 2, 29)), "2024-02");
+        assert_eq!(
+            compact(date(2024, 2, 1), date(2024, 2, 28)),
+            "2024-02-01/28"
+        );
+        assert_eq!(
+            compact(date(2022, 1, 10), date(2022, 2, 20)),
+            "2022-01-10/02-20"
+        );
+        assert_eq!(compact(date(2022, 5, 4), date(2022, 5, 4)), "2022-05-04");
+        assert_eq!(
+            compact(date(2022, 1, 1), date(2023, 12, 31)),
+            "2022-01-01/2023-12-31"
+        );
+        assert_eq!(
+            OpenEndInterval::new(date(2022, 1, 1)).format(&IntervalFormat::Compact),
+            "2022-01-01/.."
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let interval = Interval::closed_with_dates(date(2022, 1, 1), date(2022, 3, 31));
+        assert_eq!(interval.to_string(), "2022-01-01/2022-03-31");
+        assert_eq!(
+            Interval::open_start(date(2022, 3, 31)).to_string(),
+            "../2022-03-31"
+        );
+        assert_eq!(interval.to_string().parse::<Interval>().unwrap(), interval);
+    }
+}
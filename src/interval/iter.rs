@@ -1,6 +1,11 @@
 use chrono::NaiveDate;
 
-use super::{marker::End, ClosedInterval};
+use crate::RelativeDuration;
+
+use super::{
+    marker::{End, Start},
+    ClosedInterval,
+};
 
 #[derive(Debug, Clone)]
 pub struct UntilAfter<T>
@@ -39,3 +44,52 @@ where
         }
     }
 }
+
+/// Successive occurrences of an interval, see [ClosedInterval::iter]
+///
+/// Each occurrence is offset from the first by a multiple of the duration, rather than from the
+/// occurrence before it, so clamping to the end of a short month does not carry over. Monthly
+/// periods starting on the 30th start on the 30th of every month with one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrences {
+    start: NaiveDate,
+    duration: RelativeDuration,
+    index: i32,
+    until: Option<NaiveDate>,
+}
+
+impl Occurrences {
+    pub(crate) fn new(start: NaiveDate, duration: RelativeDuration) -> Self {
+        Occurrences {
+            start,
+            duration,
+            index: 0,
+            until: None,
+        }
+    }
+
+    /// The nth occurrence counting from zero, no matter how far the iterator has advanced
+    pub fn nth_occurrence(&self, n: u32) -> ClosedInterval {
+        let n = i32::try_from(n).expect("occurrence is out of range");
+        ClosedInterval::from_start_unchecked(self.start + self.duration * n, self.duration)
+    }
+
+    /// Stop after the last occurrence starting on or before the date
+    pub fn take_until(mut self, date: NaiveDate) -> Self {
+        self.until = Some(date);
+        self
+    }
+}
+
+impl Iterator for Occurrences {
+    type Item = ClosedInterval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let occurrence = self.nth_occurrence(self.index as u32);
+        if self.until.is_some_and(|until| occurrence.start() > until) {
+            return None;
+        }
+        self.index += 1;
+        Some(occurrence)
+    }
+}
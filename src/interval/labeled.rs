@@ -0,0 +1,207 @@
+//! Intervals carrying a payload
+//!
+//! A [Labeled] interval keeps a value such as a booking or contract id alongside any interval or
+//! [Period], so callers do not need to track it in a parallel array. The label is carried through
+//! iteration, shifting and splitting.
+use chrono::NaiveDate;
+
+use crate::{period::Period, RelativeDuration};
+
+use super::{
+    bound::Bound,
+    marker::{End, Start},
+    ClosedInterval, IntervalLike,
+};
+
+/// An interval or period together with a label
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::interval::{ClosedInterval, Labeled};
+/// # use calends::IntervalLike;
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 3, d).unwrap();
+/// let booking = Labeled::new(ClosedInterval::with_dates(date(1), date(10)), "room 4");
+///
+/// let (before, after) = booking.split_at(date(6));
+/// let (before, after) = (before.unwrap(), after.unwrap());
+///
+/// assert_eq!(before.iso8601(), "2022-03-01/2022-03-05");
+/// assert_eq!(after.iso8601(), "2022-03-06/2022-03-10");
+/// assert_eq!(after.label(), &"room 4");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Labeled<I, T = ()> {
+    interval: I,
+    label: T,
+}
+
+impl<I, T> Labeled<I, T> {
+    pub fn new(interval: I, label: T) -> Self {
+        Labeled { interval, label }
+    }
+
+    pub fn interval(&self) -> &I {
+        &self.interval
+    }
+
+    pub fn label(&self) -> &T {
+        &self.label
+    }
+
+    pub fn into_parts(self) -> (I, T) {
+        (self.interval, self.label)
+    }
+
+    /// Replace the label, keeping the interval
+    pub fn map_label<U>(self, f: impl FnOnce(T) -> U) -> Labeled<I, U> {
+        Labeled {
+            interval: self.interval,
+            label: f(self.label),
+        }
+    }
+
+    /// Replace the interval, keeping the label
+    pub fn map_interval<J>(self, f: impl FnOnce(I) -> J) -> Labeled<J, T> {
+        Labeled {
+            interval: f(self.interval),
+            label: self.label,
+        }
+    }
+}
+
+/// Part of a labeled interval which was split
+pub type Half<T> = Option<Labeled<ClosedInterval, T>>;
+
+impl<I: Start + End, T: Clone> Labeled<I, T> {
+    /// The interval moved by a duration, keeping its length
+    pub fn shift(&self, duration: RelativeDuration) -> Labeled<ClosedInterval, T> {
+        let (start, end) = (self.interval.start(), self.interval.end());
+        Labeled::new(
+            ClosedInterval::with_dates(start + duration, end + duration),
+            self.label.clone(),
+        )
+    }
+
+    /// Split into the dates before the given date and the dates from it onwards
+    ///
+    /// Either half is [None] when it would be empty, and both halves keep the label.
+    pub fn split_at(&self, date: NaiveDate) -> (Half<T>, Half<T>) {
+        let (start, end) = (self.interval.start(), self.interval.end());
+        let before = date.pred_opt().filter(|last| start <= *last).map(|last| {
+            Labeled::new(
+                ClosedInterval::with_dates_unchecked(start, last.min(end)),
+                self.label.clone(),
+            )
+        });
+        let after = (date <= end).then(|| {
+            Labeled::new(
+                ClosedInterval::with_dates_unchecked(date.max(start), end),
+                self.label.clone(),
+            )
+        });
+        (before, after)
+    }
+}
+
+impl<T: Clone> Labeled<Period, T> {
+    /// The following period with the same label
+    pub fn succ(&self) -> Self {
+        Labeled::new(self.interval.succ(), self.label.clone())
+    }
+
+    /// The preceding period with the same label
+    pub fn pred(&self) -> Self {
+        Labeled::new(self.interval.pred(), self.label.clone())
+    }
+}
+
+impl<I: IntervalLike, T> IntervalLike for Labeled<I, T> {
+    fn bound_start(&self) -> Bound<NaiveDate> {
+        self.interval.bound_start()
+    }
+
+    fn bound_end(&self) -> Bound<NaiveDate> {
+        self.interval.bound_end()
+    }
+
+    fn duration(&self) -> Option<RelativeDuration> {
+        self.interval.duration()
+    }
+}
+
+impl<I: Start, T> Start for Labeled<I, T> {}
+impl<I: End, T> End for Labeled<I, T> {}
+
+/// Iterating a labeled interval labels each interval it steps to
+impl<I: Iterator, T: Clone> Iterator for Labeled<I, T> {
+    type Item = Labeled<I::Item, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.interval
+            .next()
+            .map(|interval| Labeled::new(interval, self.label.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grain::Grain, period::FiscalYear, testing::date};
+
+    #[test]
+    fn test_split_outside_interval() {
+        let labeled = Labeled::new(
+            ClosedInterval::with_dates(date(2022, 3, 1), date(2022, 3, 10)),
+            7,
+        );
+
+        let (before, after) = labeled.split_at(date(2022, 3, 1));
+        assert!(before.is_none());
+        assert_eq!(after.unwrap().iso8601(), "2022-03-01/2022-03-10");
+
+        let (before, after) = labeled.split_at(date(2022, 3, 11));
+        assert_eq!(before.unwrap().iso8601(), "2022-03-01/2022-03-10");
+        assert!(after.is_none());
+    }
+
+    #[test]
+    fn test_label_follows_iteration_and_periods() {
+        let weeks = Labeled::new(
+            ClosedInterval::from_start(date(2022, 1, 3), RelativeDuration::days(6)),
+            "sprint",
+        );
+        let labeled: Vec<_> = weeks.take(2).collect();
+        assert_eq!(labeled[1].iso8601(), "2022-01-09/2022-01-15");
+        assert_eq!(labeled[1].label(), &"sprint");
+
+        let month = Labeled::new(
+            Period::containing(date(2022, 1, 15), Grain::Month, &FiscalYear::default()),
+            "contract",
+        );
+        assert_eq!(month.succ().interval().start(), date(2022, 2, 1));
+        assert_eq!(
+            month.shift(RelativeDuration::days(1)).iso8601(),
+            "2022-01-02/2022-02-01"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let labeled = Labeled::new(
+            ClosedInterval::with_dates(date(2022, 3, 1), date(2022, 3, 10)),
+            7,
+        );
+        let json = serde_json::to_string(&labeled).unwrap();
+
+        assert_eq!(json, r#"{"interval":"2022-03-01/2022-03-10","label":7}"#);
+        assert_eq!(
+            serde_json::from_str::<Labeled<ClosedInterval, i32>>(&json).unwrap(),
+            labeled
+        );
+    }
+}
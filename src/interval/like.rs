@@ -1,14 +1,20 @@
-use crate::RelativeDuration;
+//! Interval
+//!
+//! Used to coalesce both recurring and non-recurring intervals into one interface.
+use crate::{duration::diff_in, grain::Grain, RelativeDuration};
 
-///! Interval
-///!
-///! Used to coalesce both recurring and non-recurring intervals into one interface.
 use super::{
     bound::{self, Bound},
+    format::{format_bounds, IntervalFormat},
     marker::{End, Start},
 };
 use chrono::NaiveDate;
 
+/// A range of dates which may be unbounded on either side
+///
+/// The trait is object safe, so intervals of different types can be kept together as
+/// `Box<dyn IntervalLike>`, or as `Box<dyn IntervalLikeWithStartAndEnd>` when every interval needs
+/// both a start and an end.
 pub trait IntervalLike {
     fn bound_start(&self) -> Bound<NaiveDate>;
     fn bound_end(&self) -> Bound<NaiveDate>;
@@ -41,6 +47,62 @@ pub trait IntervalLike {
         bound::within(date, &self.bound_start(), &self.bound_end())
     }
 
+    /// Whether the intervals have at least one date in common
+    fn overlaps(&self, other: &dyn IntervalLike) -> bool {
+        starts_by(&self.bound_start(), &other.bound_end())
+            && starts_by(&other.bound_start(), &self.bound_end())
+    }
+
+    /// Whether the intervals do not overlap but one starts the day after the other ends
+    fn is_adjacent(&self, other: &dyn IntervalLike) -> bool {
+        let follows = |end: Option<NaiveDate>, start: Option<NaiveDate>| match (end, start) {
+            (Some(end), Some(start)) => end.succ_opt() == Some(start),
+            _ => false,
+        };
+        follows(self.end_opt(), other.start_opt()) || follows(other.end_opt(), self.start_opt())
+    }
+
+    /// Whether every date of the other interval is within this one
+    fn contains_interval(&self, other: &dyn IntervalLike) -> bool {
+        bound::cmp_start_bound(&self.bound_start(), &other.bound_start()).is_le()
+            && bound::cmp_bound(&other.bound_end(), &self.bound_end()).is_le()
+    }
+
+    /// Number of days in the interval including both ends, [None] when it is unbounded
+    fn length_days(&self) -> Option<i64> {
+        match (self.start_opt(), self.end_opt()) {
+            (Some(start), Some(end)) => Some((end - start).num_days() + 1),
+            _ => None,
+        }
+    }
+
+    /// Length of the interval in weeks including any part week, [None] when it is unbounded
+    fn length_weeks(&self) -> Option<f64> {
+        self.length_days().map(|days| days as f64 / 7.0)
+    }
+
+    /// Length of the interval in units no larger than the grain, with the remainder in days
+    ///
+    /// The length includes both ends, so 2022-01-15 to 2022-03-26 is two months and twelve days.
+    /// See [diff_in] for how the units are chosen. [None] when the interval is unbounded.
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use calends::{grain::Grain, Interval, IntervalLike, RelativeDuration};
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    /// let interval = Interval::closed_with_dates(date(1, 15), date(3, 26));
+    ///
+    /// assert_eq!(interval.length_in(Grain::Month), Some(RelativeDuration::months(2).with_days(12)));
+    /// assert_eq!(interval.length_in(Grain::Week), Some(RelativeDuration::weeks(10).with_days(1)));
+    /// assert_eq!(interval.length_days(), Some(71));
+    /// assert_eq!(Interval::open_end(date(1, 1)).length_in(Grain::Day), None);
+    /// ```
+    fn length_in(&self, unit: Grain) -> Option<RelativeDuration> {
+        let (start, end) = (self.start_opt()?, self.end_opt()?);
+        Some(diff_in(start, end.succ_opt()?, unit))
+    }
+
     /// ISO8601-2:2019 Formatting of intervals
     ///
     /// The standard allows for:
@@ -64,17 +126,153 @@ pub trait IntervalLike {
             (Bound::Unbounded, Bound::Unbounded) => "../..".to_string(),
         }
     }
+
+    /// Write the interval in the style, see [IntervalFormat]
+    fn format(&self, format: &IntervalFormat) -> String {
+        format_bounds(self.bound_start(), self.bound_end(), format)
+    }
+}
+
+/// Whether an interval starting at the start bound has begun by the end bound
+fn starts_by(start: &Bound<NaiveDate>, end: &Bound<NaiveDate>) -> bool {
+    match (start, end) {
+        (Bound::Included(start), Bound::Included(end)) => start <= end,
+        _ => true,
+    }
 }
 
 pub trait IntervalLikeWithStart: IntervalLike + Start {}
 pub trait IntervalLikeWithEnd: IntervalLike + End {}
 pub trait IntervalLikeWithStartAndEnd: IntervalLike + Start + End {}
 
+impl<T: Start + ?Sized> IntervalLikeWithStart for T {}
+impl<T: End + ?Sized> IntervalLikeWithEnd for T {}
+impl<T: Start + End + ?Sized> IntervalLikeWithStartAndEnd for T {}
+
+impl<T: IntervalLike + ?Sized> IntervalLike for &T {
+    fn bound_start(&self) -> Bound<NaiveDate> {
+        (**self).bound_start()
+    }
+
+    fn bound_end(&self) -> Bound<NaiveDate> {
+        (**self).bound_end()
+    }
+
+    fn duration(&self) -> Option<RelativeDuration> {
+        (**self).duration()
+    }
+
+    fn start_opt(&self) -> Option<NaiveDate> {
+        (**self).start_opt()
+    }
+
+    fn end_opt(&self) -> Option<NaiveDate> {
+        (**self).end_opt()
+    }
+
+    fn within(&self, date: NaiveDate) -> bool {
+        (**self).within(date)
+    }
+
+    fn overlaps(&self, other: &dyn IntervalLike) -> bool {
+        (**self).overlaps(other)
+    }
+
+    fn is_adjacent(&self, other: &dyn IntervalLike) -> bool {
+        (**self).is_adjacent(other)
+    }
+
+    fn contains_interval(&self, other: &dyn IntervalLike) -> bool {
+        (**self).contains_interval(other)
+    }
+
+    fn length_days(&self) -> Option<i64> {
+        (**self).length_days()
+    }
+
+    fn length_weeks(&self) -> Option<f64> {
+        (**self).length_weeks()
+    }
+
+    fn length_in(&self, unit: Grain) -> Option<RelativeDuration> {
+        (**self).length_in(unit)
+    }
+
+    fn iso8601(&self) -> String {
+        (**self).iso8601()
+    }
+
+    fn format(&self, format: &IntervalFormat) -> String {
+        (**self).format(format)
+    }
+}
+
+impl<T: IntervalLike + ?Sized> IntervalLike for Box<T> {
+    fn bound_start(&self) -> Bound<NaiveDate> {
+        (**self).bound_start()
+    }
+
+    fn bound_end(&self) -> Bound<NaiveDate> {
+        (**self).bound_end()
+    }
+
+    fn duration(&self) -> Option<RelativeDuration> {
+        (**self).duration()
+    }
+
+    fn start_opt(&self) -> Option<NaiveDate> {
+        (**self).start_opt()
+    }
+
+    fn end_opt(&self) -> Option<NaiveDate> {
+        (**self).end_opt()
+    }
+
+    fn within(&self, date: NaiveDate) -> bool {
+        (**self).within(date)
+    }
+
+    fn overlaps(&self, other: &dyn IntervalLike) -> bool {
+        (**self).overlaps(other)
+    }
+
+    fn is_adjacent(&self, other: &dyn IntervalLike) -> bool {
+        (**self).is_adjacent(other)
+    }
+
+    fn contains_interval(&self, other: &dyn IntervalLike) -> bool {
+        (**self).contains_interval(other)
+    }
+
+    fn length_days(&self) -> Option<i64> {
+        (**self).length_days()
+    }
+
+    fn length_weeks(&self) -> Option<f64> {
+        (**self).length_weeks()
+    }
+
+    fn length_in(&self, unit: Grain) -> Option<RelativeDuration> {
+        (**self).length_in(unit)
+    }
+
+    fn iso8601(&self) -> String {
+        (**self).iso8601()
+    }
+
+    fn format(&self, format: &IntervalFormat) -> String {
+        (**self).format(format)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::date;
+    use quickcheck::{Arbitrary, Gen};
+    use quickcheck_macros::quickcheck;
 
-    #[derive(Clone, PartialEq, Eq, serde::Serialize)]
+    #[derive(Clone, PartialEq, Eq)]
     struct Int {
         pub start: NaiveDate,
         pub end: NaiveDate,
@@ -97,41 +295,157 @@ mod tests {
     #[test]
     fn test_within() {
         let i1 = Int {
-            start: NaiveDate::from_ymd(2022, 1, 1),
-            end: NaiveDate::from_ymd(2022, 12, 31),
+            start: date(2022, 1, 1),
+            end: date(2022, 12, 31),
         };
 
-        assert!(i1.within(NaiveDate::from_ymd(2022, 5, 18)));
-        assert!(!i1.within(NaiveDate::from_ymd(2023, 5, 18)));
+        assert!(i1.within(date(2022, 5, 18)));
+        assert!(!i1.within(date(2023, 5, 18)));
     }
 
     #[test]
     fn test_start_date() {
         let i1 = Int {
-            start: NaiveDate::from_ymd(2022, 1, 1),
-            end: NaiveDate::from_ymd(2022, 12, 31),
+            start: date(2022, 1, 1),
+            end: date(2022, 12, 31),
         };
 
-        assert_eq!(i1.start_opt(), NaiveDate::from_ymd_opt(2022, 1, 1));
+        assert_eq!(i1.start_opt(), Some(date(2022, 1, 1)));
     }
 
     #[test]
     fn test_end_date() {
         let i1 = Int {
-            start: NaiveDate::from_ymd(2022, 1, 1),
-            end: NaiveDate::from_ymd(2022, 12, 31),
+            start: date(2022, 1, 1),
+            end: date(2022, 12, 31),
         };
 
-        assert_eq!(i1.end_opt(), NaiveDate::from_ymd_opt(2022, 12, 31));
+        assert_eq!(i1.end_opt(), Some(date(2022, 12, 31)));
+    }
+
+    #[test]
+    fn test_boxed_intervals() {
+        let day = |m, d| date(2022, m, d);
+        let intervals: Vec<Box<dyn IntervalLikeWithStartAndEnd>> = vec![
+            Box::new(crate::interval::ClosedInterval::with_dates(
+                day(1, 1),
+                day(1, 31),
+            )),
+            Box::new(crate::period::Period::containing(
+                day(2, 14),
+                crate::grain::Grain::Month,
+                &Default::default(),
+            )),
+        ];
+
+        fn span<I: Start + End>(intervals: &[I]) -> (NaiveDate, NaiveDate) {
+            let start = intervals.iter().map(|i| i.start()).min().unwrap();
+            let end = intervals.iter().map(|i| i.end()).max().unwrap();
+            (start, end)
+        }
+
+        assert_eq!(span(&intervals), (day(1, 1), day(2, 28)));
+        assert_eq!(intervals[1].iso8601(), "2022-02-01/2022-02-28");
     }
 
     #[test]
     fn test_iso8601() {
         let i = Int {
-            start: NaiveDate::from_ymd(2022, 1, 1),
-            end: NaiveDate::from_ymd(2022, 12, 31),
+            start: date(2022, 1, 1),
+            end: date(2022, 12, 31),
         };
 
         assert_eq!(i.iso8601(), "2022-01-01/2022-12-31")
     }
+
+    /// Dates are days after 2022-01-01, with bounds kept to the first few weeks so that the
+    /// properties can be checked against every date in a small range
+    #[derive(Clone, Debug)]
+    struct Bounds(Option<i64>, Option<i64>);
+
+    impl Arbitrary for Bounds {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let mut day = || Option::<u8>::arbitrary(g).map(|day| i64::from(day % 30));
+            match (day(), day()) {
+                (Some(a), Some(b)) => Bounds(Some(a.min(b)), Some(a.max(b))),
+                (start, end) => Bounds(start, end),
+            }
+        }
+    }
+
+    impl IntervalLike for Bounds {
+        fn bound_start(&self) -> Bound<NaiveDate> {
+            self.0
+                .map_or(Bound::Unbounded, |day| Bound::Included(day_of(day)))
+        }
+
+        fn bound_end(&self) -> Bound<NaiveDate> {
+            self.1
+                .map_or(Bound::Unbounded, |day| Bound::Included(day_of(day)))
+        }
+
+        fn duration(&self) -> Option<RelativeDuration> {
+            None
+        }
+    }
+
+    fn day_of(day: i64) -> NaiveDate {
+        date(2022, 1, 1) + chrono::Duration::days(day)
+    }
+
+    fn days() -> impl Iterator<Item = NaiveDate> {
+        (-5..40).map(day_of)
+    }
+
+    #[quickcheck]
+    fn test_overlaps_shares_a_date(a: Bounds, b: Bounds) -> bool {
+        a.overlaps(&b) == days().any(|d| a.within(d) && b.within(d))
+            && a.overlaps(&b) == b.overlaps(&a)
+    }
+
+    #[quickcheck]
+    fn test_adjacent_meets_without_overlapping(a: Bounds, b: Bounds) -> bool {
+        let meets = |x: &Bounds, y: &Bounds| {
+            days().any(|d| x.within(d) && d.succ_opt().is_some_and(|next| y.within(next)))
+        };
+        a.is_adjacent(&b) == (!a.overlaps(&b) && (meets(&a, &b) || meets(&b, &a)))
+    }
+
+    #[quickcheck]
+    fn test_contains_every_date(a: Bounds, b: Bounds) -> bool {
+        a.contains_interval(&b) == days().all(|d| !b.within(d) || a.within(d))
+    }
+
+    #[test]
+    fn test_relations() {
+        let day = |m, d| date(2022, m, d);
+        let january = crate::Interval::closed_with_dates(day(1, 1), day(1, 31));
+        let february = crate::Interval::closed_with_dates(day(2, 1), day(2, 28));
+        let until_march = crate::Interval::open_start(day(3, 1));
+
+        assert!(january.is_adjacent(&february));
+        assert!(!january.overlaps(&february));
+        assert!(until_march.contains_interval(&february));
+        assert!(!february.contains_interval(&until_march));
+        assert!(Box::new(until_march).overlaps(&january));
+    }
+
+    #[test]
+    fn test_length() {
+        let day = |m, d| date(2022, m, d);
+        let single = crate::Interval::closed_with_dates(day(2, 28), day(2, 28));
+        let quarter = crate::Interval::closed_with_dates(day(1, 1), day(3, 31));
+
+        assert_eq!(single.length_days(), Some(1));
+        assert_eq!(
+            single.length_in(Grain::Year),
+            Some(RelativeDuration::days(1))
+        );
+        assert_eq!(
+            quarter.length_in(Grain::Quarter),
+            Some(RelativeDuration::months(3))
+        );
+        assert_eq!(quarter.length_weeks(), Some(90.0 / 7.0));
+        assert_eq!(crate::Interval::open_start(day(1, 1)).length_weeks(), None);
+    }
 }
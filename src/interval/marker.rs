@@ -14,10 +14,35 @@ pub trait End: IntervalLike {
     }
 }
 
+impl<T: Start + ?Sized> Start for &T {
+    fn start(&self) -> NaiveDate {
+        (**self).start()
+    }
+}
+
+impl<T: Start + ?Sized> Start for Box<T> {
+    fn start(&self) -> NaiveDate {
+        (**self).start()
+    }
+}
+
+impl<T: End + ?Sized> End for &T {
+    fn end(&self) -> NaiveDate {
+        (**self).end()
+    }
+}
+
+impl<T: End + ?Sized> End for Box<T> {
+    fn end(&self) -> NaiveDate {
+        (**self).end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         interval::{marker, ClosedInterval, OpenStartInterval},
+        testing::date,
         RelativeDuration,
     };
 
@@ -25,9 +50,8 @@ mod tests {
 
     #[test]
     fn test_all_intervals() {
-        let i1 = OpenStartInterval::new(NaiveDate::from_ymd(2022, 1, 1));
-        let i2 =
-            ClosedInterval::from_start(NaiveDate::from_ymd(2022, 1, 1), RelativeDuration::days(2));
+        let i1 = OpenStartInterval::new(date(2022, 1, 1));
+        let i2 = ClosedInterval::from_start(date(2022, 1, 1), RelativeDuration::days(2));
 
         fn interval<I: IntervalLike + marker::End>(interval: I) -> (Option<NaiveDate>, NaiveDate) {
             (interval.start_opt(), interval.end())
@@ -35,10 +59,10 @@ mod tests {
 
         let i1 = interval(i1);
         assert_eq!(i1.0, None);
-        assert_eq!(i1.1, NaiveDate::from_ymd(2022, 1, 1));
+        assert_eq!(i1.1, date(2022, 1, 1));
 
         let i2 = interval(i2);
-        assert_eq!(i2.0, Some(NaiveDate::from_ymd(2022, 1, 1)));
-        assert_eq!(i2.1, NaiveDate::from_ymd(2022, 1, 3));
+        assert_eq!(i2.0, Some(date(2022, 1, 1)));
+        assert_eq!(i2.1, date(2022, 1, 3));
     }
 }
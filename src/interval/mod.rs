@@ -1,13 +1,31 @@
 pub mod base;
 pub mod bound;
+pub mod canonical;
 pub mod closed;
+pub mod format;
 pub mod iter;
+pub mod labeled;
 pub mod like;
 pub mod marker;
 pub mod open;
-mod parse;
+pub mod ops;
+pub(crate) mod parse;
+pub mod repeat;
+pub mod set;
+pub mod split;
+pub mod tolerance;
+pub mod tree;
+pub mod validate;
 
 pub use base::{Interval, IntervalWithEnd, IntervalWithStart};
+pub use canonical::CanonicalInterval;
 pub use closed::ClosedInterval;
+pub use format::IntervalFormat;
+pub use iter::Occurrences;
+pub use labeled::Labeled;
 pub use like::IntervalLike;
 pub use open::{OpenEndInterval, OpenStartInterval};
+pub use repeat::RepeatingInterval;
+pub use set::IntervalSet;
+pub use split::{Split, SplitPolicy};
+pub use tree::IntervalTree;
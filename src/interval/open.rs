@@ -1,13 +1,13 @@
 use chrono::NaiveDate;
+#[cfg(feature = "serde")]
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::IntervalLike;
 
-use super::{
-    bound::Bound,
-    marker,
-    parse::{parse_open_end_interval, parse_open_start_interval},
-};
+use super::{bound::Bound, marker};
+
+#[cfg(feature = "serde")]
+use super::parse::{parse_open_end_interval, parse_open_start_interval};
 
 /// Indicating that the preceeding direction is unbounded, this is the time leading up to the
 /// current time.
@@ -38,6 +38,7 @@ impl IntervalLike for OpenStartInterval {
 
 impl marker::End for OpenStartInterval {}
 
+#[cfg(feature = "serde")]
 impl Serialize for OpenStartInterval {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -47,8 +48,10 @@ impl Serialize for OpenStartInterval {
     }
 }
 
+#[cfg(feature = "serde")]
 pub struct IntervalVisitor;
 
+#[cfg(feature = "serde")]
 impl<'de> de::Visitor<'de> for IntervalVisitor {
     type Value = OpenStartInterval;
 
@@ -66,6 +69,7 @@ impl<'de> de::Visitor<'de> for IntervalVisitor {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for OpenStartInterval {
     fn deserialize<D>(deserializer: D) -> Result<OpenStartInterval, D::Error>
     where
@@ -104,6 +108,7 @@ impl IntervalLike for OpenEndInterval {
 
 impl marker::Start for OpenEndInterval {}
 
+#[cfg(feature = "serde")]
 impl Serialize for OpenEndInterval {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -113,8 +118,10 @@ impl Serialize for OpenEndInterval {
     }
 }
 
+#[cfg(feature = "serde")]
 pub struct UnboundedEndVisitor;
 
+#[cfg(feature = "serde")]
 impl<'de> de::Visitor<'de> for UnboundedEndVisitor {
     type Value = OpenEndInterval;
 
@@ -132,6 +139,7 @@ impl<'de> de::Visitor<'de> for UnboundedEndVisitor {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for OpenEndInterval {
     fn deserialize<D>(deserializer: D) -> Result<OpenEndInterval, D::Error>
     where
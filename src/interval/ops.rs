@@ -0,0 +1,183 @@
+//! Set operations on intervals
+//!
+//! The operations accept any [IntervalLike] and take care of the unbounded ends, treating an
+//! unbounded start as before every date and an unbounded end as after every date. Results are
+//! [Interval]s in ascending order. Intervals which touch, such as one ending on the 31st and the
+//! next starting on the 1st, count as contiguous.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chrono::NaiveDate;
+//! # use calends::{Interval, IntervalLike};
+//! # use calends::interval::ops::{difference, intersect, union};
+//!
+//! let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+//! let q1 = Interval::closed_with_dates(date(1, 1), date(3, 31));
+//! let from_march = Interval::open_end(date(3, 1));
+//!
+//! assert_eq!(
+//!     intersect(&q1, &from_march).map(|i| i.iso8601()),
+//!     Some("2022-03-01/2022-03-31".to_string())
+//! );
+//! assert_eq!(union(&q1, &from_march)?[0].iso8601(), "2022-01-01/..");
+//! assert_eq!(difference(&q1, &from_march)[0].iso8601(), "2022-01-01/2022-02-28");
+//! # Ok::<(), calends::CalendsError>(())
+//! ```
+use chrono::NaiveDate;
+
+use crate::error::CalendsError;
+
+use super::{bound::Bound, Interval, IntervalLike};
+
+/// Dates in both intervals, [None] when they do not overlap
+pub fn intersect<A: IntervalLike, B: IntervalLike>(a: &A, b: &B) -> Option<Interval> {
+    let start = match (a.start_opt(), b.start_opt()) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (start, None) | (None, start) => start,
+    };
+    let end = match (a.end_opt(), b.end_opt()) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (end, None) | (None, end) => end,
+    };
+    interval(start, end).ok()
+}
+
+/// Dates in either interval, one interval when they overlap or touch and otherwise both
+///
+/// # Errors
+///
+/// [CalendsError::UnboundedInterval] when the union covers every date, as an interval cannot be
+/// unbounded on both sides
+pub fn union<A: IntervalLike, B: IntervalLike>(
+    a: &A,
+    b: &B,
+) -> Result<Vec<Interval>, CalendsError> {
+    let (a, b) = (to_interval(a)?, to_interval(b)?);
+    let (first, second) = match starts_before(&a, &b) {
+        true => (a, b),
+        false => (b, a),
+    };
+
+    // the second starts on or after the first, so they are contiguous unless the first ends
+    // more than a day before the second starts
+    let apart = match (first.end_opt(), second.start_opt()) {
+        (Some(end), Some(start)) => end.succ_opt().is_some_and(|next| next < start),
+        _ => false,
+    };
+    if apart {
+        return Ok(vec![first, second]);
+    }
+
+    let end = match (first.end_opt(), second.end_opt()) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        _ => None,
+    };
+    Ok(vec![interval(first.start_opt(), end)?])
+}
+
+/// Dates in the first interval which are not in the second
+///
+/// There are two intervals when the second is in the middle of the first, one when it overlaps
+/// either end and none when it covers the first.
+pub fn difference<A: IntervalLike, B: IntervalLike>(a: &A, b: &B) -> Vec<Interval> {
+    let Ok(a) = to_interval(a) else {
+        return Vec::new();
+    };
+    if intersect(&a, b).is_none() {
+        return vec![a];
+    }
+
+    let mut parts = Vec::new();
+    if let Some(before) = b.start_opt().and_then(|start| start.pred_opt()) {
+        if a.start_opt().is_none_or(|start| start <= before) {
+            parts.extend(interval(a.start_opt(), Some(before)).ok());
+        }
+    }
+    if let Some(after) = b.end_opt().and_then(|end| end.succ_opt()) {
+        if a.end_opt().is_none_or(|end| after <= end) {
+            parts.extend(interval(Some(after), a.end_opt()).ok());
+        }
+    }
+    parts
+}
+
+fn interval(start: Option<NaiveDate>, end: Option<NaiveDate>) -> Result<Interval, CalendsError> {
+    let bound = |date: Option<NaiveDate>| date.map_or(Bound::Unbounded, Bound::Included);
+    Interval::new(bound(start), bound(end))
+}
+
+fn to_interval<I: IntervalLike>(i: &I) -> Result<Interval, CalendsError> {
+    interval(i.start_opt(), i.end_opt())
+}
+
+fn starts_before(a: &Interval, b: &Interval) -> bool {
+    match (a.start_opt(), b.start_opt()) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(a), Some(b)) => a <= b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    fn iso(intervals: Vec<Interval>) -> Vec<String> {
+        intervals.iter().map(|i| i.iso8601()).collect()
+    }
+
+    #[test]
+    fn test_unbounded_ends() {
+        let before = Interval::open_start(date(2022, 6, 30));
+        let after = Interval::open_end(date(2022, 6, 1));
+
+        assert_eq!(
+            intersect(&before, &after).map(|i| i.iso8601()),
+            Some("2022-06-01/2022-06-30".to_string())
+        );
+        assert_eq!(
+            intersect(&before, &Interval::open_start(date(2022, 1, 1))).map(|i| i.iso8601()),
+            Some("../2022-01-01".to_string())
+        );
+        assert!(intersect(&Interval::open_start(date(2022, 1, 1)), &after).is_none());
+        assert_eq!(union(&before, &after), Err(CalendsError::UnboundedInterval));
+        assert_eq!(iso(difference(&before, &after)), vec!["../2022-05-31"]);
+        assert_eq!(iso(difference(&after, &before)), vec!["2022-07-01/.."]);
+    }
+
+    #[test]
+    fn test_union_of_touching_intervals() {
+        let january = Interval::closed_with_dates(date(2022, 1, 1), date(2022, 1, 31));
+        let february = Interval::closed_with_dates(date(2022, 2, 1), date(2022, 2, 28));
+        let march = Interval::closed_with_dates(date(2022, 3, 2), date(2022, 3, 31));
+
+        assert_eq!(
+            iso(union(&february, &january).unwrap()),
+            vec!["2022-01-01/2022-02-28"]
+        );
+        assert_eq!(
+            iso(union(&march, &february).unwrap()),
+            vec!["2022-02-01/2022-02-28", "2022-03-02/2022-03-31"]
+        );
+        assert!(intersect(&january, &february).is_none());
+    }
+
+    #[test]
+    fn test_difference_splits_around_the_middle() {
+        let year = Interval::closed_with_dates(date(2022, 1, 1), date(2022, 12, 31));
+        let summer = Interval::closed_with_dates(date(2022, 6, 1), date(2022, 8, 31));
+
+        assert_eq!(
+            iso(difference(&year, &summer)),
+            vec!["2022-01-01/2022-05-31", "2022-09-01/2022-12-31"]
+        );
+        assert!(difference(&summer, &year).is_empty());
+        assert_eq!(iso(difference(&summer, &summer)), Vec::<String>::new());
+        assert_eq!(
+            iso(difference(&summer, &Interval::open_start(date(2022, 1, 1)))),
+            vec!["2022-06-01/2022-08-31"]
+        );
+    }
+}
@@ -1,64 +1,258 @@
+use std::str::FromStr;
+
 use chrono::NaiveDate;
+#[cfg(feature = "serde")]
 use nom::{
     branch::alt,
-    bytes::complete::tag,
+    error::{Error, ErrorKind},
     sequence::{preceded, terminated},
+    Err,
+};
+use nom::{
+    bytes::complete::tag,
+    combinator::{all_consuming, map_opt},
     IResult,
 };
 
-use crate::{duration::parse::parse_relative_duration, parser::take_n_digits};
+#[cfg(feature = "serde")]
+use crate::{duration::parse::parse_relative_duration, trace};
+use crate::{error::CalendsError, parser::take_n_digits, RelativeDuration};
 
-use super::{ClosedInterval, OpenEndInterval, OpenStartInterval};
+use super::{bound::Bound, ClosedInterval, Interval};
+#[cfg(feature = "serde")]
+use super::{OpenEndInterval, OpenStartInterval};
 
 pub fn parse_date(i: &[u8]) -> IResult<&[u8], NaiveDate> {
-    let (i, year) = take_n_digits(i, 4)?;
-    let (i, _) = tag(b"-")(i)?;
-    let (i, month) = take_n_digits(i, 2)?;
-    let (i, _) = tag(b"-")(i)?;
-    let (i, day) = take_n_digits(i, 2)?;
-
-    Ok((i, NaiveDate::from_ymd(year.try_into().unwrap(), month, day)))
+    map_opt(
+        |i| {
+            let (i, year) = take_n_digits(i, 4)?;
+            let (i, _) = tag(b"-")(i)?;
+            let (i, month) = take_n_digits(i, 2)?;
+            let (i, _) = tag(b"-")(i)?;
+            let (i, day) = take_n_digits(i, 2)?;
+            Ok((i, (year, month, day)))
+        },
+        |(year, month, day)| NaiveDate::from_ymd_opt(year as i32, month, day),
+    )(i)
 }
 
+#[cfg(feature = "serde")]
 fn parse_start_and_duration(i: &[u8]) -> IResult<&[u8], ClosedInterval> {
     let (i, date) = parse_date(i)?;
     let (i, _) = tag(b"/")(i)?;
     let (i, duration) = parse_relative_duration(i)?;
 
-    Ok((i, ClosedInterval::from_start(date, duration)))
+    trace::trace!(%date, duration = %duration.iso8601(), "parsed interval as start and duration");
+    validated(i, ClosedInterval::from_start_unchecked(date, duration))
 }
 
+#[cfg(feature = "serde")]
 fn parse_start_and_end(i: &[u8]) -> IResult<&[u8], ClosedInterval> {
     let (i, start) = parse_date(i)?;
     let (i, _) = tag(b"/")(i)?;
     let (i, end) = parse_date(i)?;
 
-    Ok((i, ClosedInterval::with_dates(start, end)))
+    trace::trace!(%start, %end, "parsed interval as start and end");
+    validated(i, ClosedInterval::with_dates_unchecked(start, end))
+}
+
+#[cfg(feature = "serde")]
+/// Reject intervals which end before they start
+fn validated(i: &[u8], interval: ClosedInterval) -> IResult<&[u8], ClosedInterval> {
+    match interval.validate() {
+        Ok(()) => Ok((i, interval)),
+        Err(_e) => {
+            trace::debug!(error = %_e, "rejected interval");
+            Err(Err::Error(Error::new(i, ErrorKind::Verify)))
+        }
+    }
 }
 
+#[cfg(feature = "serde")]
 pub fn parse_interval(i: &[u8]) -> IResult<&[u8], ClosedInterval> {
     alt((parse_start_and_end, parse_start_and_duration))(i)
 }
 
+#[cfg(feature = "serde")]
 pub fn parse_open_start_interval(i: &[u8]) -> IResult<&[u8], OpenStartInterval> {
     let (i, date) = preceded(tag("../"), parse_date)(i)?;
     Ok((i, OpenStartInterval::new(date)))
 }
 
+#[cfg(feature = "serde")]
 pub fn parse_open_end_interval(i: &[u8]) -> IResult<&[u8], OpenEndInterval> {
-    let (i, date) = terminated(parse_date, tag("../"))(i)?;
+    let (i, date) = terminated(parse_date, tag("/.."))(i)?;
     Ok((i, OpenEndInterval::new(date)))
 }
 
+/// One side of an interval written as `start/end`
+enum Part {
+    Date(NaiveDate),
+    Duration(RelativeDuration),
+    Open,
+}
+
+impl FromStr for Part {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == ".." {
+            return Ok(Part::Open);
+        }
+        all_consuming(parse_date)(s.as_bytes())
+            .map(|(_, date)| Part::Date(date))
+            .or_else(|_| s.parse().map(Part::Duration))
+            .map_err(|_| ())
+    }
+}
+
+/// Parse an ISO8601-2:2019 interval
+///
+/// The interval can be written as a start and end, a start and duration or a duration and end
+/// such as `2022-01-01/2022-03-31`, `2022-01-01/P1M` or `P1M/2022-03-31`. Either date can be `..`
+/// for an open interval, e.g. `2022-01-01/..`.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{CalendsError, Interval, IntervalLike};
+///
+/// let interval: Interval = "2022-01-01/P1M".parse()?;
+/// assert_eq!(interval.end_opt(), NaiveDate::from_ymd_opt(2022, 2, 1));
+///
+/// let open: Interval = "../2022-03-31".parse()?;
+/// assert_eq!(open.start_opt(), None);
+///
+/// assert_eq!("../..".parse::<Interval>(), Err(CalendsError::UnboundedInterval));
+/// assert!(matches!(
+///     "2022-03-31/2022-01-01".parse::<Interval>(),
+///     Err(CalendsError::ReversedInterval { .. })
+/// ));
+/// # Ok::<(), CalendsError>(())
+/// ```
+impl FromStr for Interval {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CalendsError::InvalidInterval(s.to_string());
+        let (start, end) = s.split_once('/').ok_or_else(invalid)?;
+        let (start, end) = (
+            start.parse().map_err(|_| invalid())?,
+            end.parse().map_err(|_| invalid())?,
+        );
+
+        let bound = |part: &Part| match part {
+            Part::Date(date) => Some(Bound::Included(*date)),
+            Part::Open => Some(Bound::Unbounded),
+            Part::Duration(_) => None,
+        };
+        let closed = match (&start, &end) {
+            (Part::Date(start), Part::Duration(duration)) => {
+                ClosedInterval::from_start_unchecked(*start, *duration)
+            }
+            (Part::Duration(duration), Part::Date(end)) => {
+                ClosedInterval::with_dates_unchecked(*end + -*duration, *end)
+            }
+            _ => {
+                return match (bound(&start), bound(&end)) {
+                    (Some(start), Some(end)) => Interval::new(start, end),
+                    _ => Err(invalid()),
+                }
+            }
+        };
+        closed.validate()?;
+        Ok(Interval::Closed(closed))
+    }
+}
+
+/// Parse an ISO8601-2:2019 interval with a start and an end, see [Interval]'s implementation
+impl FromStr for ClosedInterval {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse()? {
+            Interval::Closed(interval) => Ok(interval),
+            _ => Err(CalendsError::InvalidInterval(s.to_string())),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::IntervalLike;
+    use crate::{testing::date, IntervalLike};
 
     use super::*;
 
+    #[cfg(feature = "serde")]
     #[test]
     fn test_parse_interval() {
         let (_i, interval) = parse_interval("2022-01-01/2023-01-01".as_bytes()).unwrap();
-        assert_eq!(interval.end_opt().unwrap(), NaiveDate::from_ymd(2023, 1, 1))
+        assert_eq!(interval.end_opt().unwrap(), date(2023, 1, 1))
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_parse_open_interval() {
+        let (_, interval) = parse_open_end_interval("2022-01-01/..".as_bytes()).unwrap();
+        assert_eq!(interval.iso8601(), "2022-01-01/..");
+        assert!(parse_date("2022-02-30".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let day = |m, d| date(2022, m, d);
+        let parse = |s: &str| s.parse::<Interval>().map(|i| (i.start_opt(), i.end_opt()));
+
+        assert_eq!(
+            parse("2022-01-01/2022-03-31"),
+            Ok((Some(day(1, 1)), Some(day(3, 31))))
+        );
+        assert_eq!(
+            parse("2022-01-01/P3M-1D"),
+            Ok((Some(day(1, 1)), Some(day(3, 31))))
+        );
+        assert_eq!(
+            parse("P1M/2022-03-30"),
+            Ok((Some(day(2, 28)), Some(day(3, 30))))
+        );
+        assert_eq!(parse("2022-01-01/.."), Ok((Some(day(1, 1)), None)));
+        assert_eq!(parse("../2022-01-01"), Ok((None, Some(day(1, 1)))));
+        assert_eq!(
+            parse("P-1D/2022-01-01"),
+            Err(CalendsError::ReversedInterval {
+                start: day(1, 2),
+                end: day(1, 1)
+            })
+        );
+
+        for s in [
+            "",
+            "2022-01-01",
+            "2022-01-01/",
+            "P1M/P1M",
+            "../P1M",
+            "2022-01-01/2022-02-30",
+            "2022-01-01/2022-03-31/",
+            "2022-01-01/2022-03-31x",
+        ] {
+            assert_eq!(
+                s.parse::<Interval>(),
+                Err(CalendsError::InvalidInterval(s.to_string())),
+                "{}",
+                s
+            );
+        }
+        assert_eq!(
+            "2022-01-01/..".parse::<ClosedInterval>(),
+            Err(CalendsError::InvalidInterval("2022-01-01/..".to_string()))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_parse_reversed_interval() {
+        assert!(parse_interval("2023-01-01/2022-01-01".as_bytes()).is_err());
+        assert!(parse_interval("2023-01-01/P-1D".as_bytes()).is_err());
     }
 }
@@ -0,0 +1,176 @@
+//! Repeating intervals
+//!
+//! ISO8601-2:2019 writes a series of back to back intervals as a number of repetitions followed by
+//! the first interval, e.g. `R5/2022-01-01/P1M` for five months from January. [RepeatingInterval]
+//! parses these and iterates the intervals in the same way as iterating a [ClosedInterval].
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::CalendsError;
+
+use super::{like::IntervalLike, ClosedInterval};
+
+/// An interval repeated a number of times, or without end
+///
+/// # Examples
+///
+/// ```
+/// # use calends::{interval::RepeatingInterval, IntervalLike};
+///
+/// let quarters: RepeatingInterval = "R4/2022-01-01/P3M".parse()?;
+/// assert_eq!(quarters.repetitions(), Some(4));
+/// assert_eq!(
+///     quarters.iter().map(|i| i.iso8601()).collect::<Vec<_>>(),
+///     vec![
+///         "2022-01-01/2022-04-01",
+///         "2022-04-01/2022-07-01",
+///         "2022-07-01/2022-10-01",
+///         "2022-10-01/2023-01-01",
+///     ]
+/// );
+///
+/// let forever: RepeatingInterval = "R/2022-01-01/P1W".parse()?;
+/// assert_eq!(forever.iter().nth(52).unwrap().iso8601(), "2022-12-31/2023-01-07");
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepeatingInterval {
+    repetitions: Option<u32>,
+    interval: ClosedInterval,
+}
+
+impl RepeatingInterval {
+    /// Repeat the interval a number of times, [None] to repeat it without end
+    pub fn new(interval: ClosedInterval, repetitions: Option<u32>) -> Self {
+        RepeatingInterval {
+            repetitions,
+            interval,
+        }
+    }
+
+    /// Number of intervals in the series, [None] when it has no end
+    pub fn repetitions(&self) -> Option<u32> {
+        self.repetitions
+    }
+
+    /// The first interval of the series
+    pub fn interval(&self) -> &ClosedInterval {
+        &self.interval
+    }
+
+    /// Each interval of the series in turn, each starting where the previous one ended
+    pub fn iter(&self) -> std::iter::Take<ClosedInterval> {
+        let repetitions = self.repetitions.map_or(usize::MAX, |n| n as usize);
+        self.interval.clone().take(repetitions)
+    }
+}
+
+/// Format as the number of repetitions with the start and duration of the first interval
+impl Display for RepeatingInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "R")?;
+        if let Some(repetitions) = self.repetitions {
+            write!(f, "{}", repetitions)?;
+        }
+        write!(
+            f,
+            "/{}/{}",
+            self.interval.start_opt().unwrap(),
+            self.interval.duration().unwrap().iso8601()
+        )
+    }
+}
+
+/// Parse `Rn/` followed by a closed interval, the number of repetitions is optional
+///
+/// Repetitions always run forwards from the start of the interval. An interval written as a
+/// duration and end, which ISO8601-2:2019 repeats backwards from the end, is rejected.
+impl FromStr for RepeatingInterval {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CalendsError::InvalidInterval(s.to_string());
+        let (repetitions, interval) = s
+            .strip_prefix('R')
+            .and_then(|s| s.split_once('/'))
+            .ok_or_else(invalid)?;
+
+        let repetitions = match repetitions {
+            "" => None,
+            n if n.bytes().all(|b| b.is_ascii_digit()) => Some(n.parse().map_err(|_| invalid())?),
+            _ => return Err(invalid()),
+        };
+        if interval.starts_with('P') {
+            return Err(invalid());
+        }
+
+        let interval = interval.parse().map_err(|e| match e {
+            CalendsError::InvalidInterval(_) => invalid(),
+            e => e,
+        })?;
+        Ok(RepeatingInterval::new(interval, repetitions))
+    }
+}
+
+/// Serialize in the ISO8601-2:2019 form, e.g. `"R5/2022-01-01/P1M"`
+#[cfg(feature = "serde")]
+impl Serialize for RepeatingInterval {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for RepeatingInterval {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for s in [
+            "R5/2022-01-01/P1M",
+            "R/2022-01-31/P1M",
+            "R0/2022-01-01/P1W2D",
+        ] {
+            let repeating: RepeatingInterval = s.parse().unwrap();
+            assert_eq!(repeating.to_string(), s);
+        }
+
+        let repeating: RepeatingInterval = "R2/2022-01-01/2022-01-10".parse().unwrap();
+        assert_eq!(repeating.to_string(), "R2/2022-01-01/P9D");
+        assert_eq!(repeating.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_invalid() {
+        for s in [
+            "2022-01-01/P1M",
+            "R-1/2022-01-01/P1M",
+            "R+1/2022-01-01/P1M",
+            "R1/P1M/2022-01-31",
+            "R1/2022-01-01/..",
+            "R99999999999/2022-01-01/P1M",
+        ] {
+            assert_eq!(
+                s.parse::<RepeatingInterval>(),
+                Err(CalendsError::InvalidInterval(s.to_string())),
+                "{}",
+                s
+            );
+        }
+        assert!(matches!(
+            "R1/2022-01-31/2022-01-01".parse::<RepeatingInterval>(),
+            Err(CalendsError::ReversedInterval { .. })
+        ));
+    }
+}
@@ -0,0 +1,496 @@
+use std::collections::{btree_map, BTreeMap};
+
+use chrono::NaiveDate;
+
+use super::{
+    bound::Bound,
+    marker::{End, Start},
+    ClosedInterval, Interval, IntervalLike,
+};
+
+/// Dates covered by any number of closed intervals
+///
+/// Overlapping and adjacent intervals are merged as they are inserted, so the set always holds
+/// the fewest intervals which cover its dates.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::interval::{ClosedInterval, IntervalSet};
+/// # use calends::IntervalLike;
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 1, d).unwrap();
+///
+/// let mut set = IntervalSet::new();
+/// set.insert(&ClosedInterval::with_dates(date(1), date(3)));
+/// set.insert(&ClosedInterval::with_dates(date(4), date(5)));
+/// set.insert(&ClosedInterval::with_dates(date(10), date(10)));
+///
+/// assert!(set.contains(date(5)));
+/// assert!(!set.contains(date(6)));
+/// assert_eq!(
+///     set.iter().map(|i| i.iso8601()).collect::<Vec<_>>(),
+///     vec!["2022-01-01/2022-01-05", "2022-01-10/2022-01-10"]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    /// First and last date of each interval keyed by the first date
+    ranges: BTreeMap<NaiveDate, NaiveDate>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet::default()
+    }
+
+    /// Add every date of the interval to the set
+    ///
+    /// An interval ending before it starts, which only the unchecked constructors such as
+    /// [ClosedInterval::with_dates_unchecked] can create, covers no dates and is ignored.
+    pub fn insert<I: Start + End>(&mut self, interval: &I) {
+        self.insert_range(interval.start(), interval.end());
+    }
+
+    /// Remove every date of the interval from the set, splitting any interval it falls within
+    ///
+    /// As with [insert](IntervalSet::insert) an interval ending before it starts is ignored.
+    pub fn remove<I: Start + End>(&mut self, interval: &I) {
+        self.remove_range(interval.start(), interval.end());
+    }
+
+    /// Dates within the interval which are not in the set
+    ///
+    /// A set of closed intervals cannot hold the unbounded dates either side of its intervals, so
+    /// the complement is taken within the interval.
+    pub fn complement<I: Start + End>(&self, within: &I) -> IntervalSet {
+        let mut complement = IntervalSet::new();
+        complement.insert(within);
+        complement.difference(self)
+    }
+
+    /// Spans within the bounding interval which no interval in the set covers, in ascending order
+    ///
+    /// Unlike [complement](IntervalSet::complement) the bounding interval can be unbounded, in
+    /// which case the first or last gap is unbounded as well. An interval unbounded on both sides
+    /// with an empty set has no gap which can be represented and gives none.
+    pub fn gaps<B: IntervalLike + ?Sized>(&self, bounding: &B) -> Vec<Interval> {
+        let bound = |date: Option<NaiveDate>| date.map_or(Bound::Unbounded, Bound::Included);
+        let (from, until) = (bounding.start_opt(), bounding.end_opt());
+
+        let mut gaps = Vec::new();
+        // the first date not yet known to be covered, None before any date
+        let mut cursor = from;
+        for (start, end) in &self.ranges {
+            if until.is_some_and(|until| *start > until) {
+                break;
+            }
+            if cursor.is_some_and(|cursor| *end < cursor) {
+                continue;
+            }
+            if cursor.is_none_or(|cursor| cursor < *start) {
+                gaps.extend(Interval::new(bound(cursor), bound(start.pred_opt())).ok());
+            }
+            match end.succ_opt() {
+                Some(next) => cursor = Some(next),
+                None => return gaps,
+            }
+        }
+        if cursor.is_none_or(|cursor| until.is_none_or(|until| cursor <= until)) {
+            gaps.extend(Interval::new(bound(cursor), bound(until)).ok());
+        }
+        gaps
+    }
+
+    /// Dates in either set
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        for (start, end) in &other.ranges {
+            union.insert_range(*start, *end);
+        }
+        union
+    }
+
+    /// Dates in both sets
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intersection = IntervalSet::new();
+        let mut a = self.ranges.iter().map(|(s, e)| (*s, *e)).peekable();
+        let mut b = other.ranges.iter().map(|(s, e)| (*s, *e)).peekable();
+
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) = (a.peek(), b.peek()) {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                intersection.ranges.insert(start, end);
+            }
+            // the interval ending first cannot overlap anything after the other one
+            match a_end <= b_end {
+                true => a.next(),
+                false => b.next(),
+            };
+        }
+        intersection
+    }
+
+    /// Dates in this set which are not in the other
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut difference = self.clone();
+        for (start, end) in &other.ranges {
+            difference.remove_range(*start, *end);
+        }
+        difference
+    }
+
+    /// Whether any interval in the set includes the date
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.ranges
+            .range(..=date)
+            .next_back()
+            .is_some_and(|(_, end)| date <= *end)
+    }
+
+    /// The interval in the set which includes the date
+    pub fn containing(&self, date: NaiveDate) -> Option<ClosedInterval> {
+        self.ranges
+            .range(..=date)
+            .next_back()
+            .filter(|(_, end)| date <= **end)
+            .map(|(start, end)| ClosedInterval::with_dates_unchecked(*start, *end))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of disjoint intervals in the set
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The disjoint intervals in ascending order
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.ranges.iter())
+    }
+
+    fn insert_range(&mut self, mut start: NaiveDate, mut end: NaiveDate) {
+        if end < start {
+            return;
+        }
+        // absorb every interval which overlaps or touches the new one
+        let touching: Vec<NaiveDate> = self
+            .ranges
+            .range(..=end.succ_opt().unwrap_or(end))
+            .rev()
+            .take_while(|(_, e)| e.succ_opt().unwrap_or(**e) >= start)
+            .map(|(s, _)| *s)
+            .collect();
+        for s in touching {
+            let e = self.ranges.remove(&s).unwrap();
+            start = start.min(s);
+            end = end.max(e);
+        }
+
+        self.ranges.insert(start, end);
+    }
+
+    fn remove_range(&mut self, start: NaiveDate, end: NaiveDate) {
+        if end < start {
+            return;
+        }
+        let overlapping: Vec<(NaiveDate, NaiveDate)> = self
+            .ranges
+            .range(..=end)
+            .rev()
+            .take_while(|(_, e)| **e >= start)
+            .map(|(s, e)| (*s, *e))
+            .collect();
+
+        for (s, e) in overlapping {
+            self.ranges.remove(&s);
+            // keep whatever is left either side of the removed dates
+            if let Some(before) = start.pred_opt().filter(|before| s <= *before) {
+                self.ranges.insert(s, before);
+            }
+            if let Some(after) = end.succ_opt().filter(|after| *after <= e) {
+                self.ranges.insert(after, e);
+            }
+        }
+    }
+}
+
+/// Iterator over the disjoint intervals of an [IntervalSet] in ascending order
+#[derive(Debug, Clone)]
+pub struct Iter<'a>(btree_map::Iter<'a, NaiveDate, NaiveDate>);
+
+impl Iterator for Iter<'_> {
+    type Item = ClosedInterval;
+
+    fn next(&mut self) -> Option<ClosedInterval> {
+        self.0
+            .next()
+            .map(|(start, end)| ClosedInterval::with_dates_unchecked(*start, *end))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<ClosedInterval> {
+        self.0
+            .next_back()
+            .map(|(start, end)| ClosedInterval::with_dates_unchecked(*start, *end))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl<'a> IntoIterator for &'a IntervalSet {
+    type Item = ClosedInterval;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// Serialize the disjoint intervals in ascending order, e.g. `["2022-01-01/2022-01-05"]`
+#[cfg(feature = "serde")]
+impl serde::Serialize for IntervalSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// Deserialize any list of intervals, overlapping and touching intervals are merged
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IntervalSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let intervals = Vec::<ClosedInterval>::deserialize(deserializer)?;
+        let mut set = IntervalSet::new();
+        for interval in &intervals {
+            set.insert(interval);
+        }
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    fn ranges(set: &IntervalSet) -> Vec<(NaiveDate, NaiveDate)> {
+        set.ranges.iter().map(|(s, e)| (*s, *e)).collect()
+    }
+
+    #[test]
+    fn test_insert_merges_overlapping_intervals() {
+        let mut set = IntervalSet::new();
+        for (start, end) in [(10, 12), (1, 2), (5, 6), (14, 20)] {
+            set.insert(&ClosedInterval::with_dates(
+                date(2022, 1, start),
+                date(2022, 1, end),
+            ));
+        }
+        assert_eq!(set.len(), 4);
+
+        // spans the gap between 5..=6 and 10..=12 and touches 14..=20
+        set.insert(&ClosedInterval::with_dates(
+            date(2022, 1, 6),
+            date(2022, 1, 13),
+        ));
+        assert_eq!(
+            ranges(&set),
+            vec![
+                (date(2022, 1, 1), date(2022, 1, 2)),
+                (date(2022, 1, 5), date(2022, 1, 20))
+            ]
+        );
+
+        set.insert(&ClosedInterval::with_dates(
+            date(2022, 1, 7),
+            date(2022, 1, 8),
+        ));
+        assert_eq!(set.len(), 2);
+        assert!(!set.contains(date(2022, 1, 3)));
+        assert!(set.contains(date(2022, 1, 20)));
+        assert!(!set.contains(date(2022, 1, 21)));
+    }
+
+    fn set(ranges: &[(u32, u32)]) -> IntervalSet {
+        let mut set = IntervalSet::new();
+        for (start, end) in ranges {
+            set.insert(&ClosedInterval::with_dates(
+                date(2022, 1, *start),
+                date(2022, 1, *end),
+            ));
+        }
+        set
+    }
+
+    #[test]
+    fn test_remove_splits_intervals() {
+        let mut set = set(&[(1, 10), (15, 20)]);
+        set.remove(&ClosedInterval::with_dates(
+            date(2022, 1, 3),
+            date(2022, 1, 4),
+        ));
+        assert_eq!(
+            ranges(&set),
+            vec![
+                (date(2022, 1, 1), date(2022, 1, 2)),
+                (date(2022, 1, 5), date(2022, 1, 10)),
+                (date(2022, 1, 15), date(2022, 1, 20))
+            ]
+        );
+
+        set.remove(&ClosedInterval::with_dates(
+            date(2022, 1, 10),
+            date(2022, 1, 15),
+        ));
+        assert_eq!(
+            ranges(&set),
+            vec![
+                (date(2022, 1, 1), date(2022, 1, 2)),
+                (date(2022, 1, 5), date(2022, 1, 9)),
+                (date(2022, 1, 16), date(2022, 1, 20))
+            ]
+        );
+
+        set.remove(&ClosedInterval::with_dates(
+            date(2022, 1, 1),
+            date(2022, 1, 31),
+        ));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_reversed_intervals_are_ignored() {
+        let mut set = set(&[(6, 7)]);
+        let reversed = ClosedInterval::with_dates_unchecked(date(2022, 1, 10), date(2022, 1, 5));
+
+        set.insert(&reversed);
+        assert_eq!(ranges(&set), vec![(date(2022, 1, 6), date(2022, 1, 7))]);
+        set.remove(&reversed);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = set(&[(1, 5), (10, 15), (20, 25)]);
+        let b = set(&[(4, 11), (15, 15), (26, 28)]);
+
+        assert_eq!(
+            ranges(&a.union(&b)),
+            vec![
+                (date(2022, 1, 1), date(2022, 1, 15)),
+                (date(2022, 1, 20), date(2022, 1, 28))
+            ]
+        );
+        assert_eq!(
+            ranges(&a.intersection(&b)),
+            vec![
+                (date(2022, 1, 4), date(2022, 1, 5)),
+                (date(2022, 1, 10), date(2022, 1, 11)),
+                (date(2022, 1, 15), date(2022, 1, 15))
+            ]
+        );
+        assert_eq!(
+            ranges(&a.difference(&b)),
+            vec![
+                (date(2022, 1, 1), date(2022, 1, 3)),
+                (date(2022, 1, 12), date(2022, 1, 14)),
+                (date(2022, 1, 20), date(2022, 1, 25))
+            ]
+        );
+        assert_eq!(a.intersection(&IntervalSet::new()), IntervalSet::new());
+        assert_eq!(a.intersection(&b), b.intersection(&a));
+    }
+
+    #[test]
+    fn test_complement_within_interval() {
+        let blackout = set(&[(1, 2), (10, 12), (30, 31)]);
+        let january = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 31));
+
+        let available = blackout.complement(&january);
+        assert_eq!(
+            ranges(&available),
+            vec![
+                (date(2022, 1, 3), date(2022, 1, 9)),
+                (date(2022, 1, 13), date(2022, 1, 29))
+            ]
+        );
+        assert_eq!(available.union(&blackout), set(&[(1, 31)]));
+        assert!(available.intersection(&blackout).is_empty());
+    }
+
+    #[test]
+    fn test_gaps() {
+        let bookings = set(&[(3, 5), (10, 12), (20, 25)]);
+        let iso = |gaps: Vec<Interval>| gaps.iter().map(|g| g.iso8601()).collect::<Vec<_>>();
+
+        assert_eq!(
+            iso(bookings.gaps(&ClosedInterval::with_dates(
+                date(2022, 1, 4),
+                date(2022, 1, 21)
+            ))),
+            vec!["2022-01-06/2022-01-09", "2022-01-13/2022-01-19"]
+        );
+        assert_eq!(
+            iso(bookings.gaps(&ClosedInterval::with_dates(
+                date(2022, 1, 1),
+                date(2022, 1, 31)
+            ))),
+            vec![
+                "2022-01-01/2022-01-02",
+                "2022-01-06/2022-01-09",
+                "2022-01-13/2022-01-19",
+                "2022-01-26/2022-01-31"
+            ]
+        );
+        assert_eq!(
+            iso(bookings.gaps(&Interval::open_start(date(2022, 1, 11)))),
+            vec!["../2022-01-02", "2022-01-06/2022-01-09"]
+        );
+        assert_eq!(
+            iso(bookings.gaps(&Interval::open_end(date(2022, 1, 24)))),
+            vec!["2022-01-26/.."]
+        );
+        assert!(bookings
+            .gaps(&ClosedInterval::with_dates(
+                date(2022, 1, 10),
+                date(2022, 1, 12)
+            ))
+            .is_empty());
+        assert_eq!(
+            iso(IntervalSet::new().gaps(&ClosedInterval::with_dates(
+                date(2022, 1, 1),
+                date(2022, 1, 2)
+            ))),
+            vec!["2022-01-01/2022-01-02"]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let set: IntervalSet = serde_json::from_str(
+            r#"["2022-01-10/2022-01-12", "2022-01-01/2022-01-05", "2022-01-04/2022-01-06"]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ranges(&set),
+            vec![
+                (date(2022, 1, 1), date(2022, 1, 6)),
+                (date(2022, 1, 10), date(2022, 1, 12))
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&set).unwrap(),
+            r#"["2022-01-01/2022-01-06","2022-01-10/2022-01-12"]"#
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Splitting an interval into calendar periods
+//!
+//! Proration needs an interval broken into the months, quarters or weeks it touches. [Split]
+//! yields one piece per [Period] overlapping the interval, with the first and last pieces cut
+//! to the interval when it does not start or end on a period boundary. A [SplitPolicy] decides
+//! whether those partial pieces are kept.
+use chrono::NaiveDate;
+
+use crate::{
+    grain::Grain,
+    period::{FiscalYear, Period},
+};
+
+use super::{ClosedInterval, Interval};
+
+/// Whether pieces covering only part of a period are kept
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SplitPolicy {
+    /// Keep the partial first and last pieces
+    #[default]
+    IncludePartial,
+    /// Only give pieces covering a whole period
+    WholeOnly,
+}
+
+/// Pieces of an interval, one per period of the grain in ascending order
+///
+/// Periods are aligned to calendar boundaries, weeks start on Monday. Splitting an interval with
+/// an unbounded end never finishes.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::grain::Grain;
+/// # use calends::interval::{split::SplitPolicy, ClosedInterval};
+/// # use calends::IntervalLike;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let contract = ClosedInterval::with_dates(date(1, 15), date(3, 10));
+///
+/// let pieces: Vec<String> = contract
+///     .split_by(Grain::Month, SplitPolicy::IncludePartial)
+///     .map(|piece| piece.iso8601())
+///     .collect();
+/// assert_eq!(
+///     pieces,
+///     vec!["2022-01-15/2022-01-31", "2022-02-01/2022-02-28", "2022-03-01/2022-03-10"]
+/// );
+///
+/// assert_eq!(contract.split_by(Grain::Month, SplitPolicy::WholeOnly).count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Split {
+    period: Period,
+    start: NaiveDate,
+    end: Option<NaiveDate>,
+    policy: SplitPolicy,
+}
+
+impl Split {
+    pub(crate) fn new(
+        start: NaiveDate,
+        end: Option<NaiveDate>,
+        grain: Grain,
+        policy: SplitPolicy,
+    ) -> Self {
+        Split {
+            period: Period::containing(start, grain, &FiscalYear::calendar()),
+            start,
+            end,
+            policy,
+        }
+    }
+}
+
+impl Iterator for Split {
+    type Item = Interval;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let period = self.period;
+            if self.end.is_some_and(|end| period.start() > end) {
+                return None;
+            }
+            self.period = period.succ();
+
+            let start = period.start().max(self.start);
+            let end = self.end.map_or(period.end(), |end| end.min(period.end()));
+            let partial = start != period.start() || end != period.end();
+            if !partial || self.policy == SplitPolicy::IncludePartial {
+                return Some(Interval::Closed(ClosedInterval::with_dates(start, end)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interval::base::IntervalError, testing::date, IntervalLike};
+
+    fn iso(split: impl Iterator<Item = Interval>) -> Vec<String> {
+        split.map(|piece| piece.iso8601()).collect()
+    }
+
+    #[test]
+    fn test_split_by_grain() {
+        let year = Interval::closed_with_dates(date(2022, 1, 1), date(2022, 12, 31));
+        assert_eq!(
+            iso(year
+                .split_by(Grain::Quarter, SplitPolicy::WholeOnly)
+                .unwrap()),
+            vec![
+                "2022-01-01/2022-03-31",
+                "2022-04-01/2022-06-30",
+                "2022-07-01/2022-09-30",
+                "2022-10-01/2022-12-31"
+            ]
+        );
+
+        // 2022-01-05 is a Wednesday
+        let fortnight = Interval::closed_with_dates(date(2022, 1, 5), date(2022, 1, 18));
+        assert_eq!(
+            iso(fortnight
+                .split_by(Grain::Week, SplitPolicy::IncludePartial)
+                .unwrap()),
+            vec![
+                "2022-01-05/2022-01-09",
+                "2022-01-10/2022-01-16",
+                "2022-01-17/2022-01-18"
+            ]
+        );
+        assert_eq!(
+            iso(fortnight
+                .split_by(Grain::Year, SplitPolicy::IncludePartial)
+                .unwrap()),
+            vec!["2022-01-05/2022-01-18"]
+        );
+        assert!(fortnight
+            .split_by(Grain::Year, SplitPolicy::WholeOnly)
+            .unwrap()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn test_split_unbounded() {
+        let open = Interval::open_end(date(2022, 11, 15));
+        assert_eq!(
+            iso(open
+                .split_by(Grain::Month, SplitPolicy::IncludePartial)
+                .unwrap()
+                .take(2)),
+            vec!["2022-11-15/2022-11-30", "2022-12-01/2022-12-31"]
+        );
+        assert!(matches!(
+            Interval::open_start(date(2022, 1, 1))
+                .split_by(Grain::Month, SplitPolicy::IncludePartial),
+            Err(IntervalError::NotIterable)
+        ));
+    }
+}
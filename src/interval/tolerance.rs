@@ -0,0 +1,130 @@
+//! Fuzzy comparison of intervals
+//!
+//! Reported periods rarely line up exactly with the expected ones, a statement may start a day
+//! late or a billing cycle end a day early. These checks widen the interval by a number of slack
+//! days on both sides before comparing, rather than each caller adjusting the dates by hand.
+//! Unbounded sides stay unbounded.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chrono::NaiveDate;
+//! # use calends::Interval;
+//! # use calends::interval::tolerance::{overlaps_with_tolerance, within_tolerance};
+//!
+//! let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+//! let january = Interval::closed_with_dates(date(1, 1), date(1, 31));
+//!
+//! assert!(within_tolerance(date(2, 1), &january, 1));
+//! assert!(!within_tolerance(date(2, 2), &january, 1));
+//!
+//! let march = Interval::closed_with_dates(date(3, 2), date(3, 31));
+//! let february = Interval::closed_with_dates(date(2, 1), date(2, 28));
+//! assert!(!overlaps_with_tolerance(&february, &march, 1));
+//! assert!(overlaps_with_tolerance(&february, &march, 2));
+//! ```
+use chrono::{Days, NaiveDate};
+
+use super::{bound::Bound, IntervalLike};
+
+/// Whether the date is within the interval or no more than `slack_days` either side of it
+pub fn within_tolerance<I: IntervalLike + ?Sized>(
+    date: NaiveDate,
+    interval: &I,
+    slack_days: u32,
+) -> bool {
+    let (start, end) = widened(interval, slack_days);
+    start.is_none_or(|start| start <= date) && end.is_none_or(|end| date <= end)
+}
+
+/// Whether the intervals overlap once either is widened by `slack_days` on both sides
+///
+/// With no slack this is [IntervalLike::overlaps], while intervals separated by a gap of up to
+/// `slack_days` days count as overlapping.
+pub fn overlaps_with_tolerance<A: IntervalLike + ?Sized, B: IntervalLike + ?Sized>(
+    a: &A,
+    b: &B,
+    slack_days: u32,
+) -> bool {
+    let (start, end) = widened(a, slack_days);
+    start.is_none_or(|start| b.end_opt().is_none_or(|end| start <= end))
+        && end.is_none_or(|end| b.start_opt().is_none_or(|start| start <= end))
+}
+
+/// The first and last dates of the widened interval, [None] for an unbounded side
+///
+/// Widening past the range of dates stops at the earliest or latest date.
+fn widened<I: IntervalLike + ?Sized>(
+    interval: &I,
+    slack_days: u32,
+) -> (Option<NaiveDate>, Option<NaiveDate>) {
+    let slack = Days::new(slack_days.into());
+    let start = match interval.bound_start() {
+        Bound::Included(start) => Some(start.checked_sub_days(slack).unwrap_or(NaiveDate::MIN)),
+        Bound::Unbounded => None,
+    };
+    let end = match interval.bound_end() {
+        Bound::Included(end) => Some(end.checked_add_days(slack).unwrap_or(NaiveDate::MAX)),
+        Bound::Unbounded => None,
+    };
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::{testing::date, Interval};
+
+    fn nth_day(day: i64) -> NaiveDate {
+        date(2022, 1, 1) + chrono::Duration::days(day)
+    }
+
+    fn interval(start: Option<u8>, end: Option<u8>) -> Interval {
+        let (start, end) = match (start, end) {
+            (Some(a), Some(b)) => (Some(a.min(b)), Some(a.max(b))),
+            (None, None) => (Some(0), None),
+            bounds => bounds,
+        };
+        let bound =
+            |day: Option<u8>| day.map_or(Bound::Unbounded, |d| Bound::Included(nth_day(d.into())));
+        Interval::new(bound(start), bound(end)).unwrap()
+    }
+
+    #[quickcheck]
+    fn test_no_slack_is_exact(
+        a: (Option<u8>, Option<u8>),
+        b: (Option<u8>, Option<u8>),
+        day: u8,
+    ) -> bool {
+        let (a, b) = (interval(a.0, a.1), interval(b.0, b.1));
+        within_tolerance(nth_day(day.into()), &a, 0) == a.within(nth_day(day.into()))
+            && overlaps_with_tolerance(&a, &b, 0) == a.overlaps(&b)
+    }
+
+    #[quickcheck]
+    fn test_symmetric(a: (Option<u8>, Option<u8>), b: (Option<u8>, Option<u8>), slack: u8) -> bool {
+        let (a, b) = (interval(a.0, a.1), interval(b.0, b.1));
+        overlaps_with_tolerance(&a, &b, slack.into())
+            == overlaps_with_tolerance(&b, &a, slack.into())
+    }
+
+    #[test]
+    fn test_unbounded() {
+        let open = Interval::open_end(nth_day(10));
+        assert!(within_tolerance(nth_day(8), &open, 2));
+        assert!(!within_tolerance(nth_day(7), &open, 2));
+        assert!(within_tolerance(NaiveDate::MAX, &open, 0));
+        assert!(within_tolerance(
+            NaiveDate::MIN,
+            &Interval::open_start(NaiveDate::MIN),
+            5
+        ));
+        assert!(within_tolerance(
+            NaiveDate::MIN,
+            &Interval::closed_with_dates(NaiveDate::MIN, NaiveDate::MIN),
+            5
+        ));
+    }
+}
@@ -0,0 +1,261 @@
+//! Interval tree for point and range queries
+//!
+//! An [IntervalTree] holds labeled intervals sorted by their start date, laid out as an implicit
+//! balanced binary tree where each node also records the latest end date below it. Queries can
+//! then skip every subtree which ends before the dates in question, finding the `k` overlapping
+//! intervals among `n` in `O(log n + k)` rather than scanning all of them.
+use chrono::NaiveDate;
+
+use super::{
+    marker::{End, Start},
+    Labeled,
+};
+
+/// Labeled closed intervals indexed for overlap queries
+///
+/// The tree is built in one go from an iterator, [insert](IntervalTree::insert) keeps it ordered
+/// but rebuilds the index so bulk loads should collect instead.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::interval::{tree::IntervalTree, ClosedInterval, Labeled};
+///
+/// let date = |d| NaiveDate::from_ymd_opt(2022, 3, d).unwrap();
+/// let bookings: IntervalTree<ClosedInterval, &str> = [
+///     Labeled::new(ClosedInterval::with_dates(date(1), date(10)), "room 4"),
+///     Labeled::new(ClosedInterval::with_dates(date(5), date(6)), "room 2"),
+///     Labeled::new(ClosedInterval::with_dates(date(12), date(15)), "room 4"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let in_use = bookings.query_point(date(6));
+/// assert_eq!(in_use.iter().map(|b| *b.label()).collect::<Vec<_>>(), vec!["room 4", "room 2"]);
+///
+/// let overlapping = bookings.query_range(&ClosedInterval::with_dates(date(7), date(12)));
+/// assert_eq!(overlapping.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntervalTree<I, T = ()> {
+    entries: Vec<Labeled<I, T>>,
+    /// Start and end of each entry, and the latest end within the subtree rooted at it
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    start: NaiveDate,
+    end: NaiveDate,
+    max_end: NaiveDate,
+}
+
+impl<I: Start + End, T> IntervalTree<I, T> {
+    pub fn new() -> Self {
+        IntervalTree {
+            entries: Vec::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Add an interval, this rebuilds the index in linear time
+    pub fn insert(&mut self, entry: Labeled<I, T>) {
+        let start = entry.interval().start();
+        let i = self
+            .entries
+            .partition_point(|e| e.interval().start() <= start);
+        self.entries.insert(i, entry);
+        self.index();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every entry in order of their start dates
+    pub fn iter(&self) -> impl Iterator<Item = &Labeled<I, T>> {
+        self.entries.iter()
+    }
+
+    /// Entries including the date, in order of their start dates
+    pub fn query_point(&self, date: NaiveDate) -> Vec<&Labeled<I, T>> {
+        self.query(date, date)
+    }
+
+    /// Entries sharing at least one date with the interval, in order of their start dates
+    pub fn query_range<Q: Start + End>(&self, interval: &Q) -> Vec<&Labeled<I, T>> {
+        self.query(interval.start(), interval.end())
+    }
+
+    fn query(&self, start: NaiveDate, end: NaiveDate) -> Vec<&Labeled<I, T>> {
+        let mut found = Vec::new();
+        self.search(0, self.nodes.len(), start, end, &mut found);
+        found.into_iter().map(|i| &self.entries[i]).collect()
+    }
+
+    /// Collect the overlapping entries of the subtree over `lo..hi` in order
+    fn search(
+        &self,
+        lo: usize,
+        hi: usize,
+        start: NaiveDate,
+        end: NaiveDate,
+        found: &mut Vec<usize>,
+    ) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let node = self.nodes[mid];
+        // nothing below this node ends late enough to overlap
+        if node.max_end < start {
+            return;
+        }
+
+        self.search(lo, mid, start, end, found);
+        // everything from here on starts too late to overlap
+        if node.start > end {
+            return;
+        }
+        if node.end >= start {
+            found.push(mid);
+        }
+        self.search(mid + 1, hi, start, end, found);
+    }
+
+    fn index(&mut self) {
+        self.nodes = self
+            .entries
+            .iter()
+            .map(|e| {
+                let (start, end) = (e.interval().start(), e.interval().end());
+                Node {
+                    start,
+                    end,
+                    max_end: end,
+                }
+            })
+            .collect();
+        augment(&mut self.nodes, 0, self.entries.len());
+    }
+}
+
+/// Fill in the latest end of the subtree over `lo..hi`, returning it
+fn augment(nodes: &mut [Node], lo: usize, hi: usize) -> Option<NaiveDate> {
+    if lo >= hi {
+        return None;
+    }
+    let mid = lo + (hi - lo) / 2;
+    let left = augment(nodes, lo, mid);
+    let right = augment(nodes, mid + 1, hi);
+    let max_end = [left, right]
+        .into_iter()
+        .flatten()
+        .fold(nodes[mid].end, NaiveDate::max);
+    nodes[mid].max_end = max_end;
+    Some(max_end)
+}
+
+impl<I: Start + End, T> Default for IntervalTree<I, T> {
+    fn default() -> Self {
+        IntervalTree::new()
+    }
+}
+
+impl<I: Start + End, T> FromIterator<Labeled<I, T>> for IntervalTree<I, T> {
+    fn from_iter<It: IntoIterator<Item = Labeled<I, T>>>(iter: It) -> Self {
+        let mut entries: Vec<Labeled<I, T>> = iter.into_iter().collect();
+        // stable so entries starting on the same date keep the order they were given in
+        entries.sort_by_key(|e| e.interval().start());
+        let mut tree = IntervalTree {
+            entries,
+            nodes: Vec::new(),
+        };
+        tree.index();
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interval::ClosedInterval, testing::date};
+    use quickcheck::{Arbitrary, Gen};
+    use quickcheck_macros::quickcheck;
+
+    fn day(n: u8) -> NaiveDate {
+        date(2022, 1, 1) + chrono::Duration::days(n.into())
+    }
+
+    #[derive(Clone, Debug)]
+    struct Span(u8, u8);
+
+    impl Arbitrary for Span {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let (a, b) = (u8::arbitrary(g) % 60, u8::arbitrary(g) % 60);
+            Span(a.min(b), a.max(b))
+        }
+    }
+
+    impl Span {
+        fn interval(&self) -> ClosedInterval {
+            ClosedInterval::with_dates(day(self.0), day(self.1))
+        }
+    }
+
+    #[quickcheck]
+    fn test_query_matches_scan(spans: Vec<Span>, query: Span) -> bool {
+        let tree: IntervalTree<ClosedInterval, usize> = spans
+            .iter()
+            .enumerate()
+            .map(|(i, span)| Labeled::new(span.interval(), i))
+            .collect();
+
+        let mut found: Vec<usize> = tree
+            .query_range(&query.interval())
+            .into_iter()
+            .map(|e| *e.label())
+            .collect();
+        found.sort();
+        let expected: Vec<usize> = spans
+            .iter()
+            .enumerate()
+            .filter(|(_, span)| span.0 <= query.1 && query.0 <= span.1)
+            .map(|(i, _)| i)
+            .collect();
+
+        found == expected
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut tree = IntervalTree::new();
+        assert!(tree.query_point(day(0)).is_empty());
+
+        for (i, (start, end)) in [(10, 20), (0, 30), (15, 15), (25, 26)]
+            .into_iter()
+            .enumerate()
+        {
+            tree.insert(Labeled::new(
+                ClosedInterval::with_dates(day(start), day(end)),
+                i,
+            ));
+        }
+
+        let labels = |date| {
+            tree.query_point(date)
+                .into_iter()
+                .map(|e| *e.label())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(day(15)), vec![1, 0, 2]);
+        assert_eq!(labels(day(25)), vec![1, 3]);
+        assert_eq!(labels(day(31)), Vec::<usize>::new());
+        assert_eq!(tree.len(), 4);
+    }
+}
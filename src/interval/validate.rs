@@ -0,0 +1,305 @@
+//! Checking user-entered date ranges
+//!
+//! Effective-dated spreadsheets list one range per row, each meant to start the day after the
+//! previous one ends with only the latest left open. [validate_ranges] reports every row which
+//! breaks that pattern so an import can show all the problems at once instead of stopping at the
+//! first.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chrono::NaiveDate;
+//! # use calends::interval::validate::{validate_ranges, RangeIssue, RangePolicy};
+//!
+//! let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d);
+//! let rows = [
+//!     (date(1, 1), date(3, 31)),
+//!     (date(4, 1), date(6, 30)),
+//!     (date(7, 5), date(6, 1)),
+//!     (date(7, 5), None),
+//! ];
+//!
+//! let diagnostics = validate_ranges(rows, &RangePolicy::default());
+//! assert_eq!(diagnostics.len(), 2);
+//! assert_eq!(diagnostics[0].row, 2);
+//! assert!(matches!(diagnostics[0].issue, RangeIssue::Reversed { .. }));
+//! assert_eq!(diagnostics[1].row, 3);
+//! assert_eq!(diagnostics[1].issue.to_string(), "4 days missing from 2022-07-01 to 2022-07-04");
+//! ```
+use std::fmt::Display;
+
+use chrono::NaiveDate;
+
+use super::{
+    bound::{cmp_start_bound, Bound},
+    ops::intersect,
+    ClosedInterval, Interval, IntervalLike,
+};
+
+/// Which rows may leave their start or end open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OpenEnds {
+    /// Every row needs a start and an end
+    Reject,
+    /// Only the first row may leave its start open and only the last row its end
+    #[default]
+    Outermost,
+    /// Any row may leave its start or end open
+    Allow,
+}
+
+/// What counts as a problem when validating ranges
+///
+/// By default any gap or overlap between consecutive rows is reported, and only the first and
+/// last rows may be open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RangePolicy {
+    allowed_gap_days: u32,
+    allow_overlaps: bool,
+    open_ends: OpenEnds,
+}
+
+impl RangePolicy {
+    /// Only report gaps between rows of more than the number of days
+    pub fn with_allowed_gap(mut self, days: u32) -> Self {
+        self.allowed_gap_days = days;
+        self
+    }
+
+    /// Whether rows may overlap the row before them
+    pub fn with_overlaps(mut self, allowed: bool) -> Self {
+        self.allow_overlaps = allowed;
+        self
+    }
+
+    pub fn with_open_ends(mut self, open_ends: OpenEnds) -> Self {
+        self.open_ends = open_ends;
+        self
+    }
+}
+
+/// A problem with a row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeIssue {
+    /// The row ends before it starts, it is not compared with the rows around it
+    Reversed { start: NaiveDate, end: NaiveDate },
+    /// The row starts before the previous row
+    OutOfOrder,
+    /// Dates the row shares with the previous row
+    OverlapsPrevious(Interval),
+    /// Dates between the end of the previous row and the start of this one
+    GapFromPrevious(ClosedInterval),
+    /// The row has no start
+    OpenStart,
+    /// The row has no end
+    OpenEnd,
+}
+
+impl Display for RangeIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeIssue::Reversed { start, end } => {
+                write!(f, "ends on {} before it starts on {}", end, start)
+            }
+            RangeIssue::OutOfOrder => write!(f, "starts before the previous row"),
+            RangeIssue::OverlapsPrevious(overlap) => {
+                write!(f, "overlaps the previous row for {}", overlap)
+            }
+            RangeIssue::GapFromPrevious(gap) => write!(
+                f,
+                "{} days missing from {} to {}",
+                gap.length_days().unwrap(),
+                gap.start_opt().unwrap(),
+                gap.end_opt().unwrap()
+            ),
+            RangeIssue::OpenStart => write!(f, "has no start"),
+            RangeIssue::OpenEnd => write!(f, "has no end"),
+        }
+    }
+}
+
+/// A problem found with the row at the index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeDiagnostic {
+    /// Index of the row counting from zero
+    pub row: usize,
+    pub issue: RangeIssue,
+}
+
+/// Check each row against the policy and against the row before it
+///
+/// Rows are the start and end of each range, both included, in the order they were entered.
+/// Diagnostics are in row order and a row can have several. Reversed rows are skipped when
+/// comparing, so the row after one is compared with the last row which was not reversed.
+pub fn validate_ranges(
+    rows: impl IntoIterator<Item = (Option<NaiveDate>, Option<NaiveDate>)>,
+    policy: &RangePolicy,
+) -> Vec<RangeDiagnostic> {
+    let rows: Vec<_> = rows.into_iter().collect();
+    let mut diagnostics = Vec::new();
+    let mut previous: Option<Interval> = None;
+
+    for (row, (start, end)) in rows.iter().copied().enumerate() {
+        let mut report = |issue| diagnostics.push(RangeDiagnostic { row, issue });
+
+        let open_allowed = |outermost: bool| match policy.open_ends {
+            OpenEnds::Reject => false,
+            OpenEnds::Outermost => outermost,
+            OpenEnds::Allow => true,
+        };
+        if start.is_none() && !open_allowed(row == 0) {
+            report(RangeIssue::OpenStart);
+        }
+        if end.is_none() && !open_allowed(row + 1 == rows.len()) {
+            report(RangeIssue::OpenEnd);
+        }
+
+        let interval = match (start, end) {
+            (Some(start), Some(end)) if end < start => {
+                report(RangeIssue::Reversed { start, end });
+                continue;
+            }
+            (Some(start), Some(end)) => {
+                Interval::Closed(ClosedInterval::with_dates_unchecked(start, end))
+            }
+            (Some(start), None) => Interval::open_end(start),
+            (None, Some(end)) => Interval::open_start(end),
+            // a row without either date covers everything, there is nothing to compare
+            (None, None) => {
+                previous = None;
+                continue;
+            }
+        };
+
+        if let Some(previous) = &previous {
+            let before = |a: Bound<NaiveDate>, b: Bound<NaiveDate>| cmp_start_bound(&a, &b).is_lt();
+            if before(interval.bound_start(), previous.bound_start()) {
+                report(RangeIssue::OutOfOrder);
+            }
+            match intersect(previous, &interval) {
+                Some(overlap) if !policy.allow_overlaps => {
+                    report(RangeIssue::OverlapsPrevious(overlap))
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(gap) = gap_between(previous, &interval) {
+                        if gap.length_days().unwrap() > policy.allowed_gap_days.into() {
+                            report(RangeIssue::GapFromPrevious(gap));
+                        }
+                    }
+                }
+            }
+        }
+        previous = Some(interval);
+    }
+    diagnostics
+}
+
+/// The dates between two intervals which do not overlap, [None] when they touch
+fn gap_between(a: &Interval, b: &Interval) -> Option<ClosedInterval> {
+    let (first, second) = match (a.end_opt(), b.start_opt()) {
+        (Some(end), Some(start)) if end < start => (end, start),
+        _ => (b.end_opt()?, a.start_opt()?),
+    };
+    let (start, end) = (first.succ_opt()?, second.pred_opt()?);
+    (start <= end).then(|| ClosedInterval::with_dates_unchecked(start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    fn issues(
+        rows: &[(Option<NaiveDate>, Option<NaiveDate>)],
+        policy: &RangePolicy,
+    ) -> Vec<(usize, String)> {
+        validate_ranges(rows.iter().copied(), policy)
+            .into_iter()
+            .map(|d| (d.row, d.issue.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_contiguous_rows() {
+        let rows = [
+            (None, Some(date(2022, 1, 31))),
+            (Some(date(2022, 2, 1)), Some(date(2022, 2, 28))),
+            (Some(date(2022, 3, 1)), None),
+        ];
+        assert!(issues(&rows, &RangePolicy::default()).is_empty());
+        assert_eq!(
+            issues(
+                &rows,
+                &RangePolicy::default().with_open_ends(OpenEnds::Reject)
+            ),
+            vec![(0, "has no start".into()), (2, "has no end".into())]
+        );
+    }
+
+    #[test]
+    fn test_overlaps_and_gaps() {
+        let rows = [
+            (Some(date(2022, 1, 1)), Some(date(2022, 1, 31))),
+            (Some(date(2022, 1, 30)), Some(date(2022, 2, 28))),
+            (Some(date(2022, 3, 3)), Some(date(2022, 3, 31))),
+            (Some(date(2022, 3, 1)), None),
+            (Some(date(2022, 5, 1)), Some(date(2022, 5, 31))),
+        ];
+
+        assert_eq!(
+            issues(&rows, &RangePolicy::default()),
+            vec![
+                (
+                    1,
+                    "overlaps the previous row for 2022-01-30/2022-01-31".into()
+                ),
+                (2, "2 days missing from 2022-03-01 to 2022-03-02".into()),
+                (3, "has no end".into()),
+                (3, "starts before the previous row".into()),
+                (
+                    3,
+                    "overlaps the previous row for 2022-03-03/2022-03-31".into()
+                ),
+                (
+                    4,
+                    "overlaps the previous row for 2022-05-01/2022-05-31".into()
+                ),
+            ]
+        );
+
+        let lenient = RangePolicy::default()
+            .with_allowed_gap(2)
+            .with_overlaps(true);
+        assert_eq!(
+            issues(&rows, &lenient),
+            vec![
+                (3, "has no end".into()),
+                (3, "starts before the previous row".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reversed_rows_are_skipped() {
+        let rows = [
+            (Some(date(2022, 1, 1)), Some(date(2022, 1, 31))),
+            (Some(date(2022, 3, 1)), Some(date(2022, 2, 1))),
+            (Some(date(2022, 2, 1)), Some(date(2022, 2, 28))),
+            (None, None),
+            (Some(date(2022, 9, 1)), Some(date(2022, 9, 30))),
+        ];
+
+        assert_eq!(
+            issues(&rows, &RangePolicy::default()),
+            vec![
+                (
+                    1,
+                    "ends on 2022-02-01 before it starts on 2022-03-01".into()
+                ),
+                (3, "has no start".into()),
+                (3, "has no end".into()),
+            ]
+        );
+    }
+}
@@ -30,8 +30,8 @@
 //!
 //! // It also compatible with NaiveDate
 //! assert_eq!(
-//!     NaiveDate::from_ymd(2022, 1, 1) + rd,
-//!     NaiveDate::from_ymd(2022, 1, 30)
+//!     NaiveDate::from_ymd_opt(2022, 1, 1).unwrap() + rd,
+//!     NaiveDate::from_ymd_opt(2022, 1, 30).unwrap()
 //! );
 //! ```
 //!
@@ -44,9 +44,10 @@
 //! There are two ways to serialize a RelativeDuration:
 //! - The first one serializes it as an object.
 //! - The second way is an ISO8601-2:2019 compatible serializer. Because the format is not
-//! widely used yet we do not set it as the default (de)serializer.
+//!   widely used yet we do not set it as the default (de)serializer.
 //!
 //! ```
+//! # #[cfg(feature = "serde")] {
 //! use calends::RelativeDuration;
 //! use calends::rd_iso8601;
 //!
@@ -67,6 +68,7 @@
 //!
 //! let parsed: S = serde_json::from_str(&rd_string).unwrap();
 //! assert_eq!(rd, parsed.rd)
+//! # }
 //! ```
 //!
 //! # Recurrence & Rules
@@ -77,12 +79,12 @@
 //! use calends::{Recurrence, Rule};
 //! use chrono::NaiveDate;
 //!
-//! let date = NaiveDate::from_ymd(2022, 1, 1);
-//! let end = NaiveDate::from_ymd(2022, 3, 1);
+//! let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+//! let end = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
 //!
 //! let mut recur = Recurrence::with_start(Rule::monthly(), date).until(end);
-//! assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 1, 1)));
-//! assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 2, 1)));
+//! assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()));
+//! assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 2, 1).unwrap()));
 //! assert_eq!(recur.next(), None);
 //! ```
 //!
@@ -105,7 +107,7 @@
 //! use chrono::NaiveDate;
 //!
 //! let duration = RelativeDuration::months(1).with_days(-2);
-//! let start = NaiveDate::from_ymd(2022, 1, 1);
+//! let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
 //!
 //! let mut interval = Interval::closed_from_start(start, duration);
 //! ```
@@ -115,9 +117,10 @@
 //! There are two ways to serialize a Interval:
 //! - The first one serializes it as an object.
 //! - The second way is an ISO8601-2:2019 compatible serializer. Because the format is not
-//! widely used yet we do not set it as the default (de)serializer.
+//!   widely used yet we do not set it as the default (de)serializer.
 //!
 //! ```
+//! # #[cfg(feature = "serde")] {
 //! use chrono::NaiveDate;
 //! use calends::{Interval, RelativeDuration, IntervalLike};
 //! use calends::interval::marker::Start;
@@ -128,7 +131,7 @@
 //! }
 //!
 //! let rd = RelativeDuration::default().with_days(1).with_months(23).with_weeks(-1);
-//! let int = Interval::closed_from_start(NaiveDate::from_ymd(2022, 1, 1), rd);
+//! let int = Interval::closed_from_start(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), rd);
 //! let s = S { i: int.clone() };
 //!
 //! let int_string = serde_json::to_string(&s).unwrap();
@@ -136,18 +139,65 @@
 //!
 //! let parsed: S = serde_json::from_str(&int_string).unwrap();
 //! assert_eq!(parsed.i.start_opt().unwrap(), int.start_opt().unwrap())
+//! # }
 //! ```
+//!
+//! # Features
+//!
+//! The core of durations, intervals, periods, recurrence rules and business calendars is always
+//! available. Optional subsystems are enabled by default and can be turned off with
+//! `default-features = false` to keep binary size down, e.g. for embedded or WASM targets:
+//!
+//! - `chart`: axis labels and date grids for charts and calendar views
+//! - `country-calendars`: the built-in US federal, UK bank and TARGET2 holiday calendars and the
+//!   text schedule syntax which refers to them
+//! - `rrule`: RFC 5545 RRULE parsing, formatting and expansion
+//! - `serde`: `Serialize` and `Deserialize` for the calendar types, the `calends::serde` helper
+//!   modules and holiday calendar configuration
+//!
+//! Integrations are opt-in:
+//!
+//! - `async`: calendars refreshed from asynchronous sources
+//! - `tracing`: spans and events from schedule generation, calendar lookups and parsing
 
+pub mod bucket;
+pub mod business;
+pub mod capacity;
+#[cfg(feature = "chart")]
+pub mod chart;
+mod compat;
+#[cfg(test)]
+mod conformance;
 pub mod duration;
+pub mod encoding;
+pub mod error;
+pub mod explain;
 pub mod grain;
+pub mod holidays;
+pub mod horizon;
 pub mod interval;
+pub mod month;
+pub mod monthday;
+pub mod navigate;
 mod parser;
+pub mod period;
 pub mod recurrence;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod term;
+#[cfg(test)]
+mod testing;
+mod trace;
 pub mod unit;
 pub mod util;
+pub mod versioned;
+pub mod weekday;
+pub mod year;
 
+#[cfg(feature = "serde")]
 pub use crate::duration::serde::rd_iso8601;
 pub use crate::duration::RelativeDuration;
+pub use crate::error::{CalendsError, Error};
 pub use crate::interval::{Interval, IntervalWithEnd, IntervalWithStart};
 pub use crate::recurrence::Rule;
 pub use crate::unit::CalendarUnit;
@@ -0,0 +1,205 @@
+//! Months of the year
+//!
+//! [Month] names a month rather than numbering it, so there is no doubt whether January is 0 or 1.
+//! Adding months wraps around the year and reports how many years were carried.
+use std::{
+    fmt::Display,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{error::CalendsError, util::days_in_month};
+
+/// A month of the year
+///
+/// # Examples
+///
+/// ```
+/// # use calends::month::Month;
+///
+/// assert_eq!(Month::November + 3, (Month::February, 1));
+/// assert_eq!(Month::January - 1, (Month::December, -1));
+/// assert_eq!("sep".parse::<Month>()?, Month::September);
+/// assert_eq!(Month::September.number(), 9);
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Month {
+    January,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+const MONTHS: [Month; 12] = [
+    Month::January,
+    Month::February,
+    Month::March,
+    Month::April,
+    Month::May,
+    Month::June,
+    Month::July,
+    Month::August,
+    Month::September,
+    Month::October,
+    Month::November,
+    Month::December,
+];
+
+const NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+impl Month {
+    /// The month with the number, January being 1
+    pub fn from_number(number: u32) -> Option<Month> {
+        number
+            .checked_sub(1)
+            .and_then(|i| MONTHS.get(i as usize).copied())
+    }
+
+    /// The month the date falls in
+    pub fn of(date: &NaiveDate) -> Month {
+        MONTHS[date.month0() as usize]
+    }
+
+    /// Number of the month, January being 1
+    pub fn number(&self) -> u32 {
+        *self as u32 + 1
+    }
+
+    pub fn name(&self) -> &'static str {
+        NAMES[*self as usize]
+    }
+
+    /// Number of days in the month of the year
+    pub fn days_in(&self, year: i32) -> u32 {
+        days_in_month(year, self.number())
+    }
+
+    /// The first day of the month in the year
+    pub fn first_day(&self, year: i32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, self.number(), 1)
+    }
+}
+
+/// Add months, wrapping around the year, giving the month and the number of years carried
+impl Add<i32> for Month {
+    type Output = (Month, i32);
+
+    fn add(self, months: i32) -> (Month, i32) {
+        let total = self as i32 + months;
+        (MONTHS[total.rem_euclid(12) as usize], total.div_euclid(12))
+    }
+}
+
+/// Subtract months, wrapping around the year, giving the month and the (negative) years carried
+impl Sub<i32> for Month {
+    type Output = (Month, i32);
+
+    fn sub(self, months: i32) -> (Month, i32) {
+        self + -months
+    }
+}
+
+impl TryFrom<u32> for Month {
+    type Error = CalendsError;
+
+    fn try_from(number: u32) -> Result<Self, Self::Error> {
+        Month::from_number(number).ok_or_else(|| CalendsError::InvalidMonth(number.to_string()))
+    }
+}
+
+impl From<Month> for chrono::Month {
+    fn from(month: Month) -> Self {
+        chrono::Month::try_from(month.number() as u8).unwrap()
+    }
+}
+
+impl From<chrono::Month> for Month {
+    fn from(month: chrono::Month) -> Self {
+        MONTHS[month.number_from_month() as usize - 1]
+    }
+}
+
+impl Display for Month {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parses full names and three letter abbreviations in any case
+impl FromStr for Month {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        MONTHS
+            .iter()
+            .find(|month| {
+                let name = month.name().to_ascii_lowercase();
+                lower == name || lower == name[..3]
+            })
+            .copied()
+            .ok_or_else(|| CalendsError::InvalidMonth(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+
+    #[quickcheck]
+    fn adding_months_carries_years(number: u8, months: i16) -> bool {
+        let month = MONTHS[number as usize % 12];
+        let (shifted, years) = month + months.into();
+
+        years * 12 + shifted.number() as i32 == month.number() as i32 + i32::from(months)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("January".parse::<Month>().unwrap(), Month::January);
+        assert_eq!(" DEC ".parse::<Month>().unwrap(), Month::December);
+        assert_eq!(
+            "Ja".parse::<Month>(),
+            Err(CalendsError::InvalidMonth("Ja".to_string()))
+        );
+        assert_eq!(
+            Month::try_from(0),
+            Err(CalendsError::InvalidMonth("0".to_string()))
+        );
+        assert_eq!(Month::try_from(12).unwrap(), Month::December);
+    }
+
+    #[test]
+    fn test_chrono_round_trip() {
+        for month in MONTHS {
+            assert_eq!(Month::from(chrono::Month::from(month)), month);
+        }
+        assert_eq!(Month::February.days_in(2024), 29);
+    }
+}
@@ -0,0 +1,157 @@
+//! Days of the month
+//!
+//! [DayOfMonth] identifies a day either from the start of the month, `1..=31`, or from its end,
+//! `-1..=-31` where `-1` is the last day. Not every day exists in every month; resolving the 31st
+//! in April gives nothing rather than rolling over into May.
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDate;
+
+use crate::{error::CalendsError, month::Month};
+
+/// A validated day of the month, negative values count back from the end of the month
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{month::Month, monthday::DayOfMonth};
+///
+/// let last = DayOfMonth::new(-1)?;
+/// assert_eq!(last.resolve(2024, Month::February), NaiveDate::from_ymd_opt(2024, 2, 29));
+///
+/// let thirty_first = DayOfMonth::new(31)?;
+/// assert_eq!(thirty_first.resolve(2024, Month::April), None);
+///
+/// assert!(DayOfMonth::new(0).is_err());
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "i32", into = "i32"))]
+pub struct DayOfMonth(i8);
+
+impl DayOfMonth {
+    /// The first day of the month
+    pub const FIRST: DayOfMonth = DayOfMonth(1);
+
+    /// The fifteenth day, the middle of the month for semi-monthly schedules
+    pub const FIFTEENTH: DayOfMonth = DayOfMonth(15);
+
+    /// The last day of the month
+    pub const LAST: DayOfMonth = DayOfMonth(-1);
+
+    pub fn new(day: i32) -> Result<Self, CalendsError> {
+        match day {
+            1..=31 | -31..=-1 => Ok(DayOfMonth(day as i8)),
+            _ => Err(CalendsError::InvalidDayOfMonth(day.to_string())),
+        }
+    }
+
+    pub fn get(&self) -> i32 {
+        self.0.into()
+    }
+
+    /// Whether the day counts back from the end of the month
+    pub fn is_from_end(&self) -> bool {
+        self.0 < 0
+    }
+
+    /// The date this day falls on in the month, if the month has it
+    pub fn resolve(&self, year: i32, month: Month) -> Option<NaiveDate> {
+        let day = if self.is_from_end() {
+            month.days_in(year) as i32 + self.get() + 1
+        } else {
+            self.get()
+        };
+
+        u32::try_from(day)
+            .ok()
+            .and_then(|day| NaiveDate::from_ymd_opt(year, month.number(), day))
+    }
+}
+
+impl TryFrom<i32> for DayOfMonth {
+    type Error = CalendsError;
+
+    fn try_from(day: i32) -> Result<Self, Self::Error> {
+        DayOfMonth::new(day)
+    }
+}
+
+impl From<DayOfMonth> for i32 {
+    fn from(day: DayOfMonth) -> Self {
+        day.get()
+    }
+}
+
+impl Display for DayOfMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for DayOfMonth {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .parse::<i32>()
+            .map_err(|_| CalendsError::InvalidDayOfMonth(s.to_string()))
+            .and_then(DayOfMonth::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_validation() {
+        assert!(DayOfMonth::new(31).is_ok());
+        assert!(DayOfMonth::new(-31).is_ok());
+        assert_eq!(
+            DayOfMonth::new(32),
+            Err(CalendsError::InvalidDayOfMonth("32".to_string()))
+        );
+        assert_eq!(
+            DayOfMonth::try_from(-32),
+            Err(CalendsError::InvalidDayOfMonth("-32".to_string()))
+        );
+        assert_eq!("-3".parse::<DayOfMonth>().unwrap().to_string(), "-3");
+        assert!("first".parse::<DayOfMonth>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_from_end() {
+        assert_eq!(
+            DayOfMonth::LAST.resolve(2023, Month::February),
+            Some(date(2023, 2, 28))
+        );
+        assert_eq!(
+            DayOfMonth::new(-3).unwrap().resolve(2023, Month::January),
+            Some(date(2023, 1, 29))
+        );
+        assert_eq!(
+            DayOfMonth::new(-31).unwrap().resolve(2023, Month::January),
+            Some(date(2023, 1, 1))
+        );
+        assert_eq!(
+            DayOfMonth::new(-31).unwrap().resolve(2023, Month::February),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_from_start() {
+        assert_eq!(
+            DayOfMonth::new(29).unwrap().resolve(2024, Month::February),
+            Some(date(2024, 2, 29))
+        );
+        assert_eq!(
+            DayOfMonth::new(29).unwrap().resolve(2023, Month::February),
+            None
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Chainable calendar navigation
+//!
+//! A [Cursor] holds a date and moves it with chainable steps, such as the first Wednesday two
+//! months before the start of this month. Every move is recorded so the result can be explained
+//! with [Cursor::explain].
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{
+    explain::{Explanation, Step},
+    holidays::HolidayCalendar,
+    util::{beginning_of_month, beginning_of_week, end_of_month},
+    RelativeDuration,
+};
+
+/// A date which is moved by chaining steps
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::{NaiveDate, Weekday};
+/// # use calends::navigate::Cursor;
+///
+/// let cursor = Cursor::new(NaiveDate::from_ymd_opt(2022, 5, 18).unwrap())
+///     .start_of_month()
+///     .back_months(2)
+///     .next_weekday(Weekday::Wed);
+///
+/// assert_eq!(cursor.date(), NaiveDate::from_ymd_opt(2022, 3, 2).unwrap());
+/// assert_eq!(
+///     cursor.explain().to_string(),
+///     "2022-05-18 → start of month 2022-05-01 → -2M to 2022-03-01 → next Wed 2022-03-02"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    explanation: Explanation,
+}
+
+impl Cursor {
+    pub fn new(date: NaiveDate) -> Self {
+        Cursor {
+            explanation: Explanation::new(date),
+        }
+    }
+
+    /// The current date
+    pub fn date(&self) -> NaiveDate {
+        self.explanation.date()
+    }
+
+    /// Every move made so far
+    pub fn explain(&self) -> &Explanation {
+        &self.explanation
+    }
+
+    pub fn into_explanation(self) -> Explanation {
+        self.explanation
+    }
+
+    pub fn start_of_month(self) -> Self {
+        let from = self.date();
+        self.step(Step::StartOfMonth {
+            from,
+            to: beginning_of_month(&from),
+        })
+    }
+
+    pub fn end_of_month(self) -> Self {
+        let from = self.date();
+        self.step(Step::EndOfMonth {
+            from,
+            to: end_of_month(&from),
+        })
+    }
+
+    /// Back to the Monday of the week
+    pub fn start_of_week(self) -> Self {
+        let from = self.date();
+        self.step(Step::StartOfWeek {
+            from,
+            to: beginning_of_week(&from),
+        })
+    }
+
+    /// Move by a duration, see [Explanation::shift]
+    pub fn shift(self, duration: RelativeDuration) -> Self {
+        Cursor {
+            explanation: self.explanation.shift(duration),
+        }
+    }
+
+    pub fn forward_months(self, months: i32) -> Self {
+        self.shift(RelativeDuration::months(months))
+    }
+
+    pub fn back_months(self, months: i32) -> Self {
+        self.forward_months(-months)
+    }
+
+    pub fn forward_weeks(self, weeks: i32) -> Self {
+        self.shift(RelativeDuration::weeks(weeks))
+    }
+
+    pub fn back_weeks(self, weeks: i32) -> Self {
+        self.forward_weeks(-weeks)
+    }
+
+    pub fn forward_days(self, days: i32) -> Self {
+        self.shift(RelativeDuration::days(days))
+    }
+
+    pub fn back_days(self, days: i32) -> Self {
+        self.forward_days(-days)
+    }
+
+    /// The first day on the weekday after the current date, a week later when already on it
+    pub fn next_weekday(self, weekday: Weekday) -> Self {
+        let from = self.date();
+        let days = (weekday.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+        let to = from + chrono::Duration::days(if days == 0 { 7 } else { days.into() });
+        self.step(Step::NextWeekday { weekday, from, to })
+    }
+
+    /// The last day on the weekday before the current date, a week earlier when already on it
+    pub fn prev_weekday(self, weekday: Weekday) -> Self {
+        let from = self.date();
+        let days = (from.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+        let to = from - chrono::Duration::days(if days == 0 { 7 } else { days.into() });
+        self.step(Step::PrevWeekday { weekday, from, to })
+    }
+
+    /// Roll forward onto a business day, see [Explanation::roll_forward]
+    pub fn roll_forward<C: HolidayCalendar + ?Sized>(self, calendar: &C) -> Self {
+        Cursor {
+            explanation: self.explanation.roll_forward(calendar),
+        }
+    }
+
+    /// Record the step unless it leaves the date where it is
+    fn step(self, step: Step) -> Self {
+        if step.date() == self.date() {
+            return self;
+        }
+        Cursor {
+            explanation: self.explanation.push(step),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_weekday_moves_always_move() {
+        // 2022-03-02 is a Wednesday
+        let cursor = Cursor::new(date(2022, 3, 2));
+
+        assert_eq!(
+            cursor.clone().next_weekday(Weekday::Wed).date(),
+            date(2022, 3, 9)
+        );
+        assert_eq!(
+            cursor.clone().next_weekday(Weekday::Tue).date(),
+            date(2022, 3, 8)
+        );
+        assert_eq!(
+            cursor.clone().prev_weekday(Weekday::Wed).date(),
+            date(2022, 2, 23)
+        );
+        assert_eq!(cursor.prev_weekday(Weekday::Thu).date(), date(2022, 2, 24));
+    }
+
+    #[test]
+    fn test_moves_which_stay_put_are_not_recorded() {
+        let cursor = Cursor::new(date(2022, 3, 1))
+            .start_of_month()
+            .end_of_month()
+            .start_of_week();
+
+        assert_eq!(cursor.date(), date(2022, 3, 28));
+        assert_eq!(
+            cursor.explain().to_string(),
+            "2022-03-01 → end of month 2022-03-31 → start of week 2022-03-28"
+        );
+    }
+}
@@ -15,9 +15,10 @@ pub fn take_signed_digits(i: &[u8]) -> IResult<&[u8], i32> {
     }
 
     let s = std::str::from_utf8(digits).expect("Invalid data, expected UTF-8 string");
+    // too many digits for an i32 is an error in the input rather than a bug
     let res: i32 = s
         .parse()
-        .expect("Invalid string, expected ASCII representation of a number");
+        .map_err(|_| Err::Error(Error::new(i, nom::error::ErrorKind::TooLarge)))?;
 
     match negative {
         Some(_) => Ok((i, -res)),
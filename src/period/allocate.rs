@@ -0,0 +1,148 @@
+//! Day weighted allocation of values between overlapping periods
+//!
+//! Demand plans are commonly held by month while operations work in ISO weeks, which straddle
+//! month boundaries. Allocating by days splits a value between the periods of another grain in
+//! proportion to the days each has in common with the original period.
+use chrono::{Datelike, Days, IsoWeek, Months, NaiveDate, Weekday};
+
+use crate::{grain::Grain, month::Month};
+
+use super::{FiscalYear, Period, Periods};
+
+/// Split a value across the periods of another grain which overlap the period
+///
+/// Each share is proportional to the number of days the target period has in common with the
+/// period, so the shares always add back up to the value.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::grain::Grain;
+/// # use calends::period::{allocate_by_days, FiscalYear, Period};
+///
+/// let fiscal = FiscalYear::calendar();
+/// let week = Period::containing(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap(), Grain::Week, &fiscal);
+///
+/// // one day of the week is in January, six are in February
+/// let months = allocate_by_days(&week, 70.0, Grain::Month, &fiscal);
+/// assert_eq!(months[0].1, 10.0);
+/// assert_eq!(months[1].1, 60.0);
+/// ```
+pub fn allocate_by_days(
+    period: &Period,
+    value: f64,
+    to: Grain,
+    fiscal: &FiscalYear,
+) -> Vec<(Period, f64)> {
+    let days = overlap(period, period) as f64;
+
+    Periods::starting(Period::containing(period.start(), to, fiscal))
+        .take_while(|target| target.start() <= period.end())
+        .map(|target| (target, value * overlap(period, &target) as f64 / days))
+        .collect()
+}
+
+/// Split a monthly value across the ISO weeks overlapping the month, weighted by days
+///
+/// Gives nothing when the month, or a week overlapping it, is outside the range of dates.
+///
+/// ```
+/// # use calends::{month::Month, period::allocate_month_to_iso_weeks};
+///
+/// // February 2022 starts on a Tuesday, so its first ISO week only has six of its days
+/// let weeks = allocate_month_to_iso_weeks(2022, Month::February, 28.0).unwrap();
+/// assert_eq!(weeks.len(), 5);
+/// assert_eq!(weeks[0].0.week(), 5);
+/// assert_eq!(weeks[0].1, 6.0);
+/// ```
+pub fn allocate_month_to_iso_weeks(
+    year: i32,
+    month: Month,
+    value: f64,
+) -> Option<Vec<(IsoWeek, f64)>> {
+    let first = month.first_day(year)?;
+    // the weeks at either end overlap the neighbouring months and have to be dates too
+    first.checked_sub_days(Days::new(7))?;
+    first
+        .checked_add_months(Months::new(1))?
+        .checked_add_days(Days::new(6))?;
+
+    let month = Period::containing(first, Grain::Month, &FiscalYear::calendar());
+
+    let weeks = allocate_by_days(&month, value, Grain::Week, &FiscalYear::calendar())
+        .into_iter()
+        .map(|(week, share)| (week.start().iso_week(), share))
+        .collect();
+    Some(weeks)
+}
+
+/// Split a weekly value across the months overlapping the ISO week, weighted by days
+pub fn allocate_iso_week_to_months(week: IsoWeek, value: f64) -> Vec<(Period, f64)> {
+    let monday = NaiveDate::from_isoywd_opt(week.year(), week.week(), Weekday::Mon).unwrap();
+    let week = Period::containing(monday, Grain::Week, &FiscalYear::calendar());
+
+    allocate_by_days(&week, value, Grain::Month, &FiscalYear::calendar())
+}
+
+/// Number of days the periods have in common
+pub(crate) fn overlap(a: &Period, b: &Period) -> i64 {
+    let start = a.start().max(b.start());
+    let end = a.end().min(b.end());
+    (end.signed_duration_since(start).num_days() + 1).max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_month_to_iso_weeks_round_trip() {
+        // January 2023 starts on a Sunday which belongs to the last ISO week of 2022
+        let weeks = allocate_month_to_iso_weeks(2023, Month::January, 310.0).unwrap();
+        assert_eq!((weeks[0].0.year(), weeks[0].0.week()), (2022, 52));
+        assert!((weeks[0].1 - 10.0).abs() < 1e-9);
+        assert_eq!(weeks.len(), 6);
+        assert!((weeks.iter().map(|(_, v)| v).sum::<f64>() - 310.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_month_out_of_range() {
+        assert_eq!(
+            allocate_month_to_iso_weeks(i32::MAX, Month::June, 30.0),
+            None
+        );
+        assert_eq!(
+            allocate_month_to_iso_weeks(NaiveDate::MAX.year(), Month::November, 30.0)
+                .map(|weeks| weeks.len()),
+            Some(5)
+        );
+        assert_eq!(
+            allocate_month_to_iso_weeks(NaiveDate::MAX.year(), Month::December, 31.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_iso_week_to_months() {
+        let week = date(2022, 12, 28).iso_week();
+        let months = allocate_iso_week_to_months(week, 7.0);
+
+        assert_eq!(months.len(), 2);
+        assert_eq!(months[0].0.start(), date(2022, 12, 1));
+        assert!((months[0].1 - 6.0).abs() < 1e-9);
+        assert!((months[1].1 - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_within_a_single_period() {
+        let fiscal = FiscalYear::calendar();
+        let week = Period::containing(date(2022, 3, 9), Grain::Week, &fiscal);
+
+        assert_eq!(
+            allocate_by_days(&week, 5.0, Grain::Month, &fiscal),
+            vec![(Period::containing(week.start(), Grain::Month, &fiscal), 5.0)]
+        );
+    }
+}
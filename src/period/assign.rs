@@ -0,0 +1,111 @@
+//! Assigning dated items to periods
+use chrono::NaiveDate;
+
+use crate::interval::marker::{End, Start};
+
+/// Merge items sorted by date into sorted, non-overlapping periods in a single pass
+///
+/// Each period is returned with the items it contains, in order, including periods with no items
+/// which fall between the first and last item. Periods are only pulled from the iterator until
+/// the last item is placed, so infinite streams such as [Periods](super::Periods) can be used.
+/// Items which fall outside every period, including any which are out of order, are dropped.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::period::{assign_to_periods, Periods};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let sales = [(date(1, 5), 10), (date(1, 20), 5), (date(3, 2), 7)];
+///
+/// let by_month = assign_to_periods(sales, Periods::months(date(1, 1)));
+///
+/// assert_eq!(by_month.len(), 3);
+/// assert_eq!(by_month[0].1, vec![10, 5]);
+/// assert!(by_month[1].1.is_empty());
+/// assert_eq!(by_month[2].0.start(), date(3, 1));
+/// ```
+pub fn assign_to_periods<T, P>(
+    items: impl IntoIterator<Item = (NaiveDate, T)>,
+    periods: impl IntoIterator<Item = P>,
+) -> Vec<(P, Vec<T>)>
+where
+    P: Start + End,
+{
+    let mut periods = periods.into_iter();
+    let mut assigned: Vec<(P, Vec<T>)> = Vec::new();
+    let mut exhausted = false;
+
+    for (date, item) in items {
+        // advance to the first period which does not end before the item
+        while !exhausted && assigned.last().is_none_or(|(p, _)| p.end() < date) {
+            match periods.next() {
+                Some(period) => assigned.push((period, Vec::new())),
+                None => exhausted = true,
+            }
+        }
+
+        if let Some((period, items)) = assigned.last_mut() {
+            if period.start() <= date && date <= period.end() {
+                items.push(item);
+            }
+        }
+    }
+
+    assigned
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        grain::Grain,
+        interval::ClosedInterval,
+        period::{Period, Periods},
+        testing::date,
+        RelativeDuration,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_items_outside_periods_are_dropped() {
+        let periods = [
+            ClosedInterval::from_start(date(2022, 1, 10), RelativeDuration::days(2)),
+            ClosedInterval::from_start(date(2022, 1, 20), RelativeDuration::days(2)),
+        ];
+        let items = [
+            (date(2022, 1, 1), "before"),
+            (date(2022, 1, 11), "first"),
+            (date(2022, 1, 15), "gap"),
+            (date(2022, 1, 22), "second"),
+            (date(2022, 2, 1), "after"),
+        ];
+
+        let assigned = assign_to_periods(items, periods);
+        assert_eq!(assigned.len(), 2);
+        assert_eq!(assigned[0].1, vec!["first"]);
+        assert_eq!(assigned[1].1, vec!["second"]);
+    }
+
+    #[test]
+    fn test_empty_items_pull_no_periods() {
+        let assigned = assign_to_periods(
+            Vec::<(NaiveDate, ())>::new(),
+            Periods::containing(date(2022, 1, 1), Grain::Week),
+        );
+        assert!(assigned.is_empty());
+    }
+
+    #[test]
+    fn test_weekly_periods() {
+        let items = (1..=14).map(|day| (date(2022, 1, day), day));
+        let assigned: Vec<(Period, Vec<u32>)> =
+            assign_to_periods(items, Periods::containing(date(2022, 1, 1), Grain::Week));
+
+        // 2022-01-01 is a Saturday so the first week only has two days
+        assert_eq!(assigned[0].1, vec![1, 2]);
+        assert_eq!(assigned[1].1.len(), 7);
+        assert_eq!(assigned[2].1, vec![10, 11, 12, 13, 14]);
+    }
+}
@@ -0,0 +1,291 @@
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::{
+    grain::Grain,
+    interval::{bound::Bound, marker, ClosedInterval},
+    util::{beginning_of_month, beginning_of_week, shift_months},
+    Interval, IntervalLike, RelativeDuration,
+};
+
+use super::FiscalYear;
+
+/// A period of time of a given [Grain], aligned to calendar boundaries
+///
+/// Days start at midnight, weeks start on Monday and months start on the first of the month.
+/// Quarters, halves and longer grains are aligned to the start of a [FiscalYear], which is the
+/// calendar year unless configured otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::grain::Grain;
+/// # use calends::period::{FiscalYear, Period};
+///
+/// let period = Period::containing(
+///     NaiveDate::from_ymd_opt(2022, 5, 18).unwrap(),
+///     Grain::Quarter,
+///     &FiscalYear::starting(4),
+/// );
+///
+/// assert_eq!(period.start(), NaiveDate::from_ymd_opt(2022, 4, 1).unwrap());
+/// assert_eq!(period.end(), NaiveDate::from_ymd_opt(2022, 6, 30).unwrap());
+/// assert_eq!(period.succ().start(), NaiveDate::from_ymd_opt(2022, 7, 1).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Period {
+    start: NaiveDate,
+    grain: Grain,
+}
+
+impl Period {
+    /// The period of the given grain which contains the date
+    pub fn containing(date: NaiveDate, grain: Grain, fiscal: &FiscalYear) -> Self {
+        let start = match grain.months() {
+            None if grain == Grain::Week => beginning_of_week(&date),
+            None => date,
+            Some(months) if months <= 12 => {
+                let offset = fiscal.months_into_year(date) as i32 % months;
+                shift_months(beginning_of_month(&date), -offset)
+            }
+            Some(months) => {
+                let years = months / 12;
+                let year = fiscal.beginning_of_year(date).year();
+                NaiveDate::from_ymd_opt(year - year.rem_euclid(years), fiscal.start_month(), 1)
+                    .unwrap()
+            }
+        };
+
+        Period { start, grain }
+    }
+
+    pub fn grain(&self) -> Grain {
+        self.grain
+    }
+
+    /// First day of the period
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// Last day of the period
+    pub fn end(&self) -> NaiveDate {
+        self.start + self.inclusive_duration()
+    }
+
+    /// The nth day of the period counting from one, negative days count back from the last day
+    ///
+    /// This works the same way for every grain, so `-2` is the second to last day of a month, a
+    /// fiscal quarter or a week. Zero and days beyond the length of the period give [None].
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use calends::grain::Grain;
+    /// # use calends::period::{FiscalYear, Period};
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    /// let quarter = Period::containing(date(5, 18), Grain::Quarter, &FiscalYear::calendar());
+    ///
+    /// assert_eq!(quarter.nth_day(-2), Some(date(6, 29)));
+    /// assert_eq!(quarter.nth_day(32), Some(date(5, 2)));
+    /// assert_eq!(quarter.nth_day(92), None);
+    /// ```
+    pub fn nth_day(&self, n: i32) -> Option<NaiveDate> {
+        let day = match n {
+            0 => return None,
+            1.. => self
+                .start
+                .checked_add_signed(Duration::days(i64::from(n) - 1)),
+            _ => self
+                .end()
+                .checked_add_signed(Duration::days(i64::from(n) + 1)),
+        }?;
+        self.within(day).then_some(day)
+    }
+
+    /// The following period
+    pub fn succ(&self) -> Period {
+        Period {
+            start: self.start + self.grain.into_duration(),
+            grain: self.grain,
+        }
+    }
+
+    /// The following period, [None] when it would start after the last supported date
+    pub fn checked_succ(&self) -> Option<Period> {
+        let start = self.grain.into_duration().try_add_to(self.start).ok()?;
+        Some(Period {
+            start,
+            grain: self.grain,
+        })
+    }
+
+    /// The preceding period
+    pub fn pred(&self) -> Period {
+        Period {
+            start: self.start + -self.grain.into_duration(),
+            grain: self.grain,
+        }
+    }
+
+    /// The preceding period, [None] when it would start before the earliest supported date
+    pub fn checked_pred(&self) -> Option<Period> {
+        let start = self.grain.into_duration().try_sub_from(self.start).ok()?;
+        Some(Period {
+            start,
+            grain: self.grain,
+        })
+    }
+
+    /// Number of whole periods from this period to the period containing the date
+    ///
+    /// The period containing the date is counted from zero and dates before this period give a
+    /// negative count.
+    pub fn periods_until(&self, date: NaiveDate) -> i32 {
+        match self.grain.months() {
+            Some(months) => {
+                let elapsed = (date.year() - self.start.year()) * 12 + date.month() as i32
+                    - self.start.month() as i32;
+                elapsed.div_euclid(months)
+            }
+            None => {
+                let days = if self.grain == Grain::Week { 7 } else { 1 };
+                let elapsed = date.signed_duration_since(self.start).num_days();
+                elapsed.div_euclid(days) as i32
+            }
+        }
+    }
+
+    pub fn into_interval(&self) -> Interval {
+        Interval::Closed(ClosedInterval::from_start(
+            self.start,
+            self.inclusive_duration(),
+        ))
+    }
+
+    fn inclusive_duration(&self) -> RelativeDuration {
+        self.grain.into_duration() - RelativeDuration::days(1)
+    }
+}
+
+impl IntervalLike for Period {
+    fn bound_start(&self) -> Bound<NaiveDate> {
+        Bound::Included(self.start())
+    }
+
+    fn bound_end(&self) -> Bound<NaiveDate> {
+        Bound::Included(self.end())
+    }
+
+    fn duration(&self) -> Option<RelativeDuration> {
+        Some(self.inclusive_duration())
+    }
+}
+
+impl marker::Start for Period {}
+impl marker::End for Period {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_calendar_alignment() {
+        let fiscal = FiscalYear::calendar();
+        let d = date(2022, 8, 17);
+
+        assert_eq!(Period::containing(d, Grain::Day, &fiscal).end(), d);
+        // 2022-08-17 is a Wednesday
+        let week = Period::containing(d, Grain::Week, &fiscal);
+        assert_eq!(
+            (week.start(), week.end()),
+            (date(2022, 8, 15), date(2022, 8, 21))
+        );
+        let month = Period::containing(d, Grain::Month, &fiscal);
+        assert_eq!(
+            (month.start(), month.end()),
+            (date(2022, 8, 1), date(2022, 8, 31))
+        );
+        let half = Period::containing(d, Grain::Half, &fiscal);
+        assert_eq!(
+            (half.start(), half.end()),
+            (date(2022, 7, 1), date(2022, 12, 31))
+        );
+        let decade = Period::containing(d, Grain::Decade, &fiscal);
+        assert_eq!(
+            (decade.start(), decade.end()),
+            (date(2020, 1, 1), date(2029, 12, 31))
+        );
+    }
+
+    #[test]
+    fn test_fiscal_alignment() {
+        let fiscal = FiscalYear::starting(10);
+
+        let year = Period::containing(date(2022, 9, 30), Grain::Year, &fiscal);
+        assert_eq!(
+            (year.start(), year.end()),
+            (date(2021, 10, 1), date(2022, 9, 30))
+        );
+        let quarter = Period::containing(date(2022, 1, 15), Grain::Quarter, &fiscal);
+        assert_eq!(
+            (quarter.start(), quarter.end()),
+            (date(2022, 1, 1), date(2022, 3, 31))
+        );
+        let quarter = Period::containing(date(2022, 12, 15), Grain::Quarter, &fiscal);
+        assert_eq!(quarter.start(), date(2022, 10, 1));
+    }
+
+    #[test]
+    fn test_nth_day() {
+        let fiscal = FiscalYear::starting(4);
+        let nth = |d: NaiveDate, grain, n| Period::containing(d, grain, &fiscal).nth_day(n);
+
+        assert_eq!(
+            nth(date(2024, 2, 10), Grain::Month, -1),
+            Some(date(2024, 2, 29))
+        );
+        assert_eq!(
+            nth(date(2024, 2, 10), Grain::Month, -29),
+            Some(date(2024, 2, 1))
+        );
+        assert_eq!(nth(date(2024, 2, 10), Grain::Month, 30), None);
+        // the fiscal year starts in April so the fourth quarter ends in March
+        assert_eq!(
+            nth(date(2024, 2, 10), Grain::Quarter, -2),
+            Some(date(2024, 3, 30))
+        );
+        assert_eq!(
+            nth(date(2024, 2, 10), Grain::Year, 1),
+            Some(date(2023, 4, 1))
+        );
+        // 2024-02-10 is a Saturday
+        assert_eq!(
+            nth(date(2024, 2, 10), Grain::Week, -1),
+            Some(date(2024, 2, 11))
+        );
+        assert_eq!(nth(date(2024, 2, 10), Grain::Week, 8), None);
+        assert_eq!(nth(date(2024, 2, 10), Grain::Day, 0), None);
+    }
+
+    #[test]
+    fn test_succ_and_pred() {
+        let month = Period::containing(date(2022, 1, 31), Grain::Month, &FiscalYear::calendar());
+        assert_eq!(month.succ().end(), date(2022, 2, 28));
+        assert_eq!(month.pred().start(), date(2021, 12, 1));
+        assert_eq!(month.succ().pred(), month);
+    }
+
+    #[test]
+    fn test_periods_until() {
+        let week = Period::containing(date(2022, 1, 3), Grain::Week, &FiscalYear::calendar());
+        assert_eq!(week.periods_until(date(2022, 1, 9)), 0);
+        assert_eq!(week.periods_until(date(2022, 1, 10)), 1);
+        assert_eq!(week.periods_until(date(2022, 1, 2)), -1);
+
+        let quarter = Period::containing(date(2022, 4, 1), Grain::Quarter, &FiscalYear::calendar());
+        assert_eq!(quarter.periods_until(date(2023, 3, 31)), 3);
+        assert_eq!(quarter.periods_until(date(2022, 3, 31)), -1);
+    }
+}
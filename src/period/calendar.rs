@@ -0,0 +1,377 @@
+//! Calendar periods as values
+//!
+//! [Week], [YearMonth], [Quarter] and [Year] name one specific period, such as the third quarter of
+//! 2022, rather than a start date and a length. They are always aligned to their calendar
+//! boundaries, stepping from one to the next cannot drift the way shifting a date by a number of
+//! months can, and they can be used anywhere an [IntervalLike] is expected. Fiscal periods are
+//! covered by [Period].
+use std::fmt::Display;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{
+    error::CalendsError,
+    grain::Grain,
+    interval::{bound::Bound, marker},
+    month::Month,
+    IntervalLike, RelativeDuration,
+};
+
+pub use crate::year::Year;
+
+use super::{FiscalYear, Period};
+
+/// An ISO 8601 week, Monday to Sunday, numbered within its ISO week-numbering year
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::period::Week;
+///
+/// // the first of January 2022 was a Saturday in the last week of 2021
+/// let week = Week::of(&NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+/// assert_eq!(week, Week::new(2021, 52)?);
+/// assert_eq!(week.succ(), Week::new(2022, 1)?);
+/// assert_eq!(week.to_string(), "2021-W52");
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Week {
+    year: i32,
+    week: u32,
+}
+
+impl Week {
+    /// The week of the ISO year, the last week of a year is 52 or 53
+    pub fn new(year: i32, week: u32) -> Result<Self, CalendsError> {
+        match NaiveDate::from_isoywd_opt(year, week, Weekday::Mon) {
+            Some(_) => Ok(Week { year, week }),
+            None => Err(CalendsError::InvalidPeriod(format!(
+                "{}-W{:02}",
+                year, week
+            ))),
+        }
+    }
+
+    /// The week containing the date
+    pub fn of(date: &NaiveDate) -> Self {
+        let week = date.iso_week();
+        Week {
+            year: week.year(),
+            week: week.week(),
+        }
+    }
+
+    /// The ISO week-numbering year, which can differ from the calendar year of its days
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn number(&self) -> u32 {
+        self.week
+    }
+
+    /// Monday of the week
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Mon).unwrap()
+    }
+
+    /// Sunday of the week
+    pub fn last_day(&self) -> NaiveDate {
+        NaiveDate::from_isoywd_opt(self.year, self.week, Weekday::Sun).unwrap()
+    }
+
+    pub fn succ(&self) -> Week {
+        Week::of(&(self.last_day() + chrono::Duration::days(1)))
+    }
+
+    pub fn pred(&self) -> Week {
+        Week::of(&(self.first_day() - chrono::Duration::days(1)))
+    }
+}
+
+impl Display for Week {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-W{:02}", self.year, self.week)
+    }
+}
+
+/// A month of a specific year
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{month::Month, period::YearMonth};
+///
+/// let january = YearMonth::new(2024, Month::January);
+/// assert_eq!(january.succ().last_day(), NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+/// assert_eq!(january.pred(), YearMonth::new(2023, Month::December));
+/// assert_eq!(january.month(), Month::January);
+/// assert_eq!(january.to_string(), "2024-01");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct YearMonth {
+    year: i32,
+    month: Month,
+}
+
+impl YearMonth {
+    pub fn new(year: i32, month: Month) -> Self {
+        YearMonth { year, month }
+    }
+
+    /// The month containing the date
+    pub fn of(date: &NaiveDate) -> Self {
+        YearMonth {
+            year: date.year(),
+            month: Month::of(date),
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Number of the month, January being 1
+    pub fn number(&self) -> u32 {
+        self.month.number()
+    }
+
+    /// Which month of the year this is
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month.number(), 1).unwrap()
+    }
+
+    pub fn last_day(&self) -> NaiveDate {
+        let day = self.month.days_in(self.year);
+        NaiveDate::from_ymd_opt(self.year, self.month.number(), day).unwrap()
+    }
+
+    pub fn succ(&self) -> YearMonth {
+        let (month, years) = self.month + 1;
+        YearMonth {
+            year: self.year + years,
+            month,
+        }
+    }
+
+    pub fn pred(&self) -> YearMonth {
+        let (month, years) = self.month - 1;
+        YearMonth {
+            year: self.year + years,
+            month,
+        }
+    }
+}
+
+impl Display for YearMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.number())
+    }
+}
+
+/// A calendar quarter, January to March is the first
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{period::Quarter, IntervalLike};
+///
+/// let q3 = Quarter::new(2022, 3)?;
+/// assert_eq!(q3.iso8601(), "2022-07-01/2022-09-30");
+/// assert!(q3.contains(NaiveDate::from_ymd_opt(2022, 8, 31).unwrap()));
+/// assert_eq!(q3.succ(), Quarter::new(2022, 4)?);
+/// assert_eq!(q3.succ().succ(), Quarter::new(2023, 1)?);
+/// assert_eq!(q3.to_string(), "2022-Q3");
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Quarter {
+    year: i32,
+    quarter: u32,
+}
+
+impl Quarter {
+    /// The quarter of the year numbered from 1
+    pub fn new(year: i32, quarter: u32) -> Result<Self, CalendsError> {
+        match quarter {
+            1..=4 => Ok(Quarter { year, quarter }),
+            _ => Err(CalendsError::InvalidPeriod(format!(
+                "{}-Q{}",
+                year, quarter
+            ))),
+        }
+    }
+
+    /// The quarter containing the date
+    pub fn of(date: &NaiveDate) -> Self {
+        Quarter {
+            year: date.year(),
+            quarter: (date.month() - 1) / 3 + 1,
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn number(&self) -> u32 {
+        self.quarter
+    }
+
+    /// The three months of the quarter
+    pub fn months(&self) -> std::array::IntoIter<YearMonth, 3> {
+        let first = YearMonth::of(&self.first_day());
+        [first, first.succ(), first.succ().succ()].into_iter()
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.quarter * 3 - 2, 1).unwrap()
+    }
+
+    pub fn last_day(&self) -> NaiveDate {
+        self.months().last().unwrap().last_day()
+    }
+
+    pub fn succ(&self) -> Quarter {
+        match self.quarter {
+            4 => Quarter {
+                year: self.year + 1,
+                quarter: 1,
+            },
+            quarter => Quarter {
+                year: self.year,
+                quarter: quarter + 1,
+            },
+        }
+    }
+
+    pub fn pred(&self) -> Quarter {
+        match self.quarter {
+            1 => Quarter {
+                year: self.year - 1,
+                quarter: 4,
+            },
+            quarter => Quarter {
+                year: self.year,
+                quarter: quarter - 1,
+            },
+        }
+    }
+}
+
+impl Display for Quarter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-Q{}", self.year, self.quarter)
+    }
+}
+
+macro_rules! calendar_period {
+    ($($ty:ident => $grain:expr),*) => {
+        $(
+            impl $ty {
+                /// Whether the date falls within the period
+                pub fn contains(&self, date: NaiveDate) -> bool {
+                    self.first_day() <= date && date <= self.last_day()
+                }
+            }
+
+            impl IntervalLike for $ty {
+                fn bound_start(&self) -> Bound<NaiveDate> {
+                    Bound::Included(self.first_day())
+                }
+
+                fn bound_end(&self) -> Bound<NaiveDate> {
+                    Bound::Included(self.last_day())
+                }
+
+                fn duration(&self) -> Option<RelativeDuration> {
+                    Some($grain.into_duration() - RelativeDuration::days(1))
+                }
+            }
+
+            impl marker::Start for $ty {}
+            impl marker::End for $ty {}
+
+            impl From<$ty> for Period {
+                fn from(period: $ty) -> Self {
+                    Period::containing(period.first_day(), $grain, &FiscalYear::calendar())
+                }
+            }
+        )*
+    };
+}
+
+calendar_period!(
+    Week => Grain::Week,
+    YearMonth => Grain::Month,
+    Quarter => Grain::Quarter,
+    Year => Grain::Year
+);
+
+#[cfg(test)]
+mod tests {
+    use quickcheck_macros::quickcheck;
+
+    use super::*;
+    use crate::testing::date;
+
+    #[quickcheck]
+    fn test_periods_are_contiguous(days: u16) -> bool {
+        let date = date(2000, 1, 1) + chrono::Duration::days(days.into());
+        let (week, month, quarter, year) = (
+            Week::of(&date),
+            YearMonth::of(&date),
+            Quarter::of(&date),
+            Year::of(&date),
+        );
+
+        week.contains(date)
+            && month.contains(date)
+            && quarter.contains(date)
+            && year.contains(date)
+            && week.succ().first_day() == week.last_day().succ_opt().unwrap()
+            && week.succ().pred() == week
+            && month.succ().first_day() == month.last_day().succ_opt().unwrap()
+            && month.succ().pred() == month
+            && quarter.succ().first_day() == quarter.last_day().succ_opt().unwrap()
+            && quarter.succ().pred() == quarter
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Week::new(2020, 53).is_ok());
+        assert_eq!(
+            Week::new(2022, 53),
+            Err(CalendsError::InvalidPeriod("2022-W53".to_string()))
+        );
+        assert!(Quarter::new(2022, 5).is_err());
+        assert_eq!(
+            Quarter::new(2024, 1).unwrap().months().last(),
+            Some(YearMonth::new(2024, Month::March))
+        );
+    }
+
+    #[test]
+    fn test_into_period() {
+        let quarter = Quarter::new(2022, 2).unwrap();
+        let period = Period::from(quarter);
+        assert_eq!(
+            (period.start(), period.end()),
+            (quarter.first_day(), quarter.last_day())
+        );
+        assert_eq!(quarter.length_days(), Some(91));
+        assert_eq!(quarter.duration(), period.duration());
+        assert_eq!(
+            Period::from(Week::new(2022, 1).unwrap()).start(),
+            date(2022, 1, 3)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use chrono::NaiveDate;
+
+use crate::grain::Grain;
+
+use super::{FiscalYear, Period};
+
+/// The cohort a signup belongs to
+///
+/// Cohorts are the periods of the chosen grain, aligned using the same calendar rules as
+/// [Period] so that cohort analysis agrees with other period based reports.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::grain::Grain;
+/// # use calends::period::{cohort_of, periods_since, FiscalYear};
+///
+/// let cohort = cohort_of(
+///     NaiveDate::from_ymd_opt(2022, 1, 20).unwrap(),
+///     Grain::Month,
+///     &FiscalYear::calendar(),
+/// );
+///
+/// assert_eq!(cohort.start(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+/// assert_eq!(periods_since(&cohort, NaiveDate::from_ymd_opt(2022, 3, 2).unwrap()), 2);
+/// ```
+pub fn cohort_of(signup_date: NaiveDate, grain: Grain, fiscal: &FiscalYear) -> Period {
+    Period::containing(signup_date, grain, fiscal)
+}
+
+/// The cohort index of a date, the number of whole periods since the cohort period
+///
+/// The cohort's own period is index zero and dates before the cohort are negative.
+pub fn periods_since(cohort: &Period, as_of: NaiveDate) -> i32 {
+    cohort.periods_until(as_of)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_fiscal_cohort_index() {
+        let fiscal = FiscalYear::starting(7);
+        let cohort = cohort_of(date(2022, 6, 30), Grain::Year, &fiscal);
+
+        assert_eq!(cohort.start(), date(2021, 7, 1));
+        assert_eq!(periods_since(&cohort, date(2022, 6, 30)), 0);
+        assert_eq!(periods_since(&cohort, date(2022, 7, 1)), 1);
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::{error::CalendsError, month::Month};
+
+/// Configuration of a fiscal year
+///
+/// A fiscal year is described by the month it starts in. Quarters, halves and years are aligned to
+/// the start of the fiscal year so with a fiscal year starting in April the first quarter is April
+/// to June.
+///
+/// A fiscal year (de)serializes as the number of the month it starts in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u32", into = "u32"))]
+pub struct FiscalYear {
+    start_month: u32,
+}
+
+impl FiscalYear {
+    /// A fiscal year starting on the first day of `start_month`
+    ///
+    /// # Panics
+    ///
+    /// When the month is not within 1 to 12
+    pub fn starting(start_month: u32) -> Self {
+        assert!(
+            (1..=12).contains(&start_month),
+            "fiscal year start month must be between 1 and 12"
+        );
+        FiscalYear { start_month }
+    }
+
+    /// A fiscal year starting on the first day of the month
+    ///
+    /// ```
+    /// # use calends::{month::Month, period::FiscalYear};
+    ///
+    /// assert_eq!(FiscalYear::starting_in(Month::April), FiscalYear::starting(4));
+    /// ```
+    pub fn starting_in(month: Month) -> Self {
+        FiscalYear::starting(month.number())
+    }
+
+    /// A fiscal year which matches the calendar year
+    pub fn calendar() -> Self {
+        FiscalYear::starting(1)
+    }
+
+    /// Month the fiscal year starts in
+    pub fn start_month(&self) -> u32 {
+        self.start_month
+    }
+
+    /// Month the fiscal year starts in
+    pub fn first_month(&self) -> Month {
+        Month::from_number(self.start_month).unwrap()
+    }
+
+    /// Number of months from the start of the fiscal year to the month of the date
+    pub fn months_into_year(&self, date: NaiveDate) -> u32 {
+        (date.month() + 12 - self.start_month) % 12
+    }
+
+    /// First day of the fiscal year containing the date
+    pub fn beginning_of_year(&self, date: NaiveDate) -> NaiveDate {
+        let year = if date.month() >= self.start_month {
+            date.year()
+        } else {
+            date.year() - 1
+        };
+        NaiveDate::from_ymd_opt(year, self.start_month, 1).unwrap()
+    }
+
+    /// The year used to name the fiscal year containing the date
+    ///
+    /// Fiscal years are named after the calendar year they end in, so with a fiscal year starting
+    /// in April the fiscal year from 2022-04-01 to 2023-03-31 is FY2023.
+    pub fn label_year(&self, date: NaiveDate) -> i32 {
+        let start = self.beginning_of_year(date).year();
+        if self.start_month == 1 {
+            start
+        } else {
+            start + 1
+        }
+    }
+}
+
+impl Default for FiscalYear {
+    fn default() -> Self {
+        FiscalYear::calendar()
+    }
+}
+
+impl TryFrom<u32> for FiscalYear {
+    type Error = CalendsError;
+
+    fn try_from(start_month: u32) -> Result<Self, Self::Error> {
+        match start_month {
+            1..=12 => Ok(FiscalYear { start_month }),
+            _ => Err(CalendsError::InvalidMonth(start_month.to_string())),
+        }
+    }
+}
+
+impl From<FiscalYear> for u32 {
+    fn from(fiscal: FiscalYear) -> Self {
+        fiscal.start_month
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_beginning_of_year() {
+        let fiscal = FiscalYear::starting(4);
+        assert_eq!(
+            fiscal.beginning_of_year(date(2022, 3, 31)),
+            date(2021, 4, 1)
+        );
+        assert_eq!(fiscal.beginning_of_year(date(2022, 4, 1)), date(2022, 4, 1));
+        assert_eq!(fiscal.months_into_year(date(2022, 3, 31)), 11);
+    }
+
+    #[test]
+    fn test_label_year() {
+        let june = date(2022, 6, 1);
+        assert_eq!(FiscalYear::calendar().label_year(june), 2022);
+        assert_eq!(FiscalYear::starting(4).label_year(june), 2023);
+        assert_eq!(FiscalYear::starting(7).label_year(june), 2022);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_start_month() {
+        FiscalYear::starting(13);
+    }
+}
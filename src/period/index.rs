@@ -0,0 +1,157 @@
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::grain::Grain;
+
+use super::{FiscalYear, Period};
+
+/// Signed index of the period containing the date, counted from the period containing the epoch
+///
+/// The index is monotone in the date, which makes it a compact key for joining and storing period
+/// based data. With an epoch of 1970-01-01 the monthly index is the number of months since
+/// 1970-01 and the weekly index is the number of weeks since Monday 1969-12-29.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::grain::Grain;
+/// # use calends::period::{period_from_index, period_index, FiscalYear};
+///
+/// let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+/// let fiscal = FiscalYear::calendar();
+/// let date = NaiveDate::from_ymd_opt(2022, 5, 18).unwrap();
+///
+/// let index = period_index(date, Grain::Month, epoch, &fiscal);
+/// assert_eq!(index, 52 * 12 + 4);
+///
+/// let month = period_from_index(index, Grain::Month, epoch, &fiscal).unwrap();
+/// assert_eq!(month.start(), NaiveDate::from_ymd_opt(2022, 5, 1).unwrap());
+/// ```
+pub fn period_index(date: NaiveDate, grain: Grain, epoch: NaiveDate, fiscal: &FiscalYear) -> i64 {
+    let epoch = Period::containing(epoch, grain, fiscal);
+
+    match grain.months() {
+        Some(months) => {
+            let elapsed = month_number(Period::containing(date, grain, fiscal).start())
+                - month_number(epoch.start());
+            elapsed / i64::from(months)
+        }
+        None => date
+            .signed_duration_since(epoch.start())
+            .num_days()
+            .div_euclid(days_in(grain)),
+    }
+}
+
+/// The period at the index given by [period_index]
+///
+/// Returns [None] when the period would be outside of the dates chrono supports.
+pub fn period_from_index(
+    index: i64,
+    grain: Grain,
+    epoch: NaiveDate,
+    fiscal: &FiscalYear,
+) -> Option<Period> {
+    let epoch = Period::containing(epoch, grain, fiscal);
+
+    let start = match grain.months() {
+        Some(months) => {
+            let month = index
+                .checked_mul(i64::from(months))?
+                .checked_add(month_number(epoch.start()))?;
+            NaiveDate::from_ymd_opt(
+                i32::try_from(month.div_euclid(12)).ok()?,
+                month.rem_euclid(12) as u32 + 1,
+                1,
+            )?
+        }
+        None => epoch
+            .start()
+            .checked_add_signed(Duration::try_days(index.checked_mul(days_in(grain))?)?)?,
+    };
+
+    Some(Period::containing(start, grain, fiscal))
+}
+
+/// Months since the start of year zero
+fn month_number(date: NaiveDate) -> i64 {
+    i64::from(date.year()) * 12 + i64::from(date.month0())
+}
+
+fn days_in(grain: Grain) -> i64 {
+    match grain {
+        Grain::Week => 7,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_weeks_before_epoch() {
+        let epoch = date(1970, 1, 1);
+        let fiscal = FiscalYear::calendar();
+
+        assert_eq!(
+            period_index(date(1969, 12, 29), Grain::Week, epoch, &fiscal),
+            0
+        );
+        assert_eq!(
+            period_index(date(1969, 12, 28), Grain::Week, epoch, &fiscal),
+            -1
+        );
+        assert_eq!(
+            period_from_index(-1, Grain::Week, epoch, &fiscal)
+                .unwrap()
+                .start(),
+            date(1969, 12, 22)
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let epoch = date(1970, 1, 1);
+        let fiscal = FiscalYear::starting(4);
+
+        for grain in [
+            Grain::Day,
+            Grain::Week,
+            Grain::Month,
+            Grain::Quarter,
+            Grain::Year,
+        ] {
+            let mut previous = None;
+            for day in date(1968, 11, 1).iter_days().take(900).step_by(13) {
+                let index = period_index(day, grain, epoch, &fiscal);
+                let period = period_from_index(index, grain, epoch, &fiscal).unwrap();
+
+                assert_eq!(
+                    period,
+                    Period::containing(day, grain, &fiscal),
+                    "{:?}",
+                    grain
+                );
+                assert!(previous.is_none_or(|p| p <= index));
+                previous = Some(index);
+            }
+        }
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let epoch = date(1970, 1, 1);
+        let fiscal = FiscalYear::calendar();
+
+        assert_eq!(
+            period_from_index(i64::MAX, Grain::Month, epoch, &fiscal),
+            None
+        );
+        assert_eq!(
+            period_from_index(i64::MIN, Grain::Day, epoch, &fiscal),
+            None
+        );
+    }
+}
@@ -0,0 +1,26 @@
+//! Periods of time aligned to calendar or fiscal boundaries
+pub mod allocate;
+pub mod assign;
+pub mod base;
+pub mod calendar;
+pub mod cohort;
+pub mod fiscal;
+pub mod index;
+pub mod plan;
+pub mod resample;
+pub mod retention;
+pub mod stream;
+pub mod subyear;
+
+pub use allocate::{allocate_by_days, allocate_iso_week_to_months, allocate_month_to_iso_weeks};
+pub use assign::assign_to_periods;
+pub use base::Period;
+pub use calendar::{Quarter, Week, Year, YearMonth};
+pub use cohort::*;
+pub use fiscal::FiscalYear;
+pub use index::{period_from_index, period_index};
+pub use plan::{Adjustment, PlannedPeriod, PlanningHorizon};
+pub use resample::{resample, Resample};
+pub use retention::{expired, retention_cutoff};
+pub use stream::{DateStream, Days, PeriodStream, Periods};
+pub use subyear::{SubYear, SubYearGroup};
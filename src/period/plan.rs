@@ -0,0 +1,257 @@
+use chrono::NaiveDate;
+
+use crate::{
+    error::CalendsError,
+    grain::Grain,
+    interval::{bound::Bound, marker},
+    IntervalLike, RelativeDuration,
+};
+
+use super::{FiscalYear, Period};
+
+/// A labeled period within a plan
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlannedPeriod {
+    pub label: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl PlannedPeriod {
+    pub fn new(label: impl Into<String>, start: NaiveDate, end: NaiveDate) -> Self {
+        PlannedPeriod {
+            label: label.into(),
+            start,
+            end,
+        }
+    }
+}
+
+impl IntervalLike for PlannedPeriod {
+    fn bound_start(&self) -> Bound<NaiveDate> {
+        Bound::Included(self.start)
+    }
+
+    fn bound_end(&self) -> Bound<NaiveDate> {
+        Bound::Included(self.end)
+    }
+
+    fn duration(&self) -> Option<RelativeDuration> {
+        Some(RelativeDuration::from_duration_between(
+            self.start, self.end,
+        ))
+    }
+}
+
+impl marker::Start for PlannedPeriod {}
+impl marker::End for PlannedPeriod {}
+
+/// A change to the generated period with a given label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Adjustment {
+    /// Rename the period
+    Relabel(String),
+    /// Change the first and last day of the period
+    Resize(NaiveDate, NaiveDate),
+    /// Add another period directly after the period, e.g. a 13th adjustment period
+    InsertAfter(PlannedPeriod),
+    /// Drop the period from the plan
+    Remove,
+}
+
+/// A planning horizon expanded into labeled periods
+///
+/// The horizon is split into whole periods of the chosen grain, aligned and labeled according to
+/// the fiscal year: `FY2023 P01` for months, `FY2023 Q1` for quarters, `FY2023 H1` for halves,
+/// `FY2023` for years and `FY2023 W01` for weeks. Days are labeled with their date. Adjustments
+/// are applied in the order they were added, matching on the label of the period at that point.
+/// An adjustment to a label which no period has at that point is an error.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::grain::Grain;
+/// # use calends::period::{Adjustment, FiscalYear, PlannedPeriod, PlanningHorizon};
+///
+/// let year_end = NaiveDate::from_ymd_opt(2023, 3, 31).unwrap();
+/// let periods = PlanningHorizon::new(
+///     NaiveDate::from_ymd_opt(2022, 4, 1).unwrap(),
+///     year_end,
+///     Grain::Month,
+///     FiscalYear::starting(4),
+/// )
+/// .adjust(
+///     "FY2023 P12",
+///     Adjustment::InsertAfter(PlannedPeriod::new("FY2023 P13", year_end, year_end)),
+/// )
+/// .periods()?;
+///
+/// assert_eq!(periods.len(), 13);
+/// assert_eq!(periods[0].label, "FY2023 P01");
+/// assert_eq!(periods[12].label, "FY2023 P13");
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanningHorizon {
+    start: NaiveDate,
+    end: NaiveDate,
+    grain: Grain,
+    fiscal: FiscalYear,
+    adjustments: Vec<(String, Adjustment)>,
+}
+
+impl PlanningHorizon {
+    /// A horizon covering every period which overlaps the dates from `start` to `end` inclusive
+    pub fn new(start: NaiveDate, end: NaiveDate, grain: Grain, fiscal: FiscalYear) -> Self {
+        PlanningHorizon {
+            start,
+            end,
+            grain,
+            fiscal,
+            adjustments: Vec::new(),
+        }
+    }
+
+    /// Adjust the period with the given label
+    pub fn adjust(mut self, label: impl Into<String>, adjustment: Adjustment) -> Self {
+        self.adjustments.push((label.into(), adjustment));
+        self
+    }
+
+    /// Expand the horizon into its periods and apply the adjustments
+    pub fn periods(&self) -> Result<Vec<PlannedPeriod>, CalendsError> {
+        let mut periods = Vec::new();
+        let mut period = Period::containing(self.start, self.grain, &self.fiscal);
+        while period.start() <= self.end {
+            periods.push(PlannedPeriod::new(
+                self.label(&period),
+                period.start(),
+                period.end(),
+            ));
+            period = period.succ();
+        }
+
+        for (label, adjustment) in self.adjustments.iter() {
+            let index = periods
+                .iter()
+                .position(|p| &p.label == label)
+                .ok_or_else(|| CalendsError::UnknownPeriod(label.clone()))?;
+
+            match adjustment {
+                Adjustment::Relabel(label) => periods[index].label = label.clone(),
+                Adjustment::Resize(start, end) => {
+                    periods[index].start = *start;
+                    periods[index].end = *end;
+                }
+                Adjustment::InsertAfter(period) => periods.insert(index + 1, period.clone()),
+                Adjustment::Remove => {
+                    periods.remove(index);
+                }
+            }
+        }
+
+        Ok(periods)
+    }
+
+    fn label(&self, period: &Period) -> String {
+        let start = period.start();
+        let year = self.fiscal.label_year(start);
+        let months = self.fiscal.months_into_year(start);
+
+        match period.grain() {
+            Grain::Day => start.to_string(),
+            Grain::Week => {
+                let weeks = start
+                    .signed_duration_since(self.fiscal.beginning_of_year(start))
+                    .num_weeks();
+                format!("FY{} W{:0>2}", year, weeks + 1)
+            }
+            Grain::Month => format!("FY{} P{:0>2}", year, months + 1),
+            Grain::Quarter => format!("FY{} Q{}", year, months / 3 + 1),
+            Grain::Half => format!("FY{} H{}", year, months / 6 + 1),
+            Grain::Year | Grain::Lustrum | Grain::Decade | Grain::Century => format!("FY{}", year),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_quarters_cover_horizon() {
+        let periods = PlanningHorizon::new(
+            date(2022, 2, 15),
+            date(2022, 8, 1),
+            Grain::Quarter,
+            FiscalYear::calendar(),
+        )
+        .periods()
+        .unwrap();
+
+        let labels: Vec<&str> = periods.iter().map(|p| p.label.as_str()).collect();
+        assert_eq!(labels, vec!["FY2022 Q1", "FY2022 Q2", "FY2022 Q3"]);
+        assert_eq!(periods[0].start, date(2022, 1, 1));
+        assert_eq!(periods[2].end, date(2022, 9, 30));
+    }
+
+    #[test]
+    fn test_adjustments() {
+        let periods = PlanningHorizon::new(
+            date(2022, 1, 1),
+            date(2022, 12, 31),
+            Grain::Month,
+            FiscalYear::calendar(),
+        )
+        .adjust(
+            "FY2022 P12",
+            Adjustment::Resize(date(2022, 12, 1), date(2022, 12, 30)),
+        )
+        .adjust(
+            "FY2022 P12",
+            Adjustment::InsertAfter(PlannedPeriod::new(
+                "FY2022 P13",
+                date(2022, 12, 31),
+                date(2022, 12, 31),
+            )),
+        )
+        .adjust("FY2022 P01", Adjustment::Relabel("Opening".to_string()))
+        .adjust("FY2022 P02", Adjustment::Remove)
+        .periods()
+        .unwrap();
+
+        assert_eq!(periods.len(), 12);
+        assert_eq!(periods[0].label, "Opening");
+        assert_eq!(periods[1].label, "FY2022 P03");
+        assert_eq!(periods[10].end, date(2022, 12, 30));
+        assert!(periods[11].within(date(2022, 12, 31)));
+    }
+
+    #[test]
+    fn test_adjusting_an_unknown_label() {
+        let horizon = PlanningHorizon::new(
+            date(2022, 1, 1),
+            date(2022, 12, 31),
+            Grain::Month,
+            FiscalYear::calendar(),
+        );
+
+        assert_eq!(
+            horizon
+                .clone()
+                .adjust("FY2022 P13", Adjustment::Remove)
+                .periods(),
+            Err(CalendsError::UnknownPeriod("FY2022 P13".to_string()))
+        );
+        // the label is gone once the period has been relabeled
+        assert_eq!(
+            horizon
+                .adjust("FY2022 P01", Adjustment::Relabel("Opening".to_string()))
+                .adjust("FY2022 P01", Adjustment::Remove)
+                .periods(),
+            Err(CalendsError::UnknownPeriod("FY2022 P01".to_string()))
+        );
+    }
+}
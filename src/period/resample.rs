@@ -0,0 +1,171 @@
+//! Converting period keyed series between grains
+use std::collections::BTreeMap;
+
+use crate::{error::CalendsError, grain::Grain};
+
+use super::{allocate_by_days, FiscalYear, Period};
+
+/// How values are combined or divided when resampling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resample {
+    /// Add the values of every period falling in the coarser period
+    Sum,
+    /// Average the values of every period falling in the coarser period
+    Mean,
+    /// Divide each value equally between the finer periods it overlaps
+    Even,
+    /// Divide each value between the finer periods in proportion to the days they overlap
+    DayWeighted,
+}
+
+impl Resample {
+    fn aggregates(&self) -> bool {
+        matches!(self, Resample::Sum | Resample::Mean)
+    }
+}
+
+/// Convert a series to another grain
+///
+/// [Sum](Resample::Sum) and [Mean](Resample::Mean) aggregate to a coarser grain. Each period is
+/// placed in the coarser period containing its start, so a week which straddles two months
+/// counts towards the month it starts in.
+///
+/// [Even](Resample::Even) and [DayWeighted](Resample::DayWeighted) spread to a finer grain.
+/// Finer periods which straddle two coarser periods, such as weeks at the turn of the month,
+/// receive a share from each.
+///
+/// Resampling to the same grain returns the series unchanged, resampling in the wrong direction
+/// for the method fails.
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::BTreeMap;
+/// # use chrono::NaiveDate;
+/// # use calends::grain::Grain;
+/// # use calends::period::{resample, FiscalYear, Period, Periods, Resample};
+///
+/// let fiscal = FiscalYear::calendar();
+/// let monthly: BTreeMap<Period, f64> = Periods::months(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap())
+///     .take(3)
+///     .zip([10.0, 20.0, 30.0])
+///     .collect();
+///
+/// let quarterly = resample(&monthly, Grain::Quarter, Resample::Sum, &fiscal)?;
+/// assert_eq!(quarterly.values().collect::<Vec<_>>(), vec![&60.0]);
+///
+/// let daily = resample(&monthly, Grain::Day, Resample::Even, &fiscal)?;
+/// assert_eq!(daily.len(), 31 + 28 + 31);
+/// assert!((daily.values().sum::<f64>() - 60.0).abs() < 1e-9);
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+pub fn resample(
+    series: &BTreeMap<Period, f64>,
+    to: Grain,
+    method: Resample,
+    fiscal: &FiscalYear,
+) -> Result<BTreeMap<Period, f64>, CalendsError> {
+    let mut resampled: BTreeMap<Period, f64> = BTreeMap::new();
+    let mut counts: BTreeMap<Period, usize> = BTreeMap::new();
+
+    for (period, value) in series {
+        let from = period.grain();
+        if from == to {
+            *resampled.entry(*period).or_default() += value;
+            *counts.entry(*period).or_default() += 1;
+            continue;
+        }
+        if method.aggregates() != (from < to) {
+            return Err(CalendsError::InvalidResample { from, to });
+        }
+
+        if method.aggregates() {
+            let target = Period::containing(period.start(), to, fiscal);
+            *resampled.entry(target).or_default() += value;
+            *counts.entry(target).or_default() += 1;
+        } else {
+            let targets = allocate_by_days(period, *value, to, fiscal);
+            let count = targets.len() as f64;
+
+            for (target, share) in targets {
+                let share = match method {
+                    Resample::Even => value / count,
+                    _ => share,
+                };
+                *resampled.entry(target).or_default() += share;
+            }
+        }
+    }
+
+    if method == Resample::Mean {
+        for (period, value) in resampled.iter_mut() {
+            *value /= counts.get(period).copied().unwrap_or(1) as f64;
+        }
+    }
+
+    Ok(resampled)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::testing::date;
+
+    fn month(year: i32, month: u32) -> Period {
+        Period::containing(date(year, month, 1), Grain::Month, &FiscalYear::calendar())
+    }
+
+    #[test]
+    fn test_mean() {
+        let fiscal = FiscalYear::starting(4);
+        let series: BTreeMap<Period, f64> = [
+            (month(2022, 3), 1.0),
+            (month(2022, 4), 2.0),
+            (month(2022, 5), 4.0),
+        ]
+        .into();
+
+        let quarters = resample(&series, Grain::Quarter, Resample::Mean, &fiscal).unwrap();
+        let values: Vec<(NaiveDate, f64)> = quarters.iter().map(|(p, v)| (p.start(), *v)).collect();
+        assert_eq!(
+            values,
+            vec![(date(2022, 1, 1), 1.0), (date(2022, 4, 1), 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_day_weighted_weeks_straddle_months() {
+        let fiscal = FiscalYear::calendar();
+        let series: BTreeMap<Period, f64> = [(month(2022, 1), 31.0), (month(2022, 2), 56.0)].into();
+
+        let weeks = resample(&series, Grain::Week, Resample::DayWeighted, &fiscal).unwrap();
+        let week = |d| Period::containing(d, Grain::Week, &fiscal);
+
+        // the week of 2021-12-27 has two days in January
+        assert!((weeks[&week(date(2021, 12, 27))] - 2.0).abs() < 1e-9);
+        // the week of 2022-01-31 has one day in January and six in February
+        assert!((weeks[&week(date(2022, 1, 31))] - 13.0).abs() < 1e-9);
+        assert!((weeks.values().sum::<f64>() - 87.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_wrong_direction() {
+        let series: BTreeMap<Period, f64> = [(month(2022, 1), 1.0)].into();
+        let fiscal = FiscalYear::calendar();
+
+        assert_eq!(
+            resample(&series, Grain::Day, Resample::Sum, &fiscal),
+            Err(CalendsError::InvalidResample {
+                from: Grain::Month,
+                to: Grain::Day
+            })
+        );
+        assert!(resample(&series, Grain::Year, Resample::Even, &fiscal).is_err());
+        assert_eq!(
+            resample(&series, Grain::Month, Resample::Even, &fiscal),
+            Ok(series)
+        );
+    }
+}
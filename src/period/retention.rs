@@ -0,0 +1,125 @@
+use chrono::NaiveDate;
+
+use crate::{
+    grain::Grain,
+    interval::{
+        marker::{End, Start},
+        ClosedInterval, IntervalSet,
+    },
+    RelativeDuration,
+};
+
+use super::{FiscalYear, Period};
+
+/// Earliest date to retain when keeping data for a duration, snapped back to a period boundary
+///
+/// The cutoff is the start of the period containing `as_of - keep`, so the retained data always
+/// covers whole periods and never less than the duration. Keeping 13 months of monthly data as of
+/// 2022-05-18 retains everything from 2021-04-01.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{grain::Grain, RelativeDuration};
+/// # use calends::period::{retention_cutoff, FiscalYear};
+///
+/// let as_of = NaiveDate::from_ymd_opt(2022, 5, 18).unwrap();
+///
+/// assert_eq!(
+///     retention_cutoff(as_of, RelativeDuration::months(13), Grain::Month, &FiscalYear::calendar()),
+///     NaiveDate::from_ymd_opt(2021, 4, 1).unwrap()
+/// );
+/// ```
+pub fn retention_cutoff(
+    as_of: NaiveDate,
+    keep: RelativeDuration,
+    snap: Grain,
+    fiscal: &FiscalYear,
+) -> NaiveDate {
+    Period::containing(as_of + -keep, snap, fiscal).start()
+}
+
+/// The dates of the set which fall before the cutoff and can be deleted
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::interval::{ClosedInterval, IntervalSet};
+/// # use calends::period::expired;
+/// # use calends::IntervalLike;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+///
+/// let mut backups = IntervalSet::new();
+/// backups.insert(&ClosedInterval::with_dates(date(1, 1), date(1, 31)));
+/// backups.insert(&ClosedInterval::with_dates(date(3, 1), date(3, 31)));
+///
+/// let expired = expired(&backups, date(1, 15));
+/// assert_eq!(expired.iter().map(|i| i.iso8601()).collect::<Vec<_>>(), vec!["2022-01-01/2022-01-14"]);
+/// ```
+pub fn expired(set: &IntervalSet, cutoff: NaiveDate) -> IntervalSet {
+    let mut expired = IntervalSet::new();
+    if let Some(last) = cutoff.pred_opt() {
+        for interval in set.iter() {
+            let (start, end) = (interval.start(), interval.end());
+            if start <= last {
+                expired.insert(&ClosedInterval::with_dates_unchecked(start, end.min(last)));
+            }
+        }
+    }
+    expired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_cutoff_snaps_to_fiscal_periods() {
+        let as_of = date(2022, 5, 18);
+
+        assert_eq!(
+            retention_cutoff(
+                as_of,
+                RelativeDuration::months(6),
+                Grain::Quarter,
+                &FiscalYear::starting(2)
+            ),
+            date(2021, 11, 1)
+        );
+        assert_eq!(
+            retention_cutoff(
+                as_of,
+                RelativeDuration::days(30),
+                Grain::Week,
+                &FiscalYear::calendar()
+            ),
+            date(2022, 4, 18)
+        );
+        assert_eq!(
+            retention_cutoff(
+                as_of,
+                RelativeDuration::zero(),
+                Grain::Day,
+                &FiscalYear::calendar()
+            ),
+            as_of
+        );
+    }
+
+    #[test]
+    fn test_expired_excludes_the_cutoff() {
+        let mut set = IntervalSet::new();
+        set.insert(&ClosedInterval::with_dates(
+            date(2022, 1, 10),
+            date(2022, 1, 20),
+        ));
+
+        assert!(expired(&set, date(2022, 1, 10)).is_empty());
+        assert!(expired(&set, date(2022, 1, 11)).contains(date(2022, 1, 10)));
+        assert_eq!(expired(&set, date(2022, 2, 1)), set);
+    }
+}
@@ -0,0 +1,323 @@
+//! Lazy streams of periods
+//!
+//! [Periods] is an infinite iterator of consecutive periods. Together with the adaptors of
+//! [PeriodStream] and [DateStream] and the standard iterator combinators it allows calendar
+//! pipelines to be written without collecting intermediate results:
+//!
+//! ```
+//! # use chrono::NaiveDate;
+//! # use calends::business::Calendar;
+//! # use calends::grain::Grain;
+//! # use calends::period::{DateStream, PeriodStream, Periods};
+//!
+//! let calendar = Calendar::new().with_holidays([NaiveDate::from_ymd_opt(2022, 4, 1).unwrap()]);
+//!
+//! // the first working day of each quarter
+//! let first_days: Vec<NaiveDate> = Periods::months(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap())
+//!     .step_by(3)
+//!     .take(4)
+//!     .map(|quarter| quarter.days().business_days(&calendar).next().unwrap())
+//!     .collect();
+//!
+//! assert_eq!(first_days[1], NaiveDate::from_ymd_opt(2022, 4, 4).unwrap());
+//! ```
+use chrono::{Duration, NaiveDate};
+
+use crate::{business::RollPolicy, grain::Grain, holidays::HolidayCalendar, Interval};
+
+use super::{FiscalYear, Period};
+
+/// Infinite iterator of consecutive periods, ascending unless made [descending](Periods::descending)
+#[derive(Debug, Clone)]
+pub struct Periods {
+    next: Period,
+    descending: bool,
+}
+
+impl Periods {
+    /// Consecutive periods beginning with the period supplied
+    pub fn starting(period: Period) -> Self {
+        Periods {
+            next: period,
+            descending: false,
+        }
+    }
+
+    /// Run backwards from the first period, e.g. the last twelve complete months as of a date
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use calends::period::{PeriodStream, Periods};
+    ///
+    /// let as_of = NaiveDate::from_ymd_opt(2022, 3, 15).unwrap();
+    /// let months: Vec<NaiveDate> = Periods::months(as_of)
+    ///     .descending()
+    ///     .skip(1)
+    ///     .take(12)
+    ///     .starts()
+    ///     .collect();
+    ///
+    /// assert_eq!(months[0], NaiveDate::from_ymd_opt(2022, 2, 1).unwrap());
+    /// assert_eq!(months[11], NaiveDate::from_ymd_opt(2021, 3, 1).unwrap());
+    /// ```
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    /// Consecutive periods beginning with the calendar period of the grain containing the date
+    pub fn containing(date: NaiveDate, grain: Grain) -> Self {
+        Periods::starting(Period::containing(date, grain, &FiscalYear::calendar()))
+    }
+
+    /// Consecutive months beginning with the month containing the date
+    pub fn months(date: NaiveDate) -> Self {
+        Periods::containing(date, Grain::Month)
+    }
+}
+
+impl Iterator for Periods {
+    type Item = Period;
+
+    fn next(&mut self) -> Option<Period> {
+        let period = self.next;
+        self.next = if self.descending {
+            period.pred()
+        } else {
+            period.succ()
+        };
+        Some(period)
+    }
+}
+
+/// Every day from the first date to the last date inclusive, in either direction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Days {
+    start: NaiveDate,
+    front: i64,
+    back: i64,
+}
+
+impl Days {
+    /// The days from `first` to `last`, none when `last` is before `first`
+    pub fn between(first: NaiveDate, last: NaiveDate) -> Self {
+        Days {
+            start: first,
+            front: 0,
+            back: (last - first).num_days() + 1,
+        }
+    }
+}
+
+impl Iterator for Days {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        (self.front < self.back).then(|| {
+            self.front += 1;
+            self.start + Duration::days(self.front - 1)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::try_from(self.back - self.front).unwrap_or(0);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Days {
+    fn next_back(&mut self) -> Option<NaiveDate> {
+        (self.front < self.back).then(|| {
+            self.back -= 1;
+            self.start + Duration::days(self.back)
+        })
+    }
+}
+
+impl ExactSizeIterator for Days {}
+
+impl std::iter::FusedIterator for Days {}
+
+impl Period {
+    /// Every day of the period in order, use `.rev()` for the days in reverse
+    pub fn days(&self) -> Days {
+        Days::between(self.start(), self.end())
+    }
+}
+
+/// Adaptors for iterators of periods
+///
+/// The adaptors return named types so they can be stored in structs.
+pub trait PeriodStream: Iterator<Item = Period> + Sized {
+    /// Each period as an [Interval]
+    fn map_to_intervals(self) -> std::iter::Map<Self, fn(Period) -> Interval> {
+        self.map(|period| period.into_interval())
+    }
+
+    /// The first day of each period
+    fn starts(self) -> std::iter::Map<Self, fn(Period) -> NaiveDate> {
+        self.map(|period| period.start())
+    }
+
+    /// The last day of each period
+    fn ends(self) -> std::iter::Map<Self, fn(Period) -> NaiveDate> {
+        self.map(|period| period.end())
+    }
+
+    /// Every day of every period in order
+    fn days(self) -> std::iter::FlatMap<Self, Days, fn(Period) -> Days> {
+        self.flat_map(|period| period.days())
+    }
+}
+
+impl<I: Iterator<Item = Period>> PeriodStream for I {}
+
+/// Adaptors for iterators of dates
+pub trait DateStream: Iterator<Item = NaiveDate> + Sized {
+    /// Drop dates which are holidays of the calendar, weekends are kept
+    fn skip_holidays<'a, C: HolidayCalendar + ?Sized>(
+        self,
+        calendar: &'a C,
+    ) -> impl Iterator<Item = NaiveDate> + 'a
+    where
+        Self: 'a,
+    {
+        self.filter(|date| !calendar.is_holiday(*date))
+    }
+
+    /// Keep only the business days of the calendar, dropping both weekends and holidays
+    fn business_days<'a, C: HolidayCalendar + ?Sized>(
+        self,
+        calendar: &'a C,
+    ) -> impl Iterator<Item = NaiveDate> + 'a
+    where
+        Self: 'a,
+    {
+        self.filter(|date| calendar.is_business_day(*date))
+    }
+
+    /// Move dates which are not business days of the calendar with the roll policy
+    ///
+    /// A date rolled onto the same day as the date before it is only given once, so an ascending
+    /// stream stays strictly ascending.
+    ///
+    /// ```
+    /// # use chrono::NaiveDate;
+    /// # use calends::{business::{Calendar, RollPolicy}, period::DateStream, Recurrence, Rule};
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    ///
+    /// // paid on the 15th and the last day, brought forward from weekends
+    /// let rule = Rule::MonthDays(vec![15.try_into()?, (-1).try_into()?]);
+    /// let paydays: Vec<NaiveDate> = Recurrence::with_start(rule, date(1, 1))
+    ///     .roll(RollPolicy::Preceding, &Calendar::new())
+    ///     .take(4)
+    ///     .collect();
+    /// assert_eq!(paydays, vec![date(1, 14), date(1, 31), date(2, 15), date(2, 28)]);
+    /// # Ok::<(), calends::CalendsError>(())
+    /// ```
+    fn roll<'a, C: HolidayCalendar + ?Sized>(
+        self,
+        policy: RollPolicy,
+        calendar: &'a C,
+    ) -> impl Iterator<Item = NaiveDate> + 'a
+    where
+        Self: 'a,
+    {
+        let mut last = None;
+        self.map(move |date| policy.apply(date, calendar))
+            .filter(move |date| last.replace(*date) != Some(*date))
+    }
+}
+
+impl<I: Iterator<Item = NaiveDate>> DateStream for I {}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Datelike;
+
+    use crate::{testing::date, IntervalLike};
+
+    use super::*;
+    use crate::business::Calendar;
+
+    #[test]
+    fn test_days_from_both_ends() {
+        let mut days = Days::between(date(2022, 2, 27), date(2022, 3, 2));
+        assert_eq!(days.len(), 4);
+        assert_eq!(days.next(), Some(date(2022, 2, 27)));
+        assert_eq!(days.next_back(), Some(date(2022, 3, 2)));
+        assert_eq!(days.next_back(), Some(date(2022, 3, 1)));
+        assert_eq!(days.len(), 1);
+        assert_eq!(days.next(), Some(date(2022, 2, 28)));
+        assert_eq!((days.next(), days.next_back()), (None, None));
+
+        assert_eq!(Days::between(date(2022, 1, 2), date(2022, 1, 1)).count(), 0);
+    }
+
+    #[test]
+    fn test_adaptors_can_be_stored() {
+        struct Axis {
+            ticks: std::iter::Map<Periods, fn(Period) -> NaiveDate>,
+            days: Days,
+        }
+
+        let mut axis = Axis {
+            ticks: Periods::months(date(2022, 1, 15)).starts(),
+            days: Periods::months(date(2022, 2, 1)).next().unwrap().days(),
+        };
+        assert_eq!(axis.ticks.nth(1), Some(date(2022, 2, 1)));
+        assert_eq!(axis.days.next_back(), Some(date(2022, 2, 28)));
+    }
+
+    #[test]
+    fn test_periods_step_and_take_while() {
+        let quarters: Vec<NaiveDate> = Periods::months(date(2022, 2, 14))
+            .step_by(3)
+            .take_while(|p| p.start().year() < 2023)
+            .starts()
+            .collect();
+
+        assert_eq!(
+            quarters,
+            vec![
+                date(2022, 2, 1),
+                date(2022, 5, 1),
+                date(2022, 8, 1),
+                date(2022, 11, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_descending() {
+        let quarters: Vec<Period> = Periods::containing(date(2022, 2, 14), Grain::Quarter)
+            .descending()
+            .take(3)
+            .collect();
+
+        assert_eq!(quarters[2].start(), date(2021, 7, 1));
+        assert_eq!(quarters[2].days().next_back(), Some(date(2021, 9, 30)));
+    }
+
+    #[test]
+    fn test_map_to_intervals() {
+        let weeks: Vec<Interval> = Periods::containing(date(2022, 1, 5), Grain::Week)
+            .take(2)
+            .map_to_intervals()
+            .collect();
+
+        assert_eq!(weeks[0].start_opt(), Some(date(2022, 1, 3)));
+        assert_eq!(weeks[1].end_opt(), Some(date(2022, 1, 16)));
+    }
+
+    #[test]
+    fn test_date_adaptors() {
+        let calendar = Calendar::new().with_holidays([date(2022, 1, 3)]);
+        let january = || Periods::months(date(2022, 1, 1)).take(1).days();
+
+        assert_eq!(january().count(), 31);
+        assert_eq!(january().skip_holidays(&calendar).count(), 30);
+        assert_eq!(january().business_days(&calendar).count(), 20);
+    }
+}
@@ -0,0 +1,351 @@
+//! ISO8601-2:2019 sub-year groupings
+//!
+//! ISO8601-2 extends the month field of a year and month with codes for groups of months, e.g.
+//! `2022-21` for the spring of 2022 and `2022-33` for its first quarter. Statistical data feeds
+//! use them to label seasonal and quarterly series.
+//!
+//! | Code  | Grouping                                    |
+//! |-------|---------------------------------------------|
+//! | 21-24 | Spring, summer, autumn, winter              |
+//! | 25-28 | Spring to winter in the northern hemisphere |
+//! | 29-32 | Spring to winter in the southern hemisphere |
+//! | 33-36 | Quarters 1 to 4                             |
+//! | 37-39 | Quadrimesters 1 to 3, four months each      |
+//! | 40-41 | Semesters 1 and 2, six months each          |
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDate;
+use nom::{bytes::complete::tag, combinator::all_consuming, IResult};
+
+use crate::{error::CalendsError, grain::Grain, interval::ClosedInterval, parser::take_n_digits};
+
+use super::{FiscalYear, Period};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Hemisphere {
+    Northern,
+    Southern,
+}
+
+/// A grouping of months within a year
+///
+/// Quarters, quadrimesters and semesters are numbered from 1, the constructors fail on numbers
+/// outside of the year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubYear(Group);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Group {
+    /// A season, [None] when the code does not name a hemisphere
+    Season(Season, Option<Hemisphere>),
+    Quarter(u32),
+    Quadrimester(u32),
+    Semester(u32),
+}
+
+impl SubYear {
+    /// A season, [None] when the grouping does not name a hemisphere
+    pub fn season(season: Season, hemisphere: Option<Hemisphere>) -> SubYear {
+        SubYear(Group::Season(season, hemisphere))
+    }
+
+    /// Quarter 1 to 4
+    pub fn quarter(n: u32) -> Result<SubYear, CalendsError> {
+        SubYear::numbered(Group::Quarter, "quarter", n, 4)
+    }
+
+    /// Quadrimester 1 to 3
+    pub fn quadrimester(n: u32) -> Result<SubYear, CalendsError> {
+        SubYear::numbered(Group::Quadrimester, "quadrimester", n, 3)
+    }
+
+    /// Semester 1 or 2
+    pub fn semester(n: u32) -> Result<SubYear, CalendsError> {
+        SubYear::numbered(Group::Semester, "semester", n, 2)
+    }
+
+    fn numbered(
+        group: fn(u32) -> Group,
+        name: &str,
+        n: u32,
+        count: u32,
+    ) -> Result<SubYear, CalendsError> {
+        if (1..=count).contains(&n) {
+            Ok(SubYear(group(n)))
+        } else {
+            Err(CalendsError::InvalidSubYear(format!("{} {}", name, n)))
+        }
+    }
+
+    /// The season and hemisphere of a seasonal grouping
+    pub fn as_season(&self) -> Option<(Season, Option<Hemisphere>)> {
+        match self.0 {
+            Group::Season(season, hemisphere) => Some((season, hemisphere)),
+            _ => None,
+        }
+    }
+
+    /// The number of a quarter
+    pub fn as_quarter(&self) -> Option<u32> {
+        match self.0 {
+            Group::Quarter(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// The number of a quadrimester
+    pub fn as_quadrimester(&self) -> Option<u32> {
+        match self.0 {
+            Group::Quadrimester(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// The number of a semester
+    pub fn as_semester(&self) -> Option<u32> {
+        match self.0 {
+            Group::Semester(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// The ISO8601-2 code of the grouping
+    pub fn code(&self) -> u32 {
+        match self.0 {
+            Group::Season(season, hemisphere) => {
+                let base = match hemisphere {
+                    None => 21,
+                    Some(Hemisphere::Northern) => 25,
+                    Some(Hemisphere::Southern) => 29,
+                };
+                base + season as u32
+            }
+            Group::Quarter(n) => 32 + n,
+            Group::Quadrimester(n) => 36 + n,
+            Group::Semester(n) => 39 + n,
+        }
+    }
+
+    /// The grouping with the ISO8601-2 code
+    pub fn from_code(code: u32) -> Option<SubYear> {
+        const SEASONS: [Season; 4] = [
+            Season::Spring,
+            Season::Summer,
+            Season::Autumn,
+            Season::Winter,
+        ];
+        let season = |base: u32| SEASONS[(code - base) as usize];
+
+        let group = match code {
+            21..=24 => Group::Season(season(21), None),
+            25..=28 => Group::Season(season(25), Some(Hemisphere::Northern)),
+            29..=32 => Group::Season(season(29), Some(Hemisphere::Southern)),
+            33..=36 => Group::Quarter(code - 32),
+            37..=39 => Group::Quadrimester(code - 36),
+            40..=41 => Group::Semester(code - 39),
+            _ => return None,
+        };
+        Some(SubYear(group))
+    }
+}
+
+/// A sub-year grouping of a particular year such as `2022-21`
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::period::subyear::{SubYear, SubYearGroup};
+/// # use calends::IntervalLike;
+///
+/// let q1: SubYearGroup = "2022-33".parse()?;
+/// assert_eq!(q1.group(), SubYear::quarter(1)?);
+/// assert_eq!(q1.period().unwrap().end(), NaiveDate::from_ymd_opt(2022, 3, 31).unwrap());
+///
+/// let spring: SubYearGroup = "2022-21".parse()?;
+/// assert_eq!(spring.interval().iso8601(), "2022-03-01/2022-05-31");
+/// assert_eq!(spring.to_string(), "2022-21");
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubYearGroup {
+    year: i32,
+    group: SubYear,
+}
+
+impl SubYearGroup {
+    /// The grouping within the year, failing when its months are outside the range of dates
+    /// chrono supports
+    pub fn new(year: i32, group: SubYear) -> Result<Self, CalendsError> {
+        let sub_year = SubYearGroup { year, group };
+        match sub_year.try_interval() {
+            Some(_) => Ok(sub_year),
+            None => Err(CalendsError::InvalidSubYear(sub_year.to_string())),
+        }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn group(&self) -> SubYear {
+        self.group
+    }
+
+    /// The calendar period of quarters and semesters, [None] for groupings without a [Grain]
+    pub fn period(&self) -> Option<Period> {
+        let (grain, n) = match self.group.0 {
+            Group::Quarter(n) => (Grain::Quarter, n),
+            Group::Semester(n) => (Grain::Half, n),
+            _ => return None,
+        };
+        let months = grain.months().unwrap() as u32;
+        let start = NaiveDate::from_ymd_opt(self.year, (n - 1) * months + 1, 1)?;
+        Some(Period::containing(start, grain, &FiscalYear::calendar()))
+    }
+
+    /// The months covered by the grouping
+    ///
+    /// Seasons are meteorological seasons, which start on the first of March, June, September and
+    /// December. Seasons which do not name a hemisphere are taken as northern. A winter starts in
+    /// December of its year and ends in February of the next.
+    pub fn interval(&self) -> ClosedInterval {
+        // the constructor checks that every date of the grouping is supported
+        self.try_interval().unwrap()
+    }
+
+    fn try_interval(&self) -> Option<ClosedInterval> {
+        let (first, months) = match self.group.0 {
+            Group::Season(season, hemisphere) => {
+                let shift = match hemisphere {
+                    Some(Hemisphere::Southern) => 2,
+                    _ => 0,
+                };
+                ((season as u32 + shift) % 4 * 3 + 3, 3)
+            }
+            Group::Quarter(n) => ((n - 1) * 3 + 1, 3),
+            Group::Quadrimester(n) => ((n - 1) * 4 + 1, 4),
+            Group::Semester(n) => ((n - 1) * 6 + 1, 6),
+        };
+        let start = NaiveDate::from_ymd_opt(self.year, first, 1)?;
+        let duration = crate::RelativeDuration::months(months).with_days(-1);
+        ClosedInterval::try_from_start(start, duration).ok()
+    }
+}
+
+fn parse_sub_year(i: &[u8]) -> IResult<&[u8], SubYearGroup> {
+    let (i, year) = take_n_digits(i, 4)?;
+    let (i, _) = tag(b"-")(i)?;
+    let (rest, code) = take_n_digits(i, 2)?;
+
+    match SubYear::from_code(code).and_then(|group| SubYearGroup::new(year as i32, group).ok()) {
+        Some(sub_year) => Ok((rest, sub_year)),
+        None => Err(nom::Err::Error(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+impl FromStr for SubYearGroup {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all_consuming(parse_sub_year)(s.as_bytes())
+            .map(|(_, group)| group)
+            .map_err(|_| CalendsError::InvalidSubYear(s.to_string()))
+    }
+}
+
+impl Display for SubYearGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{}", self.year, self.group.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::date, IntervalLike};
+
+    #[test]
+    fn test_codes_round_trip() {
+        for code in 21..=41 {
+            let group = SubYear::from_code(code).unwrap();
+            assert_eq!(group.code(), code);
+
+            let s = format!("2022-{}", code);
+            assert_eq!(s.parse::<SubYearGroup>().unwrap().to_string(), s);
+        }
+        assert!(SubYear::from_code(20).is_none());
+        assert!(SubYear::from_code(42).is_none());
+    }
+
+    #[test]
+    fn test_intervals() {
+        let interval = |s: &str| s.parse::<SubYearGroup>().unwrap().interval().iso8601();
+
+        assert_eq!(interval("2022-24"), "2022-12-01/2023-02-28");
+        assert_eq!(interval("2022-29"), "2022-09-01/2022-11-30");
+        assert_eq!(interval("2022-30"), "2022-12-01/2023-02-28");
+        assert_eq!(interval("2022-31"), "2022-03-01/2022-05-31");
+        assert_eq!(interval("2022-38"), "2022-05-01/2022-08-31");
+        assert_eq!(interval("2022-41"), "2022-07-01/2022-12-31");
+    }
+
+    #[test]
+    fn test_periods() {
+        let semester: SubYearGroup = "2022-41".parse().unwrap();
+        let period = semester.period().unwrap();
+        assert_eq!(period.grain(), Grain::Half);
+        assert_eq!(period.start(), date(2022, 7, 1));
+
+        assert!("2022-37"
+            .parse::<SubYearGroup>()
+            .unwrap()
+            .period()
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalid() {
+        for s in ["2022-13", "2022-42", "2022-3", "2022-330", "22-33"] {
+            assert!(matches!(
+                s.parse::<SubYearGroup>(),
+                Err(CalendsError::InvalidSubYear(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(SubYear::quarter(4).map(|q| q.code()), Ok(36));
+        assert_eq!(SubYear::quarter(4).map(|q| q.as_quarter()), Ok(Some(4)));
+        assert_eq!(SubYear::semester(1).map(|s| s.code()), Ok(40));
+        assert_eq!(
+            SubYear::season(Season::Winter, Some(Hemisphere::Southern)).as_season(),
+            Some((Season::Winter, Some(Hemisphere::Southern)))
+        );
+        assert_eq!(SubYear::quadrimester(3).unwrap().as_quarter(), None);
+        assert_eq!(
+            SubYear::quarter(5),
+            Err(CalendsError::InvalidSubYear("quarter 5".to_string()))
+        );
+        assert!(SubYear::quarter(0).is_err());
+        assert!(SubYear::quadrimester(4).is_err());
+        assert!(SubYear::semester(3).is_err());
+
+        let winter = SubYear::season(Season::Winter, None);
+        assert!(SubYearGroup::new(262142, winter).is_err());
+        assert!(SubYearGroup::new(262142, SubYear::quarter(1).unwrap()).is_ok());
+        assert!(SubYearGroup::new(-262143, winter).is_ok());
+    }
+}
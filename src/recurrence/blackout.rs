@@ -0,0 +1,162 @@
+//! Scheduling around blackout windows
+//!
+//! Change freezes, shutdowns and similar windows rule out every date within them. [avoid] takes
+//! the occurrences of a schedule and moves each one falling inside a window to the nearest
+//! allowed date on the side chosen by a [BlackoutPolicy], or drops it.
+use chrono::NaiveDate;
+
+use crate::interval::{
+    marker::{End, Start},
+    IntervalSet,
+};
+
+/// What happens to an occurrence inside a blackout window
+///
+/// Moves are limited to a number of days, an occurrence which would have to move further is
+/// dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlackoutPolicy {
+    /// Drop the occurrence
+    Drop,
+    /// Move to the day before the window
+    Before(u32),
+    /// Move to the day after the window
+    After(u32),
+    /// Move to whichever of the days before and after the window is closer, before on a tie
+    Nearest(u32),
+}
+
+impl BlackoutPolicy {
+    /// The date an occurrence is moved to, [None] when it is dropped
+    pub fn apply(&self, date: NaiveDate, blackout: &IntervalSet) -> Option<NaiveDate> {
+        let Some(window) = blackout.containing(date) else {
+            return Some(date);
+        };
+        let before = window.start().pred_opt();
+        let after = window.end().succ_opt();
+        let within = |max_days: u32, moved: Option<NaiveDate>| {
+            moved.filter(|moved| (*moved - date).num_days().unsigned_abs() <= u64::from(max_days))
+        };
+
+        match *self {
+            BlackoutPolicy::Drop => None,
+            BlackoutPolicy::Before(max_days) => within(max_days, before),
+            BlackoutPolicy::After(max_days) => within(max_days, after),
+            BlackoutPolicy::Nearest(max_days) => {
+                let distance = |moved: Option<NaiveDate>| {
+                    moved.map_or(i64::MAX, |moved| (moved - date).num_days().abs())
+                };
+                match distance(before) <= distance(after) {
+                    true => within(max_days, before),
+                    false => within(max_days, after),
+                }
+            }
+        }
+    }
+}
+
+/// Occurrences moved out of the blackout windows with the policy
+///
+/// Several occurrences in one window can be moved onto the same date, or onto an occurrence just
+/// outside it, and are then only given once. Ascending occurrences stay in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{interval::{ClosedInterval, IntervalSet}, Recurrence, Rule};
+/// # use calends::recurrence::blackout::{avoid, BlackoutPolicy};
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// let mut freeze = IntervalSet::new();
+/// freeze.insert(&ClosedInterval::with_dates(date(2022, 12, 20), date(2022, 12, 31)));
+///
+/// let weekly = Recurrence::with_start(Rule::weekly(), date(2022, 12, 13));
+/// let releases: Vec<NaiveDate> = avoid(weekly, &freeze, BlackoutPolicy::After(7))
+///     .take(3)
+///     .collect();
+///
+/// // the 20th would have to move twelve days so it is dropped
+/// assert_eq!(
+///     releases,
+///     vec![date(2022, 12, 13), date(2023, 1, 1), date(2023, 1, 3)]
+/// );
+/// ```
+pub fn avoid<'a, I>(
+    occurrences: I,
+    blackout: &'a IntervalSet,
+    policy: BlackoutPolicy,
+) -> impl Iterator<Item = NaiveDate> + 'a
+where
+    I: IntoIterator<Item = NaiveDate>,
+    I::IntoIter: 'a,
+{
+    let mut last = None;
+    occurrences
+        .into_iter()
+        .filter_map(move |date| policy.apply(date, blackout))
+        .filter(move |date| last.replace(*date) != Some(*date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interval::ClosedInterval, testing::date};
+
+    fn blackout(windows: &[(u32, u32)]) -> IntervalSet {
+        let mut set = IntervalSet::new();
+        for (start, end) in windows {
+            set.insert(&ClosedInterval::with_dates(
+                date(2022, 3, *start),
+                date(2022, 3, *end),
+            ));
+        }
+        set
+    }
+
+    #[test]
+    fn test_policies() {
+        let freeze = blackout(&[(10, 14)]);
+        let apply = |policy: BlackoutPolicy, day| policy.apply(date(2022, 3, day), &freeze);
+
+        assert_eq!(apply(BlackoutPolicy::Drop, 9), Some(date(2022, 3, 9)));
+        assert_eq!(apply(BlackoutPolicy::Drop, 10), None);
+        assert_eq!(apply(BlackoutPolicy::Before(3), 12), Some(date(2022, 3, 9)));
+        assert_eq!(apply(BlackoutPolicy::Before(2), 12), None);
+        assert_eq!(apply(BlackoutPolicy::After(1), 14), Some(date(2022, 3, 15)));
+        assert_eq!(
+            apply(BlackoutPolicy::Nearest(3), 12),
+            Some(date(2022, 3, 9))
+        );
+        assert_eq!(
+            apply(BlackoutPolicy::Nearest(3), 13),
+            Some(date(2022, 3, 15))
+        );
+        assert_eq!(apply(BlackoutPolicy::Nearest(1), 12), None);
+    }
+
+    #[test]
+    fn test_moved_occurrences_are_given_once() {
+        let freeze = blackout(&[(10, 14), (16, 16)]);
+        let daily = (8..=17).map(|day| date(2022, 3, day));
+
+        assert_eq!(
+            avoid(daily.clone(), &freeze, BlackoutPolicy::After(10)).collect::<Vec<_>>(),
+            vec![
+                date(2022, 3, 8),
+                date(2022, 3, 9),
+                date(2022, 3, 15),
+                date(2022, 3, 17)
+            ]
+        );
+        assert_eq!(
+            avoid(daily, &freeze, BlackoutPolicy::Nearest(2)).collect::<Vec<_>>(),
+            vec![
+                date(2022, 3, 8),
+                date(2022, 3, 9),
+                date(2022, 3, 15),
+                date(2022, 3, 17)
+            ]
+        );
+    }
+}
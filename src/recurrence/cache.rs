@@ -0,0 +1,283 @@
+//! Memoized expansion of recurrences
+//!
+//! Servers which answer many queries against the same rules can wrap a [Recurrence] in a
+//! [CachedRecurrence]. Occurrences are expanded a calendar year at a time and kept until the rule
+//! changes, which is detected by comparing [Fingerprint]s.
+use std::{
+    collections::BTreeMap,
+    sync::{PoisonError, RwLock, RwLockReadGuard},
+};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    interval::marker::{End, Start},
+    trace,
+};
+
+use super::{recur::Recurrence, Fingerprint};
+
+/// A recurrence whose occurrences are cached per calendar year
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{interval::ClosedInterval, Recurrence, Rule};
+/// # use calends::recurrence::cache::CachedRecurrence;
+///
+/// let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+/// let cached = CachedRecurrence::new(Recurrence::with_start(Rule::monthly(), start));
+///
+/// let q1 = ClosedInterval::with_dates(start, NaiveDate::from_ymd_opt(2022, 3, 31).unwrap());
+/// assert_eq!(cached.occurrences(&q1).len(), 3);
+/// assert_eq!(cached.cached_years().collect::<Vec<_>>(), vec![2022]);
+/// ```
+///
+/// # Sharing
+///
+/// The cache is `Send + Sync` and [occurrences](CachedRecurrence::occurrences) only needs a shared
+/// reference, so a single cache can be kept in an `Arc` and queried from many threads or async
+/// handlers without a mutex around it. The years are behind a lock which is never held while
+/// expanding. Two queries missing the same year may both expand it, the first to finish is kept
+/// and both see the same dates. Replacing the recurrence needs exclusive access.
+pub struct CachedRecurrence {
+    recurrence: Recurrence,
+    fingerprint: Fingerprint,
+    years: RwLock<BTreeMap<i32, Vec<NaiveDate>>>,
+}
+
+impl CachedRecurrence {
+    pub fn new(recurrence: Recurrence) -> Self {
+        CachedRecurrence {
+            fingerprint: recurrence.fingerprint(),
+            recurrence,
+            years: RwLock::default(),
+        }
+    }
+
+    pub fn recurrence(&self) -> &Recurrence {
+        &self.recurrence
+    }
+
+    /// Fingerprint of the rule the cache was expanded from
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+
+    /// Replace the recurrence, the cache is only cleared when the fingerprint differs
+    pub fn set_recurrence(&mut self, recurrence: Recurrence) {
+        let fingerprint = recurrence.fingerprint();
+        if fingerprint != self.fingerprint {
+            trace::debug!(%fingerprint, previous = %self.fingerprint, "rule changed, clearing cache");
+            self.clear();
+            self.fingerprint = fingerprint;
+        }
+        self.recurrence = recurrence;
+    }
+
+    /// Years that have already been expanded
+    pub fn cached_years(&self) -> impl Iterator<Item = i32> {
+        self.read().keys().copied().collect::<Vec<_>>().into_iter()
+    }
+
+    /// Drop every cached occurrence
+    pub fn clear(&mut self) {
+        self.years
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Occurrences within the interval, expanding and caching any years not seen before
+    pub fn occurrences<I: Start + End>(&self, interval: &I) -> Vec<NaiveDate> {
+        let (start, end) = (interval.start(), interval.end());
+        if end < start {
+            return Vec::new();
+        }
+
+        self.expand(start.year(), end.year());
+
+        self.read()
+            .range(start.year()..=end.year())
+            .flat_map(|(_, dates)| dates.iter().copied())
+            .filter(|date| start <= *date && *date <= end)
+            .collect()
+    }
+
+    /// Expand every year in the range which is not yet cached in a single pass over the series
+    fn expand(&self, from: i32, to: i32) {
+        let missing: Vec<i32> = {
+            let years = self.read();
+            (from..=to)
+                .filter(|year| !years.contains_key(year))
+                .collect()
+        };
+        let (first, last) = match (missing.first(), missing.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => {
+                trace::trace!(from, to, "occurrences served from the cache");
+                return;
+            }
+        };
+        trace::debug!(first, last, rule = %self.recurrence.canonical(), "expanding years missing from the cache");
+
+        let mut expanded: BTreeMap<i32, Vec<NaiveDate>> =
+            missing.iter().map(|year| (*year, Vec::new())).collect();
+        let descending = self.recurrence.is_descending();
+
+        let mut previous = None;
+        for date in self.recurrence.restart() {
+            // stop on rules which do not move, they would never leave the window
+            let left = match descending {
+                true => date.year() < first || previous.is_some_and(|p| date >= p),
+                false => date.year() > last || previous.is_some_and(|p| date <= p),
+            };
+            if left {
+                break;
+            }
+            previous = Some(date);
+
+            if let Some(dates) = expanded.get_mut(&date.year()) {
+                dates.push(date);
+            }
+        }
+        if descending {
+            expanded.values_mut().for_each(|dates| dates.reverse());
+        }
+
+        let mut years = self.years.write().unwrap_or_else(PoisonError::into_inner);
+        for (year, dates) in expanded {
+            // another query may have expanded the year in the meantime
+            years.entry(year).or_insert(dates);
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<i32, Vec<NaiveDate>>> {
+        // years are only ever inserted whole so a panicking writer cannot leave them half updated
+        self.years.read().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for CachedRecurrence {
+    fn clone(&self) -> Self {
+        CachedRecurrence {
+            recurrence: self.recurrence.clone(),
+            fingerprint: self.fingerprint,
+            years: RwLock::new(self.read().clone()),
+        }
+    }
+}
+
+/// Only the cached years are listed rather than every occurrence
+impl std::fmt::Debug for CachedRecurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedRecurrence")
+            .field("recurrence", &self.recurrence)
+            .field("fingerprint", &self.fingerprint)
+            .field("years", &self.cached_years().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{interval::ClosedInterval, testing::date, RelativeDuration, Rule};
+
+    use super::*;
+
+    #[test]
+    fn test_occurrences_across_years() {
+        let recur = Recurrence::with_start(Rule::quarterly(), date(2021, 2, 15));
+        let cached = CachedRecurrence::new(recur);
+
+        let window = ClosedInterval::with_dates(date(2021, 12, 1), date(2022, 6, 1));
+        assert_eq!(
+            cached.occurrences(&window),
+            vec![date(2022, 2, 15), date(2022, 5, 15)]
+        );
+        assert_eq!(cached.cached_years().collect::<Vec<_>>(), vec![2021, 2022]);
+
+        // served from the cache
+        let window = ClosedInterval::with_dates(date(2021, 1, 1), date(2021, 12, 31));
+        assert_eq!(cached.occurrences(&window).len(), 4);
+    }
+
+    #[test]
+    fn test_descending_occurrences() {
+        let recur = Recurrence::with_start(Rule::monthly(), date(2022, 3, 31)).descending();
+        let cached = CachedRecurrence::new(recur.clone());
+
+        let window = ClosedInterval::with_dates(date(2021, 11, 1), date(2022, 12, 31));
+        assert_eq!(
+            cached.occurrences(&window),
+            recur.between(date(2021, 11, 1), date(2022, 12, 31))
+        );
+        assert_eq!(cached.occurrences(&window).len(), 5);
+        assert_eq!(cached.cached_years().collect::<Vec<_>>(), vec![2021, 2022]);
+    }
+
+    #[test]
+    fn test_set_recurrence_invalidates_on_change() {
+        let start = date(2022, 1, 1);
+        let mut cached = CachedRecurrence::new(Recurrence::with_start(Rule::monthly(), start));
+        let year = ClosedInterval::with_dates(start, date(2022, 12, 31));
+        assert_eq!(cached.occurrences(&year).len(), 12);
+
+        cached.set_recurrence(Recurrence::with_start(Rule::monthly(), start));
+        assert_eq!(cached.cached_years().count(), 1);
+        assert_eq!(
+            format!("{:?}", cached),
+            format!(
+                "CachedRecurrence {{ recurrence: {:?}, fingerprint: Fingerprint({}), years: [2022] }}",
+                cached.recurrence(),
+                cached.fingerprint()
+            )
+        );
+
+        cached.set_recurrence(Recurrence::with_start(Rule::quarterly(), start));
+        assert_eq!(cached.cached_years().count(), 0);
+        assert_eq!(cached.occurrences(&year).len(), 4);
+    }
+
+    #[test]
+    fn test_non_advancing_rule_terminates() {
+        let recur =
+            Recurrence::with_start(Rule::Offset(RelativeDuration::zero(), 0), date(2022, 1, 1));
+        let cached = CachedRecurrence::new(recur);
+
+        let year = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 12, 31));
+        assert_eq!(cached.occurrences(&year), vec![date(2022, 1, 1)]);
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CachedRecurrence>();
+
+        let cached =
+            CachedRecurrence::new(Recurrence::with_start(Rule::weekly(), date(2020, 1, 6)));
+        let expected: Vec<Vec<NaiveDate>> = (2020..2030)
+            .map(|year| {
+                Recurrence::with_start(Rule::weekly(), date(2020, 1, 6))
+                    .skip_while(|d| d.year() < year)
+                    .take_while(|d| d.year() <= year + 1)
+                    .collect()
+            })
+            .collect();
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    // overlapping windows so threads race to expand the same years
+                    for (i, year) in (2020..2030).enumerate() {
+                        let window =
+                            ClosedInterval::with_dates(date(year, 1, 1), date(year + 1, 12, 31));
+                        assert_eq!(cached.occurrences(&window), expected[i]);
+                    }
+                });
+            }
+        });
+        assert_eq!(cached.cached_years().count(), 11);
+    }
+}
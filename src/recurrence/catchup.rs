@@ -0,0 +1,120 @@
+//! Catching up on occurrences missed during downtime
+//!
+//! A scheduler which was down between two runs has to decide what to do about the occurrences it
+//! missed. [missed_between] finds them and a [CatchUpPolicy] picks which of them to run.
+use chrono::NaiveDate;
+
+use super::Recurrence;
+
+/// Which missed occurrences to run after downtime
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CatchUpPolicy {
+    /// Run every missed occurrence in order
+    #[default]
+    RunAll,
+    /// Run only the most recent missed occurrence, e.g. for a job which recomputes a total
+    RunLatestOnly,
+    /// Run none of them and wait for the next occurrence
+    Skip,
+}
+
+impl CatchUpPolicy {
+    /// The missed occurrences to run
+    pub fn apply(&self, mut missed: Vec<NaiveDate>) -> Vec<NaiveDate> {
+        match self {
+            CatchUpPolicy::RunAll => missed,
+            CatchUpPolicy::RunLatestOnly => missed.pop().into_iter().collect(),
+            CatchUpPolicy::Skip => Vec::new(),
+        }
+    }
+}
+
+/// Occurrences after the last run up to and including now, in ascending order
+///
+/// The recurrence is expected to be ascending. An occurrence on the day of the last run counts as
+/// run already.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{Recurrence, Rule};
+/// # use calends::recurrence::catchup::{catch_up, missed_between, CatchUpPolicy};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let weekly = Recurrence::with_start(Rule::weekly(), date(1, 3));
+///
+/// assert_eq!(
+///     missed_between(&weekly, date(1, 3), date(1, 24)),
+///     vec![date(1, 10), date(1, 17), date(1, 24)]
+/// );
+/// assert_eq!(
+///     catch_up(&weekly, date(1, 3), date(1, 24), CatchUpPolicy::RunLatestOnly),
+///     vec![date(1, 24)]
+/// );
+/// ```
+pub fn missed_between(
+    recurrence: &Recurrence,
+    last_run: NaiveDate,
+    now: NaiveDate,
+) -> Vec<NaiveDate> {
+    match last_run.succ_opt() {
+        Some(first) => recurrence.between(first, now),
+        None => Vec::new(),
+    }
+}
+
+/// The missed occurrences the policy says to run
+pub fn catch_up(
+    recurrence: &Recurrence,
+    last_run: NaiveDate,
+    now: NaiveDate,
+    policy: CatchUpPolicy,
+) -> Vec<NaiveDate> {
+    policy.apply(missed_between(recurrence, last_run, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::date, Rule};
+
+    #[test]
+    fn test_policies() {
+        let monthly = Recurrence::with_start(Rule::monthly(), date(2022, 1, 1));
+        let run = |last_run, now, policy| catch_up(&monthly, last_run, now, policy);
+
+        assert_eq!(
+            run(date(2022, 1, 15), date(2022, 4, 30), CatchUpPolicy::RunAll),
+            vec![date(2022, 2, 1), date(2022, 3, 1), date(2022, 4, 1)]
+        );
+        assert_eq!(
+            run(
+                date(2022, 1, 15),
+                date(2022, 4, 30),
+                CatchUpPolicy::RunLatestOnly
+            ),
+            vec![date(2022, 4, 1)]
+        );
+        assert!(run(date(2022, 1, 15), date(2022, 4, 30), CatchUpPolicy::Skip).is_empty());
+        // nothing was missed
+        assert!(run(
+            date(2022, 2, 1),
+            date(2022, 2, 28),
+            CatchUpPolicy::RunLatestOnly
+        )
+        .is_empty());
+        assert!(run(date(2022, 4, 30), date(2022, 1, 15), CatchUpPolicy::RunAll).is_empty());
+    }
+
+    #[test]
+    fn test_descending_schedule() {
+        let weekly = Recurrence::with_start(Rule::weekly(), date(2022, 1, 31)).descending();
+
+        assert_eq!(
+            missed_between(&weekly, date(2022, 1, 3), date(2022, 1, 24)),
+            vec![date(2022, 1, 10), date(2022, 1, 17), date(2022, 1, 24)]
+        );
+        assert!(missed_between(&weekly, NaiveDate::MAX, NaiveDate::MAX).is_empty());
+    }
+}
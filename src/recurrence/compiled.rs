@@ -0,0 +1,216 @@
+//! Closed form stepping for simple recurrences
+//!
+//! Iterating a [Recurrence] to its thousandth occurrence takes a thousand steps. Simulations which
+//! evaluate schedules millions of times can instead [compile](Recurrence::compile) a recurrence
+//! which repeats a whole number of days, weeks or months into a [CompiledRecurrence], which finds
+//! any occurrence or the index of a date directly.
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::util::{
+    beginning_of_month, days_in_month, end_of_month, shift_months, try_shift_months,
+};
+
+use super::{Recurrence, Rule};
+
+/// A recurrence reduced to arithmetic on the index of each occurrence
+///
+/// Occurrences are numbered from zero, which is the start of the recurrence, and are exactly the
+/// dates the recurrence iterates through.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{Recurrence, Rule};
+///
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// let monthly = Recurrence::with_start(Rule::monthly(), date(2022, 1, 30));
+/// let compiled = monthly.compile().unwrap();
+///
+/// assert_eq!(compiled.occurrence(1000), monthly.clone().nth(1000));
+/// // February has no 30th, so every occurrence from then on is the last day of the month
+/// assert_eq!(compiled.occurrence(2), Some(date(2022, 3, 31)));
+/// assert_eq!(compiled.index_of(date(2022, 3, 31)), Some(2));
+/// assert_eq!(compiled.index_of(date(2022, 3, 30)), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompiledRecurrence {
+    start: NaiveDate,
+    step: Step,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Step {
+    Days(i64),
+    Months {
+        months: i32,
+        /// The first occurrence which falls on the last day of its month, every later one does
+        /// as well
+        month_ends_from: Option<u32>,
+    },
+}
+
+impl Recurrence {
+    /// The closed form of the recurrence when there is one
+    ///
+    /// This is the case for ascending recurrences of [Rule::Offset] stepping by a whole number of
+    /// days and weeks, or of months, such as [Rule::daily], [Rule::weekly], [Rule::monthly] and
+    /// [Rule::yearly]. Other rules give [None].
+    pub fn compile(&self) -> Option<CompiledRecurrence> {
+        let Rule::Offset(duration, _) = self.rule() else {
+            return None;
+        };
+        if self.is_descending() {
+            return None;
+        }
+        let start = self.start();
+        let (months, days) = (
+            duration.num_months(),
+            i64::from(duration.num_weeks()) * 7 + i64::from(duration.num_days()),
+        );
+
+        let step = match (months, days) {
+            (0, 1..) => Step::Days(days),
+            (1.., 0) => Step::Months {
+                months,
+                month_ends_from: month_ends_from(start, months),
+            },
+            _ => return None,
+        };
+        Some(CompiledRecurrence { start, step })
+    }
+}
+
+/// Which occurrence is the first on the last day of its month
+///
+/// Shifting by months keeps the day of the month until a month is too short for it, after which
+/// every occurrence is the last day of the month. The lengths of months repeat every 400 years,
+/// so if no occurrence in that time is clamped none ever will be.
+fn month_ends_from(start: NaiveDate, months: i32) -> Option<u32> {
+    if start == end_of_month(&start) {
+        return Some(0);
+    }
+    // months past the range of dates have no occurrences to clamp
+    let first = beginning_of_month(&start);
+    (1..=4800 / months as u32)
+        .map_while(|k| Some((k, try_shift_months(first, k as i32 * months).ok()?)))
+        .find(|(_, month)| days_in_month(month.year(), month.month()) <= start.day())
+        .map(|(k, _)| k)
+}
+
+impl CompiledRecurrence {
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// The kth occurrence counting from zero, [None] when it is beyond the range of dates
+    pub fn occurrence(&self, k: u32) -> Option<NaiveDate> {
+        match self.step {
+            Step::Days(days) => self
+                .start
+                .checked_add_signed(Duration::try_days(days.checked_mul(k.into())?)?),
+            Step::Months {
+                months,
+                month_ends_from,
+            } => {
+                let months = months.checked_mul(i32::try_from(k).ok()?)?;
+                let first = self.start.with_day(1).unwrap();
+                // shifting would panic past the range of dates
+                first.checked_add_months(chrono::Months::new(months as u32))?;
+                let month = shift_months(first, months);
+                match month_ends_from.is_some_and(|from| k >= from) {
+                    true => Some(end_of_month(&month)),
+                    false => month.with_day(self.start.day()),
+                }
+            }
+        }
+    }
+
+    /// The index of the occurrence on the date, [None] when there is no occurrence on it
+    pub fn index_of(&self, date: NaiveDate) -> Option<u32> {
+        let k = match self.step {
+            Step::Days(days) => {
+                let elapsed = (date - self.start).num_days();
+                if elapsed < 0 || elapsed % days != 0 {
+                    return None;
+                }
+                u32::try_from(elapsed / days).ok()?
+            }
+            Step::Months { months, .. } => {
+                let elapsed = (date.year() - self.start.year()) * 12 + date.month() as i32
+                    - self.start.month() as i32;
+                if elapsed < 0 || elapsed % months != 0 {
+                    return None;
+                }
+                (elapsed / months) as u32
+            }
+        };
+        (self.occurrence(k)? == date).then_some(k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::date, RelativeDuration};
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn test_matches_iteration(day: u16, step: u8, months: bool) -> bool {
+        let start = date(2000, 1, 1) + Duration::days(i64::from(day % 1500));
+        let step = i32::from(step % 14) + 1;
+        let duration = match months {
+            true => RelativeDuration::months(step),
+            false => RelativeDuration::days(step),
+        };
+        let recurrence = Recurrence::with_start(Rule::Offset(duration, 0), start);
+        let compiled = recurrence.compile().unwrap();
+
+        recurrence.take(120).enumerate().all(|(k, date)| {
+            compiled.occurrence(k as u32) == Some(date) && compiled.index_of(date) == Some(k as u32)
+        })
+    }
+
+    #[test]
+    fn test_compile() {
+        let start = date(2024, 2, 29);
+        let compile = |rule| Recurrence::with_start(rule, start).compile();
+
+        assert!(compile(Rule::yearly()).is_some());
+        assert!(compile(Rule::Offset(RelativeDuration::months(1).with_days(-1), 0)).is_none());
+        assert!(compile(Rule::Weekdays("MO".parse().unwrap())).is_none());
+        assert!(Recurrence::with_start(Rule::daily(), start)
+            .descending()
+            .compile()
+            .is_none());
+
+        let yearly = compile(Rule::yearly()).unwrap();
+        assert_eq!(yearly.occurrence(4), Some(date(2028, 2, 29)));
+        assert_eq!(yearly.index_of(date(2025, 2, 28)), Some(1));
+        assert_eq!(yearly.index_of(date(2023, 2, 28)), None);
+        assert_eq!(yearly.occurrence(u32::MAX), None);
+
+        let weekly = compile(Rule::weekly()).unwrap();
+        assert_eq!(weekly.index_of(date(2024, 3, 7)), Some(1));
+        assert_eq!(weekly.index_of(date(2024, 3, 8)), None);
+        assert_eq!(weekly.occurrence(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_compile_near_the_last_date() {
+        let compile = |start| {
+            Recurrence::with_start(Rule::monthly(), start)
+                .compile()
+                .unwrap()
+        };
+
+        // the search for a clamped month stops at the end of the range of dates
+        let monthly = compile(date(262100, 1, 15));
+        assert_eq!(monthly.occurrence(515), Some(date(262142, 12, 15)));
+        assert_eq!(monthly.occurrence(516), None);
+        assert_eq!(monthly.index_of(date(262142, 12, 15)), Some(515));
+
+        let monthly = compile(date(262100, 1, 30));
+        assert_eq!(monthly.occurrence(2), Some(date(262100, 3, 31)));
+    }
+}
@@ -0,0 +1,158 @@
+//! Collisions between recurring schedules
+//!
+//! Booking a room or resource for several recurring meetings needs the dates on which more than
+//! one of them falls. [find_conflicts] merges the occurrences of every schedule in a single pass
+//! rather than comparing each pair of schedules.
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use chrono::NaiveDate;
+
+use crate::interval::marker::{End, Start};
+
+use super::Recurrence;
+
+/// The position of a schedule in the slice given to [find_conflicts]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScheduleId(usize);
+
+impl ScheduleId {
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// Dates within the window on which two or more of the schedules occur
+///
+/// Each date is listed once, in ascending order, with the schedules occurring on it in the order
+/// they were given.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{interval::ClosedInterval, Recurrence, Rule};
+/// # use calends::recurrence::conflict::find_conflicts;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+///
+/// let schedules = [
+///     Recurrence::with_start(Rule::weekly(), date(1, 3)),
+///     Recurrence::with_start(Rule::monthly(), date(1, 31)),
+/// ];
+/// let window = ClosedInterval::with_dates(date(1, 1), date(3, 31));
+///
+/// let conflicts = find_conflicts(&schedules, &window);
+/// assert_eq!(
+///     conflicts.iter().map(|(date, _)| *date).collect::<Vec<_>>(),
+///     vec![date(1, 31), date(2, 28)]
+/// );
+/// assert_eq!(conflicts[0].1.iter().map(|id| id.index()).collect::<Vec<_>>(), vec![0, 1]);
+/// ```
+pub fn find_conflicts<I: Start + End>(
+    schedules: &[Recurrence],
+    window: &I,
+) -> Vec<(NaiveDate, Vec<ScheduleId>)> {
+    let (start, end) = (window.start(), window.end());
+    let mut series: Vec<_> = schedules
+        .iter()
+        .map(|recurrence| recurrence.between(start, end).into_iter())
+        .collect();
+
+    // the next occurrence of each schedule, earliest first
+    let mut heap = BinaryHeap::new();
+    for (i, occurrences) in series.iter_mut().enumerate() {
+        if let Some(date) = occurrences.next() {
+            heap.push(Reverse((date, i)));
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    while let Some(Reverse((date, i))) = heap.pop() {
+        let mut ids = vec![ScheduleId(i)];
+        while let Some(Reverse((_, j))) = heap.peek().filter(|Reverse((d, _))| *d == date) {
+            ids.push(ScheduleId(*j));
+            heap.pop();
+        }
+
+        for id in &ids {
+            if let Some(next) = series[id.0].next() {
+                heap.push(Reverse((next, id.0)));
+            }
+        }
+
+        if ids.len() > 1 {
+            conflicts.push((date, ids));
+        }
+    }
+
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interval::ClosedInterval, testing::date, Rule};
+
+    #[test]
+    fn test_no_conflicts() {
+        let schedules = [
+            Recurrence::with_start(Rule::weekly(), date(2022, 1, 3)),
+            Recurrence::with_start(Rule::weekly(), date(2022, 1, 4)),
+        ];
+        let window = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 12, 31));
+
+        assert!(find_conflicts(&schedules, &window).is_empty());
+        assert!(find_conflicts(&[], &window).is_empty());
+    }
+
+    #[test]
+    fn test_descending_schedules() {
+        let schedules = [
+            Recurrence::with_start(Rule::weekly(), date(2022, 1, 31)).descending(),
+            Recurrence::with_start(Rule::biweekly(), date(2022, 1, 3)),
+        ];
+        let window = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 2, 28));
+
+        assert_eq!(
+            find_conflicts(&schedules, &window)
+                .iter()
+                .map(|(date, _)| *date)
+                .collect::<Vec<_>>(),
+            vec![date(2022, 1, 3), date(2022, 1, 17), date(2022, 1, 31)]
+        );
+    }
+
+    #[test]
+    fn test_conflicts_match_pairwise_expansion() {
+        let schedules = [
+            Recurrence::with_start(Rule::daily(), date(2022, 2, 1)),
+            Recurrence::with_start(Rule::weekly(), date(2022, 1, 3)),
+            Recurrence::with_start(Rule::biweekly(), date(2022, 1, 10)),
+            Recurrence::with_start(Rule::monthly(), date(2022, 1, 31)),
+        ];
+        let window = ClosedInterval::with_dates(date(2022, 1, 15), date(2022, 4, 15));
+
+        let expanded: Vec<Vec<NaiveDate>> = schedules
+            .iter()
+            .map(|r| {
+                r.until_and_including(date(2022, 4, 15))
+                    .filter(|d| *d >= date(2022, 1, 15))
+                    .collect()
+            })
+            .collect();
+        let expected: Vec<(NaiveDate, Vec<ScheduleId>)> = date(2022, 1, 15)
+            .iter_days()
+            .take_while(|d| *d <= date(2022, 4, 15))
+            .map(|d| {
+                let ids = (0..schedules.len())
+                    .filter(|i| expanded[*i].contains(&d))
+                    .map(ScheduleId)
+                    .collect::<Vec<_>>();
+                (d, ids)
+            })
+            .filter(|(_, ids)| ids.len() > 1)
+            .collect();
+
+        assert_eq!(find_conflicts(&schedules, &window), expected);
+    }
+}
@@ -0,0 +1,180 @@
+//! Preview the effect of editing a recurrence
+//!
+//! [diff_schedules] compares the occurrences of two recurrences within a window and classifies
+//! every difference, so an application can show what saving an edit would do before saving it.
+use std::collections::BTreeSet;
+
+use chrono::NaiveDate;
+
+use crate::interval::marker::{End, Start};
+
+use super::Recurrence;
+
+/// A difference between two schedules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// An occurrence only in the new schedule
+    Added(NaiveDate),
+    /// An occurrence only in the old schedule
+    Removed(NaiveDate),
+    /// An occurrence which the edit moved to another date
+    Moved { from: NaiveDate, to: NaiveDate },
+}
+
+impl Change {
+    /// The earliest date affected by the change
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            Change::Added(date) | Change::Removed(date) => *date,
+            Change::Moved { from, to } => *from.min(to),
+        }
+    }
+}
+
+/// Classify how the occurrences within the window change when `old` is replaced by `new`
+///
+/// Taking the occurrences of both schedules in date order, a removed occurrence next to an added
+/// one is a move and any others are added or removed. Occurrences in both schedules are unchanged
+/// and never paired across. Changes are in order of the earliest date they affect.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{interval::ClosedInterval, Recurrence, Rule};
+/// # use calends::recurrence::diff::{diff_schedules, Change};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+///
+/// let old = Recurrence::with_start(Rule::monthly(), date(1, 1));
+/// let new = Recurrence::with_start(Rule::monthly(), date(1, 15));
+/// let window = ClosedInterval::with_dates(date(1, 1), date(2, 28));
+///
+/// assert_eq!(
+///     diff_schedules(&old, &new, &window),
+///     vec![
+///         Change::Moved { from: date(1, 1), to: date(1, 15) },
+///         Change::Moved { from: date(2, 1), to: date(2, 15) },
+///     ]
+/// );
+/// ```
+pub fn diff_schedules<I: Start + End>(
+    old: &Recurrence,
+    new: &Recurrence,
+    window: &I,
+) -> Vec<Change> {
+    let old = occurrences(old, window);
+    let new = occurrences(new, window);
+
+    let mut changes = Vec::new();
+    // the previous change while it could still be paired with the next one
+    let mut pending: Option<Change> = None;
+    for date in old.union(&new).copied() {
+        let change = match (old.contains(&date), new.contains(&date)) {
+            (true, true) => {
+                changes.extend(pending.take());
+                continue;
+            }
+            (true, false) => Change::Removed(date),
+            _ => Change::Added(date),
+        };
+        pending = match (pending, change) {
+            (Some(Change::Removed(from)), Change::Added(to))
+            | (Some(Change::Added(to)), Change::Removed(from)) => {
+                changes.push(Change::Moved { from, to });
+                None
+            }
+            (previous, change) => {
+                changes.extend(previous);
+                Some(change)
+            }
+        };
+    }
+    changes.extend(pending);
+
+    changes
+}
+
+fn occurrences<I: Start + End>(recurrence: &Recurrence, window: &I) -> BTreeSet<NaiveDate> {
+    let (start, end) = (window.start(), window.end());
+    recurrence.between(start, end).into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interval::ClosedInterval, testing::date, Rule};
+
+    #[test]
+    fn test_unchanged_schedule() {
+        let rule = Recurrence::with_start(Rule::weekly(), date(2022, 1, 3));
+        let window = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 3, 31));
+
+        assert!(diff_schedules(&rule, &rule, &window).is_empty());
+    }
+
+    #[test]
+    fn test_descending_schedules() {
+        let old = Recurrence::with_start(Rule::monthly(), date(2022, 12, 1)).descending();
+        let new = Recurrence::with_start(Rule::monthly(), date(2022, 11, 1)).descending();
+        let window = ClosedInterval::with_dates(date(2022, 10, 1), date(2022, 12, 31));
+
+        assert_eq!(
+            diff_schedules(&old, &new, &window),
+            vec![Change::Removed(date(2022, 12, 1))]
+        );
+        assert!(diff_schedules(
+            &old,
+            &Recurrence::with_start(Rule::monthly(), date(2022, 10, 1)),
+            &window
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_weekly_to_biweekly() {
+        let old = Recurrence::with_start(Rule::weekly(), date(2022, 1, 3));
+        let new = Recurrence::with_start(Rule::biweekly(), date(2022, 1, 3));
+        let window = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 31));
+
+        assert_eq!(
+            diff_schedules(&old, &new, &window),
+            vec![
+                Change::Removed(date(2022, 1, 10)),
+                Change::Removed(date(2022, 1, 24))
+            ]
+        );
+        assert_eq!(
+            diff_schedules(&new, &old, &window),
+            vec![
+                Change::Added(date(2022, 1, 10)),
+                Change::Added(date(2022, 1, 24))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_moves_are_paired_within_a_gap() {
+        // weekly on Mondays to weekly on Wednesdays, starting two weeks later
+        let old = Recurrence::with_start(Rule::weekly(), date(2022, 1, 3));
+        let new = Recurrence::with_start(Rule::weekly(), date(2022, 1, 19));
+        let window = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 31));
+
+        assert_eq!(
+            diff_schedules(&old, &new, &window),
+            vec![
+                Change::Removed(date(2022, 1, 3)),
+                Change::Removed(date(2022, 1, 10)),
+                Change::Moved {
+                    from: date(2022, 1, 17),
+                    to: date(2022, 1, 19)
+                },
+                Change::Moved {
+                    from: date(2022, 1, 24),
+                    to: date(2022, 1, 26)
+                },
+                Change::Removed(date(2022, 1, 31)),
+            ]
+        );
+    }
+}
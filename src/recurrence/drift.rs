@@ -0,0 +1,189 @@
+//! Adherence of actual runs to a schedule
+//!
+//! [drift_report] pairs the dates a job actually ran on with the occurrences it was scheduled
+//! for, so a dashboard can show missed runs, unexpected runs and how late runs tend to be using
+//! the same recurrence the scheduler uses.
+use chrono::NaiveDate;
+
+use crate::interval::marker::{End, Start};
+
+use super::Recurrence;
+
+/// Actual runs compared with the expected occurrences of a schedule
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriftReport {
+    /// Expected occurrences with the actual run matched to them, in date order
+    pub matched: Vec<(NaiveDate, NaiveDate)>,
+    /// Expected occurrences without a run
+    pub missed: Vec<NaiveDate>,
+    /// Runs which were not matched to an expected occurrence
+    pub extra: Vec<NaiveDate>,
+}
+
+impl DriftReport {
+    /// Days each matched run was late by, early runs are negative
+    pub fn lateness(&self) -> impl Iterator<Item = i64> + '_ {
+        self.matched
+            .iter()
+            .map(|(expected, actual)| (*actual - *expected).num_days())
+    }
+
+    /// Mean days the matched runs were late by, [None] without any matched runs
+    pub fn average_lateness(&self) -> Option<f64> {
+        match self.matched.len() {
+            0 => None,
+            n => Some(self.lateness().sum::<i64>() as f64 / n as f64),
+        }
+    }
+}
+
+/// Match the actual runs within the window to the expected occurrences
+///
+/// Each run is matched to its nearest expected occurrence, the earlier one when two are equally
+/// near. When several runs are nearest to the same occurrence the closest is matched, or the
+/// earliest of those equally close, and the others are extra. Runs outside the window are
+/// ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{interval::ClosedInterval, Recurrence, Rule};
+/// # use calends::recurrence::drift::drift_report;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let weekly = Recurrence::with_start(Rule::weekly(), date(1, 3));
+/// let window = ClosedInterval::with_dates(date(1, 1), date(1, 31));
+///
+/// let report = drift_report(&weekly, &[date(1, 3), date(1, 11), date(1, 12), date(1, 25)], &window);
+///
+/// assert_eq!(report.missed, vec![date(1, 17), date(1, 31)]);
+/// assert_eq!(report.extra, vec![date(1, 12)]);
+/// assert_eq!(report.average_lateness(), Some(2.0 / 3.0));
+/// ```
+pub fn drift_report<I: Start + End>(
+    expected: &Recurrence,
+    actual: &[NaiveDate],
+    window: &I,
+) -> DriftReport {
+    let (start, end) = (window.start(), window.end());
+    let expected = expected.between(start, end);
+    let mut actual: Vec<NaiveDate> = actual
+        .iter()
+        .copied()
+        .filter(|date| (start..=end).contains(date))
+        .collect();
+    actual.sort();
+
+    let distance = |a: NaiveDate, b: NaiveDate| (a - b).num_days().abs();
+    // the closest run to each expected occurrence out of the runs nearest to it
+    let mut best: Vec<Option<NaiveDate>> = vec![None; expected.len()];
+    let mut extra = Vec::new();
+    for run in actual {
+        let i = expected.partition_point(|date| *date < run);
+        let nearest = match (i.checked_sub(1), (i < expected.len()).then_some(i)) {
+            (Some(before), Some(after)) => {
+                match distance(run, expected[before]) <= distance(run, expected[after]) {
+                    true => before,
+                    false => after,
+                }
+            }
+            (Some(before), None) => before,
+            (None, Some(after)) => after,
+            (None, None) => {
+                extra.push(run);
+                continue;
+            }
+        };
+        match best[nearest] {
+            Some(other)
+                if distance(other, expected[nearest]) <= distance(run, expected[nearest]) =>
+            {
+                extra.push(run)
+            }
+            other => {
+                extra.extend(other);
+                best[nearest] = Some(run);
+            }
+        }
+    }
+    extra.sort();
+
+    let mut report = DriftReport {
+        extra,
+        ..Default::default()
+    };
+    for (expected, run) in expected.into_iter().zip(best) {
+        match run {
+            Some(run) => report.matched.push((expected, run)),
+            None => report.missed.push(expected),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{interval::ClosedInterval, testing::date, Rule};
+
+    #[test]
+    fn test_matching() {
+        let monthly = Recurrence::with_start(Rule::monthly(), date(2022, 1, 1));
+        let window = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 4, 30));
+
+        // the run on 02-15 is as near to 02-01 as it is to 03-01 so it goes to the earlier one,
+        // where the run on 01-31 is closer
+        let report = drift_report(
+            &monthly,
+            &[
+                date(2022, 4, 2),
+                date(2022, 1, 31),
+                date(2022, 2, 15),
+                date(2022, 4, 1),
+                date(2022, 5, 1),
+            ],
+            &window,
+        );
+        assert_eq!(
+            report.matched,
+            vec![
+                (date(2022, 2, 1), date(2022, 1, 31)),
+                (date(2022, 4, 1), date(2022, 4, 1))
+            ]
+        );
+        assert_eq!(report.missed, vec![date(2022, 1, 1), date(2022, 3, 1)]);
+        assert_eq!(report.extra, vec![date(2022, 2, 15), date(2022, 4, 2)]);
+        assert_eq!(report.lateness().collect::<Vec<_>>(), vec![-1, 0]);
+        assert_eq!(report.average_lateness(), Some(-0.5));
+
+        let report = drift_report(
+            &monthly,
+            &[date(2022, 1, 1)],
+            &ClosedInterval::with_dates(date(2022, 1, 2), date(2022, 1, 3)),
+        );
+        assert_eq!(report, DriftReport::default());
+        assert_eq!(report.average_lateness(), None);
+    }
+
+    #[test]
+    fn test_descending_schedule() {
+        let weekly = Recurrence::with_start(Rule::weekly(), date(2022, 1, 31)).descending();
+        let window = ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 31));
+
+        let report = drift_report(
+            &weekly,
+            &[date(2022, 1, 3), date(2022, 1, 11), date(2022, 1, 31)],
+            &window,
+        );
+        assert_eq!(
+            report.matched,
+            vec![
+                (date(2022, 1, 3), date(2022, 1, 3)),
+                (date(2022, 1, 10), date(2022, 1, 11)),
+                (date(2022, 1, 31), date(2022, 1, 31))
+            ]
+        );
+        assert_eq!(report.missed, vec![date(2022, 1, 17), date(2022, 1, 24)]);
+    }
+}
@@ -0,0 +1,242 @@
+//! Stable fingerprints of recurrence rules
+//!
+//! A fingerprint is a hash of the canonical form of a rule, it is the same across processes,
+//! platforms and releases of Rust so it can be stored alongside configuration to detect drift or
+//! used as a cache key for expanded occurrences. [std::hash::Hash] makes no such guarantee.
+use std::{collections::BTreeSet, fmt::Display, ops::Bound, str::FromStr};
+
+use crate::{error::CalendsError, RelativeDuration};
+
+use super::{recur::Recurrence, recur::Rule, until::Until};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Stable hash of a rule's canonical form
+///
+/// Displays as sixteen lowercase hex digits.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// Fingerprint of a canonical form using 64 bit FNV-1a
+    pub(crate) fn of(canonical: &str) -> Fingerprint {
+        let hash = canonical.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+        Fingerprint(hash)
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl std::fmt::Debug for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fingerprint({})", self)
+    }
+}
+
+impl FromStr for Fingerprint {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u64::from_str_radix(s, 16)
+            .map(Fingerprint)
+            .map_err(|_| CalendsError::InvalidFingerprint(s.to_string()))
+    }
+}
+
+impl Rule {
+    /// Canonical text form of the rule, e.g. `offset/P1M/0`, `occurence/P1M/3/Wed` or
+    /// `weekdays/MO,WE,FR`
+    ///
+    /// Rules which occur on the same dates but are written differently have the same form, the
+    /// weeks and days of durations are merged so `P7D` is `P1W`, and the days of
+    /// [MonthDays](Rule::MonthDays) are sorted with duplicates removed.
+    pub fn canonical(&self) -> String {
+        match self {
+            Rule::Offset(duration, offset) => {
+                format!("offset/{}/{}", canonical_duration(duration), offset)
+            }
+            Rule::Occurence(duration, count, weekday) => {
+                format!(
+                    "occurence/{}/{}/{}",
+                    canonical_duration(duration),
+                    count,
+                    weekday
+                )
+            }
+            Rule::Weekdays(weekdays) => format!("weekdays/{}", weekdays),
+            Rule::MonthDay(day) => format!("monthday/{}", day),
+            Rule::MonthDays(days) => format!(
+                "monthdays/{}",
+                days.iter()
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            Rule::NthWeekday(nth) => format!("nthweekday/{}", nth),
+            Rule::PeriodDay(grain, fiscal, n) => {
+                format!("periodday/{:?}/{}/{}", grain, fiscal.start_month(), n)
+            }
+        }
+    }
+
+    /// Stable fingerprint of the rule
+    ///
+    /// ```
+    /// use calends::Rule;
+    ///
+    /// assert_eq!(Rule::monthly().fingerprint(), Rule::monthly().fingerprint());
+    /// assert_ne!(Rule::monthly().fingerprint(), Rule::quarterly().fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::of(&self.canonical())
+    }
+}
+
+/// ISO 8601 form of the duration with its weeks and days merged, in weeks when they divide evenly
+///
+/// Both are whole days added one after the other, so only their total changes the dates.
+fn canonical_duration(duration: &RelativeDuration) -> String {
+    let days = 7 * i64::from(duration.num_weeks()) + i64::from(duration.num_days());
+    let (count, unit) = match days % 7 {
+        0 => (days / 7, "W"),
+        _ => (days, "D"),
+    };
+
+    let mut result = String::from("P");
+    for (count, unit) in [(i64::from(duration.num_months()), "M"), (count, unit)] {
+        if count != 0 {
+            result.push_str(&format!("{}{}", count, unit));
+        }
+    }
+    result
+}
+
+impl Recurrence {
+    /// Canonical text form of the rule and the start of the series, e.g. `offset/P1M/0@2022-01-01`
+    ///
+    /// Descending series are suffixed with `/descending`.
+    pub fn canonical(&self) -> String {
+        let direction = if self.is_descending() {
+            "/descending"
+        } else {
+            ""
+        };
+        format!("{}@{}{}", self.rule().canonical(), self.start(), direction)
+    }
+
+    /// Stable fingerprint of the rule and the start of the series
+    ///
+    /// Iterating the recurrence does not change its fingerprint.
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::of(&self.canonical())
+    }
+}
+
+impl Until<Recurrence> {
+    /// Canonical text form of the recurrence and its limit, e.g.
+    /// `offset/P1M/0@2022-01-01..=2022-03-01`
+    pub fn canonical(&self) -> String {
+        let until = match self.until {
+            Bound::Included(date) => format!("..={}", date),
+            Bound::Excluded(date) => format!("..{}", date),
+            Bound::Unbounded => "..".to_string(),
+        };
+        format!("{}{}", self.iter.canonical(), until)
+    }
+
+    /// Stable fingerprint of the recurrence and its limit
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::of(&self.canonical())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Weekday;
+
+    use crate::{monthday::DayOfMonth, testing::date};
+
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        // pinned so that any change to the canonical form or hash is caught
+        assert_eq!(Fingerprint::of("").to_string(), "cbf29ce484222325");
+        assert_eq!(Rule::monthly().canonical(), "offset/P1M/0");
+        assert_eq!(
+            Rule::monthly().fingerprint(),
+            Fingerprint::of("offset/P1M/0")
+        );
+        assert_eq!(
+            Rule::Occurence(RelativeDuration::months(1), 3, Weekday::Wed).canonical(),
+            "occurence/P1M/3/Wed"
+        );
+        assert_eq!(
+            "cbf29ce484222325".parse::<Fingerprint>(),
+            Ok(Fingerprint::of(""))
+        );
+        assert_eq!(
+            "offset".parse::<Fingerprint>(),
+            Err(CalendsError::InvalidFingerprint("offset".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_equal_rules_written_differently() {
+        let fifteenth = DayOfMonth::new(15).unwrap();
+        assert_eq!(
+            Rule::MonthDays(vec![DayOfMonth::LAST, fifteenth]).fingerprint(),
+            Rule::MonthDays(vec![fifteenth, DayOfMonth::LAST, fifteenth]).fingerprint()
+        );
+        assert_eq!(
+            Rule::Offset(RelativeDuration::days(7), 0).fingerprint(),
+            Rule::Offset(RelativeDuration::weeks(1), 0).fingerprint()
+        );
+        assert_eq!(
+            Rule::Offset(RelativeDuration::weeks(1).with_days(1), 0).canonical(),
+            "offset/P8D/0"
+        );
+        assert_eq!(
+            Rule::Occurence(RelativeDuration::months(1).with_days(-14), 1, Weekday::Mon)
+                .canonical(),
+            "occurence/P1M-2W/1/Mon"
+        );
+    }
+
+    #[test]
+    fn test_recurrence_fingerprint() {
+        let start = date(2022, 1, 1);
+        let end = date(2022, 3, 1);
+        let mut recur = Recurrence::with_start(Rule::monthly(), start);
+        let fingerprint = recur.fingerprint();
+
+        recur.next();
+        assert_eq!(recur.fingerprint(), fingerprint);
+        assert_ne!(
+            Recurrence::with_start(Rule::monthly(), end).fingerprint(),
+            fingerprint
+        );
+
+        assert_eq!(
+            recur.until_and_including(end).canonical(),
+            "offset/P1M/0@2022-01-01..=2022-03-01"
+        );
+        assert_ne!(
+            recur.until(end).fingerprint(),
+            recur.until_and_including(end).fingerprint()
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! Recover the rule behind a list of dates
+//!
+//! Legacy schedules often survive only as the dates they produced. [infer_rule] finds a simple
+//! [Rule] which generates exactly those dates, such as monthly on the 15th or every second
+//! Friday, and [infer_adjusted_rule] also finds rules whose dates were rolled onto business days,
+//! such as the last business day of each month.
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::{
+    business::RollPolicy,
+    duration::RelativeDuration,
+    grain::Grain,
+    holidays::HolidayCalendar,
+    month::Month,
+    monthday::DayOfMonth,
+    period::{FiscalYear, Period},
+    util::days_in_month,
+    weekday::{NthWeekday, WeekdaySet},
+};
+
+use super::{Recurrence, Rule};
+
+/// How far before the first date a rolled occurrence can start, longer than any run of holidays
+const LOOKBACK_DAYS: i64 = 14;
+
+/// The simplest rule generating exactly the dates, [None] when none of the rules tried fit
+///
+/// The dates must be in ascending order without repeats and at least two are needed. Rules are
+/// tried from the simplest: a fixed number of days or weeks, a fixed number of months, a day of
+/// the month, a weekday of the month, a day of the quarter and finally a set of weekdays. Short
+/// series fit many rules, so the more dates the more likely the rule found is the intended one.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{recurrence::infer_rule, Rule};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+///
+/// let monthly = infer_rule(&[date(1, 15), date(2, 15), date(3, 15)]).unwrap();
+/// assert_eq!(monthly.rule(), &Rule::monthly());
+/// assert_eq!(monthly.start(), date(1, 15));
+///
+/// let second_friday =
+///     infer_rule(&[date(1, 14), date(2, 11), date(3, 11), date(4, 8), date(5, 13)]).unwrap();
+/// assert_eq!(second_friday.rule(), &Rule::NthWeekday("2FR".parse()?));
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+pub fn infer_rule(dates: &[NaiveDate]) -> Option<Recurrence> {
+    candidates(dates)?
+        .into_iter()
+        .find_map(|(rule, from)| fit(rule, from, dates, |date| date))
+}
+
+/// The simplest rule generating the dates once rolled onto business days
+///
+/// Dates which fit a rule without rolling give [RollPolicy::Unadjusted], otherwise rolling to the
+/// preceding and then the following business day are tried, followed by their modified forms. The
+/// start of the recurrence is the unadjusted date of the first occurrence.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{business::{Calendar, RollPolicy}, recurrence::infer_adjusted_rule, Rule};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// // the last business day of each month, the 30th of April is a Saturday
+/// let dates = [date(1, 31), date(2, 28), date(3, 31), date(4, 29), date(5, 31)];
+///
+/// let (recurrence, roll) = infer_adjusted_rule(&dates, &Calendar::new()).unwrap();
+/// assert_eq!(recurrence.rule(), &Rule::monthly());
+/// assert_eq!(recurrence.start(), date(1, 31));
+/// assert_eq!(roll, RollPolicy::Preceding);
+/// ```
+pub fn infer_adjusted_rule<C: HolidayCalendar + ?Sized>(
+    dates: &[NaiveDate],
+    calendar: &C,
+) -> Option<(Recurrence, RollPolicy)> {
+    let candidates = candidates(dates)?;
+    [
+        RollPolicy::Unadjusted,
+        RollPolicy::Preceding,
+        RollPolicy::Following,
+        RollPolicy::ModifiedFollowing,
+        RollPolicy::ModifiedPreceding,
+    ]
+    .into_iter()
+    .find_map(|roll| {
+        candidates
+            .iter()
+            .find_map(|(rule, from)| fit(rule.clone(), *from, dates, |d| roll.apply(d, calendar)))
+            .map(|recurrence| (recurrence, roll))
+    })
+}
+
+/// The recurrence of the rule whose adjusted occurrences are exactly the dates
+///
+/// Occurrences from `from` which adjust to before the first date are skipped, the first one left
+/// is the start of the recurrence.
+fn fit(
+    rule: Rule,
+    from: NaiveDate,
+    dates: &[NaiveDate],
+    adjust: impl Fn(NaiveDate) -> NaiveDate,
+) -> Option<Recurrence> {
+    let mut occurrences = Recurrence::with_start(rule.clone(), from)
+        .map(|date| (date, adjust(date)))
+        .skip_while(|(_, adjusted)| *adjusted < dates[0])
+        .peekable();
+    let start = occurrences.peek()?.0;
+
+    occurrences
+        .map(|(_, adjusted)| adjusted)
+        .take(dates.len())
+        .eq(dates.iter().copied())
+        .then(|| Recurrence::with_start(rule, start))
+}
+
+/// Rules to try in order of preference, each with the date to start looking for occurrences
+fn candidates(dates: &[NaiveDate]) -> Option<Vec<(Rule, NaiveDate)>> {
+    let (first, second) = match dates {
+        [first, second, ..] => (*first, *second),
+        _ => return None,
+    };
+    if dates.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return None;
+    }
+    let lookback = first.checked_sub_signed(Duration::days(LOOKBACK_DAYS))?;
+    let mut candidates = Vec::new();
+
+    // the step between each pair of dates, starting from the first date or, when the first date
+    // was rolled, one step before the second
+    let mut steps: Vec<i64> = dates
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).num_days())
+        .collect();
+    steps.sort_unstable();
+    steps.dedup();
+    for step in steps {
+        // steps too long for a duration cannot be a rule
+        let duration = match step % 7 {
+            0 => RelativeDuration::try_weeks((step / 7) as i32),
+            _ => RelativeDuration::try_days(step as i32),
+        };
+        let Ok(duration) = duration else { continue };
+        for from in [first, second - Duration::days(step)] {
+            candidates.push((Rule::Offset(duration, 0), from));
+        }
+    }
+
+    // the days of the month seen in the series, counted from both the start and the end
+    let mut days: Vec<(i32, i32)> = dates
+        .iter()
+        .map(|date| {
+            let day = date.day() as i32;
+            (
+                day,
+                day - days_in_month(date.year(), date.month()) as i32 - 1,
+            )
+        })
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let months = (second.year() - first.year()) * 12 + second.month() as i32 - first.month() as i32;
+    if let Some(duration) = RelativeDuration::try_months(months)
+        .ok()
+        .filter(|_| months > 0)
+    {
+        candidates.push((Rule::Offset(duration, 0), first));
+        for (day, from_end) in &days {
+            let from = DayOfMonth::new(*day)
+                .ok()
+                .and_then(|day| day.resolve(first.year(), Month::of(&first)))
+                .or_else(|| {
+                    DayOfMonth::new(*from_end)
+                        .ok()?
+                        .resolve(first.year(), Month::of(&first))
+                });
+            candidates.extend(from.map(|from| (Rule::Offset(duration, 0), from)));
+        }
+    }
+
+    for (day, from_end) in &days {
+        for day in [*day, *from_end] {
+            candidates.extend(
+                DayOfMonth::new(day)
+                    .ok()
+                    .map(|d| (Rule::MonthDay(d), lookback)),
+            );
+        }
+    }
+
+    for date in dates {
+        let (day, length) = (
+            date.day() as i32,
+            days_in_month(date.year(), date.month()) as i32,
+        );
+        for n in [(day - 1) / 7 + 1, -((length - day) / 7 + 1)] {
+            candidates.extend(
+                NthWeekday::new(n, date.weekday())
+                    .ok()
+                    .map(|nth| (Rule::NthWeekday(nth), lookback)),
+            );
+        }
+    }
+
+    let fiscal = FiscalYear::calendar();
+    for date in dates {
+        let quarter = Period::containing(*date, Grain::Quarter, &fiscal);
+        for n in [
+            (*date - quarter.start()).num_days() + 1,
+            (*date - quarter.end()).num_days() - 1,
+        ] {
+            candidates.push((Rule::PeriodDay(Grain::Quarter, fiscal, n as i32), lookback));
+        }
+    }
+
+    let weekdays: WeekdaySet = dates.iter().map(|date| date.weekday()).collect();
+    candidates.push((Rule::Weekdays(weekdays), lookback));
+
+    let mut seen = Vec::new();
+    candidates.retain(|candidate| match seen.contains(candidate) {
+        true => false,
+        false => {
+            seen.push(candidate.clone());
+            true
+        }
+    });
+    Some(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    #[test]
+    fn test_dates_at_the_limits() {
+        let min = NaiveDate::MIN;
+        assert!(infer_rule(&[min, min + Duration::days(7)]).is_none());
+
+        let far = [date(2022, 1, 1), date(2022, 1, 1) + Duration::days(1 << 21)];
+        assert!(infer_rule(&far).is_none());
+        assert!(infer_adjusted_rule(&far, &Calendar::new()).is_none());
+    }
+
+    #[test]
+    fn test_infer_rule() {
+        let biweekly =
+            infer_rule(&[date(2022, 1, 7), date(2022, 1, 21), date(2022, 2, 4)]).unwrap();
+        assert_eq!(biweekly.rule(), &Rule::biweekly());
+
+        let weekdays = infer_rule(&[
+            date(2022, 1, 3),
+            date(2022, 1, 5),
+            date(2022, 1, 7),
+            date(2022, 1, 10),
+        ])
+        .unwrap();
+        assert_eq!(
+            weekdays.rule(),
+            &Rule::Weekdays("MO,WE,FR".parse().unwrap())
+        );
+        assert_eq!(weekdays.start(), date(2022, 1, 3));
+
+        // the 31st skips the months without one
+        let thirty_first = infer_rule(&[
+            date(2022, 1, 31),
+            date(2022, 3, 31),
+            date(2022, 5, 31),
+            date(2022, 7, 31),
+            date(2022, 8, 31),
+        ]);
+        assert_eq!(
+            thirty_first.unwrap().rule(),
+            &Rule::MonthDay(DayOfMonth::new(31).unwrap())
+        );
+
+        let mid_quarter =
+            infer_rule(&[date(2022, 2, 14), date(2022, 5, 15), date(2022, 8, 14)]).unwrap();
+        assert_eq!(
+            mid_quarter.rule(),
+            &Rule::PeriodDay(Grain::Quarter, FiscalYear::calendar(), 45)
+        );
+    }
+
+    #[test]
+    fn test_no_rule() {
+        assert!(infer_rule(&[]).is_none());
+        assert!(infer_rule(&[date(2022, 1, 1)]).is_none());
+        assert!(infer_rule(&[date(2022, 1, 2), date(2022, 1, 1)]).is_none());
+        assert!(infer_rule(&[date(2022, 1, 1), date(2022, 1, 1)]).is_none());
+        assert!(infer_rule(&[
+            date(2022, 1, 1),
+            date(2022, 1, 2),
+            date(2022, 1, 4),
+            date(2022, 1, 8),
+            date(2022, 1, 16)
+        ])
+        .is_none());
+    }
+
+    #[test]
+    fn test_infer_following() {
+        // the 15th of each month rolled forward, the 15th of January and May fall on weekends
+        let calendar = Calendar::new();
+        let dates = [
+            date(2022, 1, 17),
+            date(2022, 2, 15),
+            date(2022, 3, 15),
+            date(2022, 4, 15),
+            date(2022, 5, 16),
+        ];
+
+        let (recurrence, roll) = infer_adjusted_rule(&dates, &calendar).unwrap();
+        assert_eq!(recurrence.rule(), &Rule::monthly());
+        assert_eq!(recurrence.start(), date(2022, 1, 15));
+        assert_eq!(roll, RollPolicy::Following);
+
+        let (_, roll) =
+            infer_adjusted_rule(&[date(2022, 1, 3), date(2022, 1, 10)], &calendar).unwrap();
+        assert_eq!(roll, RollPolicy::Unadjusted);
+    }
+}
@@ -0,0 +1,112 @@
+//! Deterministic jitter for spreading load
+//!
+//! Batch jobs scheduled on the same rule all start on the same day. Moving each occurrence
+//! forward by a pseudorandom number of business days spreads them out, and deriving that number
+//! from a seed and the occurrence's date means every run of the schedule moves each occurrence
+//! the same way.
+use chrono::NaiveDate;
+
+use crate::{
+    business::{add_business_days, next_business_day},
+    holidays::HolidayCalendar,
+};
+
+use super::Fingerprint;
+
+/// Business days the occurrence on the date is moved by, from zero up to and including `max_days`
+///
+/// The jitter only depends on the seed and the date, it is the same across processes and
+/// platforms.
+pub fn jitter(date: NaiveDate, max_days: u32, seed: u64) -> u32 {
+    let hash = Fingerprint::of(&format!("jitter/{}/{}", seed, date)).as_u64();
+    (hash % (u64::from(max_days) + 1)) as u32
+}
+
+/// Occurrences each moved forward by its [jitter] in business days
+///
+/// An occurrence which is not a business day is first rolled forward to one, so every jittered
+/// date is a business day. Occurrences closer together than `max_days` can be jittered past one
+/// another, so the dates are only in ascending order when the occurrences are further apart.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{business::{is_business_day, Calendar}, Recurrence, Rule};
+/// # use calends::recurrence::jitter::with_jitter;
+///
+/// let start = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+/// let calendar = Calendar::new();
+/// let recurrence = Recurrence::with_start(Rule::monthly(), start);
+///
+/// let first: Vec<NaiveDate> = with_jitter(recurrence.clone().take(12), 3, 42, &calendar).collect();
+/// let again: Vec<NaiveDate> = with_jitter(recurrence.take(12), 3, 42, &calendar).collect();
+///
+/// assert_eq!(first, again);
+/// assert!(first.iter().all(|date| is_business_day(*date, &calendar)));
+/// ```
+pub fn with_jitter<'a, I, C>(
+    occurrences: I,
+    max_days: u32,
+    seed: u64,
+    calendar: &'a C,
+) -> impl Iterator<Item = NaiveDate> + 'a
+where
+    I: IntoIterator<Item = NaiveDate>,
+    I::IntoIter: 'a,
+    C: HolidayCalendar + ?Sized,
+{
+    occurrences.into_iter().map(move |date| {
+        let rolled = match calendar.is_business_day(date) {
+            true => date,
+            false => next_business_day(date, calendar),
+        };
+        add_business_days(rolled, jitter(date, max_days, seed).into(), calendar)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        business::{business_days_between, Calendar},
+        testing::date,
+    };
+
+    #[test]
+    fn test_jitter_is_bounded_and_seeded() {
+        let dates: Vec<NaiveDate> = date(2022, 1, 1).iter_days().take(200).collect();
+        let jitters = |seed| {
+            dates
+                .iter()
+                .map(|d| jitter(*d, 4, seed))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(jitters(1).iter().all(|j| *j <= 4));
+        assert!((0..=4).all(|j| jitters(1).contains(&j)));
+        assert_eq!(jitters(1), jitters(1));
+        assert_ne!(jitters(1), jitters(2));
+        assert!(dates.iter().all(|d| jitter(*d, 0, 7) == 0));
+    }
+
+    #[test]
+    fn test_jittered_dates_are_business_days() {
+        // 2022-01-01 is a Saturday and 2022-01-03 a holiday
+        let calendar = Calendar::new().with_holidays([date(2022, 1, 3)]);
+        let occurrences = [date(2022, 1, 1), date(2022, 1, 12), date(2022, 2, 1)];
+
+        let jittered: Vec<NaiveDate> = with_jitter(occurrences, 2, 9, &calendar).collect();
+        for (occurrence, jittered) in occurrences.iter().zip(&jittered) {
+            let rolled = match calendar.is_business_day(*occurrence) {
+                true => *occurrence,
+                false => next_business_day(*occurrence, &calendar),
+            };
+            assert!(calendar.is_business_day(*jittered));
+            assert_eq!(
+                business_days_between(rolled, *jittered, &calendar) as u32,
+                jitter(*occurrence, 2, 9)
+            );
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Guardrails for expanding recurrences
+//!
+//! Recurrences are unbounded iterators, and rules supplied by users can take a very long time to
+//! produce an occurrence or never stop producing them. [ExpansionLimits] bounds how many
+//! occurrences are collected and how far past the start of the series expansion may look, failing
+//! with an error instead of running away.
+//!
+//! Limits can also carry a [Horizon]. Expansion silently stops at the edges of the horizon, so
+//! open-ended series expand up to the latest supported date rather than failing.
+use chrono::NaiveDate;
+
+use crate::{error::CalendsError, horizon::Horizon, trace, RelativeDuration};
+
+use super::{recur::Recurrence, until::Until};
+
+/// Limits applied when expanding a recurrence into a list of dates
+///
+/// By default at most 10,000 occurrences are collected within 100 years of the start of the
+/// series, without a [Horizon].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpansionLimits {
+    max_occurrences: usize,
+    max_horizon: RelativeDuration,
+    horizon: Option<Horizon>,
+}
+
+impl ExpansionLimits {
+    pub fn new() -> Self {
+        ExpansionLimits {
+            max_occurrences: 10_000,
+            max_horizon: RelativeDuration::months(1200),
+            horizon: None,
+        }
+    }
+
+    /// Fail when more than this many occurrences would be collected
+    pub fn with_max_occurrences(mut self, max_occurrences: usize) -> Self {
+        self.max_occurrences = max_occurrences;
+        self
+    }
+
+    /// Fail when expansion reaches further than this from the start of the series, backwards for a
+    /// descending series
+    pub fn with_max_horizon(mut self, max_horizon: RelativeDuration) -> Self {
+        self.max_horizon = max_horizon;
+        self
+    }
+
+    /// Only collect occurrences within the horizon, stopping at its end instead of failing
+    ///
+    /// The horizon is applied before the other limits, which still fail when the horizon reaches
+    /// further than the maximum horizon past the start of the series.
+    pub fn with_horizon(mut self, horizon: Horizon) -> Self {
+        self.horizon = Some(horizon);
+        self
+    }
+
+    pub fn max_occurrences(&self) -> usize {
+        self.max_occurrences
+    }
+
+    pub fn max_horizon(&self) -> RelativeDuration {
+        self.max_horizon
+    }
+
+    pub fn horizon(&self) -> Option<Horizon> {
+        self.horizon
+    }
+
+    /// The furthest date expansion may reach from the start of a series running in either direction,
+    /// failing when the horizon lies beyond the range of supported dates
+    fn furthest(&self, start: NaiveDate, descending: bool) -> Result<NaiveDate, CalendsError> {
+        let (furthest, limit) = match descending {
+            true => (self.max_horizon.try_sub_from(start), NaiveDate::MIN),
+            false => (self.max_horizon.try_add_to(start), NaiveDate::MAX),
+        };
+        furthest.map_err(|_| {
+            trace::warning!(%start, "expansion horizon is out of range");
+            CalendsError::HorizonExceeded(limit)
+        })
+    }
+
+    /// Collect the dates, failing as soon as a limit is crossed
+    fn collect(
+        &self,
+        start: NaiveDate,
+        descending: bool,
+        dates: impl Iterator<Item = NaiveDate>,
+    ) -> Result<Vec<NaiveDate>, CalendsError> {
+        let horizon = self.furthest(start, descending)?;
+        let mut expanded = Vec::new();
+
+        let bounds = self.horizon;
+        let dates = dates
+            .take_while(|date| {
+                bounds.is_none_or(|b| match descending {
+                    true => b.min() <= *date,
+                    false => *date <= b.max(),
+                })
+            })
+            .filter(|date| bounds.is_none_or(|b| b.min() <= *date && *date <= b.max()));
+        for date in dates {
+            if past(date, horizon, descending) {
+                trace::warning!(%start, %horizon, "expansion reached past its horizon");
+                return Err(CalendsError::HorizonExceeded(horizon));
+            }
+            if expanded.len() == self.max_occurrences {
+                trace::warning!(%start, max = self.max_occurrences, "expansion has too many occurrences");
+                return Err(CalendsError::TooManyOccurrences(self.max_occurrences));
+            }
+            expanded.push(date);
+        }
+
+        Ok(expanded)
+    }
+}
+
+/// Whether the date lies beyond the horizon in the direction the series runs
+fn past(date: NaiveDate, horizon: NaiveDate, descending: bool) -> bool {
+    match descending {
+        true => date < horizon,
+        false => date > horizon,
+    }
+}
+
+impl Default for ExpansionLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recurrence {
+    /// Expand the series up to the end of its horizon
+    ///
+    /// Reaching the horizon ends expansion rather than failing as an unbounded series always
+    /// reaches it.
+    ///
+    /// ```
+    /// use calends::{RelativeDuration, Recurrence, Rule};
+    /// use calends::recurrence::ExpansionLimits;
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    /// let limits = ExpansionLimits::new().with_max_horizon(RelativeDuration::months(12));
+    ///
+    /// assert_eq!(recur.expand(&limits)?.len(), 13);
+    /// assert!(recur.expand(&limits.with_max_occurrences(12)).is_err());
+    /// # Ok::<(), calends::CalendsError>(())
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(rule = %self.canonical())))]
+    pub fn expand(&self, limits: &ExpansionLimits) -> Result<Vec<NaiveDate>, CalendsError> {
+        let (start, descending) = (self.start(), self.is_descending());
+        let horizon = limits.furthest(start, descending)?;
+        let series = self.restart();
+
+        limits.collect(
+            start,
+            descending,
+            series.take_while(|date| !past(*date, horizon, descending)),
+        )
+    }
+}
+
+impl Until<Recurrence> {
+    /// Expand the series up to its limit
+    ///
+    /// Fails when the limit lies beyond the horizon and the series reaches past it, or when the
+    /// series has too many occurrences.
+    ///
+    /// ```
+    /// use calends::{RelativeDuration, Recurrence, Rule};
+    /// use calends::recurrence::ExpansionLimits;
+    /// use chrono::NaiveDate;
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+    /// let until = recur.until(NaiveDate::from_ymd_opt(2122, 1, 1).unwrap());
+    ///
+    /// assert_eq!(until.expand(&ExpansionLimits::new())?.len(), 1200);
+    /// assert!(until
+    ///     .expand(&ExpansionLimits::new().with_max_horizon(RelativeDuration::months(12)))
+    ///     .is_err());
+    /// # Ok::<(), calends::CalendsError>(())
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(rule = %self.canonical())))]
+    pub fn expand(&self, limits: &ExpansionLimits) -> Result<Vec<NaiveDate>, CalendsError> {
+        let start = self.iter.start();
+        let series = Until {
+            until: self.until,
+            iter: self.iter.restart(),
+            descending: self.descending,
+        };
+
+        limits.collect(start, self.iter.is_descending(), series)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::date, Rule};
+
+    use super::*;
+
+    #[test]
+    fn test_expand_within_limits() {
+        let recur = Recurrence::with_start(Rule::weekly(), date(2022, 1, 3));
+        let dates = recur
+            .until_and_including(date(2022, 1, 31))
+            .expand(&ExpansionLimits::new())
+            .unwrap();
+
+        assert_eq!(dates.len(), 5);
+        assert_eq!(dates.last(), Some(&date(2022, 1, 31)));
+    }
+
+    #[test]
+    fn test_non_advancing_rule_hits_occurrence_limit() {
+        let recur =
+            Recurrence::with_start(Rule::Offset(RelativeDuration::zero(), 0), date(2022, 1, 1));
+        let limits = ExpansionLimits::new().with_max_occurrences(100);
+
+        assert_eq!(
+            recur.until(date(2023, 1, 1)).expand(&limits),
+            Err(CalendsError::TooManyOccurrences(100))
+        );
+        assert_eq!(
+            recur.expand(&limits),
+            Err(CalendsError::TooManyOccurrences(100))
+        );
+    }
+
+    #[test]
+    fn test_horizon_exceeded() {
+        let recur = Recurrence::with_start(Rule::yearly(), date(2022, 1, 1));
+        let limits = ExpansionLimits::new().with_max_horizon(RelativeDuration::months(24));
+
+        assert_eq!(
+            recur.until(date(2030, 1, 1)).expand(&limits),
+            Err(CalendsError::HorizonExceeded(date(2024, 1, 1)))
+        );
+        assert_eq!(recur.expand(&limits).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_descending_series() {
+        let recur = Recurrence::with_start(Rule::weekly(), date(2022, 3, 10)).descending();
+        let limits = ExpansionLimits::new().with_max_horizon(RelativeDuration::weeks(2));
+
+        assert_eq!(
+            recur.expand(&limits).unwrap(),
+            vec![date(2022, 3, 10), date(2022, 3, 3), date(2022, 2, 24)]
+        );
+        assert_eq!(
+            recur
+                .until_and_including(date(2022, 3, 3))
+                .expand(&limits)
+                .unwrap(),
+            vec![date(2022, 3, 10), date(2022, 3, 3)]
+        );
+        assert_eq!(
+            recur.until(date(2022, 1, 1)).expand(&limits),
+            Err(CalendsError::HorizonExceeded(date(2022, 2, 24)))
+        );
+
+        let horizon = Horizon::new(date(2022, 2, 1), date(2022, 3, 5)).unwrap();
+        assert_eq!(
+            recur
+                .until(date(2021, 1, 1))
+                .expand(&ExpansionLimits::new().with_horizon(horizon))
+                .unwrap(),
+            vec![
+                date(2022, 3, 3),
+                date(2022, 2, 24),
+                date(2022, 2, 17),
+                date(2022, 2, 10),
+                date(2022, 2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_horizon_out_of_range() {
+        let limits = ExpansionLimits::new();
+        let late = Recurrence::with_start(Rule::yearly(), NaiveDate::MAX);
+        let early = Recurrence::with_start(Rule::yearly(), NaiveDate::MIN).descending();
+
+        assert_eq!(
+            late.expand(&limits),
+            Err(CalendsError::HorizonExceeded(NaiveDate::MAX))
+        );
+        assert_eq!(
+            late.until(NaiveDate::MAX).expand(&limits),
+            Err(CalendsError::HorizonExceeded(NaiveDate::MAX))
+        );
+        assert_eq!(
+            early.expand(&limits),
+            Err(CalendsError::HorizonExceeded(NaiveDate::MIN))
+        );
+    }
+
+    #[test]
+    fn test_open_ended_series_saturates_at_the_horizon() {
+        let recur = Recurrence::with_start(Rule::monthly(), date(2022, 1, 1));
+        let horizon = Horizon::new(date(2022, 3, 1), date(2022, 12, 31)).unwrap();
+        let limits = ExpansionLimits::new()
+            .with_max_horizon(RelativeDuration::months(24))
+            .with_horizon(horizon);
+
+        let dates = recur.until(date(2030, 1, 1)).expand(&limits).unwrap();
+        assert_eq!(dates.len(), 10);
+        assert_eq!(dates.first(), Some(&date(2022, 3, 1)));
+        assert_eq!(recur.expand(&limits).unwrap(), dates);
+    }
+}
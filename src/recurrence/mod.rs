@@ -1,4 +1,42 @@
+pub mod blackout;
+pub mod cache;
+pub mod catchup;
+pub mod compiled;
+pub mod conflict;
+pub mod diff;
+pub mod drift;
+pub mod fingerprint;
+pub mod infer;
+pub mod jitter;
+pub mod limits;
+pub mod occurrence;
+pub mod page;
 pub mod recur;
+#[cfg(feature = "rrule")]
+pub mod rrule;
+#[cfg(feature = "country-calendars")]
+pub mod schedule;
+pub mod timestamp;
 pub mod until;
+pub mod upcoming;
 
+pub use blackout::{avoid, BlackoutPolicy};
+pub use cache::CachedRecurrence;
+pub use catchup::{catch_up, missed_between, CatchUpPolicy};
+pub use compiled::CompiledRecurrence;
+pub use conflict::{find_conflicts, ScheduleId};
+pub use diff::{diff_schedules, Change};
+pub use drift::{drift_report, DriftReport};
+pub use fingerprint::Fingerprint;
+pub use infer::{infer_adjusted_rule, infer_rule};
+pub use jitter::with_jitter;
+pub use limits::ExpansionLimits;
+pub use occurrence::OccurrenceId;
+pub use page::{expand_page, Cursor};
 pub use recur::*;
+#[cfg(feature = "rrule")]
+pub use rrule::{RRule, RRuleIter};
+#[cfg(feature = "country-calendars")]
+pub use schedule::{Schedule, ScheduledDates};
+pub use timestamp::Timestamp;
+pub use upcoming::{upcoming, NamedRule};
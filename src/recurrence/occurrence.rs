@@ -0,0 +1,174 @@
+//! Identity of a single occurrence
+//!
+//! Cancelling or overriding one occurrence of a recurring event needs a reference to it which
+//! survives the series being stored and reloaded. An [OccurrenceId] pairs the fingerprint of the
+//! recurrence with the date the occurrence was originally generated on. Editing the rule changes
+//! its fingerprint, so an identifier can never be taken over by an occurrence of the edited rule
+//! which happens to fall on the same date.
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDate;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::CalendsError;
+
+use super::{recur::Recurrence, Fingerprint};
+
+/// Stable identity of an occurrence of a recurrence
+///
+/// Identifiers order by the fingerprint and then the original date, and round trip through a
+/// string formatted as `<fingerprint>/<original date>`, which is also how they are serialized.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{Recurrence, Rule};
+/// # use calends::recurrence::occurrence::OccurrenceId;
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+/// let recurrence = Recurrence::with_start(Rule::monthly(), date(1, 1));
+///
+/// let id = recurrence.occurrence_id(date(2, 1));
+/// let stored = id.to_string();
+///
+/// let id: OccurrenceId = stored.parse()?;
+/// assert!(id.belongs_to(&recurrence));
+/// assert!(!id.belongs_to(&Recurrence::with_start(Rule::monthly(), date(1, 15))));
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OccurrenceId {
+    fingerprint: Fingerprint,
+    original: NaiveDate,
+}
+
+impl OccurrenceId {
+    pub fn new(fingerprint: Fingerprint, original: NaiveDate) -> Self {
+        OccurrenceId {
+            fingerprint,
+            original,
+        }
+    }
+
+    /// Fingerprint of the recurrence which generated the occurrence
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+
+    /// Date the occurrence was generated on, before any reschedule
+    pub fn original(&self) -> NaiveDate {
+        self.original
+    }
+
+    /// Whether the occurrence was generated by this version of the recurrence
+    pub fn belongs_to(&self, recurrence: &Recurrence) -> bool {
+        self.fingerprint == recurrence.fingerprint()
+    }
+}
+
+impl Recurrence {
+    /// Identity of the occurrence on the date
+    ///
+    /// The date is not checked against the series.
+    pub fn occurrence_id(&self, date: NaiveDate) -> OccurrenceId {
+        OccurrenceId::new(self.fingerprint(), date)
+    }
+}
+
+impl Display for OccurrenceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.fingerprint, self.original)
+    }
+}
+
+impl FromStr for OccurrenceId {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CalendsError::InvalidOccurrenceId(s.to_string());
+        let (fingerprint, original) = s.split_once('/').ok_or_else(invalid)?;
+
+        Ok(OccurrenceId {
+            fingerprint: fingerprint.parse().map_err(|_| invalid())?,
+            original: original.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for OccurrenceId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub struct OccurrenceIdVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> de::Visitor<'de> for OccurrenceIdVisitor {
+    type Value = OccurrenceId;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a fingerprint and date such as 0123456789abcdef/2022-01-01")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for OccurrenceId {
+    fn deserialize<D>(deserializer: D) -> Result<OccurrenceId, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(OccurrenceIdVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::date, Rule};
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let id = Recurrence::with_start(Rule::weekly(), date(2022, 1, 3))
+            .occurrence_id(date(2022, 1, 10));
+
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!(r#""{}/2022-01-10""#, id.fingerprint()));
+        assert_eq!(serde_json::from_str::<OccurrenceId>(&json).unwrap(), id);
+
+        assert!(serde_json::from_str::<OccurrenceId>(r#""2022-01-10""#).is_err());
+        assert!(serde_json::from_str::<OccurrenceId>(r#""xyz/2022-01-10""#).is_err());
+    }
+
+    #[test]
+    fn test_ordering_groups_by_recurrence() {
+        let fingerprint = Recurrence::with_start(Rule::weekly(), date(2022, 1, 3)).fingerprint();
+        let mut ids = [
+            OccurrenceId::new(fingerprint, date(2022, 1, 17)),
+            OccurrenceId::new(fingerprint, date(2022, 1, 3)),
+            OccurrenceId::new(fingerprint, date(2022, 1, 10)),
+        ];
+        ids.sort();
+
+        assert_eq!(
+            ids.iter().map(|id| id.original()).collect::<Vec<_>>(),
+            vec![date(2022, 1, 3), date(2022, 1, 10), date(2022, 1, 17)]
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Paging through occurrences
+//!
+//! Web APIs that page through an unbounded recurrence hand a [Cursor] back to the client with
+//! each page. The cursor records the last occurrence returned, so the next page continues from
+//! there instead of expanding the series from its start again.
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDate;
+
+use crate::error::CalendsError;
+
+use super::{recur::Recurrence, Fingerprint};
+
+/// Position within a recurrence to continue paging from
+///
+/// A cursor is tied to the fingerprint of the recurrence it came from and can be round tripped
+/// through a string, formatted as `<last occurrence>/<fingerprint>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cursor {
+    after: NaiveDate,
+    fingerprint: Fingerprint,
+}
+
+impl Cursor {
+    /// Last occurrence of the previous page
+    pub fn after(&self) -> NaiveDate {
+        self.after
+    }
+
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.fingerprint
+    }
+}
+
+impl Display for Cursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.after, self.fingerprint)
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CalendsError::InvalidCursor(s.to_string());
+        let (after, fingerprint) = s.split_once('/').ok_or_else(invalid)?;
+
+        Ok(Cursor {
+            after: after.parse().map_err(|_| invalid())?,
+            fingerprint: fingerprint.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Expand up to `limit` occurrences following the cursor, or from the start of the series
+///
+/// A cursor for the next page is returned whenever the page is full; it is `None` once the series
+/// is exhausted. Cursors from a different recurrence are rejected as the position they record
+/// means nothing for this one. Descending series are paged in descending order.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{Recurrence, Rule};
+/// # use calends::recurrence::page::expand_page;
+///
+/// let recur = Recurrence::with_start(Rule::monthly(), NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+///
+/// let (page, cursor) = expand_page(&recur, None, 2)?;
+/// assert_eq!(page.last(), NaiveDate::from_ymd_opt(2022, 2, 1).as_ref());
+///
+/// let (page, _) = expand_page(&recur, cursor.as_ref(), 2)?;
+/// assert_eq!(page.first(), NaiveDate::from_ymd_opt(2022, 3, 1).as_ref());
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(rule = %recurrence.canonical(), after = ?after.map(Cursor::after), limit)))]
+pub fn expand_page(
+    recurrence: &Recurrence,
+    after: Option<&Cursor>,
+    limit: usize,
+) -> Result<(Vec<NaiveDate>, Option<Cursor>), CalendsError> {
+    let fingerprint = recurrence.fingerprint();
+    let descending = recurrence.is_descending();
+
+    let (series, mut previous) = match after {
+        Some(cursor) if cursor.fingerprint != fingerprint => {
+            return Err(CalendsError::StaleCursor(cursor.to_string()))
+        }
+        Some(cursor) => {
+            // the cursor is itself an occurrence, resume from it and skip past it
+            let mut series = Recurrence::with_start(recurrence.rule().clone(), cursor.after);
+            if descending {
+                series = series.descending();
+            }
+            series.next();
+            (series, Some(cursor.after))
+        }
+        None => (recurrence.restart(), None),
+    };
+
+    let mut page = Vec::with_capacity(limit);
+    for date in series {
+        if page.len() == limit {
+            break;
+        }
+        // rules that do not move forward would return the same occurrence on every page
+        if previous.is_some_and(|p| if descending { date >= p } else { date <= p }) {
+            return Ok((page, None));
+        }
+        previous = Some(date);
+        page.push(date);
+    }
+
+    let cursor = match page.last() {
+        Some(last) if page.len() == limit => Some(Cursor {
+            after: *last,
+            fingerprint,
+        }),
+        _ => None,
+    };
+    Ok((page, cursor))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::date, RelativeDuration, Rule};
+
+    use super::*;
+
+    #[test]
+    fn test_pages_match_continuous_expansion() {
+        // the 30th drifts to the end of the month after February, paging must drift the same way
+        let recur = Recurrence::with_start(Rule::monthly(), date(2022, 1, 30));
+        let expected: Vec<NaiveDate> = recur.clone().take(9).collect();
+
+        let mut cursor = None;
+        let mut paged = Vec::new();
+        for _ in 0..3 {
+            let (page, next) = expand_page(&recur, cursor.as_ref(), 3).unwrap();
+            paged.extend(page);
+            cursor = next;
+        }
+        assert_eq!(paged, expected);
+    }
+
+    #[test]
+    fn test_descending_pages_match_continuous_expansion() {
+        let recur = Recurrence::with_start(Rule::monthly(), date(2022, 6, 1)).descending();
+        let expected: Vec<NaiveDate> = recur.clone().take(6).collect();
+        assert_eq!(
+            expected[..3],
+            [date(2022, 6, 1), date(2022, 5, 1), date(2022, 4, 1)]
+        );
+
+        let (first, cursor) = expand_page(&recur, None, 3).unwrap();
+        let (second, _) = expand_page(&recur, cursor.as_ref(), 3).unwrap();
+        assert_eq!([first, second].concat(), expected);
+    }
+
+    #[test]
+    fn test_cursor_round_trips_through_string() {
+        let recur = Recurrence::with_start(Rule::weekly(), date(2022, 1, 3));
+        let (_, cursor) = expand_page(&recur, None, 1).unwrap();
+        let cursor = cursor.unwrap();
+
+        assert!(cursor.to_string().starts_with("2022-01-03/"));
+        assert_eq!(cursor.to_string().parse::<Cursor>(), Ok(cursor));
+        assert!(matches!(
+            "2022-01-03".parse::<Cursor>(),
+            Err(CalendsError::InvalidCursor(_))
+        ));
+    }
+
+    #[test]
+    fn test_stale_cursor_is_rejected() {
+        let monthly = Recurrence::with_start(Rule::monthly(), date(2022, 1, 1));
+        let weekly = Recurrence::with_start(Rule::weekly(), date(2022, 1, 1));
+        let (_, cursor) = expand_page(&monthly, None, 1).unwrap();
+
+        assert!(matches!(
+            expand_page(&weekly, cursor.as_ref(), 1),
+            Err(CalendsError::StaleCursor(_))
+        ));
+    }
+
+    #[test]
+    fn test_non_advancing_rule_ends() {
+        let recur =
+            Recurrence::with_start(Rule::Offset(RelativeDuration::zero(), 0), date(2022, 1, 1));
+        let (page, cursor) = expand_page(&recur, None, 5).unwrap();
+
+        assert_eq!(page, vec![date(2022, 1, 1)]);
+        assert_eq!(cursor, None);
+    }
+}
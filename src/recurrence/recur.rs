@@ -1,6 +1,16 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
 
-use crate::duration::RelativeDuration;
+use crate::{
+    duration::RelativeDuration,
+    error::CalendsError,
+    grain::Grain,
+    month::Month,
+    monthday::DayOfMonth,
+    period::{FiscalYear, Period},
+    shift, trace,
+    util::beginning_of_month,
+    weekday::{Nth, NthWeekday, WeekdaySet},
+};
 
 use super::until::Until;
 
@@ -23,16 +33,27 @@ use super::until::Until;
 ///
 /// - Until a point in time (inclusive or exclusive)
 /// - Count of recurrences (end after a count of occurences) (inclusive)
+///
+/// ## Serialization
+///
+/// Rules (de)serialize as the variant in snake case with its fields, e.g. `{"offset":["P1M",0]}`
+/// or `{"weekdays":"MO,FR"}`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Rule {
     /// An offset within an interval
     ///
     /// - Duration ([RelativeDuration]): A duration of time
     /// - Offset ([i32]): the offset in days with positive starting at the beginning of the cycle and
-    /// negative being referenced from the end of the interval.
+    ///   negative being referenced from the end of the interval.
     ///
     /// This covers cases 1.1 and 1.2 in the rules of recurrence
-    Offset(RelativeDuration, i32),
+    Offset(
+        #[cfg_attr(feature = "serde", serde(with = "crate::duration::rd_iso8601"))]
+        RelativeDuration,
+        i32,
+    ),
 
     /// An occurence within an interval
     ///
@@ -43,7 +64,40 @@ pub enum Rule {
     /// This covers cases 2.1 and 2.2
     ///
     /// TODO: Describe the ruleset for finding a day of the week
-    Occurence(RelativeDuration, i32, chrono::Weekday),
+    Occurence(
+        #[cfg_attr(feature = "serde", serde(with = "crate::duration::rd_iso8601"))]
+        RelativeDuration,
+        i32,
+        chrono::Weekday,
+    ),
+
+    /// Every day falling on one of the weekdays
+    ///
+    /// This covers case 3.1
+    Weekdays(WeekdaySet),
+
+    /// A day of each month, months without the day are skipped
+    ///
+    /// e.g. the 31st only occurs in months with 31 days while the last day (-1) occurs in every
+    /// month
+    MonthDay(DayOfMonth),
+
+    /// Several days of each month merged in date order, e.g. the 1st and 15th or the 15th and the
+    /// last day for payroll
+    ///
+    /// Days which resolve to the same date occur once and months without a day are skipped for
+    /// that day only.
+    MonthDays(Vec<DayOfMonth>),
+
+    /// An occurrence of a weekday each month such as the second Tuesday, months without it are
+    /// skipped
+    NthWeekday(NthWeekday),
+
+    /// A day of each period of the grain, see [Period::nth_day]
+    ///
+    /// e.g. `-2` with [Grain::Quarter] is the second to last day of each quarter. Periods are
+    /// aligned to the fiscal year and periods without the day are skipped.
+    PeriodDay(Grain, FiscalYear, i32),
 }
 
 impl Rule {
@@ -76,15 +130,61 @@ impl Rule {
     pub fn daily() -> Rule {
         Rule::Offset(RelativeDuration::days(1), 0)
     }
+
+    /// Create a recurrence that occurs on the 1st and 15th of each month
+    pub fn semimonthly() -> Rule {
+        Rule::MonthDays(vec![DayOfMonth::FIRST, DayOfMonth::FIFTEENTH])
+    }
+
+    /// Create a recurrence that occurs on the nth weekday of each month, such as the third
+    /// Thursday or the last Friday
+    pub fn monthly_on(nth: Nth, weekday: chrono::Weekday) -> Rule {
+        Rule::NthWeekday(NthWeekday::from_nth(nth, weekday))
+    }
+
+    /// Check the rule can produce a series of dates
+    ///
+    /// Fails with [CalendsError::UnsatisfiableRule] for an offset which never moves from the start,
+    /// an empty set of weekdays or days of the month and a day no period of the grain has, such as the 0th or the 367th
+    /// day of a year. Such rules give at most one date.
+    ///
+    /// ```
+    /// # use calends::{RelativeDuration, Rule};
+    ///
+    /// assert!(Rule::monthly().validate().is_ok());
+    /// assert!(Rule::Offset(RelativeDuration::weeks(1).with_days(-7), 0).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), CalendsError> {
+        let satisfiable = match self {
+            Rule::Offset(duration, _) => {
+                duration.num_months() != 0
+                    || 7 * i64::from(duration.num_weeks()) + i64::from(duration.num_days()) != 0
+            }
+            Rule::Weekdays(weekdays) => !weekdays.is_empty(),
+            Rule::MonthDays(days) => !days.is_empty(),
+            Rule::PeriodDay(grain, _, n) => *n != 0 && n.unsigned_abs() <= grain.max_days(),
+            Rule::Occurence(..) | Rule::MonthDay(_) | Rule::NthWeekday(_) => true,
+        };
+        match satisfiable {
+            true => Ok(()),
+            false => Err(CalendsError::UnsatisfiableRule(self.canonical())),
+        }
+    }
 }
 
 /// Evaluate an existing rule
-#[derive(Debug, Clone)]
+///
+/// The debug output is compact and deterministic so it can be used in snapshot tests, e.g.
+/// `Recurrence { rule: offset/P1M/0, start: 2022-01-01, next: 2022-02-01, descending: false }`.
+#[derive(Clone)]
 pub struct Recurrence {
     rule: Rule,
     #[allow(dead_code)]
     occurence_count: i32,
-    date: NaiveDate,
+    start: NaiveDate,
+    /// Where to look for the next occurrence, [None] once the series runs past the range of dates
+    date: Option<NaiveDate>,
+    descending: bool,
 }
 
 impl Recurrence {
@@ -94,37 +194,146 @@ impl Recurrence {
     /// use calends::{Recurrence, Rule};
     /// use chrono::NaiveDate;
     ///
-    /// let date = NaiveDate::from_ymd(2022, 1, 1);
-    /// let end = NaiveDate::from_ymd(2022, 3, 1);
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
     ///
     /// let mut recur = Recurrence::with_start(Rule::monthly(), date);
-    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 1, 1)));
-    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 2, 1)));
+    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()));
+    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 2, 1).unwrap()));
     /// ```
     pub fn with_start(rule: Rule, date: NaiveDate) -> Self {
         Self {
             rule,
             occurence_count: 0,
-            date,
+            start: date,
+            date: Some(date),
+            descending: false,
+        }
+    }
+
+    /// The nth weekday of each month from the date onwards, months without a fifth occurrence are
+    /// skipped
+    ///
+    /// ```
+    /// use calends::{weekday::Nth, Recurrence};
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    ///
+    /// let mut expiries = Recurrence::monthly_on(Nth::Third, Weekday::Fri, date);
+    /// assert_eq!(expiries.next(), NaiveDate::from_ymd_opt(2022, 1, 21));
+    /// assert_eq!(expiries.next(), NaiveDate::from_ymd_opt(2022, 2, 18));
+    ///
+    /// let mut last_fridays = Recurrence::monthly_on(Nth::Last, Weekday::Fri, date);
+    /// assert_eq!(last_fridays.next(), NaiveDate::from_ymd_opt(2022, 1, 28));
+    /// assert_eq!(last_fridays.next(), NaiveDate::from_ymd_opt(2022, 2, 25));
+    /// ```
+    pub fn monthly_on(nth: Nth, weekday: chrono::Weekday, date: NaiveDate) -> Self {
+        Recurrence::with_start(Rule::monthly_on(nth, weekday), date)
+    }
+
+    /// Starting point for the recurring series, failing when the rule cannot produce a series of
+    /// dates, see [Rule::validate]
+    pub fn try_with_start(rule: Rule, date: NaiveDate) -> Result<Self, CalendsError> {
+        rule.validate()?;
+        Ok(Recurrence::with_start(rule, date))
+    }
+
+    /// Run the series backwards from its start
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = NaiveDate::from_ymd_opt(2022, 3, 31).unwrap();
+    ///
+    /// let mut recur = Recurrence::with_start(Rule::monthly(), date).descending();
+    /// assert_eq!(recur.next(), Some(date));
+    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 2, 28).unwrap()));
+    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap()));
+    /// ```
+    ///
+    /// An [until](Recurrence::until) limit on a descending series is the earliest date it runs
+    /// back to.
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    /// First date of the series, this does not change as the recurrence is iterated
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// The series from its first date again, running in the same direction
+    pub(crate) fn restart(&self) -> Recurrence {
+        Recurrence {
+            occurence_count: 0,
+            date: Some(self.start),
+            ..self.clone()
         }
     }
 
+    /// Occurrences from `start` up to and including `end`, earliest first whichever direction the
+    /// series runs
+    ///
+    /// Iteration continues from the next occurrence, so a descending series only has occurrences
+    /// on or before its start within the window.
+    ///
+    /// ```
+    /// use calends::{Recurrence, Rule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+    ///
+    /// let recur = Recurrence::with_start(Rule::monthly(), date(3, 31)).descending();
+    /// assert_eq!(recur.between(date(1, 1), date(2, 28)), vec![date(1, 31), date(2, 28)]);
+    /// ```
+    pub fn between(&self, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        let limit = if self.descending { start } else { end };
+
+        let mut dates = Vec::new();
+        let mut previous = None;
+        for date in self.until_and_including(limit) {
+            // stop on rules which do not move, they would never reach the limit
+            if previous == Some(date) {
+                break;
+            }
+            previous = Some(date);
+            if start <= date && date <= end {
+                dates.push(date);
+            }
+        }
+        if self.descending {
+            dates.reverse();
+        }
+        dates
+    }
+
     /// Iterate up to a date
     ///
     /// ```
     /// use calends::{Recurrence, Rule};
     /// use chrono::NaiveDate;
     ///
-    /// let date = NaiveDate::from_ymd(2022, 1, 1);
-    /// let end = NaiveDate::from_ymd(2022, 3, 1);
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
     ///
     /// let mut recur = Recurrence::with_start(Rule::monthly(), date).until(end);
-    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 1, 1)));
-    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 2, 1)));
+    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()));
+    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 2, 1).unwrap()));
     /// assert_eq!(recur.next(), None);
     /// ```
     pub fn until(&self, date: NaiveDate) -> Until<Recurrence> {
-        Until::exclusive(date, self.clone())
+        self.limit(Until::exclusive(date, self.clone()))
     }
 
     /// Iterate up to and including the date
@@ -133,16 +342,77 @@ impl Recurrence {
     /// use calends::{Recurrence, Rule};
     /// use chrono::NaiveDate;
     ///
-    /// let date = NaiveDate::from_ymd(2022, 1, 1);
-    /// let end = NaiveDate::from_ymd(2022, 3, 1);
+    /// let date = NaiveDate::from_ymd_opt(2022, 1, 1).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
     ///
     /// let mut recur = Recurrence::with_start(Rule::monthly(), date).until(end);
-    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 1, 1)));
-    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 2, 1)));
+    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()));
+    /// assert_eq!(recur.next(), Some(NaiveDate::from_ymd_opt(2022, 2, 1).unwrap()));
     /// assert_eq!(recur.next(), None);
     /// ```
     pub fn until_and_including(&self, date: NaiveDate) -> Until<Recurrence> {
-        Until::inclusive(date, self.clone())
+        self.limit(Until::inclusive(date, self.clone()))
+    }
+
+    fn limit(&self, until: Until<Recurrence>) -> Until<Recurrence> {
+        match self.descending {
+            true => until.descending(),
+            false => until,
+        }
+    }
+}
+
+impl std::fmt::Debug for Recurrence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Recurrence");
+        debug
+            .field("rule", &format_args!("{}", self.rule.canonical()))
+            .field("start", &self.start);
+        match self.date {
+            Some(date) => debug.field("next", &date),
+            None => debug.field("next", &format_args!("none")),
+        };
+        debug.field("descending", &self.descending).finish()
+    }
+}
+
+/// The rule and start of the series, fields of [Recurrence] which are serialized
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Series<R> {
+    rule: R,
+    start: NaiveDate,
+    #[serde(default)]
+    descending: bool,
+}
+
+/// Serialize the rule and start of the series, e.g.
+/// `{"rule":{"offset":["P1M",0]},"start":"2022-01-01","descending":false}`
+///
+/// How far the series has been iterated is not serialized, a deserialized recurrence starts again
+/// from its first date.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Recurrence {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Series {
+            rule: &self.rule,
+            start: self.start,
+            descending: self.descending,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Recurrence {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let series = Series::<Rule>::deserialize(deserializer)?;
+        let recurrence = Recurrence::with_start(series.rule, series.start);
+        Ok(match series.descending {
+            true => recurrence.descending(),
+            false => recurrence,
+        })
     }
 }
 
@@ -150,16 +420,111 @@ impl Iterator for Recurrence {
     type Item = NaiveDate;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let date = self.date;
+        let date = self.date?;
+        let descending = self.descending;
+        // series end on the first or last supported date rather than stepping past it
+        let step = |date: NaiveDate| {
+            if descending {
+                date.pred_opt()
+            } else {
+                date.succ_opt()
+            }
+        };
+        let shift_months =
+            |month: NaiveDate| shift::try_shift_months(month, if descending { -1 } else { 1 }).ok();
 
         match &self.rule {
             Rule::Offset(duration, _) => {
-                self.date = date + *duration;
+                self.date = if descending {
+                    duration.try_sub_from(date).ok()
+                } else {
+                    duration.try_add_to(date).ok()
+                };
+                trace::trace!(%date, next = ?self.date, "occurrence");
+                Some(date)
+            }
+            Rule::Weekdays(weekdays) => {
+                if weekdays.is_empty() {
+                    return None;
+                }
+                let mut date = date;
+                while !weekdays.contains(date.weekday()) {
+                    date = step(date)?;
+                }
+                self.date = step(date);
+                trace::trace!(%date, "occurrence");
+                Some(date)
+            }
+            Rule::MonthDay(day) => {
+                // every day of the month occurs in a month with 31 days, so this ends within a
+                // couple of months
+                let mut month = beginning_of_month(&date);
+                let date = loop {
+                    match day.resolve(month.year(), Month::of(&month)) {
+                        Some(resolved) if !descending && resolved >= date => break resolved,
+                        Some(resolved) if descending && resolved <= date => break resolved,
+                        _ => month = shift_months(month)?,
+                    }
+                };
+                self.date = step(date);
+                trace::trace!(%date, "occurrence");
+                Some(date)
+            }
+            Rule::MonthDays(days) => {
+                if days.is_empty() {
+                    return None;
+                }
+                // as with a single day this ends within a couple of months
+                let mut month = beginning_of_month(&date);
+                let date = loop {
+                    let resolved = days
+                        .iter()
+                        .filter_map(|day| day.resolve(month.year(), Month::of(&month)));
+                    let next = match descending {
+                        false => resolved.filter(|resolved| *resolved >= date).min(),
+                        true => resolved.filter(|resolved| *resolved <= date).max(),
+                    };
+                    match next {
+                        Some(next) => break next,
+                        None => month = shift_months(month)?,
+                    }
+                };
+                self.date = step(date);
+                trace::trace!(%date, "occurrence");
+                Some(date)
+            }
+            Rule::NthWeekday(nth) => {
+                let date = match nth.resolve(date.year(), Month::of(&date)) == Some(date) {
+                    true => date,
+                    false if descending => nth.checked_prev_before(date)?,
+                    false => nth.checked_next_after(date)?,
+                };
+                self.date = step(date);
+                trace::trace!(%date, "occurrence");
+                Some(date)
+            }
+            Rule::PeriodDay(grain, fiscal, n) => {
+                // the 0th day and a day beyond the longest period never occur, the others occur
+                // within a few periods
+                if *n == 0 || n.unsigned_abs() > grain.max_days() {
+                    return None;
+                }
+                let mut period = Period::containing(date, *grain, fiscal);
+                let date = loop {
+                    match period.nth_day(*n) {
+                        Some(day) if !descending && day >= date => break day,
+                        Some(day) if descending && day <= date => break day,
+                        _ if descending => period = period.checked_pred()?,
+                        _ => period = period.checked_succ()?,
+                    }
+                };
+                self.date = step(date);
+                trace::trace!(%date, "occurrence");
                 Some(date)
             }
             Rule::Occurence(duration, count, _) => {
                 if count < &self.occurence_count {
-                    self.date = date + *duration;
+                    self.date = duration.try_add_to(date).ok();
                     Some(date)
                 } else {
                     None
@@ -172,45 +537,404 @@ impl Iterator for Recurrence {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::date;
 
     #[test]
     fn test_recur_monthly_until_inclusive() {
-        let date = NaiveDate::from_ymd(2022, 1, 1);
-        let end = NaiveDate::from_ymd(2022, 3, 1);
+        let start = date(2022, 1, 1);
+        let end = date(2022, 3, 1);
 
-        let mut recur = Recurrence::with_start(Rule::monthly(), date).until_and_including(end);
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 1, 1)));
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 2, 1)));
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 3, 1)));
+        let mut recur = Recurrence::with_start(Rule::monthly(), start).until_and_including(end);
+        assert_eq!(recur.next(), Some(date(2022, 1, 1)));
+        assert_eq!(recur.next(), Some(date(2022, 2, 1)));
+        assert_eq!(recur.next(), Some(date(2022, 3, 1)));
         assert_eq!(recur.next(), None);
     }
 
     #[test]
     fn test_recur_monthly_until_exclusive() {
-        let date = NaiveDate::from_ymd(2022, 1, 1);
-        let end = NaiveDate::from_ymd(2022, 3, 1);
+        let start = date(2022, 1, 1);
+        let end = date(2022, 3, 1);
 
-        let mut recur = Recurrence::with_start(Rule::monthly(), date).until(end);
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 1, 1)));
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 2, 1)));
+        let mut recur = Recurrence::with_start(Rule::monthly(), start).until(end);
+        assert_eq!(recur.next(), Some(date(2022, 1, 1)));
+        assert_eq!(recur.next(), Some(date(2022, 2, 1)));
         assert_eq!(recur.next(), None);
     }
 
     #[test]
     fn test_recur_monthly() {
-        let date = NaiveDate::from_ymd(2022, 1, 1);
+        let start = date(2022, 1, 1);
 
-        let mut recur = Recurrence::with_start(Rule::monthly(), date);
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 1, 1)));
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 2, 1)));
+        let mut recur = Recurrence::with_start(Rule::monthly(), start);
+        assert_eq!(recur.next(), Some(date(2022, 1, 1)));
+        assert_eq!(recur.next(), Some(date(2022, 2, 1)));
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut recur = Recurrence::with_start(Rule::monthly(), date(2022, 1, 1));
+        recur.next();
+
+        assert_eq!(
+            format!("{:?}", recur),
+            "Recurrence { rule: offset/P1M/0, start: 2022-01-01, next: 2022-02-01, descending: false }"
+        );
+        assert_eq!(
+            format!("{:?}", recur.until(date(2022, 3, 1))),
+            "Until { until: Excluded(2022-03-01), iter: Recurrence { rule: offset/P1M/0, start: 2022-01-01, next: 2022-02-01, descending: false }, descending: false }"
+        );
+        assert_eq!(
+            format!("{:?}", Rule::weekly()),
+            "Offset(RelativeDuration(P1W), 0)"
+        );
+    }
+
+    #[test]
+    fn test_recur_weekdays() {
+        // 2022-01-01 is a Saturday
+        let date = date(2022, 1, 1);
+        let weekdays = "MO,WE,FR".parse().unwrap();
+
+        let recur = Recurrence::with_start(Rule::Weekdays(weekdays), date);
+        assert_eq!(
+            recur.take(4).map(|d| d.day()).collect::<Vec<_>>(),
+            vec![3, 5, 7, 10]
+        );
+
+        let mut never = Recurrence::with_start(Rule::Weekdays(WeekdaySet::EMPTY), date);
+        assert_eq!(never.next(), None);
+    }
+
+    #[test]
+    fn test_recur_month_day() {
+        let date = date(2022, 1, 31);
+
+        let recur = Recurrence::with_start(Rule::MonthDay(DayOfMonth::new(31).unwrap()), date);
+        assert_eq!(
+            recur.take(3).map(|d| d.month()).collect::<Vec<_>>(),
+            vec![1, 3, 5]
+        );
+
+        let recur = Recurrence::with_start(Rule::MonthDay(DayOfMonth::LAST), date);
+        assert_eq!(
+            recur.take(3).map(|d| d.day()).collect::<Vec<_>>(),
+            vec![31, 28, 31]
+        );
+    }
+
+    #[test]
+    fn test_recur_nth_weekday() {
+        // 2022-01-11 is the second Tuesday of January
+        let date = date(2022, 1, 11);
+
+        let recur = Recurrence::with_start(Rule::NthWeekday("2TU".parse().unwrap()), date);
+        assert_eq!(
+            recur.take(3).map(|d| d.to_string()).collect::<Vec<_>>(),
+            vec!["2022-01-11", "2022-02-08", "2022-03-08"]
+        );
+    }
+
+    #[test]
+    fn test_recur_period_day() {
+        let start = date(2022, 1, 1);
+        let days = |rule: Rule| {
+            Recurrence::with_start(rule, start)
+                .take(3)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            days(Rule::PeriodDay(Grain::Quarter, FiscalYear::calendar(), -2)),
+            vec!["2022-03-30", "2022-06-29", "2022-09-29"]
+        );
+        assert_eq!(
+            days(Rule::PeriodDay(Grain::Year, FiscalYear::calendar(), 366)),
+            vec!["2024-12-31", "2028-12-31", "2032-12-31"]
+        );
+        assert_eq!(
+            days(Rule::PeriodDay(Grain::Week, FiscalYear::calendar(), 8)),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            days(Rule::PeriodDay(Grain::Month, FiscalYear::calendar(), 0)),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            Recurrence::with_start(
+                Rule::PeriodDay(Grain::Month, FiscalYear::calendar(), -1),
+                start
+            )
+            .descending()
+            .take(2)
+            .collect::<Vec<_>>(),
+            vec![date(2021, 12, 31), date(2021, 11, 30)]
+        );
+    }
+
+    #[test]
+    fn test_recur_month_days() {
+        let on = |m, d| date(2022, m, d);
+        let day = |d| DayOfMonth::new(d).unwrap();
+
+        assert_eq!(
+            Recurrence::with_start(Rule::semimonthly(), on(1, 2))
+                .take(4)
+                .collect::<Vec<_>>(),
+            vec![on(1, 15), on(2, 1), on(2, 15), on(3, 1)]
+        );
+        assert_eq!(
+            Recurrence::with_start(Rule::MonthDays(vec![DayOfMonth::LAST, day(15)]), on(3, 1))
+                .descending()
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![on(2, 28), on(2, 15), on(1, 31)]
+        );
+        // the 31st and the last day are the same date in long months and the 30th is skipped in
+        // February
+        assert_eq!(
+            Recurrence::with_start(
+                Rule::MonthDays(vec![day(31), DayOfMonth::LAST, day(30)]),
+                on(1, 30)
+            )
+            .take(5)
+            .collect::<Vec<_>>(),
+            vec![on(1, 30), on(1, 31), on(2, 28), on(3, 30), on(3, 31)]
+        );
+        assert_eq!(Rule::semimonthly().canonical(), "monthdays/1,15");
+        assert!(Rule::MonthDays(Vec::new()).validate().is_err());
+        assert_eq!(
+            Recurrence::with_start(Rule::MonthDays(Vec::new()), on(1, 1)).next(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_recur_monthly_on() {
+        let day = |m, d| date(2022, m, d);
+        let dates = |nth, weekday, start| {
+            Recurrence::monthly_on(nth, weekday, start)
+                .take(4)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            dates(Nth::Third, chrono::Weekday::Thu, day(1, 20)),
+            vec![day(1, 20), day(2, 17), day(3, 17), day(4, 21)]
+        );
+        assert_eq!(
+            dates(Nth::Last, chrono::Weekday::Fri, day(1, 29)),
+            vec![day(2, 25), day(3, 25), day(4, 29), day(5, 27)]
+        );
+        assert_eq!(
+            dates(Nth::Fifth, chrono::Weekday::Mon, day(1, 1)),
+            vec![day(1, 31), day(5, 30), day(8, 29), day(10, 31)]
+        );
+    }
+
+    #[test]
+    fn test_unsatisfiable_rules() {
+        let date = date(2022, 1, 1);
+
+        for rule in [
+            Rule::Offset(RelativeDuration::zero(), 0),
+            Rule::Offset(RelativeDuration::weeks(-1).with_days(7), 0),
+            Rule::Weekdays(WeekdaySet::default()),
+            Rule::PeriodDay(Grain::Month, FiscalYear::calendar(), 0),
+            Rule::PeriodDay(Grain::Quarter, FiscalYear::calendar(), -93),
+        ] {
+            assert_eq!(
+                Recurrence::try_with_start(rule.clone(), date).err(),
+                Some(CalendsError::UnsatisfiableRule(rule.canonical()))
+            );
+        }
+
+        for rule in [
+            Rule::monthly(),
+            Rule::Offset(RelativeDuration::months(1).with_days(-30), 0),
+            Rule::PeriodDay(Grain::Quarter, FiscalYear::calendar(), -92),
+        ] {
+            assert!(Recurrence::try_with_start(rule, date).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_recur_descending() {
+        let date = date(2022, 3, 10);
+        let days = |rule: Rule| {
+            Recurrence::with_start(rule, date)
+                .descending()
+                .take(3)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            days(Rule::weekly()),
+            vec!["2022-03-10", "2022-03-03", "2022-02-24"]
+        );
+        assert_eq!(
+            days(Rule::Weekdays("MO,FR".parse().unwrap())),
+            vec!["2022-03-07", "2022-03-04", "2022-02-28"]
+        );
+        assert_eq!(
+            days(Rule::MonthDay(DayOfMonth::new(30).unwrap())),
+            vec!["2022-01-30", "2021-12-30", "2021-11-30"]
+        );
+        assert_eq!(
+            days(Rule::NthWeekday(NthWeekday::last(chrono::Weekday::Fri))),
+            vec!["2022-02-25", "2022-01-28", "2021-12-31"]
+        );
+    }
+
+    #[test]
+    fn test_recur_descending_limits() {
+        let day = |m, d| date(2022, m, d);
+        let weekly = Recurrence::with_start(Rule::weekly(), day(3, 10)).descending();
+
+        assert_eq!(
+            weekly.until(day(2, 24)).collect::<Vec<_>>(),
+            vec![day(3, 10), day(3, 3)]
+        );
+        assert_eq!(
+            weekly.until_and_including(day(2, 24)).collect::<Vec<_>>(),
+            vec![day(3, 10), day(3, 3), day(2, 24)]
+        );
+        assert_eq!(
+            weekly.between(day(2, 20), day(3, 5)),
+            vec![day(2, 24), day(3, 3)]
+        );
+        assert!(weekly.between(day(3, 11), day(3, 31)).is_empty());
+
+        let ascending = Recurrence::with_start(Rule::weekly(), day(2, 24));
+        assert_eq!(
+            ascending.between(day(2, 20), day(3, 5)),
+            weekly.between(day(2, 20), day(3, 5))
+        );
+        let stuck = Recurrence::with_start(Rule::Offset(RelativeDuration::zero(), 0), day(1, 1));
+        assert_eq!(stuck.between(day(1, 1), day(1, 31)), vec![day(1, 1)]);
+    }
+
+    #[test]
+    fn test_recur_at_the_limits() {
+        let (min, max) = (NaiveDate::MIN, NaiveDate::MAX);
+        let days = chrono::Duration::days;
+        let dates = |rule, start, descending| {
+            let recurrence = Recurrence::with_start(rule, start);
+            match descending {
+                true => recurrence.descending().take(4).collect::<Vec<_>>(),
+                false => recurrence.take(4).collect::<Vec<_>>(),
+            }
+        };
+
+        // the last supported date is a Monday
+        let monday = Rule::Weekdays("MO".parse().unwrap());
+        assert_eq!(dates(monday, max - days(3), false), vec![max]);
+        let tuesday = Rule::Weekdays("TU".parse().unwrap());
+        assert!(dates(tuesday, max - days(3), false).is_empty());
+        assert_eq!(
+            dates(Rule::daily(), min + days(1), true),
+            vec![min + days(1), min]
+        );
+        assert_eq!(
+            dates(Rule::semimonthly(), max - days(29), false),
+            vec![max - days(16)]
+        );
+        assert_eq!(
+            dates(
+                Rule::MonthDays(vec![DayOfMonth::LAST]),
+                min + days(40),
+                true
+            ),
+            vec![min + days(30)]
+        );
+        assert_eq!(
+            dates(
+                Rule::NthWeekday(NthWeekday::last(chrono::Weekday::Mon)),
+                max - days(30),
+                false
+            ),
+            vec![max]
+        );
+
+        let mut daily = Recurrence::with_start(Rule::daily(), max);
+        assert_eq!(daily.next(), Some(max));
+        assert_eq!(daily.next(), None);
+        assert!(format!("{:?}", daily).contains("next: none"));
+
+        let daily = Recurrence::with_start(Rule::daily(), max - days(10));
+        assert_eq!(daily.between(max - days(10), max).len(), 11);
+        let weekly = Recurrence::with_start(Rule::weekly(), min + days(14)).descending();
+        assert_eq!(
+            weekly.between(min, max),
+            vec![min, min + days(7), min + days(14)]
+        );
     }
 
     #[test]
     fn test_recur_quarterly() {
-        let date = NaiveDate::from_ymd(2022, 1, 1);
+        let start = date(2022, 1, 1);
+
+        let mut recur = Recurrence::with_start(Rule::quarterly(), start);
+        assert_eq!(recur.next(), Some(date(2022, 1, 1)));
+        assert_eq!(recur.next(), Some(date(2022, 4, 1)));
+    }
 
-        let mut recur = Recurrence::with_start(Rule::quarterly(), date);
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 1, 1)));
-        assert_eq!(recur.next(), Some(NaiveDate::from_ymd(2022, 4, 1)));
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let start = date(2022, 1, 31);
+        let rules = [
+            Rule::monthly(),
+            Rule::Occurence(RelativeDuration::months(1), 2, chrono::Weekday::Tue),
+            Rule::Weekdays("MO,FR".parse().unwrap()),
+            Rule::MonthDay(DayOfMonth::LAST),
+            Rule::semimonthly(),
+            Rule::NthWeekday("-1FR".parse().unwrap()),
+            Rule::PeriodDay(Grain::Quarter, FiscalYear::starting(4), -2),
+        ];
+        for rule in rules {
+            let json = serde_json::to_string(&rule).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Rule>(&json).unwrap(),
+                rule,
+                "{}",
+                json
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&Rule::PeriodDay(
+                Grain::Quarter,
+                FiscalYear::starting(4),
+                -2
+            ))
+            .unwrap(),
+            r#"{"period_day":["quarter",4,-2]}"#
+        );
+
+        let mut recurrence = Recurrence::with_start(Rule::monthly(), start).descending();
+        recurrence.next();
+        let json = serde_json::to_string(&recurrence).unwrap();
+        assert_eq!(
+            json,
+            r#"{"rule":{"offset":["P1M",0]},"start":"2022-01-31","descending":true}"#
+        );
+        // the deserialized recurrence starts again from the first date
+        let mut parsed: Recurrence = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.next(), Some(start));
+        assert!(parsed.is_descending());
+
+        for json in [
+            r#"{"rule":{"month_day":0},"start":"2022-01-31"}"#,
+            r#"{"rule":{"period_day":["quarter",13,1]},"start":"2022-01-31"}"#,
+            r#"{"rule":{"offset":["P1M",0]},"start":"2022-01-31","next":"2022-02-28"}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Recurrence>(json).is_err(),
+                "{}",
+                json
+            );
+        }
     }
 }
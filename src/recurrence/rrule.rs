@@ -0,0 +1,559 @@
+//! iCalendar recurrence rules
+//!
+//! [RRule] parses the RRULE property of RFC 5545 so schedules from calendar feeds can be expanded
+//! alongside calends' own rules. Only the parts which make sense for whole dates are supported:
+//! `FREQ` of `DAILY`, `WEEKLY`, `MONTHLY` or `YEARLY` with `INTERVAL`, `COUNT`, `UNTIL`,
+//! `BYMONTH`, `BYMONTHDAY`, `BYDAY`, `BYSETPOS` and `WKST`. The time of an `UNTIL` is ignored.
+//!
+//! As with python-dateutil, only dates matching the rule are produced so the start is not an
+//! occurrence unless it matches.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chrono::NaiveDate;
+//! # use calends::recurrence::RRule;
+//!
+//! // the last weekday of each month
+//! let rule: RRule = "RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;COUNT=3".parse()?;
+//! let start = NaiveDate::from_ymd_opt(2022, 4, 1).unwrap();
+//!
+//! assert_eq!(
+//!     rule.iter(start).collect::<Vec<_>>(),
+//!     vec![
+//!         NaiveDate::from_ymd_opt(2022, 4, 29).unwrap(),
+//!         NaiveDate::from_ymd_opt(2022, 5, 31).unwrap(),
+//!         NaiveDate::from_ymd_opt(2022, 6, 30).unwrap(),
+//!     ]
+//! );
+//! # Ok::<(), calends::CalendsError>(())
+//! ```
+use std::{collections::VecDeque, fmt::Display, str::FromStr};
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::{
+    error::CalendsError,
+    grain::Grain,
+    util::{beginning_of_month, beginning_of_week_starting, days_in_month, end_of_month},
+    weekday::{code, parse_code},
+};
+
+/// A weekday within a rule, optionally the nth occurrence within the month or year
+type ByDay = (Option<i32>, Weekday);
+
+/// A parsed RRULE
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RRule {
+    freq: Grain,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<NaiveDate>,
+    by_month: Vec<u32>,
+    by_month_day: Vec<i32>,
+    by_day: Vec<ByDay>,
+    by_set_pos: Vec<i32>,
+    week_start: Weekday,
+}
+
+impl RRule {
+    /// The step between periods, one of [Grain::Day], [Grain::Week], [Grain::Month] or
+    /// [Grain::Year]
+    pub fn freq(&self) -> Grain {
+        self.freq
+    }
+
+    /// Number of periods between each period with occurrences
+    pub fn interval(&self) -> u32 {
+        self.interval
+    }
+
+    /// Maximum number of occurrences
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Last date an occurrence may fall on
+    pub fn until(&self) -> Option<NaiveDate> {
+        self.until
+    }
+
+    /// Occurrences on or after the start in ascending order
+    pub fn iter(&self, start: NaiveDate) -> RRuleIter {
+        RRuleIter::new(self.clone(), start)
+    }
+}
+
+impl Display for RRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let freq = match self.freq {
+            Grain::Day => "DAILY",
+            Grain::Week => "WEEKLY",
+            Grain::Month => "MONTHLY",
+            _ => "YEARLY",
+        };
+        write!(f, "FREQ={}", freq)?;
+
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        write_list(f, "BYMONTH", self.by_month.iter())?;
+        write_list(f, "BYMONTHDAY", self.by_month_day.iter())?;
+        write_list(
+            f,
+            "BYDAY",
+            self.by_day.iter().map(|(n, weekday)| match n {
+                Some(n) => format!("{}{}", n, code(*weekday)),
+                None => code(*weekday).to_string(),
+            }),
+        )?;
+        write_list(f, "BYSETPOS", self.by_set_pos.iter())?;
+        if self.week_start != Weekday::Mon {
+            write!(f, ";WKST={}", code(self.week_start))?;
+        }
+        Ok(())
+    }
+}
+
+fn write_list<T: Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    name: &str,
+    mut values: impl Iterator<Item = T>,
+) -> std::fmt::Result {
+    if let Some(first) = values.next() {
+        write!(f, ";{}={}", name, first)?;
+        for value in values {
+            write!(f, ",{}", value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Serialize as the value of the RRULE property, e.g. `"FREQ=MONTHLY;BYMONTHDAY=-1"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for RRule {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for RRule {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse the value of an RRULE property, with or without the `RRULE:` name in front of it
+impl FromStr for RRule {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| CalendsError::InvalidRRule(reason.to_string());
+        let s = s.trim();
+        let value = match s.get(..6) {
+            Some(name) if name.eq_ignore_ascii_case("RRULE:") => &s[6..],
+            _ => s,
+        };
+
+        let mut freq = None;
+        let mut rule = RRule {
+            freq: Grain::Day,
+            interval: 1,
+            count: None,
+            until: None,
+            by_month: Vec::new(),
+            by_month_day: Vec::new(),
+            by_day: Vec::new(),
+            by_set_pos: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+        let mut seen = Vec::new();
+
+        for part in value.split(';').filter(|part| !part.is_empty()) {
+            let (name, value) = part.split_once('=').ok_or_else(|| invalid(part))?;
+            let name = name.trim().to_ascii_uppercase();
+            let value = value.trim().to_ascii_uppercase();
+            if seen.contains(&name) {
+                return Err(invalid(&format!("{} is given more than once", name)));
+            }
+            let bad = || invalid(part);
+
+            match name.as_str() {
+                "FREQ" => {
+                    freq = Some(match value.as_str() {
+                        "DAILY" => Grain::Day,
+                        "WEEKLY" => Grain::Week,
+                        "MONTHLY" => Grain::Month,
+                        "YEARLY" => Grain::Year,
+                        _ => return Err(bad()),
+                    })
+                }
+                "INTERVAL" => {
+                    rule.interval = value.parse().ok().filter(|i| *i > 0).ok_or_else(bad)?
+                }
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| bad())?),
+                "UNTIL" => rule.until = Some(parse_until(&value).ok_or_else(bad)?),
+                "BYMONTH" => rule.by_month = parse_list(&value, |m| (1..=12).contains(m), bad)?,
+                "BYMONTHDAY" => {
+                    rule.by_month_day =
+                        parse_list(&value, |d: &i32| (1..=31).contains(&d.abs()), bad)?
+                }
+                "BYSETPOS" => {
+                    rule.by_set_pos =
+                        parse_list(&value, |p: &i32| (1..=366).contains(&p.abs()), bad)?
+                }
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(|day| parse_by_day(day.trim()).ok_or_else(bad))
+                        .collect::<Result<_, _>>()?
+                }
+                "WKST" => rule.week_start = parse_code(&value).ok_or_else(bad)?,
+                _ => return Err(invalid(&format!("{} is not supported", name))),
+            }
+            seen.push(name);
+        }
+
+        rule.freq = freq.ok_or_else(|| invalid("FREQ is required"))?;
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err(invalid("COUNT and UNTIL cannot both be given"));
+        }
+        if rule.freq == Grain::Week && !rule.by_month_day.is_empty() {
+            return Err(invalid("BYMONTHDAY cannot be used with FREQ=WEEKLY"));
+        }
+        let max_nth = match rule.freq {
+            Grain::Month => 5,
+            Grain::Year if rule.by_month.is_empty() => 53,
+            Grain::Year => 5,
+            _ => 0,
+        };
+        if rule
+            .by_day
+            .iter()
+            .any(|(n, _)| n.is_some_and(|n| n.abs() > max_nth))
+        {
+            return Err(invalid("BYDAY occurrence is out of range for FREQ"));
+        }
+        if !rule.by_set_pos.is_empty()
+            && rule.by_month.is_empty()
+            && rule.by_month_day.is_empty()
+            && rule.by_day.is_empty()
+        {
+            return Err(invalid("BYSETPOS needs another BYxxx part"));
+        }
+        Ok(rule)
+    }
+}
+
+/// A date, or a date with a time whose time is dropped
+fn parse_until(value: &str) -> Option<NaiveDate> {
+    let date = value.split_once('T').map_or(value, |(date, _)| date);
+    if date.len() != 8 {
+        return None;
+    }
+    NaiveDate::parse_from_str(date, "%Y%m%d").ok()
+}
+
+fn parse_list<T: FromStr>(
+    value: &str,
+    valid: impl Fn(&T) -> bool,
+    bad: impl Fn() -> CalendsError,
+) -> Result<Vec<T>, CalendsError> {
+    value
+        .split(',')
+        .map(|v| {
+            let v = v.trim();
+            v.strip_prefix('+')
+                .unwrap_or(v)
+                .parse()
+                .ok()
+                .filter(&valid)
+                .ok_or_else(&bad)
+        })
+        .collect()
+}
+
+fn parse_by_day(day: &str) -> Option<ByDay> {
+    let split = day.len().checked_sub(2)?;
+    let (n, weekday) = day.split_at_checked(split)?;
+    let weekday = parse_code(weekday)?;
+    let n = match n {
+        "" => None,
+        n => Some(
+            n.strip_prefix('+')
+                .unwrap_or(n)
+                .parse()
+                .ok()
+                .filter(|n| *n != 0)?,
+        ),
+    };
+    Some((n, weekday))
+}
+
+/// The occurrences of an [RRule]
+///
+/// Each period of the rule is expanded in turn, so a year is the most held in memory at once.
+/// Rules which can never match, such as the 30th of February, end after 400 periods' worth of
+/// years without an occurrence, as the Gregorian calendar repeats every 400 years. Expansion also
+/// ends after the year 9999, the last year an RRULE can express.
+#[derive(Debug, Clone)]
+pub struct RRuleIter {
+    rule: RRule,
+    start: NaiveDate,
+    period: Option<NaiveDate>,
+    pending: VecDeque<NaiveDate>,
+    emitted: u32,
+    /// Year of the last occurrence, or of the start before there are any
+    last_year: i32,
+}
+
+impl RRuleIter {
+    fn new(mut rule: RRule, start: NaiveDate) -> Self {
+        // parts which are left out are taken from the start, as RFC 5545 requires
+        if rule.by_month_day.is_empty() && rule.by_day.is_empty() {
+            match rule.freq {
+                Grain::Year => {
+                    if rule.by_month.is_empty() {
+                        rule.by_month.push(start.month());
+                    }
+                    rule.by_month_day.push(start.day() as i32);
+                }
+                Grain::Month => rule.by_month_day.push(start.day() as i32),
+                Grain::Week => rule.by_day.push((None, start.weekday())),
+                _ => {}
+            }
+        }
+
+        let period = match rule.freq {
+            Grain::Day => start,
+            Grain::Week => beginning_of_week_starting(&start, rule.week_start),
+            Grain::Month => beginning_of_month(&start),
+            _ => NaiveDate::from_ymd_opt(start.year(), 1, 1).unwrap(),
+        };
+
+        RRuleIter {
+            rule,
+            start,
+            period: Some(period),
+            pending: VecDeque::new(),
+            emitted: 0,
+            last_year: start.year(),
+        }
+    }
+
+    /// Dates within the period which match the rule, in ascending order
+    fn expand(&self, period: NaiveDate) -> Vec<NaiveDate> {
+        let end = match self.rule.freq {
+            Grain::Day => period,
+            Grain::Week => period + Duration::days(6),
+            Grain::Month => end_of_month(&period),
+            _ => NaiveDate::from_ymd_opt(period.year(), 12, 31).unwrap(),
+        };
+        let matches: Vec<NaiveDate> = period
+            .iter_days()
+            .take_while(|date| *date <= end)
+            .filter(|date| self.matches(*date))
+            .collect();
+
+        if self.rule.by_set_pos.is_empty() {
+            return matches;
+        }
+        let len = matches.len() as i32;
+        let mut selected: Vec<NaiveDate> = self
+            .rule
+            .by_set_pos
+            .iter()
+            .filter_map(|pos| {
+                let i = if *pos > 0 { pos - 1 } else { len + pos };
+                (0..len).contains(&i).then(|| matches[i as usize])
+            })
+            .collect();
+        selected.sort();
+        selected.dedup();
+        selected
+    }
+
+    fn matches(&self, date: NaiveDate) -> bool {
+        let rule = &self.rule;
+        if !rule.by_month.is_empty() && !rule.by_month.contains(&date.month()) {
+            return false;
+        }
+
+        let days_in_month = days_in_month(date.year(), date.month()) as i32;
+        let day = date.day() as i32;
+        if !rule.by_month_day.is_empty()
+            && !rule
+                .by_month_day
+                .iter()
+                .any(|d| *d == day || *d == day - days_in_month - 1)
+        {
+            return false;
+        }
+
+        if rule.by_day.is_empty() {
+            return true;
+        }
+        // occurrences are counted within the year unless the rule is monthly or picks months
+        let (index, len) = match rule.freq {
+            Grain::Year if rule.by_month.is_empty() => {
+                let days_in_year = NaiveDate::from_ymd_opt(date.year(), 12, 31)
+                    .unwrap()
+                    .ordinal() as i32;
+                (date.ordinal0() as i32, days_in_year)
+            }
+            _ => (day - 1, days_in_month),
+        };
+        let (nth, nth_back) = (index / 7 + 1, -((len - 1 - index) / 7 + 1));
+
+        rule.by_day.iter().any(|(n, weekday)| {
+            *weekday == date.weekday() && n.is_none_or(|n| n == nth || n == nth_back)
+        })
+    }
+
+    fn next_period(&self, period: NaiveDate) -> Option<NaiveDate> {
+        let interval = i64::from(self.rule.interval);
+        match self.rule.freq {
+            Grain::Day => period.checked_add_signed(Duration::try_days(interval)?),
+            Grain::Week => period.checked_add_signed(Duration::try_weeks(interval)?),
+            Grain::Month => {
+                let months = i64::from(period.month0()) + interval;
+                let year = i64::from(period.year()) + months / 12;
+                NaiveDate::from_ymd_opt(year.try_into().ok()?, (months % 12) as u32 + 1, 1)
+            }
+            _ => NaiveDate::from_ymd_opt(
+                (i64::from(period.year()) + interval).try_into().ok()?,
+                1,
+                1,
+            ),
+        }
+    }
+}
+
+impl Iterator for RRuleIter {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rule.count.is_some_and(|count| self.emitted >= count) {
+                return None;
+            }
+            if let Some(date) = self.pending.pop_front() {
+                if self.rule.until.is_some_and(|until| date > until) {
+                    self.period = None;
+                    self.pending.clear();
+                    return None;
+                }
+                self.emitted += 1;
+                self.last_year = date.year();
+                return Some(date);
+            }
+
+            let period = self.period?;
+            let give_up = i64::from(self.last_year) + 400 * i64::from(self.rule.interval);
+            if period.year() > 9999
+                || i64::from(period.year()) > give_up
+                || self.rule.until.is_some_and(|until| period > until)
+            {
+                self.period = None;
+                return None;
+            }
+
+            let start = self.start;
+            self.pending = self
+                .expand(period)
+                .into_iter()
+                .filter(|date| *date >= start)
+                .collect();
+            self.period = self.next_period(period);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_display_round_trips() {
+        for s in [
+            "FREQ=DAILY",
+            "FREQ=WEEKLY;INTERVAL=2;UNTIL=19971224;BYDAY=TU,TH;WKST=SU",
+            "FREQ=MONTHLY;COUNT=10;BYMONTHDAY=1,-1",
+            "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH",
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-2",
+        ] {
+            assert_eq!(s.parse::<RRule>().unwrap().to_string(), s);
+        }
+        assert_eq!(
+            "rrule:freq=yearly;byday=+20mo;until=19971224T000000Z"
+                .parse::<RRule>()
+                .unwrap()
+                .to_string(),
+            "FREQ=YEARLY;UNTIL=19971224;BYDAY=20MO"
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for s in [
+            "",
+            "INTERVAL=2",
+            "FREQ=HOURLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;COUNT=2;UNTIL=20220101",
+            "FREQ=DAILY;COUNT=2;COUNT=3",
+            "FREQ=DAILY;BYHOUR=9",
+            "FREQ=DAILY;UNTIL=2022-01-01",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=MONTHLY;BYDAY=6MO",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=WEEKLY;BYMONTHDAY=1",
+            "FREQ=MONTHLY;BYSETPOS=1",
+            "FREQ=MONTHLY;BYDAY=0MO",
+        ] {
+            assert!(
+                matches!(s.parse::<RRule>(), Err(CalendsError::InvalidRRule(_))),
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_impossible_rule_ends() {
+        let rule: RRule = "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30".parse().unwrap();
+        assert_eq!(rule.iter(date(2022, 1, 1)).next(), None);
+    }
+
+    #[test]
+    fn test_start_only_counts_when_it_matches() {
+        let rule: RRule = "FREQ=WEEKLY;BYDAY=FR;COUNT=2".parse().unwrap();
+
+        // 2022-01-03 is a Monday
+        assert_eq!(
+            rule.iter(date(2022, 1, 3)).collect::<Vec<_>>(),
+            vec![date(2022, 1, 7), date(2022, 1, 14)]
+        );
+    }
+
+    #[test]
+    fn test_leap_day_every_four_years() {
+        let rule: RRule = "FREQ=YEARLY;COUNT=3".parse().unwrap();
+
+        assert_eq!(
+            rule.iter(date(2024, 2, 29)).collect::<Vec<_>>(),
+            vec![date(2024, 2, 29), date(2028, 2, 29), date(2032, 2, 29)]
+        );
+        assert_eq!(rule.iter(date(9996, 2, 29)).count(), 1);
+    }
+}
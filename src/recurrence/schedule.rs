@@ -0,0 +1,475 @@
+//! Schedules written as short sentences
+//!
+//! A [Schedule] is a [Rule] together with the days it avoids and an optional end, parsed from a
+//! compact text form so jobs can be configured from an environment variable or a command line
+//! flag. The text is a frequency followed by clauses separated by `;`, and is not case sensitive:
+//!
+//! ```text
+//! monthly on last business day; skip us holidays; until 2026-12-31
+//! ```
+//!
+//! The frequency is one of `daily`, `weekly`, `biweekly`, `monthly`, `quarterly` or `yearly`,
+//! which repeat from the start date. Weekly and monthly schedules can pick their days instead:
+//!
+//! - `weekly on mon, wed, fri`: each of the weekdays, given by name or RFC 5545 code
+//! - `monthly on 15th` or `monthly on day 15`: a day of the month, negative days count from
+//!   the end and months without the day are skipped
+//! - `monthly on last day`: the last day of the month
+//! - `monthly on second tuesday` or `monthly on last friday`: an occurrence of a weekday, with
+//!   `first` to `fifth` or `last`
+//! - `monthly on first business day` and `monthly on last business day`: the first or last day
+//!   of the month which is not a weekend or a skipped holiday
+//!
+//! The clauses are:
+//!
+//! - `skip weekends`: Saturdays and Sundays are skipped
+//! - `skip <calendar> holidays`: the holidays of a [Builtin] calendar, `us`, `uk` or `target2`,
+//!   are skipped
+//! - `roll following` or `roll preceding`: skipped days are moved to the next or previous
+//!   business day rather than dropped
+//! - `roll modified following` or `roll modified preceding`: as above unless that would move the
+//!   day into another month, when it is moved the other way
+//! - `until <date>`: the last date the schedule can occur on
+//!
+//! # Examples
+//!
+//! ```
+//! # use chrono::NaiveDate;
+//! # use calends::recurrence::Schedule;
+//!
+//! let schedule: Schedule = "monthly on last business day; skip us holidays; until 2022-06-30"
+//!     .parse()?;
+//!
+//! let start = NaiveDate::from_ymd_opt(2022, 4, 1).unwrap();
+//! assert_eq!(
+//!     schedule.occurrences(start).map(|d| d.to_string()).collect::<Vec<_>>(),
+//!     vec!["2022-04-29", "2022-05-31", "2022-06-30"]
+//! );
+//! # Ok::<(), calends::CalendsError>(())
+//! ```
+use std::str::FromStr;
+
+use chrono::{NaiveDate, Weekday};
+
+use crate::{
+    business::RollPolicy,
+    error::CalendsError,
+    holidays::{Builtin, HolidayCalendar},
+    monthday::DayOfMonth,
+    trace,
+    weekday::{parse_code, NthWeekday, WeekdaySet},
+};
+
+use super::{Recurrence, Rule};
+
+/// Occurrences in a row which can be skipped before a schedule is taken to never occur again, a
+/// year of daily occurrences
+const MAX_SKIPPED: usize = 366;
+
+/// A recurrence rule with the days it skips, see the [module](self) documentation for the syntax
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    rule: Rule,
+    skip_weekends: bool,
+    holidays: Vec<Builtin>,
+    roll: Option<RollPolicy>,
+    until: Option<NaiveDate>,
+}
+
+impl Schedule {
+    pub fn new(rule: Rule) -> Self {
+        Schedule {
+            rule,
+            skip_weekends: false,
+            holidays: Vec::new(),
+            roll: None,
+            until: None,
+        }
+    }
+
+    pub fn with_skip_weekends(mut self, skip: bool) -> Self {
+        self.skip_weekends = skip;
+        self
+    }
+
+    /// Also skip the holidays of the calendar
+    pub fn with_holidays(mut self, holidays: Builtin) -> Self {
+        if !self.holidays.contains(&holidays) {
+            self.holidays.push(holidays);
+        }
+        self
+    }
+
+    /// Move skipped days to a business day with the policy instead of dropping them
+    pub fn with_roll(mut self, roll: RollPolicy) -> Self {
+        self.roll = Some(roll);
+        self
+    }
+
+    /// The last date the schedule can occur on
+    pub fn with_until(mut self, until: NaiveDate) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    pub fn skips_weekends(&self) -> bool {
+        self.skip_weekends
+    }
+
+    pub fn holidays(&self) -> &[Builtin] {
+        &self.holidays
+    }
+
+    pub fn roll(&self) -> Option<RollPolicy> {
+        self.roll
+    }
+
+    pub fn until(&self) -> Option<NaiveDate> {
+        self.until
+    }
+
+    /// Whether the schedule avoids the date
+    pub fn is_skipped(&self, date: NaiveDate) -> bool {
+        !self.is_business_day(date)
+    }
+
+    /// Dates of the schedule from the start in ascending order
+    ///
+    /// Rules which repeat a duration, such as `monthly` without a day, are anchored on the start.
+    /// Occurrences on skipped days are rolled when the schedule has a roll policy and dropped
+    /// otherwise, rolling never moves an occurrence past the schedule's end. The dates end once
+    /// 366 occurrences in a row have been dropped, as for `yearly; skip us holidays; skip weekends`
+    /// starting on New Year's Day.
+    pub fn occurrences(&self, start: NaiveDate) -> ScheduledDates<'_> {
+        ScheduledDates {
+            schedule: self,
+            recurrence: Recurrence::with_start(self.rule.clone(), start),
+            start,
+            last: None,
+        }
+    }
+}
+
+impl HolidayCalendar for Schedule {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays
+            .iter()
+            .any(|holidays| holidays.is_holiday(date))
+    }
+
+    fn is_weekend(&self, date: NaiveDate) -> bool {
+        self.skip_weekends && WeekdaySet::WEEKEND.contains(chrono::Datelike::weekday(&date))
+    }
+}
+
+/// Dates of a [Schedule], see [Schedule::occurrences]
+#[derive(Debug, Clone)]
+pub struct ScheduledDates<'a> {
+    schedule: &'a Schedule,
+    recurrence: Recurrence,
+    start: NaiveDate,
+    last: Option<NaiveDate>,
+}
+
+impl Iterator for ScheduledDates<'_> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        let schedule = self.schedule;
+        let within = |date: NaiveDate| schedule.until.is_none_or(|until| date <= until);
+        for _ in 0..MAX_SKIPPED {
+            let date = self.recurrence.next().filter(|date| within(*date))?;
+            let date = match (schedule.is_skipped(date), schedule.roll) {
+                (false, _) => date,
+                (true, Some(roll)) => roll.apply(date, schedule),
+                (true, None) => continue,
+            };
+            // rolling can move two occurrences onto the same business day
+            if date >= self.start && within(date) && self.last.replace(date) != Some(date) {
+                return Some(date);
+            }
+        }
+        trace::warning!(rule = %schedule.rule.canonical(), "schedule skips every occurrence");
+        None
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |clause: &str| CalendsError::InvalidSchedule(clause.trim().to_string());
+        let lowercase = s.to_ascii_lowercase();
+        let mut clauses = lowercase.split(';').map(str::trim);
+
+        let first = clauses.next().unwrap_or_default();
+        let mut schedule = parse_rule(first).ok_or_else(|| invalid(first))?;
+
+        for clause in clauses.filter(|clause| !clause.is_empty()) {
+            let words: Vec<&str> = clause.split_whitespace().collect();
+            schedule = match words.as_slice() {
+                ["skip", "weekends"] => schedule.with_skip_weekends(true),
+                ["skip", name, "holidays"] => schedule.with_holidays(name.parse()?),
+                ["roll", "following"] => schedule.with_roll(RollPolicy::Following),
+                ["roll", "preceding"] => schedule.with_roll(RollPolicy::Preceding),
+                ["roll", "modified", "following"] => {
+                    schedule.with_roll(RollPolicy::ModifiedFollowing)
+                }
+                ["roll", "modified", "preceding"] => {
+                    schedule.with_roll(RollPolicy::ModifiedPreceding)
+                }
+                ["until", date] if schedule.until.is_none() => {
+                    schedule.with_until(date.parse().map_err(|_| invalid(clause))?)
+                }
+                _ => return Err(invalid(clause)),
+            };
+        }
+
+        // only weekend days with weekends skipped and nowhere to roll them would never occur
+        if let Rule::Weekdays(days) = &schedule.rule {
+            let weekend_only = days.iter().all(|day| WeekdaySet::WEEKEND.contains(day));
+            if weekend_only && schedule.skip_weekends && schedule.roll.is_none() {
+                return Err(invalid(s));
+            }
+        }
+        Ok(schedule)
+    }
+}
+
+fn parse_rule(clause: &str) -> Option<Schedule> {
+    let (frequency, on) = match clause.split_once(" on ") {
+        Some((frequency, on)) => (frequency.trim(), Some(on.trim())),
+        None => (clause, None),
+    };
+
+    let rule = match (frequency, on) {
+        ("daily", None) => Rule::daily(),
+        ("weekly", None) => Rule::weekly(),
+        ("biweekly", None) => Rule::biweekly(),
+        ("monthly", None) => Rule::monthly(),
+        ("quarterly", None) => Rule::quarterly(),
+        ("yearly", None) => Rule::yearly(),
+        ("weekly", Some(days)) => Rule::Weekdays(
+            days.split(',')
+                .map(|day| parse_weekday(day.trim()))
+                .collect::<Option<WeekdaySet>>()?,
+        ),
+        ("monthly", Some("first business day")) => {
+            return Some(business_days(
+                DayOfMonth::new(1).ok()?,
+                RollPolicy::Following,
+            ))
+        }
+        ("monthly", Some("last business day")) => {
+            return Some(business_days(DayOfMonth::LAST, RollPolicy::Preceding))
+        }
+        ("monthly", Some(day)) => parse_month_day(day)?,
+        _ => return None,
+    };
+    Some(Schedule::new(rule))
+}
+
+/// A day of the month rolled to a business day, which is never dropped
+fn business_days(day: DayOfMonth, roll: RollPolicy) -> Schedule {
+    Schedule::new(Rule::MonthDay(day))
+        .with_skip_weekends(true)
+        .with_roll(roll)
+}
+
+fn parse_month_day(day: &str) -> Option<Rule> {
+    let words: Vec<&str> = day.split_whitespace().collect();
+    match words.as_slice() {
+        ["last", "day"] => Some(Rule::MonthDay(DayOfMonth::LAST)),
+        ["day", day] => DayOfMonth::new(day.parse().ok()?).ok().map(Rule::MonthDay),
+        [day] => {
+            let day = day.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+            DayOfMonth::new(day.parse().ok()?).ok().map(Rule::MonthDay)
+        }
+        [nth, weekday] => {
+            let nth = match *nth {
+                "first" => 1,
+                "second" => 2,
+                "third" => 3,
+                "fourth" => 4,
+                "fifth" => 5,
+                "last" => -1,
+                _ => return None,
+            };
+            NthWeekday::new(nth, parse_weekday(weekday)?)
+                .ok()
+                .map(Rule::NthWeekday)
+        }
+        _ => None,
+    }
+}
+
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    parse_code(&day.to_ascii_uppercase()).or_else(|| day.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    fn dates(schedule: &str, start: NaiveDate, n: usize) -> Vec<String> {
+        schedule
+            .parse::<Schedule>()
+            .unwrap()
+            .occurrences(start)
+            .take(n)
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rule = |s: &str| s.parse::<Schedule>().map(|s| s.rule().clone());
+
+        assert_eq!(rule("Daily"), Ok(Rule::daily()));
+        assert_eq!(
+            rule("weekly on mon, WE,friday"),
+            Ok(Rule::Weekdays("MO,WE,FR".parse().unwrap()))
+        );
+        assert_eq!(
+            rule("monthly on 31st"),
+            Ok(Rule::MonthDay(DayOfMonth::new(31).unwrap()))
+        );
+        assert_eq!(
+            rule("monthly on day -2"),
+            Ok(Rule::MonthDay(DayOfMonth::new(-2).unwrap()))
+        );
+        assert_eq!(
+            rule("monthly on last friday"),
+            Ok(Rule::NthWeekday(NthWeekday::last(Weekday::Fri)))
+        );
+
+        for invalid in [
+            "",
+            "hourly",
+            "daily on monday",
+            "monthly on 32nd",
+            "monthly on sixth monday",
+            "weekly on someday",
+            "monthly; until tomorrow",
+            "monthly; until 2022-01-01; until 2023-01-01",
+            "monthly; skip mondays",
+        ] {
+            assert!(invalid.parse::<Schedule>().is_err(), "{}", invalid);
+        }
+        assert_eq!(
+            "monthly; skip mars holidays".parse::<Schedule>(),
+            Err(CalendsError::UnknownCalendar("mars".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_business_days() {
+        // 2022-04-30 was a Saturday and 2024-01-01 was New Year's Day
+        assert_eq!(
+            dates("monthly on last business day", date(2022, 4, 1), 2),
+            vec!["2022-04-29", "2022-05-31"]
+        );
+        assert_eq!(
+            dates(
+                "monthly on first business day; skip US holidays",
+                date(2023, 12, 15),
+                2
+            ),
+            vec!["2024-01-02", "2024-02-01"]
+        );
+    }
+
+    #[test]
+    fn test_skipped_days_are_dropped_or_rolled() {
+        // 2022-05-02 was the Early May bank holiday in the UK and the Spring bank holiday was
+        // moved to 2022-06-02
+        assert_eq!(
+            dates("monthly on 2nd; skip uk holidays", date(2022, 4, 1), 2),
+            vec!["2022-04-02", "2022-07-02"]
+        );
+        assert_eq!(
+            dates(
+                "monthly on 2nd; skip uk holidays; skip weekends; roll following",
+                date(2022, 4, 1),
+                2
+            ),
+            vec!["2022-04-04", "2022-05-03"]
+        );
+        // both Saturday and Sunday roll onto the same Monday
+        assert_eq!(
+            dates(
+                "weekly on sat, sun, mon; skip weekends; roll following",
+                date(2022, 1, 1),
+                2
+            ),
+            vec!["2022-01-03", "2022-01-10"]
+        );
+        // 2022-04-30 is a Saturday and rolling forward would leave April
+        assert_eq!(
+            dates(
+                "monthly on last day; skip weekends; roll modified following",
+                date(2022, 4, 1),
+                2
+            ),
+            vec!["2022-04-29", "2022-05-31"]
+        );
+        assert_eq!(
+            dates(
+                "monthly on 1st; skip weekends; roll modified preceding",
+                date(2022, 1, 1),
+                2
+            ),
+            vec!["2022-01-03", "2022-02-01"]
+        );
+    }
+
+    #[test]
+    fn test_schedules_which_never_occur() {
+        for weekends in [
+            "weekly on sat; skip weekends",
+            "weekly on sun, sat; skip weekends",
+        ] {
+            assert_eq!(
+                weekends.parse::<Schedule>(),
+                Err(CalendsError::InvalidSchedule(weekends.to_string()))
+            );
+        }
+        assert_eq!(
+            dates(
+                "weekly on sat; skip weekends; roll following",
+                date(2022, 1, 1),
+                1
+            ),
+            vec!["2022-01-03"]
+        );
+
+        let saturdays =
+            Schedule::new(Rule::Weekdays("SA".parse().unwrap())).with_skip_weekends(true);
+        assert_eq!(saturdays.occurrences(date(2022, 1, 1)).next(), None);
+        // New Year's Day is always either a holiday or at a weekend
+        assert!(dates(
+            "yearly; skip us holidays; skip weekends",
+            date(2022, 1, 1),
+            1
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_until_is_inclusive() {
+        let schedule: Schedule = "weekly; until 2022-01-15; roll preceding; skip weekends"
+            .parse()
+            .unwrap();
+
+        assert_eq!(schedule.until(), Some(date(2022, 1, 15)));
+        assert_eq!(
+            schedule.occurrences(date(2022, 1, 1)).collect::<Vec<_>>(),
+            vec![date(2022, 1, 7), date(2022, 1, 14)]
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Occurrences as RFC 3339 and RFC 9557 timestamps
+//!
+//! Recurrences produce dates, while systems written in JavaScript or Java usually exchange
+//! instants. A [Timestamp] places an occurrence at a time of day with a UTC offset and formats it
+//! as RFC 3339, e.g. `2022-01-03T09:00:00+01:00`, or as the RFC 9557 extension with a time zone
+//! annotation, e.g. `2022-01-03T09:00:00+01:00[Europe/Paris]`, which is what `Temporal` and
+//! `java.time.ZonedDateTime` produce.
+//!
+//! There is no time zone database here, so the zone is carried as its name and the offset is
+//! taken from the timestamp rather than checked against the zone.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chrono::{FixedOffset, NaiveDate, NaiveTime};
+//! # use calends::{Recurrence, Rule};
+//! # use calends::recurrence::timestamp::{timestamps, Timestamp};
+//!
+//! let start = NaiveDate::from_ymd_opt(2022, 1, 3).unwrap();
+//! let nine = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+//! let paris = FixedOffset::east_opt(3600).unwrap();
+//!
+//! let output: Vec<String> = timestamps(Recurrence::with_start(Rule::weekly(), start).take(2), nine, paris)
+//!     .map(|t| t.with_zone("Europe/Paris").to_string())
+//!     .collect();
+//! assert_eq!(
+//!     output,
+//!     vec!["2022-01-03T09:00:00+01:00[Europe/Paris]", "2022-01-10T09:00:00+01:00[Europe/Paris]"]
+//! );
+//!
+//! let parsed: Timestamp = output[0].parse()?;
+//! assert_eq!(parsed.date(), start);
+//! assert_eq!(parsed.zone(), Some("Europe/Paris"));
+//! assert_eq!(parsed.to_rfc3339(), "2022-01-03T09:00:00+01:00");
+//! # Ok::<(), calends::CalendsError>(())
+//! ```
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, SecondsFormat};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::CalendsError;
+
+/// An instant with its UTC offset and optionally the time zone it was recorded in
+///
+/// Timestamps display in the RFC 9557 form, which is plain RFC 3339 when there is no zone, and
+/// are serialized the same way. Parsing accepts both forms, ignoring elective annotations such as
+/// `[u-ca=gregory]` and rejecting critical ones such as `[!u-ca=gregory]` as none are supported.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Timestamp {
+    datetime: DateTime<FixedOffset>,
+    zone: Option<String>,
+}
+
+impl Timestamp {
+    pub fn new(datetime: DateTime<FixedOffset>) -> Self {
+        Timestamp {
+            datetime,
+            zone: None,
+        }
+    }
+
+    /// The date at a local time with the offset, [None] when it is outside of chrono's range
+    pub fn at(date: NaiveDate, time: NaiveTime, offset: FixedOffset) -> Option<Self> {
+        date.and_time(time)
+            .and_local_timezone(offset)
+            .single()
+            .map(Timestamp::new)
+    }
+
+    /// Annotate the timestamp with the name of its time zone, such as `Europe/Paris`
+    pub fn with_zone(mut self, zone: impl Into<String>) -> Self {
+        self.zone = Some(zone.into());
+        self
+    }
+
+    pub fn datetime(&self) -> DateTime<FixedOffset> {
+        self.datetime
+    }
+
+    /// The local date, which is the occurrence the timestamp was made from
+    pub fn date(&self) -> NaiveDate {
+        self.datetime.date_naive()
+    }
+
+    pub fn zone(&self) -> Option<&str> {
+        self.zone.as_deref()
+    }
+
+    /// The timestamp without its zone, UTC is written as `Z`
+    pub fn to_rfc3339(&self) -> String {
+        self.datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+    }
+}
+
+/// Occurrences at a local time with the offset, dates outside of chrono's range are skipped
+pub fn timestamps<I: IntoIterator<Item = NaiveDate>>(
+    dates: I,
+    time: NaiveTime,
+    offset: FixedOffset,
+) -> impl Iterator<Item = Timestamp> {
+    dates
+        .into_iter()
+        .filter_map(move |date| Timestamp::at(date, time, offset))
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_rfc3339())?;
+        match &self.zone {
+            Some(zone) => write!(f, "[{}]", zone),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CalendsError::InvalidTimestamp(s.to_string());
+        let (datetime, mut suffix) = s.split_at(s.find('[').unwrap_or(s.len()));
+        let mut timestamp =
+            Timestamp::new(DateTime::parse_from_rfc3339(datetime).map_err(|_| invalid())?);
+
+        let mut first = true;
+        while !suffix.is_empty() {
+            let end = suffix.find(']').ok_or_else(invalid)?;
+            let annotation = suffix.strip_prefix('[').ok_or_else(invalid)?[..end - 1].to_string();
+            suffix = &suffix[end + 1..];
+
+            let (critical, annotation) = match annotation.strip_prefix('!') {
+                Some(annotation) => (true, annotation),
+                None => (false, annotation.as_str()),
+            };
+            match annotation.split_once('=') {
+                // the time zone can only be the first annotation
+                None if first && is_zone(annotation) => {
+                    timestamp.zone = Some(annotation.to_string())
+                }
+                Some((key, value)) if !critical && is_key(key) && !value.is_empty() => {}
+                _ => return Err(invalid()),
+            }
+            first = false;
+        }
+        Ok(timestamp)
+    }
+}
+
+/// A zone name such as `Europe/Paris` or a numeric offset such as `+01:00`
+fn is_zone(zone: &str) -> bool {
+    !zone.is_empty()
+        && zone
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/_-+.:".contains(c))
+}
+
+fn is_key(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-".contains(c))
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub struct TimestampVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> de::Visitor<'de> for TimestampVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an RFC 3339 timestamp such as 2022-01-03T09:00:00+01:00[Europe/Paris]")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Timestamp, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TimestampVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    fn utc_nine(day: u32) -> Timestamp {
+        Timestamp::at(
+            date(2022, 1, day),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            FixedOffset::east_opt(0).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_round_trip() {
+        for s in [
+            "2022-01-03T09:00:00Z",
+            "2022-01-03T09:00:00.250-05:00",
+            "2022-01-03T09:00:00+01:00[Europe/Paris]",
+            "2022-01-03T09:00:00+05:30[+05:30]",
+        ] {
+            assert_eq!(s.parse::<Timestamp>().unwrap().to_string(), s);
+        }
+        assert_eq!(utc_nine(3).to_string(), "2022-01-03T09:00:00Z");
+
+        let json = serde_json::to_string(&utc_nine(3).with_zone("UTC")).unwrap();
+        assert_eq!(json, r#""2022-01-03T09:00:00Z[UTC]""#);
+        assert_eq!(
+            serde_json::from_str::<Timestamp>(&json).unwrap(),
+            utc_nine(3).with_zone("UTC")
+        );
+    }
+
+    #[test]
+    fn test_annotations() {
+        let parse = |s: &str| s.parse::<Timestamp>();
+
+        // lowercase separators and elective annotations are accepted
+        let timestamp = parse("2022-01-03t09:00:00z[Etc/UTC][u-ca=gregory]").unwrap();
+        assert_eq!(timestamp, utc_nine(3).with_zone("Etc/UTC"));
+        assert_eq!(parse("2022-01-03T09:00:00Z[u-ca=iso8601]"), Ok(utc_nine(3)));
+
+        for invalid in [
+            "2022-01-03",
+            "2022-01-03T09:00:00",
+            "2022-01-03T09:00:00Z[!u-ca=gregory]",
+            "2022-01-03T09:00:00Z[u-ca=gregory][Europe/Paris]",
+            "2022-01-03T09:00:00Z[Europe/Paris",
+            "2022-01-03T09:00:00Z[]",
+            "2022-01-03T09:00:00Z[Europe Paris]",
+            "2022-01-03T09:00:00Z x",
+        ] {
+            assert_eq!(
+                parse(invalid),
+                Err(CalendsError::InvalidTimestamp(invalid.to_string())),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
@@ -1,7 +1,12 @@
-use std::{cmp::Ordering, ops::Bound};
+use std::{
+    cmp::{Ordering, Reverse},
+    ops::Bound,
+};
 
 use chrono::NaiveDate;
 
+use crate::trace;
+
 fn cmp_bound<Q>(e1: &Bound<Q>, e2: &Bound<Q>) -> Ordering
 where
     Q: Ord,
@@ -29,6 +34,9 @@ where
 }
 
 /// Iterates until a certain point in time
+///
+/// The limit is an upper bound on the dates unless the iterator runs backwards through time and
+/// the limit is marked as [descending](Until::descending), in which case it is a lower bound.
 #[derive(Debug, Clone)]
 pub struct Until<T>
 where
@@ -36,6 +44,7 @@ where
 {
     pub until: Bound<NaiveDate>,
     pub iter: T,
+    pub descending: bool,
 }
 
 impl<T> Until<T>
@@ -46,6 +55,7 @@ where
         Self {
             until: Bound::Included(until),
             iter,
+            descending: false,
         }
     }
 
@@ -53,8 +63,15 @@ where
         Self {
             until: Bound::Excluded(until),
             iter,
+            descending: false,
         }
     }
+
+    /// Treat the limit as the earliest date of an iterator which runs backwards through time
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
 }
 
 impl<T> Iterator for Until<T>
@@ -65,10 +82,18 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         let event = self.iter.next()?;
-        match cmp_bound(&Bound::Included(event), &self.until) {
+        let ordering = match self.descending {
+            // reversing the order turns the earliest date into an upper bound
+            true => cmp_bound(&Bound::Included(Reverse(event)), &self.until.map(Reverse)),
+            false => cmp_bound(&Bound::Included(event), &self.until),
+        };
+        match ordering {
             std::cmp::Ordering::Less => Some(event),
             std::cmp::Ordering::Equal => Some(event),
-            std::cmp::Ordering::Greater => None,
+            std::cmp::Ordering::Greater => {
+                trace::debug!(%event, until = ?self.until, "occurrence is past the limit");
+                None
+            }
         }
     }
 }
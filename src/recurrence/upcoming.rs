@@ -0,0 +1,201 @@
+//! What is due soon across many rules
+//!
+//! Dashboards listing the deadlines in the next month need the next occurrences of many unrelated
+//! rules in date order. [upcoming] merges them lazily, so each rule is only expanded as far as the
+//! horizon.
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use chrono::NaiveDate;
+
+use crate::RelativeDuration;
+
+use super::Recurrence;
+
+#[derive(Debug, Clone)]
+enum Source {
+    Recurrence(Recurrence),
+    Dates(Vec<NaiveDate>),
+}
+
+/// A rule giving the dates of a named event
+#[derive(Debug, Clone)]
+pub struct NamedRule {
+    name: String,
+    source: Source,
+}
+
+impl NamedRule {
+    /// Occurs on every date of the recurrence, which should be ascending
+    pub fn recurrence(name: impl Into<String>, recurrence: Recurrence) -> Self {
+        NamedRule {
+            name: name.into(),
+            source: Source::Recurrence(recurrence),
+        }
+    }
+
+    /// Occurs on each of the dates, in any order
+    pub fn dates(name: impl Into<String>, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        let mut dates: Vec<NaiveDate> = dates.into_iter().collect();
+        dates.sort();
+        dates.dedup();
+
+        NamedRule {
+            name: name.into(),
+            source: Source::Dates(dates),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Occurrences from the date onwards, in ascending order
+    fn from(&self, date: NaiveDate) -> Box<dyn Iterator<Item = NaiveDate> + '_> {
+        match &self.source {
+            Source::Recurrence(recurrence) => {
+                let mut previous = None;
+                Box::new(
+                    recurrence
+                        .clone()
+                        // stop on rules which do not move forward, they would never reach the date
+                        .take_while(move |d| {
+                            let forward = previous.is_none_or(|p| *d > p);
+                            previous = Some(*d);
+                            forward
+                        })
+                        .skip_while(move |d| *d < date),
+                )
+            }
+            Source::Dates(dates) => {
+                let i = dates.partition_point(|d| *d < date);
+                Box::new(dates[i..].iter().copied())
+            }
+        }
+    }
+}
+
+/// Occurrences of every rule from `as_of` up to and including `as_of + horizon`
+///
+/// Occurrences are in ascending date order, and rules occurring on the same date are in the order
+/// they were given. A horizon reaching beyond the range of supported dates runs to its end.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{RelativeDuration, Recurrence, Rule};
+/// # use calends::recurrence::upcoming::{upcoming, NamedRule};
+///
+/// let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+///
+/// let rules = [
+///     NamedRule::recurrence("rent", Recurrence::with_start(Rule::monthly(), date(1, 1))),
+///     NamedRule::dates("tax return", [date(4, 18)]),
+/// ];
+///
+/// assert_eq!(
+///     upcoming(&rules, date(3, 20), RelativeDuration::days(30)),
+///     vec![(date(4, 1), "rent"), (date(4, 18), "tax return")]
+/// );
+/// ```
+pub fn upcoming(
+    rules: &[NamedRule],
+    as_of: NaiveDate,
+    horizon: RelativeDuration,
+) -> Vec<(NaiveDate, &str)> {
+    let until = horizon.try_add_to(as_of).unwrap_or_else(|_| {
+        match (horizon.num_months(), horizon.num_days()) {
+            (months, days) if months < 0 || (months == 0 && days < 0) => NaiveDate::MIN,
+            _ => NaiveDate::MAX,
+        }
+    });
+    let mut series: Vec<_> = rules
+        .iter()
+        .map(|rule| rule.from(as_of).take_while(move |d| *d <= until))
+        .collect();
+
+    let mut heap = BinaryHeap::new();
+    for (i, occurrences) in series.iter_mut().enumerate() {
+        if let Some(date) = occurrences.next() {
+            heap.push(Reverse((date, i)));
+        }
+    }
+
+    let mut due = Vec::new();
+    while let Some(Reverse((date, i))) = heap.pop() {
+        due.push((date, rules[i].name()));
+        if let Some(next) = series[i].next() {
+            heap.push(Reverse((next, i)));
+        }
+    }
+
+    due
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Days;
+
+    use super::*;
+    use crate::{testing::date, Rule};
+
+    #[test]
+    fn test_upcoming_merges_in_date_order() {
+        let rules = [
+            NamedRule::recurrence(
+                "standup",
+                Recurrence::with_start(Rule::weekly(), date(2022, 1, 3)),
+            ),
+            NamedRule::dates(
+                "launch",
+                [date(2022, 1, 17), date(2022, 1, 12), date(2022, 3, 1)],
+            ),
+            NamedRule::recurrence(
+                "review",
+                Recurrence::with_start(Rule::biweekly(), date(2022, 1, 3)),
+            ),
+        ];
+
+        assert_eq!(
+            upcoming(&rules, date(2022, 1, 10), RelativeDuration::weeks(1)),
+            vec![
+                (date(2022, 1, 10), "standup"),
+                (date(2022, 1, 12), "launch"),
+                (date(2022, 1, 17), "standup"),
+                (date(2022, 1, 17), "launch"),
+                (date(2022, 1, 17), "review"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_upcoming_outside_horizon() {
+        let rules = [NamedRule::dates("launch", [date(2022, 3, 1)])];
+
+        assert!(upcoming(&rules, date(2022, 1, 1), RelativeDuration::months(1)).is_empty());
+        assert!(upcoming(&rules, date(2022, 3, 2), RelativeDuration::months(1)).is_empty());
+        assert_eq!(
+            upcoming(&rules, date(2022, 3, 1), RelativeDuration::zero()),
+            vec![(date(2022, 3, 1), "launch")]
+        );
+    }
+
+    #[test]
+    fn test_upcoming_beyond_supported_dates() {
+        let late = NaiveDate::MAX.pred_opt().unwrap();
+        let rules = [NamedRule::dates("launch", [late])];
+        let as_of = NaiveDate::MAX - Days::new(10);
+
+        assert_eq!(
+            upcoming(&rules, as_of, RelativeDuration::months(1)),
+            vec![(late, "launch")]
+        );
+        assert!(upcoming(&rules, as_of, RelativeDuration::months(-1)).is_empty());
+        assert!(upcoming(
+            &rules,
+            NaiveDate::MIN + Days::new(10),
+            RelativeDuration::months(-1)
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,276 @@
+//! Serde helpers for chrono fields
+//!
+//! These modules (de)serialize plain chrono values using the string formats calends uses
+//! elsewhere, so applications can persist them with `#[serde(with = "...")]` and without a wrapper
+//! newtype.
+//!
+//! - [ym]: a month as `2022-05`
+//! - [quarter]: a calendar quarter as `2022-Q2`
+//! - [iso_interval]: a pair of dates as an ISO8601-2 interval such as `2022-01-01/2022-03-31`
+//!
+//! # Example
+//!
+//! ```
+//! use chrono::NaiveDate;
+//!
+//! #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+//! struct Forecast {
+//!     #[serde(with = "calends::serde::ym")]
+//!     month: NaiveDate,
+//!     #[serde(with = "calends::serde::quarter")]
+//!     quarter: NaiveDate,
+//!     #[serde(with = "calends::serde::iso_interval")]
+//!     window: (NaiveDate, NaiveDate),
+//! }
+//!
+//! let forecast = Forecast {
+//!     month: NaiveDate::from_ymd_opt(2022, 5, 1).unwrap(),
+//!     quarter: NaiveDate::from_ymd_opt(2022, 4, 1).unwrap(),
+//!     window: (
+//!         NaiveDate::from_ymd_opt(2022, 4, 1).unwrap(),
+//!         NaiveDate::from_ymd_opt(2022, 6, 30).unwrap(),
+//!     ),
+//! };
+//!
+//! let json = serde_json::to_string(&forecast)?;
+//! assert_eq!(
+//!     json,
+//!     r#"{"month":"2022-05","quarter":"2022-Q2","window":"2022-04-01/2022-06-30"}"#
+//! );
+//! assert_eq!(serde_json::from_str::<Forecast>(&json)?, forecast);
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+use chrono::NaiveDate;
+use nom::{bytes::complete::tag, combinator::map_opt, IResult};
+
+use crate::parser::take_n_digits;
+
+fn parse_year_month(i: &[u8]) -> IResult<&[u8], NaiveDate> {
+    map_opt(
+        |i| {
+            let (i, year) = take_n_digits(i, 4)?;
+            let (i, _) = tag(b"-")(i)?;
+            let (i, month) = take_n_digits(i, 2)?;
+            Ok((i, (year, month)))
+        },
+        |(year, month)| NaiveDate::from_ymd_opt(year as i32, month, 1),
+    )(i)
+}
+
+fn parse_quarter(i: &[u8]) -> IResult<&[u8], NaiveDate> {
+    map_opt(
+        |i| {
+            let (i, year) = take_n_digits(i, 4)?;
+            let (i, _) = tag(b"-Q")(i)?;
+            let (i, quarter) = take_n_digits(i, 1)?;
+            Ok((i, (year, quarter)))
+        },
+        |(year, quarter)| match quarter {
+            1..=4 => NaiveDate::from_ymd_opt(year as i32, 3 * quarter - 2, 1),
+            _ => None,
+        },
+    )(i)
+}
+
+/// (De)serialize a date as the month containing it, e.g. `2022-05`
+///
+/// Serializing drops the day of the month and deserializing gives the first day of the month.
+pub mod ym {
+    use chrono::{Datelike, NaiveDate};
+    use nom::combinator::all_consuming;
+    use serde::{de, ser};
+
+    /// Serialize the month of a date
+    pub fn serialize<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(&format_args!("{}-{:0>2}", date.year(), date.month()))
+    }
+
+    /// Deserialize the first day of a month
+    pub fn deserialize<'de, D>(d: D) -> Result<NaiveDate, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_str(YearMonthVisitor)
+    }
+
+    pub struct YearMonthVisitor;
+
+    impl<'de> de::Visitor<'de> for YearMonthVisitor {
+        type Value = NaiveDate;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a year and month such as 2022-05")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            all_consuming(super::parse_year_month)(v.as_bytes())
+                .map(|(_, d)| d)
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+}
+
+/// (De)serialize a date as the calendar quarter containing it, e.g. `2022-Q2`
+///
+/// Serializing drops the month and day and deserializing gives the first day of the quarter.
+/// Quarters are always calendar quarters, fiscal quarters should be stored as dates.
+pub mod quarter {
+    use chrono::{Datelike, NaiveDate};
+    use nom::combinator::all_consuming;
+    use serde::{de, ser};
+
+    /// Serialize the quarter of a date
+    pub fn serialize<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_str(&format_args!(
+            "{}-Q{}",
+            date.year(),
+            (date.month() - 1) / 3 + 1
+        ))
+    }
+
+    /// Deserialize the first day of a quarter
+    pub fn deserialize<'de, D>(d: D) -> Result<NaiveDate, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_str(QuarterVisitor)
+    }
+
+    pub struct QuarterVisitor;
+
+    impl<'de> de::Visitor<'de> for QuarterVisitor {
+        type Value = NaiveDate;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a year and quarter such as 2022-Q2")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            all_consuming(super::parse_quarter)(v.as_bytes())
+                .map(|(_, d)| d)
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+    }
+}
+
+/// (De)serialize a pair of dates as an ISO8601-2:2019 interval, e.g. `2022-01-01/2022-03-31`
+///
+/// Both dates are included in the interval. Intervals written as a start and a duration such as
+/// `2022-01-01/P3M-1D` are also accepted when deserializing.
+pub mod iso_interval {
+    use chrono::NaiveDate;
+    use nom::combinator::all_consuming;
+    use serde::{de, ser};
+
+    use crate::{interval::parse::parse_interval, IntervalLike};
+
+    /// Serialize the first and last dates of an interval
+    pub fn serialize<S>(dates: &(NaiveDate, NaiveDate), serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        if dates.1 < dates.0 {
+            return Err(ser::Error::custom(crate::CalendsError::ReversedInterval {
+                start: dates.0,
+                end: dates.1,
+            }));
+        }
+        serializer.collect_str(&format_args!("{}/{}", dates.0, dates.1))
+    }
+
+    /// Deserialize the first and last dates of an interval
+    pub fn deserialize<'de, D>(d: D) -> Result<(NaiveDate, NaiveDate), D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        d.deserialize_str(IsoIntervalVisitor)
+    }
+
+    pub struct IsoIntervalVisitor;
+
+    impl<'de> de::Visitor<'de> for IsoIntervalVisitor {
+        type Value = (NaiveDate, NaiveDate);
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a closed ISO8601-2:2019 interval")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            match all_consuming(parse_interval)(v.as_bytes()) {
+                Ok((_, interval)) => {
+                    Ok((interval.start_opt().unwrap(), interval.end_opt().unwrap()))
+                }
+                Err(_) => Err(E::invalid_value(de::Unexpected::Str(v), &self)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde(with = "ym")]
+        month: NaiveDate,
+        #[serde(with = "quarter")]
+        quarter: NaiveDate,
+        #[serde(with = "iso_interval")]
+        window: (NaiveDate, NaiveDate),
+    }
+
+    #[test]
+    fn test_serialize_truncates_to_the_period() {
+        let s = S {
+            month: date(2022, 5, 18),
+            quarter: date(2022, 12, 31),
+            window: (date(2022, 1, 1), date(2022, 1, 1)),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&s).unwrap(),
+            r#"{"month":"2022-05","quarter":"2022-Q4","window":"2022-01-01/2022-01-01"}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_duration_interval() {
+        let s: S = serde_json::from_str(
+            r#"{"month":"2022-05","quarter":"2022-Q1","window":"2022-01-01/P3M-1D"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(s.quarter, date(2022, 1, 1));
+        assert_eq!(s.window, (date(2022, 1, 1), date(2022, 3, 31)));
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid_values() {
+        for json in [
+            r#"{"month":"2022-13","quarter":"2022-Q1","window":"2022-01-01/2022-01-02"}"#,
+            r#"{"month":"2022-05-01","quarter":"2022-Q1","window":"2022-01-01/2022-01-02"}"#,
+            r#"{"month":"2022-05","quarter":"2022-Q5","window":"2022-01-01/2022-01-02"}"#,
+            r#"{"month":"2022-05","quarter":"2022-Q1","window":"2022-01-02/2022-01-01"}"#,
+            r#"{"month":"2022-05","quarter":"2022-Q1","window":"2022-01-01/2022-01-02x"}"#,
+        ] {
+            assert!(serde_json::from_str::<S>(json).is_err(), "{}", json);
+        }
+    }
+}
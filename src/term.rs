@@ -0,0 +1,233 @@
+//! School terms
+//!
+//! A [TermSchedule] is built from the terms of a school year, each with optional breaks such as
+//! half-term holidays. It answers whether a date is a school day, counts teaching days and numbers
+//! the weeks of a term.
+use chrono::NaiveDate;
+
+use crate::{business::Calendar, util::beginning_of_week, IntervalLike};
+
+/// Definition of a single term
+///
+/// With the `serde` feature terms can be deserialized from configuration:
+///
+/// ```
+/// # use calends::term::Term;
+/// # #[cfg(feature = "serde")] {
+///
+/// let term: Term = serde_json::from_str(
+///     r#"{
+///         "name": "Autumn",
+///         "start": "2022-09-05",
+///         "end": "2022-12-16",
+///         "breaks": [["2022-10-24", "2022-10-28"]]
+///     }"#,
+/// )?;
+/// # }
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Term {
+    pub name: String,
+    /// First day of the term
+    pub start: NaiveDate,
+    /// Last day of the term
+    pub end: NaiveDate,
+    /// Breaks within the term such as half-term, inclusive on both ends
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub breaks: Vec<(NaiveDate, NaiveDate)>,
+}
+
+impl Term {
+    pub fn new(name: impl Into<String>, start: NaiveDate, end: NaiveDate) -> Self {
+        Term {
+            name: name.into(),
+            start,
+            end,
+            breaks: Vec::new(),
+        }
+    }
+
+    /// Add a break to the term
+    pub fn with_break(mut self, start: NaiveDate, end: NaiveDate) -> Self {
+        self.breaks.push((start, end));
+        self
+    }
+
+    /// Whether the date is within the term, including its breaks
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    /// Whether the date falls within one of the term's breaks
+    pub fn is_break(&self, date: NaiveDate) -> bool {
+        self.breaks
+            .iter()
+            .any(|(start, end)| *start <= date && date <= *end)
+    }
+}
+
+/// The terms of a school calendar
+///
+/// School days are days within a term which are not in a break and are business days of the
+/// schedule's [Calendar], by default Monday to Friday without holidays.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::term::{Term, TermSchedule};
+///
+/// let schedule = TermSchedule::new([Term::new(
+///     "Autumn",
+///     NaiveDate::from_ymd_opt(2022, 9, 5).unwrap(),
+///     NaiveDate::from_ymd_opt(2022, 12, 16).unwrap(),
+/// )
+/// .with_break(
+///     NaiveDate::from_ymd_opt(2022, 10, 24).unwrap(),
+///     NaiveDate::from_ymd_opt(2022, 10, 28).unwrap(),
+/// )]);
+///
+/// assert!(schedule.is_school_day(NaiveDate::from_ymd_opt(2022, 9, 5).unwrap()));
+/// assert!(!schedule.is_school_day(NaiveDate::from_ymd_opt(2022, 10, 25).unwrap()));
+/// assert_eq!(schedule.week_of_term(NaiveDate::from_ymd_opt(2022, 10, 31).unwrap()), Some(8));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TermSchedule {
+    terms: Vec<Term>,
+    calendar: Calendar,
+}
+
+impl TermSchedule {
+    pub fn new(terms: impl IntoIterator<Item = Term>) -> Self {
+        let mut terms: Vec<Term> = terms.into_iter().collect();
+        terms.sort_by_key(|t| t.start);
+
+        TermSchedule {
+            terms,
+            calendar: Calendar::new(),
+        }
+    }
+
+    /// Use a calendar for the weekend and holidays that apply across all terms
+    pub fn with_calendar(mut self, calendar: Calendar) -> Self {
+        self.calendar = calendar;
+        self
+    }
+
+    pub fn terms(&self) -> &[Term] {
+        &self.terms
+    }
+
+    /// The term containing the date
+    pub fn term_of(&self, date: NaiveDate) -> Option<&Term> {
+        self.terms.iter().find(|t| t.contains(date))
+    }
+
+    /// Whether pupils are taught on the date
+    pub fn is_school_day(&self, date: NaiveDate) -> bool {
+        match self.term_of(date) {
+            Some(term) => !term.is_break(date) && self.calendar.is_business_day(date),
+            None => false,
+        }
+    }
+
+    /// Number of school days within the interval
+    ///
+    /// Unbounded intervals are limited to the dates covered by the terms.
+    pub fn teaching_days<I: IntervalLike>(&self, interval: &I) -> usize {
+        self.terms
+            .iter()
+            .map(|term| {
+                term.start
+                    .iter_days()
+                    .take_while(|d| *d <= term.end)
+                    .filter(|d| interval.within(*d) && self.is_school_day(*d))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of the teaching week within its term, counted from one
+    ///
+    /// Weeks start on Monday and only weeks which contain a school day are counted, so weeks
+    /// spent entirely in a break are skipped. Dates which are not school days have no week.
+    pub fn week_of_term(&self, date: NaiveDate) -> Option<u32> {
+        if !self.is_school_day(date) {
+            return None;
+        }
+        let term = self.term_of(date)?;
+
+        let mut weeks = 0;
+        let mut current = None;
+        for day in term.start.iter_days().take_while(|d| *d <= date) {
+            let start = beginning_of_week(&day);
+            if current != Some(start) && self.is_school_day(day) {
+                current = Some(start);
+                weeks += 1;
+            }
+        }
+
+        Some(weeks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::date, Interval};
+
+    use super::*;
+
+    fn schedule() -> TermSchedule {
+        TermSchedule::new([
+            Term::new("Spring", date(2023, 1, 4), date(2023, 3, 31))
+                .with_break(date(2023, 2, 13), date(2023, 2, 17)),
+            Term::new("Autumn", date(2022, 9, 5), date(2022, 12, 16))
+                .with_break(date(2022, 10, 24), date(2022, 10, 28)),
+        ])
+    }
+
+    #[test]
+    fn test_term_order_and_lookup() {
+        let schedule = schedule();
+
+        assert_eq!(schedule.terms()[0].name, "Autumn");
+        assert_eq!(schedule.term_of(date(2023, 2, 14)).unwrap().name, "Spring");
+        assert!(schedule.term_of(date(2022, 12, 25)).is_none());
+    }
+
+    #[test]
+    fn test_school_days_respect_calendar() {
+        let schedule = schedule().with_calendar(Calendar::new().with_holidays([date(2022, 9, 19)]));
+
+        assert!(!schedule.is_school_day(date(2022, 9, 19)));
+        assert!(!schedule.is_school_day(date(2022, 9, 17)));
+        assert!(schedule.is_school_day(date(2022, 9, 20)));
+    }
+
+    #[test]
+    fn test_teaching_days() {
+        let schedule = schedule();
+        let october = Interval::closed_with_dates(date(2022, 10, 1), date(2022, 10, 31));
+
+        // 21 weekdays in October less the five day break
+        assert_eq!(schedule.teaching_days(&october), 16);
+        assert_eq!(
+            schedule.teaching_days(&Interval::open_end(date(2023, 3, 27))),
+            5
+        );
+    }
+
+    #[test]
+    fn test_week_of_term() {
+        let schedule = schedule();
+
+        // spring term starts on a Wednesday
+        assert_eq!(schedule.week_of_term(date(2023, 1, 4)), Some(1));
+        assert_eq!(schedule.week_of_term(date(2023, 1, 9)), Some(2));
+        assert_eq!(schedule.week_of_term(date(2023, 2, 10)), Some(6));
+        assert_eq!(schedule.week_of_term(date(2023, 2, 14)), None);
+        assert_eq!(schedule.week_of_term(date(2023, 2, 20)), Some(7));
+    }
+}
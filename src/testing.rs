@@ -0,0 +1,7 @@
+//! Helpers shared by the unit tests
+use chrono::NaiveDate;
+
+/// The date, panicking when it does not exist
+pub(crate) fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
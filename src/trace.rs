@@ -0,0 +1,26 @@
+//! Instrumentation which compiles to nothing unless the `tracing` feature is enabled
+//!
+//! The macros take the same arguments as their counterparts in the [tracing] crate.
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($arg)*);
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)*);
+    };
+}
+
+macro_rules! warning {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::warn!($($arg)*);
+    };
+}
+
+pub(crate) use {debug, trace, warning};
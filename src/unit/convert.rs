@@ -36,16 +36,17 @@ pub fn convert_to_iso_week(date: NaiveDate) -> CalendarUnit {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::date;
 
     #[test]
     fn test_convert_week() {
         assert_eq!(
-            convert_to_iso_week(NaiveDate::from_ymd(2020, 2, 29)),
+            convert_to_iso_week(date(2020, 2, 29)),
             CalendarUnit::Week(2020, 9)
         );
 
         assert_eq!(
-            convert_to_iso_week(NaiveDate::from_ymd(2022, 12, 31)),
+            convert_to_iso_week(date(2022, 12, 31)),
             CalendarUnit::Week(2022, 52)
         )
     }
@@ -53,12 +54,12 @@ mod tests {
     #[test]
     fn test_convert_month() {
         assert_eq!(
-            convert_to_month(NaiveDate::from_ymd(2020, 2, 29)),
+            convert_to_month(date(2020, 2, 29)),
             CalendarUnit::Month(2020, 2)
         );
 
         assert_eq!(
-            convert_to_month(NaiveDate::from_ymd(2022, 12, 31)),
+            convert_to_month(date(2022, 12, 31)),
             CalendarUnit::Month(2022, 12)
         )
     }
@@ -66,12 +67,12 @@ mod tests {
     #[test]
     fn test_convert_quarter() {
         assert_eq!(
-            convert_to_quarter(NaiveDate::from_ymd(2020, 2, 29)),
+            convert_to_quarter(date(2020, 2, 29)),
             CalendarUnit::Quarter(2020, 1)
         );
 
         assert_eq!(
-            convert_to_quarter(NaiveDate::from_ymd(2022, 12, 31)),
+            convert_to_quarter(date(2022, 12, 31)),
             CalendarUnit::Quarter(2022, 4)
         )
     }
@@ -79,25 +80,22 @@ mod tests {
     #[test]
     fn test_convert_half() {
         assert_eq!(
-            convert_to_half(NaiveDate::from_ymd(2020, 2, 29)),
+            convert_to_half(date(2020, 2, 29)),
             CalendarUnit::Half(2020, 1)
         );
 
         assert_eq!(
-            convert_to_half(NaiveDate::from_ymd(2022, 12, 31)),
+            convert_to_half(date(2022, 12, 31)),
             CalendarUnit::Half(2022, 2)
         )
     }
 
     #[test]
     fn test_convert_year() {
-        assert_eq!(
-            convert_to_year(NaiveDate::from_ymd(2020, 2, 29)),
-            CalendarUnit::Year(2020)
-        );
+        assert_eq!(convert_to_year(date(2020, 2, 29)), CalendarUnit::Year(2020));
 
         assert_eq!(
-            convert_to_year(NaiveDate::from_ymd(2022, 12, 31)),
+            convert_to_year(date(2022, 12, 31)),
             CalendarUnit::Year(2022)
         )
     }
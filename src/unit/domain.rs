@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 use chrono::NaiveDate;
+#[cfg(feature = "serde")]
 use serde::{Serialize, Serializer};
 
 use crate::{interval::ClosedInterval, Interval, RelativeDuration};
@@ -32,26 +33,26 @@ impl CalendarUnit {
     pub fn into_interval(&self) -> Interval {
         let res = match self {
             CalendarUnit::Year(year) => ClosedInterval::from_start(
-                NaiveDate::from_yo(*year, 1),
+                NaiveDate::from_yo_opt(*year, 1).unwrap(),
                 RelativeDuration::months(12).with_days(-1),
             ),
             CalendarUnit::Quarter(year, quarter) => ClosedInterval::from_start(
-                NaiveDate::from_ymd(*year, (*quarter * 3 - 2).try_into().unwrap(), 1),
+                NaiveDate::from_ymd_opt(*year, (*quarter * 3 - 2).into(), 1).unwrap(),
                 RelativeDuration::months(3).with_days(-1),
             ),
 
             CalendarUnit::Half(year, half) => ClosedInterval::from_start(
-                NaiveDate::from_ymd(*year, (*half * 6 - 5).try_into().unwrap(), 1),
+                NaiveDate::from_ymd_opt(*year, (*half * 6 - 5).into(), 1).unwrap(),
                 RelativeDuration::months(6).with_days(-1),
             ),
 
             CalendarUnit::Month(year, month) => ClosedInterval::from_start(
-                NaiveDate::from_ymd(*year, (*month).try_into().unwrap(), 1),
+                NaiveDate::from_ymd_opt(*year, (*month).into(), 1).unwrap(),
                 RelativeDuration::months(1).with_days(-1),
             ),
 
             CalendarUnit::Week(year, week) => ClosedInterval::from_start(
-                NaiveDate::from_isoywd(*year, (*week).into(), chrono::Weekday::Mon),
+                NaiveDate::from_isoywd_opt(*year, (*week).into(), chrono::Weekday::Mon).unwrap(),
                 RelativeDuration::days(7),
             ),
         };
@@ -123,6 +124,7 @@ impl Display for CalendarUnit {
 }
 
 /// Serialize a `CalendarUnit`
+#[cfg(feature = "serde")]
 impl Serialize for CalendarUnit {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -134,7 +136,7 @@ impl Serialize for CalendarUnit {
 
 #[cfg(test)]
 mod tests {
-    use crate::IntervalLike;
+    use crate::{testing::date, IntervalLike};
 
     use super::*;
 
@@ -159,36 +161,18 @@ mod tests {
     #[test]
     fn test_quarter_interval() {
         let interval = CalendarUnit::Quarter(2022, 1).into_interval();
-        assert_eq!(
-            interval.start_opt().unwrap(),
-            NaiveDate::from_ymd(2022, 1, 1)
-        );
-        assert_eq!(
-            interval.end_opt().unwrap(),
-            NaiveDate::from_ymd(2022, 3, 31)
-        );
+        assert_eq!(interval.start_opt().unwrap(), date(2022, 1, 1));
+        assert_eq!(interval.end_opt().unwrap(), date(2022, 3, 31));
 
         let interval = CalendarUnit::Quarter(2022, 2).into_interval();
-        assert_eq!(
-            interval.start_opt().unwrap(),
-            NaiveDate::from_ymd(2022, 4, 1)
-        );
-        assert_eq!(
-            interval.end_opt().unwrap(),
-            NaiveDate::from_ymd(2022, 6, 30)
-        );
+        assert_eq!(interval.start_opt().unwrap(), date(2022, 4, 1));
+        assert_eq!(interval.end_opt().unwrap(), date(2022, 6, 30));
     }
 
     #[test]
     fn test_half_interval() {
         let interval = CalendarUnit::Half(2022, 2).into_interval();
-        assert_eq!(
-            interval.start_opt().unwrap(),
-            NaiveDate::from_ymd(2022, 7, 1)
-        );
-        assert_eq!(
-            interval.end_opt().unwrap(),
-            NaiveDate::from_ymd(2022, 12, 31)
-        );
+        assert_eq!(interval.start_opt().unwrap(), date(2022, 7, 1));
+        assert_eq!(interval.end_opt().unwrap(), date(2022, 12, 31));
     }
 }
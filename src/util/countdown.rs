@@ -0,0 +1,49 @@
+//! Countdowns to a target date
+use chrono::NaiveDate;
+
+/// Number of days from `as_of` until the target, negative once the target has passed
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::util::days_until;
+///
+/// let target = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+///
+/// assert_eq!(days_until(target, NaiveDate::from_ymd_opt(2022, 2, 27).unwrap()), 2);
+/// assert_eq!(days_until(target, NaiveDate::from_ymd_opt(2022, 3, 1).unwrap()), 0);
+/// assert_eq!(days_until(target, NaiveDate::from_ymd_opt(2022, 3, 4).unwrap()), -3);
+/// ```
+#[inline]
+pub fn days_until(target: NaiveDate, as_of: NaiveDate) -> i64 {
+    target.signed_duration_since(as_of).num_days()
+}
+
+/// Whether the target date has passed as of the date supplied
+///
+/// A target is due on its own date and only overdue from the following day.
+#[inline]
+pub fn is_overdue(target: NaiveDate, as_of: NaiveDate) -> bool {
+    as_of > target
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_is_overdue() {
+        let target = date(2022, 3, 1);
+
+        assert!(!is_overdue(target, date(2022, 2, 28)));
+        assert!(!is_overdue(target, target));
+        assert!(is_overdue(target, date(2022, 3, 2)));
+    }
+
+    #[test]
+    fn test_days_until_across_years() {
+        assert_eq!(days_until(date(2023, 1, 1), date(2022, 1, 1)), 365);
+    }
+}
@@ -1,5 +1,11 @@
+pub mod countdown;
+pub mod parity;
 pub mod search;
 pub mod shift;
+pub mod snapshot;
 
+pub use countdown::*;
+pub use parity::*;
 pub use search::*;
 pub use shift::*;
+pub use snapshot::*;
@@ -0,0 +1,115 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::{Recurrence, Rule};
+
+/// Which of two alternating weeks a date falls in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Parity {
+    /// The week starting on the epoch and every second week from it
+    A,
+    /// The weeks between the A weeks
+    B,
+}
+
+impl Parity {
+    /// The other week
+    pub fn flip(&self) -> Parity {
+        match self {
+            Parity::A => Parity::B,
+            Parity::B => Parity::A,
+        }
+    }
+}
+
+/// Whether the date is in an A week or a B week, counting weeks from the epoch
+///
+/// Weeks start on the epoch's day of the week, so an epoch on a Sunday gives weeks running Sunday
+/// to Saturday. Dates before the epoch continue the alternation backwards.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{week_parity, Parity};
+///
+/// // the municipal collection calendar started on Monday 2024-01-01
+/// let epoch = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+///
+/// assert_eq!(week_parity(NaiveDate::from_ymd_opt(2024, 1, 7).unwrap(), epoch), Parity::A);
+/// assert_eq!(week_parity(NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(), epoch), Parity::B);
+/// assert_eq!(week_parity(NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(), epoch), Parity::B);
+/// ```
+pub fn week_parity(date: NaiveDate, epoch_week_start: NaiveDate) -> Parity {
+    let weeks = (date - epoch_week_start).num_days().div_euclid(7);
+    match weeks.rem_euclid(2) {
+        0 => Parity::A,
+        _ => Parity::B,
+    }
+}
+
+impl Recurrence {
+    /// Every second week on the weekday, in the weeks of the given parity
+    ///
+    /// The series starts at the first matching date on or after `from`, so unlike
+    /// [Rule::biweekly] from an arbitrary date it always stays in step with the epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chrono::{NaiveDate, Weekday};
+    /// # use calends::{Parity, Recurrence};
+    ///
+    /// let date = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+    ///
+    /// let recycling = Recurrence::alternate_weeks(Weekday::Wed, Parity::B, date(1, 1), date(3, 1));
+    /// assert_eq!(recycling.take(2).collect::<Vec<_>>(), vec![date(3, 6), date(3, 20)]);
+    /// ```
+    pub fn alternate_weeks(
+        weekday: Weekday,
+        parity: Parity,
+        epoch_week_start: NaiveDate,
+        from: NaiveDate,
+    ) -> Recurrence {
+        let start = from
+            .iter_days()
+            .find(|date| {
+                date.weekday() == weekday && week_parity(*date, epoch_week_start) == parity
+            })
+            .unwrap();
+        Recurrence::with_start(Rule::biweekly(), start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_alternate_weeks_cover_every_week() {
+        let epoch = date(2024, 1, 1);
+        let a = Recurrence::alternate_weeks(Weekday::Fri, Parity::A, epoch, date(2024, 2, 1));
+        let b = Recurrence::alternate_weeks(Weekday::Fri, Parity::B, epoch, date(2024, 2, 1));
+
+        let mut fridays: Vec<NaiveDate> = a.take(4).chain(b.take(4)).collect();
+        fridays.sort();
+        assert_eq!(
+            fridays,
+            date(2024, 2, 2).iter_weeks().take(8).collect::<Vec<_>>()
+        );
+        assert!(
+            fridays
+                .iter()
+                .all(|d| week_parity(*d, epoch)
+                    != week_parity(*d + chrono::Duration::weeks(1), epoch))
+        );
+    }
+
+    #[test]
+    fn test_parity_on_sunday_epoch() {
+        let epoch = date(2024, 1, 7);
+        assert_eq!(week_parity(date(2024, 1, 13), epoch), Parity::A);
+        assert_eq!(week_parity(date(2024, 1, 14), epoch), Parity::B);
+        assert_eq!(week_parity(date(2024, 1, 6), epoch).flip(), Parity::A);
+    }
+}
@@ -2,49 +2,83 @@ use chrono::{Datelike, Duration, NaiveDate, Weekday};
 
 use crate::shift;
 
-// Borrowed from bdays
+/// Number of days in the month of the year in the proleptic Gregorian calendar
+///
+/// # Panics
+///
+/// When the month is not between 1 and 12
 pub fn days_in_month(year: i32, month: u32) -> u32 {
-    NaiveDate::from_ymd(
-        match month {
-            12 => year + 1,
-            _ => year,
-        },
-        match month {
-            12 => 1,
-            _ => month + 1,
-        },
-        1,
-    )
-    .signed_duration_since(NaiveDate::from_ymd(year, month, 1))
-    .num_days() as u32
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => panic!("{} is not a month", month),
+    }
 }
 
+// Borrowed from bdays
+/// The nth occurrence of the weekday in the month
+///
+/// # Panics
+///
+/// When the month or the occurrence is outside the range of supported dates, see
+/// [checked_find_weekday_ascending] for a fallible alternative
 pub fn find_weekday_ascending(weekday: Weekday, yy: i32, mm: u32, occurrence: u32) -> NaiveDate {
-    let anchor = NaiveDate::from_ymd(yy, mm, 1);
+    checked_find_weekday_ascending(weekday, yy, mm, occurrence).unwrap()
+}
+
+/// The nth occurrence of the weekday in the month, [None] when the month or the occurrence is
+/// outside the range of supported dates
+pub fn checked_find_weekday_ascending(
+    weekday: Weekday,
+    yy: i32,
+    mm: u32,
+    occurrence: u32,
+) -> Option<NaiveDate> {
+    let anchor = NaiveDate::from_ymd_opt(yy, mm, 1)?;
     let mut offset = (weekday.number_from_monday() + 7 - anchor.weekday().number_from_monday()) % 7;
 
     if occurrence > 1 {
         offset += 7 * (occurrence - 1);
     }
 
-    anchor + Duration::days(offset as i64)
+    anchor.checked_add_signed(Duration::days(offset as i64))
 }
 
+/// The nth occurrence of the weekday in the month counting back from its end
+///
+/// # Panics
+///
+/// When the month or the occurrence is outside the range of supported dates, see
+/// [checked_find_weekday_descending] for a fallible alternative
 pub fn find_weekday_descending(weekday: Weekday, yy: i32, mm: u32, occurrence: u32) -> NaiveDate {
-    let anchor = month_end(yy, mm);
+    checked_find_weekday_descending(weekday, yy, mm, occurrence).unwrap()
+}
+
+/// The nth occurrence of the weekday in the month counting back from its end, [None] when the
+/// month or the occurrence is outside the range of supported dates
+pub fn checked_find_weekday_descending(
+    weekday: Weekday,
+    yy: i32,
+    mm: u32,
+    occurrence: u32,
+) -> Option<NaiveDate> {
+    let anchor = checked_month_end(yy, mm)?;
     let mut offset = (anchor.weekday().number_from_monday() + 7 - weekday.number_from_monday()) % 7;
 
     if occurrence > 1 {
         offset += 7 * (occurrence - 1);
     }
 
-    anchor - Duration::days(offset as i64)
+    anchor.checked_sub_signed(Duration::days(offset as i64))
 }
 // End Borrowed
 
 /// Weeks in year
 pub fn weeks_in_year(date: &NaiveDate) -> u32 {
-    NaiveDate::from_ymd(date.year(), 12, 31).iso_week().week()
+    end_of_year(date).iso_week().week()
 }
 
 /// Returns the quarter start month
@@ -53,31 +87,43 @@ pub fn quarter_month(date: &NaiveDate) -> u32 {
     1 + 3 * ((date.month() - 1) / 3)
 }
 
+/// Last day of the month of the year
+///
+/// # Panics
+///
+/// When the month is outside the range of supported dates, see [checked_month_end] for a
+/// fallible alternative
 #[inline]
-pub fn month_end(mut yy: i32, mut mm: u32) -> NaiveDate {
-    if mm == 12 {
-        yy += 1;
-        mm = 1;
-    } else {
-        mm += 1;
-    }
+pub fn month_end(yy: i32, mm: u32) -> NaiveDate {
+    checked_month_end(yy, mm).unwrap()
+}
 
-    NaiveDate::from_ymd(yy, mm, 1).pred()
+/// Last day of the month of the year, [None] when the month is outside the range of supported
+/// dates
+#[inline]
+pub fn checked_month_end(yy: i32, mm: u32) -> Option<NaiveDate> {
+    match mm {
+        1..=12 => NaiveDate::from_ymd_opt(yy, mm, days_in_month(yy, mm)),
+        _ => None,
+    }
 }
 
+// Supported dates run from a January 1st to a December 31st, so the calendar periods of a
+// supported date are always supported in full and the functions below cannot fail.
+
 #[inline]
 pub fn beginning_of_quarter(d: &NaiveDate) -> NaiveDate {
-    NaiveDate::from_ymd(d.year(), quarter_month(d), 1)
+    NaiveDate::from_ymd_opt(d.year(), quarter_month(d), 1).expect("quarter is supported")
 }
 
 #[inline]
 pub fn beginning_of_year(d: &NaiveDate) -> NaiveDate {
-    NaiveDate::from_ymd(d.year(), 1, 1)
+    NaiveDate::from_ymd_opt(d.year(), 1, 1).expect("year is supported")
 }
 
 #[inline]
 pub fn beginning_of_month(d: &NaiveDate) -> NaiveDate {
-    NaiveDate::from_ymd(d.year(), d.month(), 1)
+    NaiveDate::from_ymd_opt(d.year(), d.month(), 1).expect("month is supported")
 }
 
 /// Beginning of a biweek
@@ -89,11 +135,11 @@ pub fn beginning_of_month(d: &NaiveDate) -> NaiveDate {
 ///
 #[inline]
 pub fn beginning_of_biweek(d: &NaiveDate) -> NaiveDate {
-    let beginning = if d.iso_week().week() % 2 == 0 {
-        NaiveDate::from_isoywd(d.iso_week().year(), d.iso_week().week(), Weekday::Mon)
+    let beginning = if d.iso_week().week().is_multiple_of(2) {
+        NaiveDate::from_isoywd_opt(d.iso_week().year(), d.iso_week().week(), Weekday::Mon).unwrap()
             - Duration::weeks(1)
     } else {
-        NaiveDate::from_isoywd(d.iso_week().year(), d.iso_week().week(), Weekday::Mon)
+        NaiveDate::from_isoywd_opt(d.iso_week().year(), d.iso_week().week(), Weekday::Mon).unwrap()
     };
 
     debug_assert!(
@@ -112,41 +158,102 @@ pub fn beginning_of_biweek(d: &NaiveDate) -> NaiveDate {
 ///
 #[inline]
 pub fn beginning_of_week(d: &NaiveDate) -> NaiveDate {
-    NaiveDate::from_isoywd(d.iso_week().year(), d.iso_week().week(), Weekday::Mon)
+    NaiveDate::from_isoywd_opt(d.iso_week().year(), d.iso_week().week(), Weekday::Mon).unwrap()
+}
+
+/// Beginning of a week which starts on `weekstart`
+///
+/// e.g. with a `weekstart` of Sunday, Wednesday 2022-01-05 is in the week beginning Sunday
+/// 2022-01-02
+///
+/// # Panics
+///
+/// When the week begins before the earliest supported date, see
+/// [checked_beginning_of_week_starting] for a fallible alternative
+#[inline]
+pub fn beginning_of_week_starting(d: &NaiveDate, weekstart: Weekday) -> NaiveDate {
+    checked_beginning_of_week_starting(d, weekstart).unwrap()
+}
+
+/// Beginning of a week which starts on `weekstart`, [None] when it is before the earliest
+/// supported date
+#[inline]
+pub fn checked_beginning_of_week_starting(d: &NaiveDate, weekstart: Weekday) -> Option<NaiveDate> {
+    let offset = (d.weekday().num_days_from_monday() + 7 - weekstart.num_days_from_monday()) % 7;
+    d.checked_sub_signed(Duration::days(offset.into()))
+}
+
+/// Easter Sunday of the year in the Gregorian calendar
+///
+/// Uses the anonymous Gregorian algorithm (Meeus/Jones/Butcher).
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::util::easter_sunday;
+///
+/// assert_eq!(easter_sunday(2024), NaiveDate::from_ymd_opt(2024, 3, 31).unwrap());
+/// ```
+///
+/// # Panics
+///
+/// When the year is outside the range of supported dates, see [checked_easter_sunday] for a
+/// fallible alternative
+pub fn easter_sunday(year: i32) -> NaiveDate {
+    checked_easter_sunday(year).unwrap()
+}
+
+/// Easter Sunday of the year in the Gregorian calendar, [None] when the year is outside the
+/// range of supported dates
+pub fn checked_easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
 }
 
 #[inline]
 pub fn end_of_year(d: &NaiveDate) -> NaiveDate {
-    NaiveDate::from_ymd(d.year(), 12, 31)
+    NaiveDate::from_ymd_opt(d.year(), 12, 31).expect("year is supported")
 }
 
 #[inline]
 pub fn end_of_quarter(d: &NaiveDate) -> NaiveDate {
-    shift::shift_quarters(*d, 1).pred()
+    shift::shift_quarters(*d, 1).pred_opt().unwrap()
 }
 
 #[inline]
 pub fn end_of_month(d: &NaiveDate) -> NaiveDate {
-    let month = d.month();
-    let year = d.year();
-    let days_in_month = days_in_month(year, month);
-
-    NaiveDate::from_ymd(year, month, days_in_month)
+    checked_month_end(d.year(), d.month()).expect("month is supported")
 }
 
 #[inline]
 pub fn end_of_biweek(d: &NaiveDate) -> NaiveDate {
-    shift::shift_weeks(beginning_of_biweek(d), 2).pred()
+    shift::shift_weeks(beginning_of_biweek(d), 2)
+        .pred_opt()
+        .unwrap()
 }
 
 #[inline]
 pub fn end_of_week(d: &NaiveDate) -> NaiveDate {
-    NaiveDate::from_isoywd(d.iso_week().year(), d.iso_week().week(), Weekday::Sun)
+    NaiveDate::from_isoywd_opt(d.iso_week().year(), d.iso_week().week(), Weekday::Sun).unwrap()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::date;
     use quickcheck::{Arbitrary, Gen};
     use quickcheck_macros::quickcheck;
 
@@ -155,10 +262,51 @@ mod tests {
 
     #[test]
     fn test_beginning_of_biweek() {
+        assert_eq!(beginning_of_biweek(&date(2022, 1, 1)), date(2021, 12, 20))
+    }
+
+    #[test]
+    fn test_easter_sunday() {
+        for (year, month, day) in [(1961, 4, 2), (2000, 4, 23), (2019, 4, 21), (2038, 4, 25)] {
+            assert_eq!(easter_sunday(year), date(year, month, day));
+        }
+    }
+
+    #[test]
+    fn test_checked_at_the_limits() {
+        let (min, max) = (NaiveDate::MIN.year(), NaiveDate::MAX.year());
+        assert_eq!(checked_easter_sunday(max + 1), None);
+        assert_eq!(checked_month_end(max, 12), Some(NaiveDate::MAX));
+        assert_eq!(checked_month_end(max + 1, 1), None);
+        assert_eq!(checked_month_end(2024, 13), None);
+        assert_eq!(end_of_month(&NaiveDate::MAX), NaiveDate::MAX);
+        assert_eq!(beginning_of_quarter(&NaiveDate::MIN), NaiveDate::MIN);
+        // the last Monday of the range is its last day, no later Tuesday or fifth Monday exists
+        assert_eq!(
+            checked_find_weekday_descending(Weekday::Mon, max, 12, 1),
+            Some(NaiveDate::MAX)
+        );
+        assert_eq!(
+            checked_find_weekday_ascending(Weekday::Tue, max, 12, 5),
+            None
+        );
+        assert_eq!(
+            checked_find_weekday_ascending(Weekday::Mon, min - 1, 12, 1),
+            None
+        );
         assert_eq!(
-            beginning_of_biweek(&NaiveDate::from_ymd(2022, 1, 1)),
-            NaiveDate::from_ymd(2021, 12, 20)
-        )
+            checked_find_weekday_descending(NaiveDate::MIN.weekday().pred(), min, 1, 5),
+            None
+        );
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2100, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(NaiveDate::MAX.year(), 12), 31);
+        assert_eq!(days_in_month(-4, 2), 29);
     }
 
     #[quickcheck]
@@ -166,7 +314,6 @@ mod tests {
         shift::shift_months(d.0, 1);
     }
 
-    #[ignore = "broken af"]
     #[quickcheck]
     fn test_add_quarter_quickcheck(d: NaiveDateWrapper) {
         shift::shift_quarters(d.0, 1);
@@ -177,29 +324,91 @@ mod tests {
         beginning_of_biweek(&d.0);
     }
 
+    fn same_month(a: NaiveDate, b: NaiveDate) -> bool {
+        a.year() == b.year() && a.month() == b.month()
+    }
+
+    fn is_anchored(d: NaiveDate) -> bool {
+        d.day() <= 27 || d == end_of_month(&d)
+    }
+
+    #[quickcheck]
+    fn test_shift_months_zero_is_identity(d: NaiveDateWrapper) -> bool {
+        shift::shift_months(d.0, 0) == d.0
+    }
+
+    #[quickcheck]
+    fn test_shift_months_inverse_same_month(d: NaiveDateWrapper, n: Months) -> bool {
+        let back = shift::shift_months(shift::shift_months(d.0, n.0), -n.0);
+        same_month(back, d.0) && (!is_anchored(d.0) || back == d.0)
+    }
+
+    #[quickcheck]
+    fn test_shift_months_sequential(d: NaiveDateWrapper, a: Months, b: Months) -> bool {
+        let sequential = shift::shift_months(shift::shift_months(d.0, a.0), b.0);
+        let direct = shift::shift_months(d.0, a.0 + b.0);
+        same_month(sequential, direct) && (!is_anchored(d.0) || sequential == direct)
+    }
+
+    #[quickcheck]
+    fn test_shift_months_preserves_end_of_month(d: NaiveDateWrapper, n: Months) -> bool {
+        let eom = end_of_month(&d.0);
+        shift::shift_months(eom, n.0) == end_of_month(&shift::shift_months(eom, n.0))
+    }
+
+    #[quickcheck]
+    fn test_shift_months_clamping_is_idempotent(d: NaiveDateWrapper, n: Months) -> bool {
+        // once clamped to the end of a month the date stays anchored to the end of the month
+        let once = shift::shift_months(d.0, n.0);
+        once.day() <= d.0.day() || once == end_of_month(&once)
+    }
+
+    #[quickcheck]
+    fn test_shift_years_is_twelve_months(d: NaiveDateWrapper, n: Months) -> bool {
+        let years = n.0 / 12;
+        shift::shift_years(d.0, years) == shift::shift_months(d.0, 12 * years)
+            && shift::shift_quarters(d.0, years) == shift::shift_months(d.0, 3 * years)
+    }
+
+    #[quickcheck]
+    fn test_shift_days_inverse(d: NaiveDateWrapper, n: Months) -> bool {
+        shift::shift_days(shift::shift_days(d.0, n.0), -n.0) == d.0
+            && shift::shift_weeks(shift::shift_weeks(d.0, n.0), -n.0) == d.0
+    }
+
+    #[quickcheck]
+    fn test_clamp_only_parity_with_chrono(d: NaiveDateWrapper, n: Months) -> bool {
+        let chrono = match n.0 {
+            n if n < 0 => {
+                d.0.checked_sub_months(chrono::Months::new(n.unsigned_abs()))
+            }
+            n => d.0.checked_add_months(chrono::Months::new(n as u32)),
+        };
+        chrono
+            == Some(shift::shift_months_with_policy(
+                d.0,
+                n.0,
+                shift::EomPolicy::ClampOnly,
+            ))
+    }
+
+    /// A number of months that keeps shifted dates well within the range chrono supports
+    #[derive(Clone, Copy, Debug)]
+    struct Months(i32);
+
+    impl Arbitrary for Months {
+        fn arbitrary(g: &mut Gen) -> Months {
+            Months(i32::arbitrary(g) % 1200)
+        }
+    }
+
     impl Arbitrary for NaiveDateWrapper {
         fn arbitrary(g: &mut Gen) -> NaiveDateWrapper {
-            #[allow(clippy::min_max)]
-            let year = std::cmp::max(std::cmp::min(i32::arbitrary(g), 1584), 2800);
+            let year = 1584 + (u32::arbitrary(g) % 1217) as i32;
             let month = 1 + u32::arbitrary(g) % 12;
-            let day = 1 + u32::arbitrary(g) % 31;
-
-            let first_date = NaiveDate::from_ymd_opt(year, month, day);
-            if day > 27 {
-                let result = vec![
-                    first_date,
-                    NaiveDate::from_ymd_opt(year, month, day - 1),
-                    NaiveDate::from_ymd_opt(year, month, day - 2),
-                ]
-                .into_iter()
-                .flatten()
-                .next()
-                .unwrap();
-
-                NaiveDateWrapper(result)
-            } else {
-                NaiveDateWrapper(first_date.unwrap())
-            }
+            let day = 1 + u32::arbitrary(g) % days_in_month(year, month);
+
+            NaiveDateWrapper(date(year, month, day))
         }
     }
 }
@@ -1,6 +1,6 @@
 use chrono::{Datelike, NaiveDate};
 
-use crate::util;
+use crate::{error::CalendsError, util};
 
 /// Shift a month duration to the current date
 ///
@@ -12,14 +12,14 @@ use crate::util;
 /// # use chrono::NaiveDate;
 /// # use calends::util::shift_months;
 ///
-/// let n1 = shift_months(NaiveDate::from_ymd(2022, 1, 1), 1);
-/// assert_eq!(n1, NaiveDate::from_ymd(2022, 2, 1));
+/// let n1 = shift_months(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), 1);
+/// assert_eq!(n1, NaiveDate::from_ymd_opt(2022, 2, 1).unwrap());
 ///
-/// let n2 = shift_months(NaiveDate::from_ymd(2022, 2, 3), 2);
-/// assert_eq!(n2, NaiveDate::from_ymd(2022, 4, 3));
+/// let n2 = shift_months(NaiveDate::from_ymd_opt(2022, 2, 3).unwrap(), 2);
+/// assert_eq!(n2, NaiveDate::from_ymd_opt(2022, 4, 3).unwrap());
 ///
-/// let n3 = shift_months(NaiveDate::from_ymd(2022, 2, 3), -1);
-/// assert_eq!(n3, NaiveDate::from_ymd(2022, 1, 3));
+/// let n3 = shift_months(NaiveDate::from_ymd_opt(2022, 2, 3).unwrap(), -1);
+/// assert_eq!(n3, NaiveDate::from_ymd_opt(2022, 1, 3).unwrap());
 /// ```
 ///
 /// The behavior for end of month works as follows:
@@ -29,12 +29,12 @@ use crate::util;
 /// # use calends::util::shift_months;
 ///
 /// assert_eq!(
-///   shift_months(NaiveDate::from_ymd(2022, 2, 28), 1),
-///   NaiveDate::from_ymd(2022, 3, 31)
+///   shift_months(NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(), 1),
+///   NaiveDate::from_ymd_opt(2022, 3, 31).unwrap()
 /// );
 /// assert_eq!(
-///   shift_months(NaiveDate::from_ymd(2022, 3, 31), 1),
-///   NaiveDate::from_ymd(2022, 4, 30)
+///   shift_months(NaiveDate::from_ymd_opt(2022, 3, 31).unwrap(), 1),
+///   NaiveDate::from_ymd_opt(2022, 4, 30).unwrap()
 /// );
 /// ```
 ///
@@ -44,32 +44,174 @@ use crate::util;
 /// # use chrono::NaiveDate;
 /// # use calends::util::shift_months;
 ///
-/// let n4 = shift_months(NaiveDate::from_ymd(2022, 2, 28), 11);
-/// assert_eq!(n4, NaiveDate::from_ymd(2023, 1, 31));
+/// let n4 = shift_months(NaiveDate::from_ymd_opt(2022, 2, 28).unwrap(), 11);
+/// assert_eq!(n4, NaiveDate::from_ymd_opt(2023, 1, 31).unwrap());
 /// ```
 ///
+/// # Laws
 ///
+/// The day of the month is the anchor: the last day of a month stays anchored to the end of the
+/// month and any other day is clamped to the length of the target month. From this it follows
+/// that, for any date `d` and months `n`, `a` and `b`:
+///
+/// - `shift_months(d, 0) == d`
+/// - `shift_months(shift_months(d, n), -n)` is in the same month as `d`
+/// - `shift_months(shift_months(d, a), b)` is in the same month as `shift_months(d, a + b)`
+/// - the last day of a month is always shifted to the last day of a month
+/// - when `d` is on or before the 27th, or is the last day of its month, both round trips and
+///   sequential shifts are exact
+///
+/// Other days can land on the last day of a shorter month, which is then anchored to the end of
+/// the month. Shifting 2022-01-30 forward a month clamps to 2022-02-28 so shifting back gives
+/// 2022-01-31, and 2022-01-28 behaves the same way as 2022-02-28 is the end of February.
+///
+/// These are checked by property tests.
 #[inline]
 pub fn shift_months(date: NaiveDate, months: i32) -> NaiveDate {
-    let mut year = date.year() + (date.month() as i32 + months) / 12;
-    let mut month = (date.month() as i32 + months) % 12;
+    try_shift_months(date, months).unwrap()
+}
 
-    if month < 1 {
-        year -= 1;
-        month += 12;
-    }
+/// Shift a date by a number of months, as [shift_months], failing instead of panicking when the
+/// result is outside the range of dates chrono supports
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::util::try_shift_months;
+///
+/// let date = NaiveDate::from_ymd_opt(2022, 3, 31).unwrap();
+/// assert_eq!(try_shift_months(date, -1), Ok(NaiveDate::from_ymd_opt(2022, 2, 28).unwrap()));
+/// assert!(try_shift_months(NaiveDate::MAX, 1).is_err());
+/// ```
+pub fn try_shift_months(date: NaiveDate, months: i32) -> Result<NaiveDate, CalendsError> {
+    let out_of_range = || out_of_range(date, months, "months");
 
-    let date_end_of_month = util::month_end(date.year(), date.month());
-    let day = if date_end_of_month.day() == date.day() {
+    // the month wraps around the year any number of times in either direction
+    let total = i64::from(date.month0()) + i64::from(months);
+    let year =
+        i32::try_from(i64::from(date.year()) + total.div_euclid(12)).map_err(|_| out_of_range())?;
+    let month = total.rem_euclid(12) as u32 + 1;
+
+    let days_in_month = (28..=31)
+        .rev()
+        .find(|day| NaiveDate::from_ymd_opt(year, month, *day).is_some())
+        .ok_or_else(out_of_range)?;
+    let day = if is_last_day_of_month(date) {
         // if the current date is the last date of the month, the next month will need to be the
         // last date as well
-        util::month_end(year, month as u32).day()
+        days_in_month
     } else {
         // get the maximum of the month and clamp it to that, we cannot exceed the end of the current
         // month
-        std::cmp::min(date.day(), util::month_end(year, month as u32).day())
+        std::cmp::min(date.day(), days_in_month)
     };
-    NaiveDate::from_ymd(year, month as u32, day)
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(out_of_range)
+}
+
+fn is_last_day_of_month(date: NaiveDate) -> bool {
+    date.succ_opt()
+        .is_none_or(|next| next.month() != date.month())
+}
+
+fn out_of_range(date: NaiveDate, n: impl std::fmt::Display, unit: &str) -> CalendsError {
+    CalendsError::DateOutOfRange {
+        date,
+        shift: format!("{} {}", n, unit),
+    }
+}
+
+/// How month arithmetic treats the day of the month
+///
+/// The policy only matters when shifting lands on a day the target month does not have or when
+/// starting from the last day of a month. Where the day exists in both months every policy gives
+/// the same date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EomPolicy {
+    /// The last day of a month stays anchored to the last day of the month and other days are
+    /// clamped to the end of shorter months, as [shift_months] does
+    #[default]
+    Preserve,
+    /// Days are only clamped to the end of shorter months, matching chrono's
+    /// [NaiveDate::checked_add_months] and [NaiveDate::checked_sub_months]
+    ///
+    /// 2022-01-31 plus a month is 2022-02-28, and 2022-02-28 plus a month is 2022-03-28.
+    ClampOnly,
+    /// Days the target month does not have roll over into the following month
+    ///
+    /// 2022-01-31 plus a month is 2022-03-03, three days past the end of February.
+    Overflow,
+}
+
+/// Shift a date by a number of months using the given end of month policy
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::util::{shift_months_with_policy, EomPolicy};
+///
+/// let feb = NaiveDate::from_ymd_opt(2022, 2, 28).unwrap();
+///
+/// assert_eq!(
+///     shift_months_with_policy(feb, 1, EomPolicy::Preserve),
+///     NaiveDate::from_ymd_opt(2022, 3, 31).unwrap()
+/// );
+/// assert_eq!(
+///     shift_months_with_policy(feb, 1, EomPolicy::ClampOnly),
+///     NaiveDate::from_ymd_opt(2022, 3, 28).unwrap()
+/// );
+///
+/// let jan = NaiveDate::from_ymd_opt(2022, 1, 31).unwrap();
+///
+/// assert_eq!(
+///     shift_months_with_policy(jan, 1, EomPolicy::Overflow),
+///     NaiveDate::from_ymd_opt(2022, 3, 3).unwrap()
+/// );
+/// ```
+pub fn shift_months_with_policy(date: NaiveDate, months: i32, policy: EomPolicy) -> NaiveDate {
+    try_shift_months_with_policy(date, months, policy).unwrap()
+}
+
+/// Shift a date by a number of months using the given end of month policy, failing instead of
+/// panicking when the result is outside the range of supported dates
+pub fn try_shift_months_with_policy(
+    date: NaiveDate,
+    months: i32,
+    policy: EomPolicy,
+) -> Result<NaiveDate, CalendsError> {
+    match policy {
+        EomPolicy::Preserve => try_shift_months(date, months),
+        EomPolicy::ClampOnly => {
+            let target = try_shift_months(util::beginning_of_month(&date), months)?;
+            let day = std::cmp::min(date.day(), util::end_of_month(&target).day());
+            Ok(target.with_day(day).unwrap())
+        }
+        EomPolicy::Overflow => {
+            let target = try_shift_months(util::beginning_of_month(&date), months)?;
+            target
+                .checked_add_signed(chrono::Duration::days(i64::from(date.day() - 1)))
+                .ok_or_else(|| out_of_range(date, months, "months"))
+        }
+    }
+}
+
+/// Add a number of months to a date using the given end of month policy, the same as
+/// [shift_months_with_policy]
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::util::{add_months_with_policy, EomPolicy};
+///
+/// let jan = NaiveDate::from_ymd_opt(2003, 1, 31).unwrap();
+///
+/// assert_eq!(
+///     add_months_with_policy(jan, 1, EomPolicy::ClampOnly),
+///     NaiveDate::from_ymd_opt(2003, 2, 28).unwrap()
+/// );
+/// ```
+pub fn add_months_with_policy(date: NaiveDate, months: i32, policy: EomPolicy) -> NaiveDate {
+    shift_months_with_policy(date, months, policy)
 }
 
 /// Add a quarter to the date supplied
@@ -80,39 +222,120 @@ pub fn shift_months(date: NaiveDate, months: i32) -> NaiveDate {
 /// If the current date falls in the last quarter of the year, this will shift to the first quarter
 /// of the next year.
 ///
+/// Shifting a quarter is shifting three months, so days the target month does not have are
+/// clamped and the last day of a month stays anchored to the end of the month as with
+/// [shift_months]. 2022-01-31 shifted by a quarter is 2022-04-30.
+///
 /// # Examples
 ///
-/// ```ignore
+/// ```
 /// # use chrono::NaiveDate;
-/// # use dateutil::addition;
-///
-/// assert_eq!(addition::add_quarter_duration(NaiveDate::from_ymd(2022, 1, 1)), NaiveDate::from_ymd(2022, 4, 1));
-/// assert_eq!(addition::add_quarter_duration(NaiveDate::from_ymd(2022, 11, 3)), NaiveDate::from_ymd(2023, 2, 3));
+/// # use calends::util::shift_quarters;
 ///
+/// assert_eq!(shift_quarters(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), 1), NaiveDate::from_ymd_opt(2022, 4, 1).unwrap());
+/// assert_eq!(shift_quarters(NaiveDate::from_ymd_opt(2022, 11, 3).unwrap(), 1), NaiveDate::from_ymd_opt(2023, 2, 3).unwrap());
 /// ```
 #[inline]
 pub fn shift_quarters(date: NaiveDate, quarters: i32) -> NaiveDate {
-    shift_months(date, 3 * quarters)
+    try_shift_quarters(date, quarters).unwrap()
+}
+
+/// Shift a date by a number of quarters, failing instead of panicking when the result is outside
+/// the range of supported dates
+pub fn try_shift_quarters(date: NaiveDate, quarters: i32) -> Result<NaiveDate, CalendsError> {
+    quarters
+        .checked_mul(3)
+        .ok_or_else(|| out_of_range(date, quarters, "quarters"))
+        .and_then(|months| try_shift_months(date, months))
 }
 
 /// Adds a year to the current date
 ///
+/// The 29th of February is shifted to the 28th in years which are not leap years, as with
+/// [LeapPolicy::Feb28], and the last day of February stays anchored to the end of the month.
+///
 /// # Examples
 ///
 /// ```
 /// # use chrono::NaiveDate;
 /// # use calends::shift_years;
 ///
-/// let n1 = shift_years(NaiveDate::from_ymd(2022, 1, 1), 1);
-/// let n2 = shift_years(NaiveDate::from_ymd(1584, 2, 3), -1);
+/// let n1 = shift_years(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(), 1);
+/// let n2 = shift_years(NaiveDate::from_ymd_opt(1584, 2, 3).unwrap(), -1);
 ///
-/// assert_eq!(n1, NaiveDate::from_ymd(2023, 1, 1));
-/// assert_eq!(n2, NaiveDate::from_ymd(1583, 2, 3));
+/// assert_eq!(n1, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+/// assert_eq!(n2, NaiveDate::from_ymd_opt(1583, 2, 3).unwrap());
 ///
 /// ```
 #[inline]
 pub fn shift_years(date: NaiveDate, years: i32) -> NaiveDate {
-    shift_months(date, years * 12)
+    shift_years_with_policy(date, years, LeapPolicy::Feb28)
+}
+
+/// Shift a date by a number of years, failing instead of panicking when the result is outside the
+/// range of supported dates
+pub fn try_shift_years(date: NaiveDate, years: i32) -> Result<NaiveDate, CalendsError> {
+    try_shift_years_with_policy(date, years, LeapPolicy::Feb28)
+}
+
+/// Where the 29th of February lands in a year which is not a leap year
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LeapPolicy {
+    /// The last day of February, the 28th
+    #[default]
+    Feb28,
+    /// The day after the 28th of February, the 1st of March
+    Mar1,
+}
+
+/// Shift a date by a number of years using the given leap day policy
+///
+/// The policy only applies when shifting the 29th of February to a year which is not a leap year,
+/// other dates are shifted as with [shift_years]. The policy is applied once to the final year
+/// however many years are shifted, so a leap day shifted four years is another leap day.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::util::{shift_years_with_policy, LeapPolicy};
+///
+/// let leap_day = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap();
+///
+/// assert_eq!(
+///     shift_years_with_policy(leap_day, 1, LeapPolicy::Feb28),
+///     NaiveDate::from_ymd_opt(2021, 2, 28).unwrap()
+/// );
+/// assert_eq!(
+///     shift_years_with_policy(leap_day, -3, LeapPolicy::Mar1),
+///     NaiveDate::from_ymd_opt(2017, 3, 1).unwrap()
+/// );
+/// assert_eq!(
+///     shift_years_with_policy(leap_day, 4, LeapPolicy::Mar1),
+///     NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+/// );
+/// ```
+pub fn shift_years_with_policy(date: NaiveDate, years: i32, policy: LeapPolicy) -> NaiveDate {
+    try_shift_years_with_policy(date, years, policy).unwrap()
+}
+
+/// Shift a date by a number of years using the given leap day policy, failing instead of
+/// panicking when the result is outside the range of supported dates
+pub fn try_shift_years_with_policy(
+    date: NaiveDate,
+    years: i32,
+    policy: LeapPolicy,
+) -> Result<NaiveDate, CalendsError> {
+    let out_of_range = || out_of_range(date, years, "years");
+
+    let shifted = try_shift_months(date, years.checked_mul(12).ok_or_else(out_of_range)?)
+        .map_err(|_| out_of_range())?;
+    match policy {
+        LeapPolicy::Mar1 if date.month() == 2 && date.day() == 29 && shifted.day() == 28 => {
+            shifted.succ_opt().ok_or_else(out_of_range)
+        }
+        _ => Ok(shifted),
+    }
 }
 
 /// Add a week
@@ -120,50 +343,167 @@ pub fn shift_years(date: NaiveDate, years: i32) -> NaiveDate {
 /// Simple enough
 #[inline]
 pub fn shift_weeks(date: NaiveDate, delta: i32) -> NaiveDate {
-    date + chrono::Duration::weeks(delta as i64)
+    try_shift_weeks(date, delta).unwrap()
+}
+
+/// Add a week, failing instead of panicking when the result is outside the range of supported
+/// dates
+pub fn try_shift_weeks(date: NaiveDate, delta: i32) -> Result<NaiveDate, CalendsError> {
+    date.checked_add_signed(chrono::Duration::weeks(delta.into()))
+        .ok_or_else(|| out_of_range(date, delta, "weeks"))
 }
 
 /// Add a day
 #[inline]
 pub fn shift_days(date: NaiveDate, days: i32) -> NaiveDate {
-    date + chrono::Duration::days(days.into())
+    try_shift_days(date, days).unwrap()
+}
+
+/// Add a day, failing instead of panicking when the result is outside the range of supported
+/// dates
+pub fn try_shift_days(date: NaiveDate, days: i32) -> Result<NaiveDate, CalendsError> {
+    date.checked_add_signed(chrono::Duration::days(days.into()))
+        .ok_or_else(|| out_of_range(date, days, "days"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::date;
 
     #[test]
     fn test_shift_months() {
+        assert_eq!(shift_months(date(2022, 1, 1), 1), date(2022, 2, 1));
+
+        assert_eq!(shift_months(date(2022, 1, 1), -1), date(2021, 12, 1))
+    }
+
+    #[test]
+    fn test_shift_months_across_many_years() {
+        assert_eq!(shift_months(date(2022, 11, 15), 25), date(2024, 12, 15));
+        assert_eq!(shift_months(date(2022, 1, 15), 24), date(2024, 1, 15));
+        assert_eq!(shift_months(date(2022, 12, 31), 14), date(2024, 2, 29));
+        assert_eq!(shift_months(date(2022, 1, 15), -12), date(2021, 1, 15));
+        assert_eq!(shift_months(date(2022, 1, 15), -25), date(2019, 12, 15));
+        assert_eq!(shift_months(date(2022, 3, 31), -1201), date(1922, 2, 28));
+        assert_eq!(shift_months(date(2022, 6, 30), 12 * 400), date(2422, 6, 30));
+    }
+
+    #[test]
+    fn test_shift_months_round_trip_through_february() {
+        let feb = shift_months(date(2022, 1, 28), 1);
+        assert_eq!(feb, date(2022, 2, 28));
+        assert_eq!(shift_months(feb, -1), date(2022, 1, 31));
+    }
+
+    #[test]
+    fn test_shift_months_clamp_only() {
+        let jan = date(2022, 1, 31);
+        let feb = shift_months_with_policy(jan, 1, EomPolicy::ClampOnly);
+        assert_eq!(feb, date(2022, 2, 28));
         assert_eq!(
-            shift_months(NaiveDate::from_ymd(2022, 1, 1), 1),
-            NaiveDate::from_ymd(2022, 2, 1)
+            shift_months_with_policy(feb, 1, EomPolicy::ClampOnly),
+            date(2022, 3, 28)
         );
-
         assert_eq!(
-            shift_months(NaiveDate::from_ymd(2022, 1, 1), -1),
-            NaiveDate::from_ymd(2021, 12, 1)
-        )
+            shift_months_with_policy(feb, 1, EomPolicy::Preserve),
+            date(2022, 3, 31)
+        );
     }
 
     #[test]
-    fn test_shift_quarters() {
+    fn test_shift_months_overflow() {
+        assert_eq!(
+            shift_months_with_policy(date(2024, 3, 31), -1, EomPolicy::Overflow),
+            date(2024, 3, 2)
+        );
         assert_eq!(
-            shift_quarters(NaiveDate::from_ymd(2022, 1, 1), 1),
-            NaiveDate::from_ymd(2022, 4, 1)
+            shift_months_with_policy(date(2022, 12, 31), 11, EomPolicy::Overflow),
+            date(2023, 12, 1)
         );
+        assert_eq!(
+            shift_months_with_policy(date(2022, 1, 28), 1, EomPolicy::Overflow),
+            date(2022, 2, 28)
+        );
+    }
+
+    #[test]
+    fn test_shift_quarters() {
+        assert_eq!(shift_quarters(date(2022, 1, 1), 1), date(2022, 4, 1));
+    }
+
+    #[test]
+    fn test_shift_quarters_clamps_to_shorter_months() {
+        assert_eq!(shift_quarters(date(2022, 1, 31), 1), date(2022, 4, 30));
+        assert_eq!(shift_quarters(date(2022, 1, 30), 1), date(2022, 4, 30));
+        assert_eq!(shift_quarters(date(2022, 4, 30), 1), date(2022, 7, 31));
+        assert_eq!(shift_quarters(date(2022, 5, 31), -1), date(2022, 2, 28));
+        assert_eq!(shift_quarters(date(2023, 11, 30), 1), date(2024, 2, 29));
+        assert_eq!(shift_quarters(date(2022, 1, 31), 5), date(2023, 4, 30));
     }
 
     #[test]
     fn test_shift_years() {
+        assert_eq!(shift_years(date(2022, 1, 1), 1), date(2023, 1, 1));
+
+        assert_eq!(shift_years(date(2024, 2, 29), 1), date(2025, 2, 28));
+    }
+
+    #[test]
+    fn test_try_shift_out_of_range() {
         assert_eq!(
-            shift_years(NaiveDate::from_ymd(2022, 1, 1), 1),
-            NaiveDate::from_ymd(2023, 1, 1)
+            try_shift_months(NaiveDate::MAX, 1),
+            Err(CalendsError::DateOutOfRange {
+                date: NaiveDate::MAX,
+                shift: "1 months".to_string()
+            })
         );
 
+        assert!(try_shift_months(NaiveDate::MIN, -1).is_err());
+        assert!(try_shift_months(NaiveDate::MAX, i32::MAX).is_err());
+        assert!(try_shift_months(NaiveDate::MIN, i32::MIN).is_err());
+        assert!(try_shift_quarters(NaiveDate::MAX, i32::MAX).is_err());
+        assert!(try_shift_years(NaiveDate::MAX, i32::MIN).is_err());
+        assert!(try_shift_weeks(NaiveDate::MIN, -1).is_err());
+        assert!(try_shift_days(NaiveDate::MAX, 1).is_err());
+        assert!(try_shift_months_with_policy(NaiveDate::MAX, 0, EomPolicy::Overflow).is_ok());
+        assert!(try_shift_months_with_policy(NaiveDate::MAX, 1, EomPolicy::ClampOnly).is_err());
+
+        // the last month chrono supports is still anchored to its end
+        let december = NaiveDate::MAX.with_day(1).unwrap();
+        assert_eq!(
+            try_shift_months(NaiveDate::MAX - chrono::Duration::days(31), 1),
+            Ok(NaiveDate::MAX)
+        );
+        assert_eq!(try_shift_days(december, 30), Ok(NaiveDate::MAX));
+    }
+
+    #[test]
+    fn test_shift_years_from_leap_day() {
+        let leap_day = date(2020, 2, 29);
+
+        for (years, feb28, mar1) in [
+            (1, date(2021, 2, 28), date(2021, 3, 1)),
+            (-1, date(2019, 2, 28), date(2019, 3, 1)),
+            (4, date(2024, 2, 29), date(2024, 2, 29)),
+            (80, date(2100, 2, 28), date(2100, 3, 1)),
+            (-20, date(2000, 2, 29), date(2000, 2, 29)),
+        ] {
+            assert_eq!(shift_years(leap_day, years), feb28);
+            assert_eq!(
+                shift_years_with_policy(leap_day, years, LeapPolicy::Mar1),
+                mar1
+            );
+        }
+
+        // only the leap day itself is affected by the policy
+        assert_eq!(
+            shift_years_with_policy(date(2021, 2, 28), 3, LeapPolicy::Mar1),
+            date(2024, 2, 29)
+        );
         assert_eq!(
-            shift_years(NaiveDate::from_ymd(2024, 2, 29), 1),
-            NaiveDate::from_ymd(2025, 2, 28)
+            shift_years_with_policy(date(2020, 2, 28), 1, LeapPolicy::Mar1),
+            date(2021, 2, 28)
         );
     }
 }
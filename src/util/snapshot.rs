@@ -0,0 +1,163 @@
+//! Snapshot dates
+//!
+//! Helpers for finding the most recent period that has fully elapsed as of a given date. A
+//! period is only considered complete once `as_of` falls after its last day, so the period
+//! containing `as_of` is never returned even when `as_of` is its final day. This is the
+//! behavior ETL style jobs want when they run on a date and must not include partial data.
+use chrono::{Duration, NaiveDate, Weekday};
+
+use crate::{
+    interval::ClosedInterval,
+    unit::{convert_to_month, convert_to_quarter},
+    util::search::{beginning_of_month, beginning_of_quarter, checked_beginning_of_week_starting},
+    Interval, RelativeDuration,
+};
+
+/// The last month which is complete as of the date supplied
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{IntervalLike, util::last_complete_month};
+///
+/// let month = last_complete_month(NaiveDate::from_ymd_opt(2022, 3, 15).unwrap());
+/// assert_eq!(month.start_opt(), NaiveDate::from_ymd_opt(2022, 2, 1));
+/// assert_eq!(month.end_opt(), NaiveDate::from_ymd_opt(2022, 2, 28));
+/// ```
+///
+/// # Panics
+///
+/// When no month before the one containing the date is supported, see
+/// [checked_last_complete_month] for a fallible alternative
+pub fn last_complete_month(as_of: NaiveDate) -> Interval {
+    checked_last_complete_month(as_of).unwrap()
+}
+
+/// The last month which is complete as of the date supplied, [None] when no month before the one
+/// containing the date is supported
+pub fn checked_last_complete_month(as_of: NaiveDate) -> Option<Interval> {
+    let end = beginning_of_month(&as_of).pred_opt()?;
+    Some(convert_to_month(end).into_interval())
+}
+
+/// The last quarter which is complete as of the date supplied
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::NaiveDate;
+/// # use calends::{IntervalLike, util::last_complete_quarter};
+///
+/// let quarter = last_complete_quarter(NaiveDate::from_ymd_opt(2022, 2, 10).unwrap());
+/// assert_eq!(quarter.start_opt(), NaiveDate::from_ymd_opt(2021, 10, 1));
+/// assert_eq!(quarter.end_opt(), NaiveDate::from_ymd_opt(2021, 12, 31));
+/// ```
+///
+/// # Panics
+///
+/// When no quarter before the one containing the date is supported, see
+/// [checked_last_complete_quarter] for a fallible alternative
+pub fn last_complete_quarter(as_of: NaiveDate) -> Interval {
+    checked_last_complete_quarter(as_of).unwrap()
+}
+
+/// The last quarter which is complete as of the date supplied, [None] when no quarter before the
+/// one containing the date is supported
+pub fn checked_last_complete_quarter(as_of: NaiveDate) -> Option<Interval> {
+    let end = beginning_of_quarter(&as_of).pred_opt()?;
+    Some(convert_to_quarter(end).into_interval())
+}
+
+/// The last week which is complete as of the date supplied, with weeks beginning on `weekstart`
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::{NaiveDate, Weekday};
+/// # use calends::{IntervalLike, util::last_complete_week};
+///
+/// // 2022-01-05 is a Wednesday
+/// let week = last_complete_week(NaiveDate::from_ymd_opt(2022, 1, 5).unwrap(), Weekday::Sun);
+/// assert_eq!(week.start_opt(), NaiveDate::from_ymd_opt(2021, 12, 26));
+/// assert_eq!(week.end_opt(), NaiveDate::from_ymd_opt(2022, 1, 1));
+/// ```
+///
+/// # Panics
+///
+/// When the week before the one containing the date starts before the earliest supported date,
+/// see [checked_last_complete_week] for a fallible alternative
+pub fn last_complete_week(as_of: NaiveDate, weekstart: Weekday) -> Interval {
+    checked_last_complete_week(as_of, weekstart).unwrap()
+}
+
+/// The last week which is complete as of the date supplied, with weeks beginning on `weekstart`,
+/// [None] when it would start before the earliest supported date
+pub fn checked_last_complete_week(as_of: NaiveDate, weekstart: Weekday) -> Option<Interval> {
+    let start = checked_beginning_of_week_starting(&as_of, weekstart)?
+        .checked_sub_signed(Duration::weeks(1))?;
+    Some(Interval::Closed(ClosedInterval::from_start(
+        start,
+        RelativeDuration::days(6),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Datelike;
+
+    use crate::{testing::date, IntervalLike};
+
+    use super::*;
+
+    #[test]
+    fn test_last_complete_month_excludes_current_month() {
+        // even on the last day of the month the month itself is still in progress
+        let month = last_complete_month(date(2022, 1, 31));
+        assert_eq!(month.start_opt(), Some(date(2021, 12, 1)));
+        assert_eq!(month.end_opt(), Some(date(2021, 12, 31)));
+
+        let month = last_complete_month(date(2022, 2, 1));
+        assert_eq!(month.start_opt(), Some(date(2022, 1, 1)));
+        assert_eq!(month.end_opt(), Some(date(2022, 1, 31)));
+    }
+
+    #[test]
+    fn test_last_complete_quarter() {
+        let quarter = last_complete_quarter(date(2022, 4, 1));
+        assert_eq!(quarter.start_opt(), Some(date(2022, 1, 1)));
+        assert_eq!(quarter.end_opt(), Some(date(2022, 3, 31)));
+
+        let quarter = last_complete_quarter(date(2022, 9, 30));
+        assert_eq!(quarter.start_opt(), Some(date(2022, 4, 1)));
+        assert_eq!(quarter.end_opt(), Some(date(2022, 6, 30)));
+    }
+
+    #[test]
+    fn test_last_complete_week() {
+        // 2022-01-03 is a Monday
+        let week = last_complete_week(date(2022, 1, 3), Weekday::Mon);
+        assert_eq!(week.start_opt(), Some(date(2021, 12, 27)));
+        assert_eq!(week.end_opt(), Some(date(2022, 1, 2)));
+
+        let week = last_complete_week(date(2022, 1, 2), Weekday::Mon);
+        assert_eq!(week.start_opt(), Some(date(2021, 12, 20)));
+        assert_eq!(week.end_opt(), Some(date(2021, 12, 26)));
+    }
+
+    #[test]
+    fn test_at_the_earliest_date() {
+        let min = NaiveDate::MIN;
+        assert_eq!(checked_last_complete_month(min), None);
+        assert_eq!(
+            checked_last_complete_quarter(min + Duration::days(40)),
+            None
+        );
+        assert_eq!(checked_last_complete_week(min, min.weekday()), None);
+
+        let week = checked_last_complete_week(min + Duration::weeks(1), min.weekday());
+        assert_eq!(week.and_then(|week| week.start_opt()), Some(min));
+        let month = checked_last_complete_month(min + Duration::days(31));
+        assert_eq!(month.and_then(|month| month.start_opt()), Some(min));
+    }
+}
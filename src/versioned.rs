@@ -0,0 +1,203 @@
+//! Definitions which change over time
+//!
+//! Holiday lists and recurrence rules get amended, and for regulatory replay it matters which
+//! definition was in force when a schedule was produced. A [Versioned] value keeps every version
+//! of a definition with the date it came into force, and an [AsOf] context evaluates schedules
+//! with the definitions in force on a given date.
+//!
+//! # Examples
+//!
+//! ```
+//! # use chrono::NaiveDate;
+//! # use calends::{business::Calendar, interval::ClosedInterval, Recurrence, Rule};
+//! # use calends::versioned::{AsOf, Versioned};
+//!
+//! let date = |m, d| NaiveDate::from_ymd_opt(2022, m, d).unwrap();
+//!
+//! let rules = Versioned::new(date(1, 1), Recurrence::with_start(Rule::monthly(), date(1, 1)));
+//! // a holiday on the first of February was announced in March
+//! let calendars = Versioned::new(date(1, 1), Calendar::new())
+//!     .with_version(date(3, 1), Calendar::new().with_holidays([date(2, 1)]));
+//!
+//! let interval = ClosedInterval::with_dates(date(1, 1), date(2, 28));
+//!
+//! let before = AsOf::new(date(2, 15)).schedule(&rules, &calendars, &interval);
+//! assert_eq!(before, vec![date(1, 3), date(2, 1)]);
+//!
+//! let after = AsOf::new(date(3, 15)).schedule(&rules, &calendars, &interval);
+//! assert_eq!(after, vec![date(1, 3), date(2, 2)]);
+//! ```
+use chrono::NaiveDate;
+
+use crate::{
+    business::next_business_day,
+    holidays::HolidayCalendar,
+    interval::marker::{End, Start},
+    Recurrence,
+};
+
+/// Every version of a definition, each with the date it came into force
+///
+/// A version stays in force until the next version comes into force, so the versions cover every
+/// date from the first version onwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Versioned<T> {
+    versions: Vec<(NaiveDate, T)>,
+}
+
+impl<T> Versioned<T> {
+    /// A definition with its first version
+    pub fn new(in_force_from: NaiveDate, value: T) -> Self {
+        Versioned {
+            versions: vec![(in_force_from, value)],
+        }
+    }
+
+    /// Add a version which comes into force on the date, replacing any version from the same date
+    pub fn with_version(mut self, in_force_from: NaiveDate, value: T) -> Self {
+        self.insert(in_force_from, value);
+        self
+    }
+
+    /// Add a version which comes into force on the date, replacing any version from the same date
+    pub fn insert(&mut self, in_force_from: NaiveDate, value: T) {
+        match self
+            .versions
+            .binary_search_by_key(&in_force_from, |(from, _)| *from)
+        {
+            Ok(i) => self.versions[i].1 = value,
+            Err(i) => self.versions.insert(i, (in_force_from, value)),
+        }
+    }
+
+    /// The version in force on the date, [None] before the first version
+    pub fn in_force_on(&self, date: NaiveDate) -> Option<&T> {
+        let i = self.versions.partition_point(|(from, _)| *from <= date);
+        i.checked_sub(1).map(|i| &self.versions[i].1)
+    }
+
+    /// Each version with its effective range, the end is exclusive and [None] for the latest version
+    pub fn versions(&self) -> impl Iterator<Item = (NaiveDate, Option<NaiveDate>, &T)> {
+        self.versions.iter().enumerate().map(|(i, (from, value))| {
+            (
+                *from,
+                self.versions.get(i + 1).map(|(until, _)| *until),
+                value,
+            )
+        })
+    }
+}
+
+/// Evaluation using the definitions in force on a date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsOf {
+    date: NaiveDate,
+}
+
+impl AsOf {
+    pub fn new(date: NaiveDate) -> Self {
+        AsOf { date }
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// The version of the definition in force on the context's date
+    pub fn resolve<'a, T>(&self, versioned: &'a Versioned<T>) -> Option<&'a T> {
+        versioned.in_force_on(self.date)
+    }
+
+    /// Occurrences within the interval, each rolled forward to a business day
+    ///
+    /// Both the recurrence and the calendar are the versions in force on the context's date,
+    /// whatever dates the schedule itself covers. The schedule is empty when either has no version
+    /// in force yet.
+    pub fn schedule<I: Start + End, C: HolidayCalendar>(
+        &self,
+        recurrences: &Versioned<Recurrence>,
+        calendars: &Versioned<C>,
+        interval: &I,
+    ) -> Vec<NaiveDate> {
+        let (recurrence, calendar) = match (self.resolve(recurrences), self.resolve(calendars)) {
+            (Some(recurrence), Some(calendar)) => (recurrence, calendar),
+            _ => return Vec::new(),
+        };
+        let (start, end) = (interval.start(), interval.end());
+
+        recurrence
+            .between(start, end)
+            .into_iter()
+            .map(|date| match calendar.is_business_day(date) {
+                true => date,
+                false => next_business_day(date, calendar),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{business::Calendar, testing::date};
+
+    #[test]
+    fn test_in_force_on() {
+        let versioned = Versioned::new(date(2022, 3, 1), "b")
+            .with_version(date(2022, 1, 1), "a")
+            .with_version(date(2022, 6, 1), "c")
+            .with_version(date(2022, 3, 1), "b2");
+
+        assert_eq!(versioned.in_force_on(date(2022, 12, 31)), Some(&"c"));
+        assert_eq!(versioned.in_force_on(date(2022, 3, 1)), Some(&"b2"));
+        assert_eq!(versioned.in_force_on(date(2022, 2, 28)), Some(&"a"));
+        assert_eq!(
+            Some(date(2021, 12, 31)).and_then(|d| versioned.in_force_on(d)),
+            None
+        );
+        assert_eq!(
+            versioned
+                .versions()
+                .map(|(_, until, _)| until)
+                .collect::<Vec<_>>(),
+            vec![Some(date(2022, 3, 1)), Some(date(2022, 6, 1)), None]
+        );
+    }
+
+    #[test]
+    fn test_schedule_before_first_version() {
+        let rules = Versioned::new(
+            date(2022, 6, 1),
+            Recurrence::with_start(crate::Rule::weekly(), date(2022, 1, 3)),
+        );
+        let calendars = Versioned::new(date(2022, 1, 1), Calendar::new());
+        let interval =
+            crate::interval::ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 1, 31));
+
+        assert!(AsOf::new(date(2022, 5, 31))
+            .schedule(&rules, &calendars, &interval)
+            .is_empty());
+        assert_eq!(
+            AsOf::new(date(2022, 6, 1))
+                .schedule(&rules, &calendars, &interval)
+                .len(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_descending_schedule() {
+        let rules = Versioned::new(
+            date(2022, 1, 1),
+            Recurrence::with_start(crate::Rule::monthly(), date(2022, 12, 1)).descending(),
+        );
+        let calendars = Versioned::new(date(2022, 1, 1), Calendar::new());
+        let interval =
+            crate::interval::ClosedInterval::with_dates(date(2022, 1, 1), date(2022, 3, 31));
+
+        assert_eq!(
+            AsOf::new(date(2022, 6, 1)).schedule(&rules, &calendars, &interval),
+            vec![date(2022, 1, 3), date(2022, 2, 1), date(2022, 3, 1)]
+        );
+    }
+}
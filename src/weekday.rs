@@ -0,0 +1,529 @@
+//! Weekdays
+//!
+//! [WeekdaySet] is a compact bitset used wherever a selection of days of the week is needed, such
+//! as the weekend of a [Calendar](crate::business::Calendar) or the days a recurrence falls on.
+//! [NthWeekday] picks a single weekday within a month such as the second Tuesday or last Friday.
+use std::{
+    fmt::{Debug, Display},
+    ops::{BitAnd, BitOr, Not},
+    str::FromStr,
+};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    error::CalendsError,
+    month::Month,
+    shift,
+    util::{beginning_of_month, checked_find_weekday_ascending, checked_find_weekday_descending},
+};
+
+/// RFC 5545 codes for each weekday, Monday first
+const CODES: [&str; 7] = ["MO", "TU", "WE", "TH", "FR", "SA", "SU"];
+
+/// A set of days of the week
+///
+/// Displays and parses using the two letter codes of RFC 5545 in Monday first order, e.g.
+/// `MO,WE,FR`.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::Weekday;
+/// # use calends::weekday::WeekdaySet;
+///
+/// let set: WeekdaySet = "fr,mo,we".parse()?;
+/// assert_eq!(set.to_string(), "MO,WE,FR");
+/// assert!(set.contains(Weekday::Wed));
+/// assert_eq!(set.complement().to_string(), "TU,TH,SA,SU");
+/// assert_eq!(set.iter().next(), Some(Weekday::Mon));
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WeekdaySet(u8);
+
+impl WeekdaySet {
+    pub const EMPTY: WeekdaySet = WeekdaySet(0);
+    pub const ALL: WeekdaySet = WeekdaySet(0b111_1111);
+    /// Monday to Friday
+    pub const WEEKDAYS: WeekdaySet = WeekdaySet(0b001_1111);
+    /// Saturday and Sunday
+    pub const WEEKEND: WeekdaySet = WeekdaySet(0b110_0000);
+
+    fn bit(weekday: Weekday) -> u8 {
+        1 << weekday.num_days_from_monday()
+    }
+
+    pub fn single(weekday: Weekday) -> Self {
+        WeekdaySet(Self::bit(weekday))
+    }
+
+    /// Set with the weekday added
+    pub fn with(self, weekday: Weekday) -> Self {
+        WeekdaySet(self.0 | Self::bit(weekday))
+    }
+
+    /// Set with the weekday removed
+    pub fn without(self, weekday: Weekday) -> Self {
+        WeekdaySet(self.0 & !Self::bit(weekday))
+    }
+
+    pub fn insert(&mut self, weekday: Weekday) {
+        *self = self.with(weekday);
+    }
+
+    pub fn remove(&mut self, weekday: Weekday) {
+        *self = self.without(weekday);
+    }
+
+    pub fn contains(&self, weekday: Weekday) -> bool {
+        self.0 & Self::bit(weekday) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Every weekday not in the set
+    pub fn complement(&self) -> Self {
+        WeekdaySet(!self.0 & Self::ALL.0)
+    }
+
+    /// Weekdays in the set, Monday first
+    pub fn iter(&self) -> Iter {
+        Iter {
+            set: *self,
+            next: Some(Weekday::Mon),
+        }
+    }
+}
+
+/// Iterator over the weekdays of a [WeekdaySet]
+#[derive(Debug, Clone)]
+pub struct Iter {
+    set: WeekdaySet,
+    next: Option<Weekday>,
+}
+
+impl Iterator for Iter {
+    type Item = Weekday;
+
+    fn next(&mut self) -> Option<Weekday> {
+        while let Some(weekday) = self.next {
+            self.next = match weekday {
+                Weekday::Sun => None,
+                _ => Some(weekday.succ()),
+            };
+            if self.set.contains(weekday) {
+                return Some(weekday);
+            }
+        }
+        None
+    }
+}
+
+impl IntoIterator for WeekdaySet {
+    type Item = Weekday;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Weekday> for WeekdaySet {
+    fn from_iter<T: IntoIterator<Item = Weekday>>(iter: T) -> Self {
+        iter.into_iter().fold(WeekdaySet::EMPTY, WeekdaySet::with)
+    }
+}
+
+impl From<Weekday> for WeekdaySet {
+    fn from(weekday: Weekday) -> Self {
+        WeekdaySet::single(weekday)
+    }
+}
+
+impl BitOr for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn bitor(self, rhs: WeekdaySet) -> WeekdaySet {
+        WeekdaySet(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn bitand(self, rhs: WeekdaySet) -> WeekdaySet {
+        WeekdaySet(self.0 & rhs.0)
+    }
+}
+
+impl Not for WeekdaySet {
+    type Output = WeekdaySet;
+
+    fn not(self) -> WeekdaySet {
+        self.complement()
+    }
+}
+
+impl Display for WeekdaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, weekday) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(code(weekday))?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for WeekdaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WeekdaySet({})", self)
+    }
+}
+
+/// Parse a comma separated list of two letter codes, ignoring case and surrounding whitespace
+impl FromStr for WeekdaySet {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(WeekdaySet::EMPTY);
+        }
+
+        s.split(',')
+            .map(|code| {
+                let code = code.trim().to_ascii_uppercase();
+                parse_code(&code).ok_or(CalendsError::InvalidWeekday(code))
+            })
+            .collect()
+    }
+}
+
+/// Serialize as the comma separated codes, e.g. `"SA,SU"`
+#[cfg(feature = "serde")]
+impl Serialize for WeekdaySet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for WeekdaySet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+pub(crate) fn parse_code(code: &str) -> Option<Weekday> {
+    CODES
+        .iter()
+        .position(|c| *c == code)
+        .map(|i| Weekday::try_from(i as u8).unwrap())
+}
+
+pub(crate) fn code(weekday: Weekday) -> &'static str {
+    CODES[weekday.num_days_from_monday() as usize]
+}
+
+/// Which occurrence of a weekday within a month, see [NthWeekday]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Nth {
+    First,
+    Second,
+    Third,
+    Fourth,
+    /// Only some months have a fifth occurrence of a weekday
+    Fifth,
+    Last,
+}
+
+impl Nth {
+    /// The occurrence counted from the start of the month, `-1` for the last
+    pub fn number(&self) -> i32 {
+        match self {
+            Nth::First => 1,
+            Nth::Second => 2,
+            Nth::Third => 3,
+            Nth::Fourth => 4,
+            Nth::Fifth => 5,
+            Nth::Last => -1,
+        }
+    }
+}
+
+/// The nth occurrence of a weekday within a month
+///
+/// Occurrences are counted from the start of the month with `1..=5` or from the end with
+/// `-1..=-5`, where `-1` is the last occurrence. They display and parse in the RFC 5545 style,
+/// e.g. `2TU` for the second Tuesday and `-1FR` for the last Friday.
+///
+/// # Examples
+///
+/// ```
+/// # use chrono::{NaiveDate, Weekday};
+/// # use calends::{month::Month, weekday::NthWeekday};
+///
+/// let thanksgiving: NthWeekday = "4TH".parse()?;
+/// assert_eq!(thanksgiving.resolve(2022, Month::November), NaiveDate::from_ymd_opt(2022, 11, 24));
+///
+/// let last_friday = NthWeekday::last(Weekday::Fri);
+/// assert_eq!(
+///     last_friday.next_after(NaiveDate::from_ymd_opt(2022, 1, 28).unwrap()),
+///     NaiveDate::from_ymd_opt(2022, 2, 25).unwrap()
+/// );
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NthWeekday {
+    n: i8,
+    weekday: Weekday,
+}
+
+impl NthWeekday {
+    pub fn new(n: i32, weekday: Weekday) -> Result<Self, CalendsError> {
+        match n {
+            1..=5 | -5..=-1 => Ok(NthWeekday {
+                n: n as i8,
+                weekday,
+            }),
+            _ => Err(CalendsError::InvalidNthWeekday(format!(
+                "{}{}",
+                n,
+                code(weekday)
+            ))),
+        }
+    }
+
+    pub fn first(weekday: Weekday) -> Self {
+        NthWeekday { n: 1, weekday }
+    }
+
+    pub fn from_nth(nth: Nth, weekday: Weekday) -> Self {
+        NthWeekday {
+            n: nth.number() as i8,
+            weekday,
+        }
+    }
+
+    pub fn last(weekday: Weekday) -> Self {
+        NthWeekday { n: -1, weekday }
+    }
+
+    /// Which occurrence, negative when counting from the end of the month
+    pub fn n(&self) -> i32 {
+        self.n.into()
+    }
+
+    pub fn weekday(&self) -> Weekday {
+        self.weekday
+    }
+
+    /// The date of the occurrence in the month, the fifth occurrence does not exist in every month
+    /// and no occurrence exists outside the range of supported dates
+    pub fn resolve(&self, year: i32, month: Month) -> Option<NaiveDate> {
+        let number = month.number();
+        let date = if self.n > 0 {
+            checked_find_weekday_ascending(self.weekday, year, number, self.n as u32)
+        } else {
+            checked_find_weekday_descending(
+                self.weekday,
+                year,
+                number,
+                self.n.unsigned_abs().into(),
+            )
+        }?;
+
+        (date.year() == year && date.month() == number).then_some(date)
+    }
+
+    /// The first occurrence after the date
+    ///
+    /// # Panics
+    ///
+    /// When there is no occurrence before the last supported date, see
+    /// [checked_next_after](NthWeekday::checked_next_after) for a fallible alternative
+    pub fn next_after(&self, date: NaiveDate) -> NaiveDate {
+        self.checked_next_after(date).unwrap()
+    }
+
+    /// The first occurrence after the date, [None] when there is none before the last supported
+    /// date
+    pub fn checked_next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        // a fifth weekday occurs at least once every three months
+        let mut month = beginning_of_month(&date);
+        loop {
+            match self.resolve(month.year(), Month::of(&month)) {
+                Some(resolved) if resolved > date => return Some(resolved),
+                _ => month = shift::try_shift_months(month, 1).ok()?,
+            }
+        }
+    }
+
+    /// The last occurrence before the date
+    ///
+    /// # Panics
+    ///
+    /// When there is no occurrence after the earliest supported date, see
+    /// [checked_prev_before](NthWeekday::checked_prev_before) for a fallible alternative
+    pub fn prev_before(&self, date: NaiveDate) -> NaiveDate {
+        self.checked_prev_before(date).unwrap()
+    }
+
+    /// The last occurrence before the date, [None] when there is none after the earliest supported
+    /// date
+    pub fn checked_prev_before(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let mut month = beginning_of_month(&date);
+        loop {
+            match self.resolve(month.year(), Month::of(&month)) {
+                Some(resolved) if resolved < date => return Some(resolved),
+                _ => month = shift::try_shift_months(month, -1).ok()?,
+            }
+        }
+    }
+}
+
+impl Display for NthWeekday {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.n, code(self.weekday))
+    }
+}
+
+impl FromStr for NthWeekday {
+    type Err = CalendsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CalendsError::InvalidNthWeekday(s.to_string());
+        let upper = s.trim().to_ascii_uppercase();
+        let split = upper.len().checked_sub(2).ok_or_else(invalid)?;
+        let (n, weekday) = upper.split_at_checked(split).ok_or_else(invalid)?;
+
+        let n = n
+            .strip_prefix('+')
+            .unwrap_or(n)
+            .parse::<i32>()
+            .map_err(|_| invalid())?;
+        let weekday = parse_code(weekday).ok_or_else(invalid)?;
+        NthWeekday::new(n, weekday).map_err(|_| invalid())
+    }
+}
+
+/// Serialize in the RFC 5545 form, e.g. `"-1FR"`
+#[cfg(feature = "serde")]
+impl Serialize for NthWeekday {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for NthWeekday {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(WeekdaySet::WEEKDAYS.len(), 5);
+        assert_eq!(WeekdaySet::WEEKEND.to_string(), "SA,SU");
+        assert_eq!(WeekdaySet::WEEKDAYS.complement(), WeekdaySet::WEEKEND);
+        assert_eq!(!WeekdaySet::ALL, WeekdaySet::EMPTY);
+        assert_eq!(WeekdaySet::WEEKDAYS | WeekdaySet::WEEKEND, WeekdaySet::ALL);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for bits in 0..=WeekdaySet::ALL.0 {
+            let set = WeekdaySet(bits);
+            assert_eq!(set.to_string().parse::<WeekdaySet>(), Ok(set));
+            assert_eq!(set.iter().collect::<WeekdaySet>(), set);
+            assert_eq!(set.iter().count(), set.len());
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            "MO,XX".parse::<WeekdaySet>(),
+            Err(CalendsError::InvalidWeekday("XX".to_string()))
+        );
+        assert_eq!(
+            " mo , Su ".parse::<WeekdaySet>().unwrap().to_string(),
+            "MO,SU"
+        );
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut set = WeekdaySet::EMPTY;
+        set.insert(Weekday::Sun);
+        set.insert(Weekday::Tue);
+        assert_eq!(format!("{:?}", set), "WeekdaySet(TU,SU)");
+
+        set.remove(Weekday::Sun);
+        assert_eq!(set, WeekdaySet::from(Weekday::Tue));
+    }
+
+    #[test]
+    fn test_nth_weekday_parse() {
+        let nth: NthWeekday = "2tu".parse().unwrap();
+        assert_eq!((nth.n(), nth.weekday()), (2, Weekday::Tue));
+        assert_eq!(
+            "-1FR".parse::<NthWeekday>(),
+            Ok(NthWeekday::last(Weekday::Fri))
+        );
+        assert_eq!(
+            "+1MO".parse::<NthWeekday>(),
+            Ok(NthWeekday::first(Weekday::Mon))
+        );
+        assert_eq!(NthWeekday::last(Weekday::Fri).to_string(), "-1FR");
+
+        for invalid in ["", "MO", "0MO", "6MO", "2XX", "é"] {
+            assert!(invalid.parse::<NthWeekday>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_nth_weekday_resolve() {
+        let fifth_monday = NthWeekday::new(5, Weekday::Mon).unwrap();
+        // January 2022 has five Mondays, February only four
+        assert_eq!(
+            fifth_monday.resolve(2022, Month::January),
+            Some(date(2022, 1, 31))
+        );
+        assert_eq!(fifth_monday.resolve(2022, Month::February), None);
+        assert_eq!(
+            fifth_monday.resolve(NaiveDate::MAX.year() + 1, Month::January),
+            None
+        );
+        assert_eq!(
+            NthWeekday::new(-5, Weekday::Mon)
+                .unwrap()
+                .resolve(2022, Month::January),
+            Some(date(2022, 1, 3))
+        );
+        assert_eq!(
+            fifth_monday.next_after(date(2022, 1, 31)),
+            date(2022, 5, 30)
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Calendar years
+//!
+//! [Year] is a starting point for navigating the calendar: its months, quarters and ISO weeks.
+use std::fmt::Display;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    error::CalendsError,
+    grain::Grain,
+    interval::ClosedInterval,
+    month::Month,
+    period::{FiscalYear, Period, Periods},
+};
+
+/// A year of the proleptic Gregorian calendar
+///
+/// # Examples
+///
+/// ```
+/// # use calends::year::Year;
+/// # use calends::IntervalLike;
+///
+/// let year = Year::new(2020)?;
+///
+/// assert!(year.is_leap());
+/// assert_eq!(year.days(), 366);
+/// assert_eq!(year.iso_weeks(), 53);
+/// assert_eq!(year.quarters().count(), 4);
+/// assert_eq!(year.interval().iso8601(), "2020-01-01/2020-12-31");
+/// assert!(Year::new(i32::MAX).is_err());
+/// # Ok::<(), calends::CalendsError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Year(i32);
+
+impl Year {
+    /// The year, which has to be within the range of supported dates
+    pub fn new(year: i32) -> Result<Self, CalendsError> {
+        match (NaiveDate::MIN.year()..=NaiveDate::MAX.year()).contains(&year) {
+            true => Ok(Year(year)),
+            false => Err(CalendsError::InvalidPeriod(year.to_string())),
+        }
+    }
+
+    /// The year the date falls in
+    pub fn of(date: &NaiveDate) -> Self {
+        Year(date.year())
+    }
+
+    pub fn number(&self) -> i32 {
+        self.0
+    }
+
+    pub fn is_leap(&self) -> bool {
+        (self.0 % 4 == 0 && self.0 % 100 != 0) || self.0 % 400 == 0
+    }
+
+    /// Number of days in the year
+    pub fn days(&self) -> u32 {
+        if self.is_leap() {
+            366
+        } else {
+            365
+        }
+    }
+
+    /// Number of ISO 8601 weeks in the ISO week-numbering year of the same number
+    pub fn iso_weeks(&self) -> u32 {
+        // 28 December is always in the last week of its ISO year
+        NaiveDate::from_ymd_opt(self.0, 12, 28)
+            .unwrap()
+            .iso_week()
+            .week()
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.0, 1, 1).unwrap()
+    }
+
+    pub fn last_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.0, 12, 31).unwrap()
+    }
+
+    /// Every day of the year
+    pub fn interval(&self) -> ClosedInterval {
+        ClosedInterval::with_dates(self.first_day(), self.last_day())
+    }
+
+    /// The twelve months of the year as periods
+    pub fn months(&self) -> std::iter::Take<Periods> {
+        self.periods(Grain::Month).take(12)
+    }
+
+    /// The four calendar quarters of the year as periods
+    pub fn quarters(&self) -> std::iter::Take<Periods> {
+        self.periods(Grain::Quarter).take(4)
+    }
+
+    /// The period of the month in the year
+    pub fn month(&self, month: Month) -> Period {
+        let start = month.first_day(self.0).unwrap();
+        Period::containing(start, Grain::Month, &FiscalYear::calendar())
+    }
+
+    /// The following year, if it is within the range of supported dates
+    pub fn succ(&self) -> Option<Year> {
+        Year::new(self.0 + 1).ok()
+    }
+
+    /// The preceding year, if it is within the range of supported dates
+    pub fn pred(&self) -> Option<Year> {
+        Year::new(self.0 - 1).ok()
+    }
+
+    fn periods(&self, grain: Grain) -> Periods {
+        Periods::containing(self.first_day(), grain)
+    }
+}
+
+impl TryFrom<i32> for Year {
+    type Error = CalendsError;
+
+    fn try_from(year: i32) -> Result<Self, Self::Error> {
+        Year::new(year)
+    }
+}
+
+impl From<Year> for i32 {
+    fn from(year: Year) -> Self {
+        year.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::date;
+
+    #[test]
+    fn test_leap_years() {
+        assert!(Year::new(2000).unwrap().is_leap());
+        assert!(!Year::new(1900).unwrap().is_leap());
+        assert!(!Year::new(2023).unwrap().is_leap());
+        assert_eq!(Year::new(1900).unwrap().days(), 365);
+    }
+
+    #[test]
+    fn test_iso_weeks() {
+        assert_eq!(Year::new(2015).unwrap().iso_weeks(), 53);
+        assert_eq!(Year::new(2022).unwrap().iso_weeks(), 52);
+        assert_eq!(Year::new(2026).unwrap().iso_weeks(), 53);
+    }
+
+    #[test]
+    fn test_months() {
+        let months: Vec<Period> = Year::new(2022).unwrap().months().collect();
+        assert_eq!(months.len(), 12);
+        assert_eq!(months[11].end(), date(2022, 12, 31));
+        assert_eq!(Year::new(2022).unwrap().month(Month::March), months[2]);
+    }
+
+    #[test]
+    fn test_years_at_the_limits() {
+        let last = Year::of(&NaiveDate::MAX);
+        let first = Year::of(&NaiveDate::MIN);
+
+        assert_eq!(last.last_day(), NaiveDate::MAX);
+        assert_eq!(first.first_day(), NaiveDate::MIN);
+        assert!(last.iso_weeks() >= 52 && first.iso_weeks() >= 52);
+        assert_eq!(last.succ(), None);
+        assert_eq!(first.pred(), None);
+        assert_eq!(last.pred().and_then(|year| year.succ()), Some(last));
+        assert_eq!(
+            Year::try_from(last.number() + 1),
+            Err(CalendsError::InvalidPeriod((last.number() + 1).to_string()))
+        );
+    }
+}